@@ -0,0 +1,118 @@
+// benches/effect_resolution.rs
+use ascent::effects::{
+    BoostEffect, BoostType, DamageEffect, Duration, Effect, EffectTarget, EffectValue,
+    SourceRemoval,
+};
+use ascent::{Deck, GameState, Player};
+use criterion::{black_box, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use uuid::Uuid;
+
+// Counts every heap allocation so the report below can show how many the
+// effect pipeline performs per application, not just how long it takes.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn player(name: &str) -> Player {
+    Player::new(
+        name.to_string(),
+        Deck {
+            cards: vec![].into(),
+            owner_id: Uuid::new_v4(),
+        },
+    )
+}
+
+fn setup() -> (GameState, Uuid, Uuid) {
+    let attacker = player("Attacker");
+    let defender = player("Defender");
+    let (source, target) = (attacker.id, defender.id);
+    (GameState::new(attacker, defender), source, target)
+}
+
+fn effects(source: Uuid, target: Uuid) -> Vec<Effect> {
+    vec![
+        Effect::Damage(DamageEffect {
+            value: EffectValue {
+                base: 0,
+                scaling: None,
+            },
+            target: EffectTarget::AllPlayers(vec![source, target]),
+            penetrating: false,
+            ranged: false,
+            priority: 0,
+        }),
+        Effect::Boost(BoostEffect {
+            value: EffectValue {
+                base: 1,
+                scaling: None,
+            },
+            target: EffectTarget::Specific(target),
+            stat: BoostType::Both,
+            duration: Duration::Temporary(2),
+            on_source_removed: SourceRemoval::Persist,
+            priority: 0,
+        }),
+    ]
+}
+
+fn bench_effects(c: &mut Criterion) {
+    let (mut game_state, source, target) = setup();
+    let effects = effects(source, target);
+
+    c.bench_function("apply_damage_all_players", |b| {
+        b.iter(|| effects[0].apply(black_box(&mut game_state), source))
+    });
+    c.bench_function("apply_boost_and_expire", |b| {
+        b.iter(|| {
+            effects[1]
+                .apply(black_box(&mut game_state), source)
+                .unwrap();
+            game_state.players.get_mut(&target).unwrap().update_turn();
+        })
+    });
+}
+
+fn report_allocations() {
+    const APPLICATIONS: usize = 10_000;
+    let (mut game_state, source, target) = setup();
+    let effects = effects(source, target);
+
+    for (name, effect) in ["damage", "boost"].iter().zip(&effects) {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        for _ in 0..APPLICATIONS {
+            effect.apply(&mut game_state, source).unwrap();
+            game_state.players.get_mut(&target).unwrap().update_turn();
+        }
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        println!(
+            "{}: {:.2} allocations per application",
+            name,
+            allocations as f64 / APPLICATIONS as f64
+        );
+    }
+}
+
+fn main() {
+    report_allocations();
+
+    let mut criterion = Criterion::default().configure_from_args();
+    bench_effects(&mut criterion);
+    criterion.final_summary();
+}
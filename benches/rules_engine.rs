@@ -0,0 +1,196 @@
+// benches/rules_engine.rs
+use ascent::effects::{
+    DamageEffect, Effect, EffectTarget, EffectValue, HealEffect, TargetCondition,
+};
+use ascent::models::{CardType, Mountain, Position};
+use ascent::{Card, Deck, GameState, Player, Rarity};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use uuid::Uuid;
+
+fn player(name: String, cards: Vec<Card>) -> Player {
+    Player::new(
+        name,
+        Deck {
+            cards: cards.into(),
+            owner_id: Uuid::new_v4(),
+        },
+    )
+}
+
+fn card(cost: u32, effects: Vec<Effect>) -> Card {
+    Card {
+        id: Uuid::new_v4(),
+        name: "Bench Card".to_string(),
+        cost,
+        power: 1,
+        rarity: Rarity::Common,
+        effects,
+        card_type: CardType::Spell,
+    }
+}
+
+// A 50-level mountain with a player standing on every tenth tile
+fn full_board() -> (GameState, Uuid) {
+    let first = player("Player 0".to_string(), vec![]);
+    let source = first.id;
+    let mut game_state = GameState::new(first, player("Player 1".to_string(), vec![]));
+    game_state.mountain = Mountain::new(50);
+
+    let positions: Vec<Position> = game_state
+        .mountain
+        .tiles
+        .iter()
+        .step_by(10)
+        .map(|tile| Position {
+            x: tile.x,
+            y: tile.y,
+            z: tile.z,
+            level: tile.level,
+        })
+        .collect();
+    for (i, position) in positions.into_iter().enumerate() {
+        let mut unit = player(format!("Unit {}", i), vec![]);
+        unit.position = position;
+        game_state.players.insert(unit.id, unit);
+    }
+    (game_state, source)
+}
+
+fn bench_effect_application(c: &mut Criterion) {
+    let (mut game_state, source) = full_board();
+    let target = game_state.turn_order[1];
+    let effect = Effect::Heal(HealEffect {
+        value: EffectValue {
+            base: 1,
+            scaling: None,
+        },
+        target: EffectTarget::Specific(target),
+        over_heal: false,
+        priority: 0,
+    });
+
+    c.bench_function("effect_apply_single_target", |b| {
+        b.iter(|| effect.apply(black_box(&mut game_state), source))
+    });
+}
+
+fn bench_target_resolution(c: &mut Criterion) {
+    let (game_state, source) = full_board();
+    let area = Effect::Heal(HealEffect {
+        value: EffectValue {
+            base: 0,
+            scaling: None,
+        },
+        target: EffectTarget::Area {
+            center: source,
+            radius: 10,
+        },
+        over_heal: false,
+        priority: 0,
+    });
+    let conditional = Effect::Damage(DamageEffect {
+        value: EffectValue {
+            base: 0,
+            scaling: None,
+        },
+        target: EffectTarget::Conditional {
+            condition: TargetCondition::PowerLessThan(5),
+            max_targets: None,
+        },
+        penetrating: false,
+        ranged: false,
+        priority: 0,
+    });
+
+    // Zero-value effects return before mutating, isolating resolution cost
+    c.bench_function("resolve_area_full_board", |b| {
+        b.iter_batched_ref(
+            || game_state.clone(),
+            |state| area.apply(state, source),
+            BatchSize::SmallInput,
+        )
+    });
+    c.bench_function("resolve_conditional_full_board", |b| {
+        b.iter_batched_ref(
+            || game_state.clone(),
+            |state| conditional.apply(state, source),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_tiles_in_range(c: &mut Criterion) {
+    let mountain = Mountain::new(50);
+    let center = Position {
+        x: 10,
+        y: 5,
+        z: 5,
+        level: 10,
+    };
+
+    c.bench_function("tiles_in_range_50_levels", |b| {
+        b.iter(|| {
+            mountain
+                .get_tiles_in_range(black_box(center), black_box(3))
+                .len()
+        })
+    });
+}
+
+fn bench_full_turn(c: &mut Criterion) {
+    let setup = || {
+        let mut first = player("Player 0".to_string(), vec![]);
+        let second = player("Player 1".to_string(), vec![]);
+        let opponent = second.id;
+        first.mana = 10;
+        first.set_hand(
+            (0..5)
+                .map(|cost| {
+                    card(
+                        cost,
+                        vec![Effect::Damage(DamageEffect {
+                            value: EffectValue {
+                                base: 1,
+                                scaling: None,
+                            },
+                            target: EffectTarget::Specific(opponent),
+                            penetrating: false,
+                            ranged: false,
+                            priority: 0,
+                        })],
+                    )
+                })
+                .collect(),
+        );
+        GameState::new(first, second)
+    };
+
+    c.bench_function("full_turn_play_hand_and_pass", |b| {
+        b.iter_batched(
+            setup,
+            |mut game_state| {
+                let active = game_state.active_player;
+                let hand: Vec<Uuid> = game_state.players[&active]
+                    .hand
+                    .iter()
+                    .map(|card| card.id)
+                    .collect();
+                for card_id in hand {
+                    game_state.play_card(active, card_id).unwrap();
+                }
+                game_state.end_turn(active).unwrap();
+                game_state
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_effect_application,
+    bench_target_resolution,
+    bench_tiles_in_range,
+    bench_full_turn
+);
+criterion_main!(benches);
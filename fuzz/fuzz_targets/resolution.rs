@@ -0,0 +1,106 @@
+// fuzz/fuzz_targets/resolution.rs
+// Drives arbitrary effects and actions through a generated game and checks
+// that nothing panics and the state stays structurally valid.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use ascent::effects::Effect;
+use ascent::game_state::{GameAction, GameState};
+use ascent::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
+use libfuzzer_sys::fuzz_target;
+use uuid::Uuid;
+
+#[derive(Debug, Arbitrary)]
+struct Seat {
+    health: u8,
+    mana: u8,
+    hand: Vec<(u8, u8, Vec<Effect>)>,
+    deck_size: u8,
+}
+
+#[derive(Debug, Arbitrary)]
+enum Step {
+    Action { seat: u8, action: GameAction },
+    PlayFromHand { seat: u8, slot: u8 },
+    Effect { seat: u8, effect: Effect },
+}
+
+#[derive(Debug, Arbitrary)]
+struct Scenario {
+    levels: u8,
+    seats: Vec<Seat>,
+    steps: Vec<Step>,
+}
+
+fn build(scenario: &Scenario) -> GameState {
+    let mut builder = GameStateBuilder::new().mountain_levels(u32::from(scenario.levels % 8) + 1);
+    for (index, seat) in scenario.seats.iter().take(4).enumerate() {
+        let hand = seat
+            .hand
+            .iter()
+            .take(10)
+            .map(|(cost, power, effects)| {
+                effects
+                    .iter()
+                    .take(4)
+                    .cloned()
+                    .fold(
+                        CardBuilder::new("Fuzz")
+                            .cost(u32::from(*cost))
+                            .power(u32::from(*power)),
+                        CardBuilder::effect,
+                    )
+                    .build()
+            })
+            .collect();
+        let deck = (0..seat.deck_size % 30)
+            .map(|_| CardBuilder::new("Filler").build())
+            .collect();
+        let health = u32::from(seat.health).max(1);
+        builder = builder.player(
+            PlayerBuilder::new(&format!("Seat {}", index))
+                .health(health)
+                .max_health(health)
+                .mana(u32::from(seat.mana))
+                .hand(hand)
+                .deck(deck)
+                .build(),
+        );
+    }
+    builder.build()
+}
+
+fn seat_id(game_state: &GameState, seat: u8) -> Uuid {
+    game_state.turn_order[seat as usize % game_state.turn_order.len()]
+}
+
+fuzz_target!(|scenario: Scenario| {
+    let mut game_state = build(&scenario);
+    assert!(game_state.validate_invariants().is_ok());
+
+    for step in scenario.steps.iter().take(64) {
+        match step {
+            Step::Action { seat, action } => {
+                let player_id = seat_id(&game_state, *seat);
+                let _ = game_state.apply_action(player_id, action);
+            }
+            Step::PlayFromHand { seat, slot } => {
+                let player_id = seat_id(&game_state, *seat);
+                let hand = &game_state.players[&player_id].hand;
+                if hand.is_empty() {
+                    continue;
+                }
+                let card_id = hand[*slot as usize % hand.len()].id;
+                let _ = game_state.apply_action(player_id, &GameAction::PlayCard { card_id });
+            }
+            Step::Effect { seat, effect } => {
+                let source = seat_id(&game_state, *seat);
+                let _ = effect.apply(&mut game_state, source);
+            }
+        }
+
+        if let Err(violation) = game_state.validate_invariants() {
+            panic!("invariant broken after {:?}: {:?}", step, violation);
+        }
+    }
+});
@@ -0,0 +1,878 @@
+// src/admin/mod.rs
+// Operations staff can run against live games, behind the admin API. Every
+// one that goes through is written to the audit log.
+use crate::cards::bans::{BanLists, BanStatus};
+use crate::cards::errata::{CardErrata, ErrataTable};
+use crate::collections::Collection;
+use crate::errors::AdminError;
+use crate::game_state::{ConcedeReason, MAX_TURN_TIME_LIMIT};
+use crate::matchmaking::MatchmakingQueue;
+use crate::models::Format;
+use crate::moderation::{ModerationService, SanctionKind};
+use crate::networking::{unix_millis, ServerMessage};
+use crate::recovery::ServerSnapshot;
+use crate::sessions::SessionManager;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdminAction {
+    // Ends a stuck game in someone's favour
+    ForceEnd {
+        winner: Uuid,
+    },
+    Kick {
+        player_id: Uuid,
+    },
+    // Undoes at least this many events, back to the nearest action boundary
+    Rollback {
+        events: usize,
+    },
+    // Lets a halted game take actions again, usually after a rollback
+    Resume,
+    SetTurnTimer {
+        limit: Option<Duration>,
+    },
+    // Card pool changes; these apply to every game started afterwards
+    DisableCard {
+        name: String,
+    },
+    EnableCard {
+        name: String,
+    },
+    // None reverts the card to its printed version
+    AmendCard {
+        name: String,
+        errata: Option<CardErrata>,
+    },
+    // Bans or restricts a card in one format from `effective_at`; a status
+    // of None lifts it
+    SetBanStatus {
+        format: Format,
+        name: String,
+        status: Option<BanStatus>,
+        effective_at: SystemTime,
+    },
+    // Player sanctions; None as the duration makes one permanent
+    IssueSanction {
+        player_id: Uuid,
+        kind: SanctionKind,
+        duration: Option<Duration>,
+    },
+    RevokeSanction {
+        sanction_id: Uuid,
+    },
+    // Whole-server disaster recovery snapshots, see crate::recovery
+    ExportSnapshot {
+        path: PathBuf,
+    },
+    ImportSnapshot {
+        path: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub admin_id: Uuid,
+    // None for anything not aimed at one game
+    pub game_id: Option<Uuid>,
+    pub action: AdminAction,
+    pub reason: String,
+    pub at: SystemTime,
+}
+
+// Sent to a player whose saved deck a ban list change makes illegal
+#[derive(Debug, Clone, PartialEq)]
+pub struct BanNotice {
+    pub owner_id: Uuid,
+    pub deck_name: String,
+    pub format: Format,
+    // Every card the deck breaks the list with once the change is in
+    pub cards: Vec<(String, BanStatus)>,
+    pub effective_at: SystemTime,
+}
+
+impl BanNotice {
+    pub fn to_message(&self) -> ServerMessage {
+        ServerMessage::DeckBanNotice {
+            deck_name: self.deck_name.clone(),
+            format: self.format,
+            cards: self.cards.clone(),
+            effective_at_ms: unix_millis(self.effective_at),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AdminService {
+    audit_log: Vec<AuditEntry>,
+}
+
+impl AdminService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn execute(
+        &mut self,
+        sessions: &mut SessionManager,
+        admin_id: Uuid,
+        game_id: Uuid,
+        action: AdminAction,
+        reason: String,
+        now: SystemTime,
+    ) -> Result<(), AdminError> {
+        let session = sessions.get_mut(game_id).ok_or(AdminError::UnknownGame)?;
+        // Forced results run like a player's action: all or nothing, with a
+        // checkpoint to roll back to. Unlike one they also reach a halted game.
+        match &action {
+            AdminAction::ForceEnd { winner } => {
+                let winner = *winner;
+                if !session.game.players.contains_key(&winner) {
+                    return Err(AdminError::NotAPlayer);
+                }
+                if session.game.winner().is_some() {
+                    return Err(AdminError::GameOver);
+                }
+                session
+                    .force_checkpointed(|game| {
+                        // The winner's teammates win with them
+                        let losers: Vec<Uuid> = game
+                            .turn_order
+                            .iter()
+                            .copied()
+                            .filter(|id| {
+                                game.are_enemies(*id, winner) && game.players[id].health > 0
+                            })
+                            .collect();
+                        for player_id in losers {
+                            game.concede(player_id, ConcedeReason::Kicked)?;
+                        }
+                        Ok(())
+                    })
+                    .map_err(AdminError::Game)?;
+            }
+            AdminAction::Kick { player_id } => {
+                if !session.game.players.contains_key(player_id) {
+                    return Err(AdminError::NotAPlayer);
+                }
+                session
+                    .force_checkpointed(|game| game.concede(*player_id, ConcedeReason::Kicked))
+                    .map_err(AdminError::Game)?;
+            }
+            AdminAction::Rollback { events } => {
+                session
+                    .rollback(*events)
+                    .ok_or(AdminError::NothingToRollBack)?;
+            }
+            AdminAction::Resume => {
+                if !session.resume() {
+                    return Err(AdminError::NotHalted);
+                }
+            }
+            AdminAction::SetTurnTimer { limit } => {
+                if limit.is_some_and(|limit| limit.is_zero() || limit > MAX_TURN_TIME_LIMIT) {
+                    return Err(AdminError::InvalidTurnTimer);
+                }
+                session.game.rules.turn_time_limit = *limit;
+            }
+            AdminAction::DisableCard { .. }
+            | AdminAction::EnableCard { .. }
+            | AdminAction::AmendCard { .. }
+            | AdminAction::SetBanStatus { .. }
+            | AdminAction::IssueSanction { .. }
+            | AdminAction::RevokeSanction { .. }
+            | AdminAction::ExportSnapshot { .. }
+            | AdminAction::ImportSnapshot { .. } => return Err(AdminError::WrongScope),
+        }
+
+        self.audit_log.push(AuditEntry {
+            id: Uuid::new_v4(),
+            admin_id,
+            game_id: Some(game_id),
+            action,
+            reason,
+            at: now,
+        });
+        Ok(())
+    }
+
+    // Changes to the card pool rather than to one game
+    pub fn amend_cards(
+        &mut self,
+        errata: &mut ErrataTable,
+        admin_id: Uuid,
+        action: AdminAction,
+        reason: String,
+        now: SystemTime,
+    ) -> Result<(), AdminError> {
+        match &action {
+            AdminAction::DisableCard { name } => {
+                errata.disable(name);
+            }
+            AdminAction::EnableCard { name } => {
+                errata.enable(name);
+            }
+            AdminAction::AmendCard {
+                name,
+                errata: amended,
+            } => {
+                errata.amend(name, amended.clone());
+            }
+            _ => return Err(AdminError::WrongScope),
+        }
+
+        self.audit_log.push(AuditEntry {
+            id: Uuid::new_v4(),
+            admin_id,
+            game_id: None,
+            action,
+            reason,
+            at: now,
+        });
+        Ok(())
+    }
+
+    // Schedules a ban list change and returns a notice for every saved deck
+    // built for the format that holds the card and breaks the list once the
+    // change is in
+    pub fn manage_bans<'a>(
+        &mut self,
+        bans: &mut BanLists,
+        collections: impl IntoIterator<Item = &'a Collection>,
+        admin_id: Uuid,
+        action: AdminAction,
+        reason: String,
+        now: SystemTime,
+    ) -> Result<Vec<BanNotice>, AdminError> {
+        let AdminAction::SetBanStatus {
+            format,
+            name,
+            status,
+            effective_at,
+        } = &action
+        else {
+            return Err(AdminError::WrongScope);
+        };
+        let list = bans.list_mut(*format);
+        list.schedule(name, *status, *effective_at);
+
+        let mut notices = Vec::new();
+        for collection in collections {
+            let mut decks: Vec<_> = collection
+                .decks
+                .iter()
+                .filter(|(deck_name, _)| collection.deck_format(deck_name) == *format)
+                .collect();
+            decks.sort_by_key(|(deck_name, _)| *deck_name);
+            for (deck_name, deck) in decks {
+                let cards = list.violations(deck, (*effective_at).max(now));
+                if cards.iter().any(|(banned, _)| banned == name) {
+                    notices.push(BanNotice {
+                        owner_id: collection.owner_id,
+                        deck_name: deck_name.clone(),
+                        format: *format,
+                        cards,
+                        effective_at: *effective_at,
+                    });
+                }
+            }
+        }
+
+        self.audit_log.push(AuditEntry {
+            id: Uuid::new_v4(),
+            admin_id,
+            game_id: None,
+            action,
+            reason,
+            at: now,
+        });
+        Ok(notices)
+    }
+
+    // Issues or revokes a sanction; the reason given is also the one the
+    // player is shown. Returns the id of a new sanction.
+    pub fn moderate(
+        &mut self,
+        moderation: &mut ModerationService,
+        admin_id: Uuid,
+        action: AdminAction,
+        reason: String,
+        now: SystemTime,
+    ) -> Result<Option<Uuid>, AdminError> {
+        let issued = match &action {
+            AdminAction::IssueSanction {
+                player_id,
+                kind,
+                duration,
+            } => {
+                Some(moderation.issue(admin_id, *player_id, *kind, *duration, reason.clone(), now))
+            }
+            AdminAction::RevokeSanction { sanction_id } => {
+                moderation
+                    .revoke(*sanction_id)
+                    .map_err(AdminError::Moderation)?;
+                None
+            }
+            _ => return Err(AdminError::WrongScope),
+        };
+
+        self.audit_log.push(AuditEntry {
+            id: Uuid::new_v4(),
+            admin_id,
+            game_id: None,
+            action,
+            reason,
+            at: now,
+        });
+        Ok(issued)
+    }
+
+    // Export writes every hosted game and the queue to `path`; import brings
+    // a snapshot up on this instance. Both return how many games they moved.
+    pub fn run_snapshot(
+        &mut self,
+        sessions: &mut SessionManager,
+        queue: &mut MatchmakingQueue,
+        admin_id: Uuid,
+        action: AdminAction,
+        reason: String,
+        now: SystemTime,
+    ) -> Result<usize, AdminError> {
+        let games = match &action {
+            AdminAction::ExportSnapshot { path } => {
+                let snapshot = ServerSnapshot::capture(sessions, queue, now);
+                snapshot.save(path).map_err(AdminError::Recovery)?;
+                snapshot.sessions.len()
+            }
+            AdminAction::ImportSnapshot { path } => ServerSnapshot::load(path)
+                .and_then(|snapshot| snapshot.restore(sessions, queue))
+                .map_err(AdminError::Recovery)?,
+            _ => return Err(AdminError::WrongScope),
+        };
+
+        self.audit_log.push(AuditEntry {
+            id: Uuid::new_v4(),
+            admin_id,
+            game_id: None,
+            action,
+            reason,
+            at: now,
+        });
+        Ok(games)
+    }
+
+    pub fn audit_log(&self) -> &[AuditEntry] {
+        &self.audit_log
+    }
+
+    pub fn audit_for_game(&self, game_id: Uuid) -> impl Iterator<Item = &AuditEntry> {
+        self.audit_log
+            .iter()
+            .filter(move |entry| entry.game_id == Some(game_id))
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod admin_tests {
+    use super::*;
+    use crate::game_state::GameAction;
+    use crate::metrics::ErrorTelemetry;
+    use crate::testing::GameStateBuilder;
+
+    #[test]
+    fn test_rollback_and_force_end_are_audited() {
+        let game = GameStateBuilder::new().build();
+        let (first, second) = (game.turn_order[0], game.turn_order[1]);
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(game);
+        let mut admin = AdminService::new();
+        let now = SystemTime::UNIX_EPOCH;
+
+        let session = sessions.get_mut(game_id).unwrap();
+        let before = session.game.events.len();
+        session.apply_action(first, &GameAction::EndTurn).unwrap();
+        assert!(session.game.events.len() > before);
+
+        assert!(matches!(
+            admin.execute(
+                &mut sessions,
+                Uuid::new_v4(),
+                game_id,
+                AdminAction::Rollback { events: 0 },
+                "nothing".to_string(),
+                now,
+            ),
+            Err(AdminError::NothingToRollBack)
+        ));
+        assert!(sessions.get(game_id).unwrap().game.events.len() > before);
+
+        admin
+            .execute(
+                &mut sessions,
+                Uuid::new_v4(),
+                game_id,
+                AdminAction::Rollback { events: 1 },
+                "desync".to_string(),
+                now,
+            )
+            .unwrap();
+        let game = &sessions.get(game_id).unwrap().game;
+        assert_eq!(game.events.len(), before);
+        assert_eq!(game.active_player, first);
+
+        let force_end = AdminAction::ForceEnd { winner: second };
+        let admin_id = Uuid::new_v4();
+        admin
+            .execute(
+                &mut sessions,
+                admin_id,
+                game_id,
+                force_end.clone(),
+                "stuck".to_string(),
+                now,
+            )
+            .unwrap();
+        assert_eq!(sessions.get(game_id).unwrap().game.winner(), Some(second));
+        assert!(matches!(
+            admin.execute(
+                &mut sessions,
+                admin_id,
+                game_id,
+                force_end,
+                "again".to_string(),
+                now
+            ),
+            Err(AdminError::GameOver)
+        ));
+        assert_eq!(admin.audit_for_game(game_id).count(), 2);
+    }
+
+    #[test]
+    fn test_force_end_spares_the_winners_team_and_can_be_rolled_back() {
+        use crate::game_state::GameState;
+        use crate::testing::PlayerBuilder;
+
+        let [a1, a2, b1, b2] =
+            ["A1", "A2", "B1", "B2"].map(|name| PlayerBuilder::new(name).build());
+        let ids = [a1.id, a2.id, b1.id, b2.id];
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(GameState::new_teams([a1, a2], [b1, b2]));
+        let mut admin = AdminService::new();
+        let (admin_id, now) = (Uuid::new_v4(), SystemTime::UNIX_EPOCH);
+        let before = sessions.get(game_id).unwrap().game.state_hash();
+
+        admin
+            .execute(
+                &mut sessions,
+                admin_id,
+                game_id,
+                AdminAction::ForceEnd { winner: ids[1] },
+                "stuck".to_string(),
+                now,
+            )
+            .unwrap();
+        let game = &sessions.get(game_id).unwrap().game;
+        assert_eq!(game.winning_team(), Some(0));
+        assert!(game.players[&ids[0]].health > 0);
+        assert!(ids[2..].iter().all(|id| game.players[id].health == 0));
+
+        admin
+            .execute(
+                &mut sessions,
+                admin_id,
+                game_id,
+                AdminAction::Rollback { events: 1 },
+                "wrong game".to_string(),
+                now,
+            )
+            .unwrap();
+        let game = &sessions.get(game_id).unwrap().game;
+        assert_eq!(game.state_hash(), before);
+        assert_eq!(game.winner(), None);
+    }
+
+    #[test]
+    fn test_turn_timer_must_be_within_bounds() {
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(GameStateBuilder::new().build());
+        let mut admin = AdminService::new();
+        let (admin_id, now) = (Uuid::new_v4(), SystemTime::UNIX_EPOCH);
+
+        for limit in [
+            Duration::ZERO,
+            MAX_TURN_TIME_LIMIT + Duration::from_secs(1),
+            Duration::MAX,
+        ] {
+            assert!(matches!(
+                admin.execute(
+                    &mut sessions,
+                    admin_id,
+                    game_id,
+                    AdminAction::SetTurnTimer { limit: Some(limit) },
+                    String::new(),
+                    now,
+                ),
+                Err(AdminError::InvalidTurnTimer)
+            ));
+        }
+        assert_eq!(
+            sessions.get(game_id).unwrap().game.rules.turn_time_limit,
+            None
+        );
+
+        admin
+            .execute(
+                &mut sessions,
+                admin_id,
+                game_id,
+                AdminAction::SetTurnTimer {
+                    limit: Some(MAX_TURN_TIME_LIMIT),
+                },
+                String::new(),
+                now,
+            )
+            .unwrap();
+        assert_eq!(
+            sessions.get(game_id).unwrap().game.rules.turn_time_limit,
+            Some(MAX_TURN_TIME_LIMIT)
+        );
+    }
+
+    #[test]
+    fn test_halted_game_can_be_force_ended_or_resumed() {
+        use crate::errors::GameError;
+        use crate::sessions::SessionStatus;
+
+        let halt = |sessions: &mut SessionManager, game_id| {
+            let session = sessions.get_mut(game_id).unwrap();
+            let first = session.game.active_player;
+            session.apply_action(first, &GameAction::EndTurn).unwrap();
+            let _ = session.run(|_| -> Result<(), GameError> { panic!("resolution bug") });
+            assert_ne!(session.status, SessionStatus::Running);
+        };
+        let mut admin = AdminService::new();
+        let (admin_id, now) = (Uuid::new_v4(), SystemTime::UNIX_EPOCH);
+
+        let game = GameStateBuilder::new().build();
+        let second = game.turn_order[1];
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(game);
+        halt(&mut sessions, game_id);
+        admin
+            .execute(
+                &mut sessions,
+                admin_id,
+                game_id,
+                AdminAction::ForceEnd { winner: second },
+                "halted".to_string(),
+                now,
+            )
+            .unwrap();
+        assert_eq!(sessions.get(game_id).unwrap().game.winner(), Some(second));
+
+        let game = GameStateBuilder::new().build();
+        let first = game.active_player;
+        let game_id = sessions.start(game);
+        assert!(matches!(
+            admin.execute(
+                &mut sessions,
+                admin_id,
+                game_id,
+                AdminAction::Resume,
+                "not halted".to_string(),
+                now
+            ),
+            Err(AdminError::NotHalted)
+        ));
+        halt(&mut sessions, game_id);
+        for action in [AdminAction::Rollback { events: 1 }, AdminAction::Resume] {
+            admin
+                .execute(
+                    &mut sessions,
+                    admin_id,
+                    game_id,
+                    action,
+                    "bad turn".to_string(),
+                    now,
+                )
+                .unwrap();
+        }
+        let session = sessions.get_mut(game_id).unwrap();
+        assert_eq!(session.status, SessionStatus::Running);
+        assert_eq!(session.game.active_player, first);
+        session.apply_action(first, &GameAction::EndTurn).unwrap();
+        assert_eq!(admin.audit_for_game(game_id).count(), 2);
+    }
+
+    #[test]
+    fn test_card_pool_changes_reach_new_games_and_matchmaking() {
+        use crate::cards::CardRegistry;
+        use crate::errors::{QueueError, ValidationError};
+        use crate::matchmaking::{DeckRules, MatchmakingQueue};
+        use crate::models::Deck;
+        use crate::rating::Rating;
+        use crate::testing::CardBuilder;
+
+        let golem = CardBuilder::new("Ice Golem").cost(5).power(6).build();
+        let printed = CardRegistry::new([golem.clone(), CardBuilder::new("Rope").build()]);
+        let mut errata = ErrataTable::new();
+        let mut admin = AdminService::new();
+        let (admin_id, now) = (Uuid::new_v4(), SystemTime::UNIX_EPOCH);
+        let amend = |name: &str, errata| AdminAction::AmendCard {
+            name: name.to_string(),
+            errata,
+        };
+
+        admin
+            .amend_cards(
+                &mut errata,
+                admin_id,
+                amend(
+                    "Ice Golem",
+                    Some(CardErrata {
+                        cost: Some(6),
+                        ..CardErrata::default()
+                    }),
+                ),
+                "too strong".to_string(),
+                now,
+            )
+            .unwrap();
+        admin
+            .amend_cards(
+                &mut errata,
+                admin_id,
+                AdminAction::DisableCard {
+                    name: "Rope".to_string(),
+                },
+                "crash on play".to_string(),
+                now,
+            )
+            .unwrap();
+        assert!(matches!(
+            admin.amend_cards(
+                &mut errata,
+                admin_id,
+                AdminAction::SetTurnTimer { limit: None },
+                String::new(),
+                now
+            ),
+            Err(AdminError::WrongScope)
+        ));
+
+        let registry = errata.registry(&printed);
+        assert_eq!(registry.len(), 1);
+        let served = registry.get(&golem.id).unwrap();
+        assert_eq!((served.cost, served.power), (6, 6));
+
+        let deck = |names: &[&str]| Deck {
+            cards: names
+                .iter()
+                .map(|name| CardBuilder::new(name).build())
+                .collect(),
+            owner_id: Uuid::new_v4(),
+        };
+        let telemetry = std::sync::Arc::new(ErrorTelemetry::new());
+        let mut queue = MatchmakingQueue::new().with_telemetry(telemetry.clone());
+        let rating = Rating::new(1500.0, 50.0, 0.06);
+        let moderation = ModerationService::new();
+        let rules = DeckRules {
+            errata: &errata,
+            bans: None,
+        };
+        assert!(matches!(
+            queue.enqueue_with_deck(
+                Uuid::new_v4(),
+                rating,
+                &deck(&["Ice Golem", "Rope"]),
+                rules,
+                &moderation,
+                now
+            ),
+            Err(QueueError::InvalidDeck(ValidationError::DisabledCard(name))) if name == "Rope"
+        ));
+        assert!(matches!(
+            queue.enqueue_with_deck(
+                Uuid::new_v4(),
+                rating,
+                &deck(&["Ice Golem"]),
+                rules,
+                &moderation,
+                now
+            ),
+            Ok(true)
+        ));
+        assert_eq!(telemetry.by_code().get("disabled_card"), Some(&1));
+
+        admin
+            .amend_cards(
+                &mut errata,
+                admin_id,
+                amend("Ice Golem", None),
+                "reverted".to_string(),
+                now,
+            )
+            .unwrap();
+        assert_eq!(errata.registry(&printed).get(&golem.id).unwrap().cost, 5);
+        assert!(admin
+            .audit_log()
+            .iter()
+            .all(|entry| entry.game_id.is_none()));
+    }
+
+    #[test]
+    fn test_ban_reaches_deck_owners_and_matchmaking_when_due() {
+        use crate::errors::{QueueError, ValidationError};
+        use crate::matchmaking::DeckRules;
+        use crate::models::Deck;
+        use crate::rating::Rating;
+        use crate::testing::CardBuilder;
+
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let deck = |owner_id, names: &[&str]| Deck {
+            cards: names
+                .iter()
+                .map(|name| CardBuilder::new(name).build())
+                .collect(),
+            owner_id,
+        };
+        let mut alices = Collection::new(alice);
+        alices
+            .decks
+            .insert("Slide".to_string(), deck(alice, &["Avalanche", "Rope"]));
+        alices
+            .decks
+            .insert("Climb".to_string(), deck(alice, &["Rope"]));
+        // Drafted decks aren't held to the constructed list
+        alices.save_deck(
+            "Draft night".to_string(),
+            deck(alice, &["Avalanche", "Avalanche"]),
+            Format::Draft,
+        );
+        let mut bobs = Collection::new(bob);
+        bobs.decks.insert("Ropes".to_string(), deck(bob, &["Rope"]));
+
+        let mut bans = BanLists::new();
+        let mut admin = AdminService::new();
+        let now = SystemTime::UNIX_EPOCH;
+        let next_week = now + Duration::from_secs(7 * 24 * 3600);
+        let notices = admin
+            .manage_bans(
+                &mut bans,
+                [&alices, &bobs],
+                Uuid::new_v4(),
+                AdminAction::SetBanStatus {
+                    format: Format::Constructed,
+                    name: "Avalanche".to_string(),
+                    status: Some(BanStatus::Banned),
+                    effective_at: next_week,
+                },
+                "dominating the ladder".to_string(),
+                now,
+            )
+            .unwrap();
+        assert_eq!(
+            notices,
+            vec![BanNotice {
+                owner_id: alice,
+                deck_name: "Slide".to_string(),
+                format: Format::Constructed,
+                cards: vec![("Avalanche".to_string(), BanStatus::Banned)],
+                effective_at: next_week,
+            }]
+        );
+        assert!(matches!(
+            notices[0].to_message(),
+            ServerMessage::DeckBanNotice { effective_at_ms, .. } if effective_at_ms == unix_millis(next_week)
+        ));
+        assert_eq!(admin.audit_log().len(), 1);
+
+        let list = bans.list(Format::Constructed);
+        let mut queue = MatchmakingQueue::new();
+        let rating = Rating::new(1500.0, 50.0, 0.06);
+        let errata = ErrataTable::new();
+        let rules = DeckRules {
+            errata: &errata,
+            bans: Some(&list),
+        };
+        let moderation = ModerationService::new();
+        let slide = &alices.decks["Slide"];
+        assert!(matches!(
+            queue.enqueue_with_deck(alice, rating, slide, rules, &moderation, now),
+            Ok(true)
+        ));
+        queue.dequeue(alice);
+        assert!(matches!(
+            queue.enqueue_with_deck(alice, rating, slide, rules, &moderation, next_week),
+            Err(QueueError::InvalidDeck(ValidationError::BannedCard(name))) if name == "Avalanche"
+        ));
+    }
+
+    #[test]
+    fn test_sanctions_are_managed_and_audited() {
+        use crate::errors::ModerationError;
+
+        let mut moderation = ModerationService::new();
+        let mut admin = AdminService::new();
+        let (admin_id, player) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = SystemTime::UNIX_EPOCH;
+        let mute = admin
+            .moderate(
+                &mut moderation,
+                admin_id,
+                AdminAction::IssueSanction {
+                    player_id: player,
+                    kind: SanctionKind::ChatMute,
+                    duration: Some(Duration::from_secs(3600)),
+                },
+                "slurs in chat".to_string(),
+                now,
+            )
+            .unwrap()
+            .unwrap();
+        let sanction = &moderation.history(player)[0];
+        assert_eq!(
+            (sanction.issued_by, sanction.reason.as_str()),
+            (admin_id, "slurs in chat")
+        );
+        assert!(moderation.check_chat(player, now).is_err());
+
+        admin
+            .moderate(
+                &mut moderation,
+                admin_id,
+                AdminAction::RevokeSanction { sanction_id: mute },
+                "appeal upheld".to_string(),
+                now,
+            )
+            .unwrap();
+        assert!(moderation.check_chat(player, now).is_ok());
+        assert!(matches!(
+            admin.moderate(
+                &mut moderation,
+                admin_id,
+                AdminAction::RevokeSanction {
+                    sanction_id: Uuid::new_v4()
+                },
+                String::new(),
+                now
+            ),
+            Err(AdminError::Moderation(ModerationError::SanctionNotFound))
+        ));
+        assert!(matches!(
+            admin.moderate(
+                &mut moderation,
+                admin_id,
+                AdminAction::SetTurnTimer { limit: None },
+                String::new(),
+                now
+            ),
+            Err(AdminError::WrongScope)
+        ));
+        assert_eq!(admin.audit_log().len(), 2);
+    }
+}
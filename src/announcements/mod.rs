@@ -0,0 +1,163 @@
+// src/announcements/mod.rs
+use crate::errors::AnnouncementError;
+use crate::messages::LocalizedMessage;
+use crate::networking::ServerMessage;
+use std::collections::HashSet;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Announcement {
+    pub id: Uuid,
+    pub key: String,  // Localization key clients look up
+    pub text: String, // Shown when a client has no translation for the key
+    pub posted_by: Uuid,
+    pub starts_at: SystemTime,
+    pub ends_at: Option<SystemTime>,
+    // Also shown to players as they connect while it is live
+    pub motd: bool,
+}
+
+impl Announcement {
+    pub fn is_live(&self, now: SystemTime) -> bool {
+        self.starts_at <= now && self.ends_at.is_none_or(|end| now < end)
+    }
+
+    pub fn to_message(&self) -> ServerMessage {
+        ServerMessage::Announcement {
+            id: self.id,
+            message: LocalizedMessage::new(&self.key),
+            text: self.text.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AnnouncementBoard {
+    announcements: Vec<Announcement>,
+    broadcast: HashSet<Uuid>,
+}
+
+impl AnnouncementBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Schedules an announcement. It goes out to everyone connected on the
+    // first poll after `starts_at`.
+    pub fn post(
+        &mut self,
+        admin_id: Uuid,
+        key: String,
+        text: String,
+        starts_at: SystemTime,
+        ends_at: Option<SystemTime>,
+        motd: bool,
+    ) -> Result<Uuid, AnnouncementError> {
+        if ends_at.is_some_and(|end| end <= starts_at) {
+            return Err(AnnouncementError::EndsBeforeStart);
+        }
+        let announcement = Announcement {
+            id: Uuid::new_v4(),
+            key,
+            text,
+            posted_by: admin_id,
+            starts_at,
+            ends_at,
+            motd,
+        };
+        let id = announcement.id;
+        self.announcements.push(announcement);
+        Ok(id)
+    }
+
+    pub fn withdraw(&mut self, id: Uuid) -> Result<(), AnnouncementError> {
+        let before = self.announcements.len();
+        self.announcements
+            .retain(|announcement| announcement.id != id);
+        if self.announcements.len() == before {
+            return Err(AnnouncementError::NotFound);
+        }
+        self.broadcast.remove(&id);
+        Ok(())
+    }
+
+    // Live announcements not broadcast yet, marked as sent. Called on a
+    // timer by the server, which sends the messages to every connection.
+    pub fn due(&mut self, now: SystemTime) -> Vec<ServerMessage> {
+        let due: Vec<ServerMessage> = self
+            .announcements
+            .iter()
+            .filter(|announcement| {
+                announcement.is_live(now) && !self.broadcast.contains(&announcement.id)
+            })
+            .map(Announcement::to_message)
+            .collect();
+        for message in &due {
+            if let ServerMessage::Announcement { id, .. } = message {
+                self.broadcast.insert(*id);
+            }
+        }
+        self.announcements
+            .retain(|announcement| announcement.ends_at.is_none_or(|end| now < end));
+        let announcements = &self.announcements;
+        self.broadcast.retain(|id| {
+            announcements
+                .iter()
+                .any(|announcement| announcement.id == *id)
+        });
+        due
+    }
+
+    // What a newly connected client is shown
+    pub fn motd(&self, now: SystemTime) -> Vec<ServerMessage> {
+        self.announcements
+            .iter()
+            .filter(|announcement| announcement.motd && announcement.is_live(now))
+            .map(Announcement::to_message)
+            .collect()
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod announcements_tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_scheduled_announcement_broadcasts_once_and_expires() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let end = start + Duration::from_secs(60);
+        let mut board = AnnouncementBoard::new();
+        assert_eq!(
+            board.post(
+                Uuid::new_v4(),
+                "k".into(),
+                "t".into(),
+                end,
+                Some(start),
+                false
+            ),
+            Err(AnnouncementError::EndsBeforeStart)
+        );
+        board
+            .post(
+                Uuid::new_v4(),
+                "maintenance.tonight".into(),
+                "Maintenance tonight".into(),
+                start,
+                Some(end),
+                true,
+            )
+            .unwrap();
+
+        assert!(board.due(SystemTime::UNIX_EPOCH).is_empty());
+        assert_eq!(board.due(start).len(), 1);
+        assert!(board.due(start + Duration::from_secs(1)).is_empty());
+        assert_eq!(board.motd(start + Duration::from_secs(30)).len(), 1);
+
+        board.due(end);
+        assert!(board.motd(end).is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+// src/bots/mod.rs
+// Hosting for third-party AI. A bot only ever sees its PlayerView and
+// answers with one action at a time. The runner gives each decision a time
+// limit and contains failures: a bot that panics or plays something illegal
+// passes the turn, and one that times out is dropped and passes from then on.
+use crate::errors::GameError;
+use crate::game_state::{GameAction, GameState};
+use crate::view::PlayerView;
+use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub const DEFAULT_DECISION_LIMIT: Duration = Duration::from_millis(200);
+
+// Turns end after this many actions even if the bot keeps going
+pub const MAX_ACTIONS_PER_TURN: u32 = 32;
+
+pub trait AscentBot: Send {
+    fn name(&self) -> &str;
+    fn decide(&mut self, view: &PlayerView) -> GameAction;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotFault {
+    Panicked,
+    TimedOut,
+    IllegalAction,
+}
+
+struct HostedBot {
+    // None once the bot has been dropped for timing out
+    bot: Option<Box<dyn AscentBot>>,
+    faults: Vec<BotFault>,
+}
+
+pub struct BotRunner {
+    seats: HashMap<Uuid, HostedBot>,
+    decision_limit: Duration,
+}
+
+impl Default for BotRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BotRunner {
+    pub fn new() -> Self {
+        Self {
+            seats: HashMap::new(),
+            decision_limit: DEFAULT_DECISION_LIMIT,
+        }
+    }
+
+    pub fn with_decision_limit(mut self, limit: Duration) -> Self {
+        self.decision_limit = limit;
+        self
+    }
+
+    // Puts a bot in the seat of `player_id`, whether that seat came from
+    // matchmaking or a simulation
+    pub fn seat(&mut self, player_id: Uuid, bot: Box<dyn AscentBot>) {
+        self.seats.insert(
+            player_id,
+            HostedBot {
+                bot: Some(bot),
+                faults: Vec::new(),
+            },
+        );
+    }
+
+    pub fn hosts(&self, player_id: Uuid) -> bool {
+        self.seats.contains_key(&player_id)
+    }
+
+    pub fn faults(&self, player_id: Uuid) -> &[BotFault] {
+        self.seats
+            .get(&player_id)
+            .map_or(&[], |hosted| hosted.faults.as_slice())
+    }
+
+    // Asks the bot on the thread it runs on. Anything other than an answer
+    // in time counts as a pass.
+    pub fn decide(&mut self, player_id: Uuid, view: &PlayerView) -> GameAction {
+        let Some(hosted) = self.seats.get_mut(&player_id) else {
+            return GameAction::EndTurn;
+        };
+        let Some(mut bot) = hosted.bot.take() else {
+            return GameAction::EndTurn;
+        };
+        let (sender, receiver) = mpsc::channel();
+        let view = view.clone();
+        std::thread::spawn(move || {
+            let decision = catch_unwind(AssertUnwindSafe(|| bot.decide(&view)));
+            let _ = sender.send((bot, decision));
+        });
+        match receiver.recv_timeout(self.decision_limit) {
+            Ok((bot, Ok(action))) => {
+                hosted.bot = Some(bot);
+                action
+            }
+            Ok((bot, Err(_))) => {
+                tracing::warn!(%player_id, bot = bot.name(), "bot panicked");
+                hosted.bot = Some(bot);
+                hosted.faults.push(BotFault::Panicked);
+                GameAction::EndTurn
+            }
+            Err(_) => {
+                tracing::warn!(%player_id, "bot timed out and was dropped");
+                hosted.faults.push(BotFault::TimedOut);
+                GameAction::EndTurn
+            }
+        }
+    }
+
+    // Plays the active player's whole turn if a bot holds that seat.
+    // Returns false when the seat isn't one of ours.
+    pub fn play_turn(&mut self, game_state: &mut GameState) -> Result<bool, GameError> {
+        let player_id = game_state.active_player;
+        if !self.hosts(player_id) {
+            return Ok(false);
+        }
+        for _ in 0..MAX_ACTIONS_PER_TURN {
+            let action = self.decide(player_id, &game_state.view_for(player_id));
+            if action == GameAction::EndTurn {
+                break;
+            }
+            if game_state.apply_action(player_id, &action).is_err() {
+                if let Some(hosted) = self.seats.get_mut(&player_id) {
+                    hosted.faults.push(BotFault::IllegalAction);
+                }
+                break;
+            }
+            if game_state.winner().is_some() {
+                return Ok(true);
+            }
+        }
+        game_state.end_turn(player_id)?;
+        Ok(true)
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod bots_tests {
+    use super::*;
+    use crate::testing::GameStateBuilder;
+
+    struct Panicky;
+
+    impl AscentBot for Panicky {
+        fn name(&self) -> &str {
+            "panicky"
+        }
+
+        fn decide(&mut self, _view: &PlayerView) -> GameAction {
+            panic!("bad bot")
+        }
+    }
+
+    struct Slow;
+
+    impl AscentBot for Slow {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn decide(&mut self, _view: &PlayerView) -> GameAction {
+            std::thread::sleep(Duration::from_secs(5));
+            GameAction::EndTurn
+        }
+    }
+
+    #[test]
+    fn test_failing_bots_pass_their_turn() {
+        let mut game_state = GameStateBuilder::new().build();
+        let (first, second) = (game_state.turn_order[0], game_state.turn_order[1]);
+        let mut runner = BotRunner::new().with_decision_limit(Duration::from_millis(500));
+        runner.seat(first, Box::new(Panicky));
+        runner.seat(second, Box::new(Slow));
+
+        assert_eq!(game_state.active_player, first);
+        assert!(runner.play_turn(&mut game_state).unwrap());
+        assert_eq!(runner.faults(first), [BotFault::Panicked]);
+        assert_eq!(game_state.active_player, second);
+
+        assert!(runner.play_turn(&mut game_state).unwrap());
+        assert_eq!(runner.faults(second), [BotFault::TimedOut]);
+        // Dropped bots pass straight away without another fault
+        let view = game_state.view_for(second);
+        assert_eq!(runner.decide(second, &view), GameAction::EndTurn);
+        assert_eq!(runner.faults(second).len(), 1);
+    }
+}
@@ -0,0 +1,189 @@
+// src/cards/bans.rs
+// Banned and restricted cards, one list per format. Changes are scheduled
+// with the date they take effect, so a ban can be announced ahead of time
+// and decks keep working until then. Like errata, entries go by card name
+// and cover every copy.
+use crate::errors::ValidationError;
+use crate::models::{Deck, Format};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BanStatus {
+    Banned,
+    // At most one copy per deck
+    Restricted,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BanChange {
+    pub name: String,
+    // None lifts whatever the card had before
+    pub status: Option<BanStatus>,
+    pub effective_at: SystemTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct BanList {
+    pub format: Format,
+    // Oldest first; of two changes at the same moment the later one wins
+    changes: Vec<BanChange>,
+}
+
+impl BanList {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn schedule(&mut self, name: &str, status: Option<BanStatus>, effective_at: SystemTime) {
+        let index = self
+            .changes
+            .partition_point(|change| change.effective_at <= effective_at);
+        self.changes.insert(
+            index,
+            BanChange {
+                name: name.to_string(),
+                status,
+                effective_at,
+            },
+        );
+    }
+
+    pub fn status(&self, name: &str, at: SystemTime) -> Option<BanStatus> {
+        self.changes
+            .iter()
+            .take_while(|change| change.effective_at <= at)
+            .filter(|change| change.name == name)
+            .last()
+            .and_then(|change| change.status)
+    }
+
+    // Changes that haven't taken effect yet, soonest first
+    pub fn upcoming(&self, now: SystemTime) -> impl Iterator<Item = &BanChange> {
+        self.changes
+            .iter()
+            .filter(move |change| change.effective_at > now)
+    }
+
+    // Cards in the deck the list would turn it away for at `at`, by name
+    pub fn violations(&self, deck: &Deck, at: SystemTime) -> Vec<(String, BanStatus)> {
+        let mut copies: BTreeMap<&str, usize> = BTreeMap::new();
+        for card in deck.cards.iter() {
+            *copies.entry(card.name.as_str()).or_default() += 1;
+        }
+        copies
+            .into_iter()
+            .filter_map(|(name, count)| match self.status(name, at)? {
+                BanStatus::Banned => Some((name.to_string(), BanStatus::Banned)),
+                BanStatus::Restricted if count > 1 => {
+                    Some((name.to_string(), BanStatus::Restricted))
+                }
+                BanStatus::Restricted => None,
+            })
+            .collect()
+    }
+
+    pub fn check_deck(&self, deck: &Deck, now: SystemTime) -> Result<(), ValidationError> {
+        match self.violations(deck, now).into_iter().next() {
+            Some((name, BanStatus::Banned)) => Err(ValidationError::BannedCard(name)),
+            Some((name, BanStatus::Restricted)) => Err(ValidationError::RestrictedCard(name)),
+            None => Ok(()),
+        }
+    }
+
+    // Deck::validate for the list's format with the list applied on top
+    pub fn validate(&self, deck: &Deck, now: SystemTime) -> Result<(), ValidationError> {
+        deck.validate(self.format)?;
+        self.check_deck(deck, now)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BanLists {
+    lists: HashMap<Format, BanList>,
+}
+
+impl BanLists {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Formats without a list yet get an empty one
+    pub fn list(&self, format: Format) -> BanList {
+        self.lists
+            .get(&format)
+            .cloned()
+            .unwrap_or_else(|| BanList::new(format))
+    }
+
+    pub fn list_mut(&mut self, format: Format) -> &mut BanList {
+        self.lists
+            .entry(format)
+            .or_insert_with(|| BanList::new(format))
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod bans_tests {
+    use super::*;
+    use crate::models::Rarity;
+    use crate::testing::CardBuilder;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_scheduled_bans_apply_from_their_date() {
+        let names = (0..27)
+            .map(|i| format!("Ridge {}", i))
+            .chain(["Piton", "Avalanche", "Avalanche"].map(String::from));
+        let deck = Deck {
+            cards: names
+                .map(|name| CardBuilder::new(&name).rarity(Rarity::Common).build())
+                .collect::<Vec<_>>()
+                .into(),
+            owner_id: Uuid::new_v4(),
+        };
+        let now = SystemTime::UNIX_EPOCH;
+        let next_week = now + Duration::from_secs(7 * 24 * 3600);
+        let mut lists = BanLists::new();
+        let list = lists.list_mut(Format::Constructed);
+        list.schedule("Avalanche", Some(BanStatus::Restricted), now);
+        list.schedule("Piton", Some(BanStatus::Banned), next_week);
+
+        // Piton is only out from next week
+        let list = lists.list(Format::Constructed);
+        assert!(matches!(
+            list.check_deck(&deck, now),
+            Err(ValidationError::RestrictedCard(name)) if name == "Avalanche"
+        ));
+        assert_eq!(list.upcoming(now).count(), 1);
+        assert_eq!(
+            list.violations(&deck, next_week),
+            vec![
+                ("Avalanche".to_string(), BanStatus::Restricted),
+                ("Piton".to_string(), BanStatus::Banned),
+            ]
+        );
+
+        lists
+            .list_mut(Format::Constructed)
+            .schedule("Avalanche", None, now);
+        let list = lists.list(Format::Constructed);
+        assert_eq!(list.status("Avalanche", now), None);
+        assert!(list.validate(&deck, now).is_ok());
+        assert!(matches!(
+            list.validate(&deck, next_week),
+            Err(ValidationError::BannedCard(name)) if name == "Piton"
+        ));
+        assert!(lists
+            .list(Format::Draft)
+            .check_deck(&deck, next_week)
+            .is_ok());
+    }
+}
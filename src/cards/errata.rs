@@ -0,0 +1,106 @@
+// src/cards/errata.rs
+// Hotfixes to the card pool that take effect without a deploy. Cards are
+// matched by name, the same way deck building counts copies, so an entry
+// covers every printed copy. Games already running keep the cards they
+// started with; only new games see a change.
+use crate::cards::CardRegistry;
+use crate::effects::Effect;
+use crate::errors::ValidationError;
+use crate::models::{Card, Deck};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+// Replacement stats for a card; fields left as None keep the printed value
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CardErrata {
+    pub cost: Option<u32>,
+    pub power: Option<u32>,
+    pub effects: Option<Vec<Effect>>,
+}
+
+impl CardErrata {
+    pub fn apply(&self, card: &mut Card) {
+        if let Some(cost) = self.cost {
+            card.cost = cost;
+        }
+        if let Some(power) = self.power {
+            card.power = power;
+        }
+        if let Some(effects) = &self.effects {
+            card.effects = effects.clone();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ErrataTable {
+    disabled: HashSet<String>,
+    amended: HashMap<String, CardErrata>,
+}
+
+impl ErrataTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn disable(&mut self, name: &str) -> bool {
+        self.disabled.insert(name.to_string())
+    }
+
+    pub fn enable(&mut self, name: &str) -> bool {
+        self.disabled.remove(name)
+    }
+
+    pub fn is_disabled(&self, name: &str) -> bool {
+        self.disabled.contains(name)
+    }
+
+    // None goes back to the printed card
+    pub fn amend(&mut self, name: &str, errata: Option<CardErrata>) -> Option<CardErrata> {
+        match errata {
+            Some(errata) => self.amended.insert(name.to_string(), errata),
+            None => self.amended.remove(name),
+        }
+    }
+
+    pub fn errata_for(&self, name: &str) -> Option<&CardErrata> {
+        self.amended.get(name)
+    }
+
+    pub fn apply(&self, card: &mut Card) {
+        if let Some(errata) = self.amended.get(&card.name) {
+            errata.apply(card);
+        }
+    }
+
+    // Matchmaking turns away decks with any disabled card in them
+    pub fn check_deck(&self, deck: &Deck) -> Result<(), ValidationError> {
+        match deck.cards.iter().find(|card| self.is_disabled(&card.name)) {
+            Some(card) => Err(ValidationError::DisabledCard(card.name.clone())),
+            None => Ok(()),
+        }
+    }
+
+    // Brings a deck's cards up to date before it goes into a new game
+    pub fn apply_to_deck(&self, deck: &mut Deck) {
+        for card in deck.cards.iter_mut() {
+            self.apply(card);
+        }
+    }
+
+    // A copy of the registry for a new game: disabled cards left out and
+    // the rest with their errata applied
+    pub fn registry(&self, printed: &CardRegistry) -> CardRegistry {
+        CardRegistry::new(
+            printed
+                .definitions()
+                .filter(|card| !self.is_disabled(&card.name))
+                .map(|card| {
+                    let mut card = card.clone();
+                    self.apply(&mut card);
+                    card
+                }),
+        )
+    }
+}
@@ -0,0 +1,143 @@
+// src/cards/labs.rs
+// Experimental cards that are only let into some games while a new
+// mechanic is tried out. A labs card can be limited to certain formats,
+// to a share of matches, or both. Whether a match is in the share is
+// worked out from its id, so every node gives the same answer and
+// submitting the deck again for the same match can't reroll it.
+use crate::errors::ValidationError;
+use crate::models::{Deck, Format};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabsRule {
+    // Empty means every format
+    pub formats: HashSet<Format>,
+    // Share of matches the card may be played in, 0 to 100
+    pub match_percent: u8,
+}
+
+impl LabsRule {
+    pub fn allows(&self, name: &str, format: Format, match_id: Uuid) -> bool {
+        if !self.formats.is_empty() && !self.formats.contains(&format) {
+            return false;
+        }
+        rollout_bucket(name, match_id) < u64::from(self.match_percent.min(100))
+    }
+}
+
+// 0..100 for a card in a match. Hashed with the card's name too, so two
+// labs cards at 10% don't land in the same tenth of matches.
+fn rollout_bucket(name: &str, match_id: Uuid) -> u64 {
+    match_id
+        .as_bytes()
+        .iter()
+        .chain(name.as_bytes())
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+        })
+        % 100
+}
+
+// Keyed by card name like the errata table, so a rule covers every copy
+#[derive(Debug, Clone, Default)]
+pub struct LabsTable {
+    rules: HashMap<String, LabsRule>,
+}
+
+impl LabsTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // None takes the card out of labs and lets it into every game
+    pub fn set(&mut self, name: &str, rule: Option<LabsRule>) -> Option<LabsRule> {
+        match rule {
+            Some(rule) => self.rules.insert(name.to_string(), rule),
+            None => self.rules.remove(name),
+        }
+    }
+
+    pub fn rule_for(&self, name: &str) -> Option<&LabsRule> {
+        self.rules.get(name)
+    }
+
+    pub fn is_labs(&self, name: &str) -> bool {
+        self.rules.contains_key(name)
+    }
+
+    // Names of the labs cards in a deck, sorted and without repeats
+    pub fn labs_cards(&self, deck: &Deck) -> Vec<String> {
+        let mut names: Vec<String> = deck
+            .cards
+            .iter()
+            .filter(|card| self.is_labs(&card.name))
+            .map(|card| card.name.clone())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    // Run by SessionManager::start_match once the match id is known
+    pub fn check_deck(
+        &self,
+        deck: &Deck,
+        format: Format,
+        match_id: Uuid,
+    ) -> Result<(), ValidationError> {
+        for name in self.labs_cards(deck) {
+            if !self.rules[&name].allows(&name, format, match_id) {
+                return Err(ValidationError::LabsCard(name));
+            }
+        }
+        Ok(())
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod labs_tests {
+    use super::*;
+    use crate::testing::CardBuilder;
+
+    #[test]
+    fn test_labs_cards_limited_by_format_and_share_of_matches() {
+        let deck = Deck {
+            cards: vec![
+                CardBuilder::new("Rope").build(),
+                CardBuilder::new("Sky Anchor").build(),
+            ]
+            .into(),
+            owner_id: Uuid::new_v4(),
+        };
+        let mut labs = LabsTable::new();
+        labs.set(
+            "Sky Anchor",
+            Some(LabsRule {
+                formats: HashSet::from([Format::Draft]),
+                match_percent: 100,
+            }),
+        );
+        assert_eq!(labs.labs_cards(&deck), vec!["Sky Anchor".to_string()]);
+        let match_id = Uuid::new_v4();
+        assert!(labs.check_deck(&deck, Format::Draft, match_id).is_ok());
+        assert!(matches!(
+            labs.check_deck(&deck, Format::Constructed, match_id),
+            Err(ValidationError::LabsCard(name)) if name == "Sky Anchor"
+        ));
+
+        let rule = LabsRule {
+            formats: HashSet::new(),
+            match_percent: 25,
+        };
+        let allowed = (0..2_000)
+            .filter(|_| rule.allows("Sky Anchor", Format::Constructed, Uuid::new_v4()))
+            .count();
+        assert!((300..700).contains(&allowed), "{allowed} of 2000");
+        assert_eq!(
+            rule.allows("Sky Anchor", Format::Draft, match_id),
+            rule.allows("Sky Anchor", Format::Draft, match_id)
+        );
+    }
+}
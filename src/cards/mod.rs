@@ -0,0 +1,83 @@
+// src/cards/mod.rs
+use crate::models::Card;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub mod bans;
+pub mod errata;
+pub mod labs;
+pub mod text;
+pub mod versions;
+
+// Card definitions by id. Effects that create or reshape cards look up
+// what to produce here instead of carrying a full card around.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CardRegistry {
+    definitions: HashMap<Uuid, Card>,
+}
+
+impl CardRegistry {
+    pub fn new(definitions: impl IntoIterator<Item = Card>) -> Self {
+        Self {
+            definitions: definitions
+                .into_iter()
+                .map(|card| (card.id, card))
+                .collect(),
+        }
+    }
+
+    pub fn insert(&mut self, definition: Card) {
+        self.definitions.insert(definition.id, definition);
+    }
+
+    pub fn get(&self, def_id: &Uuid) -> Option<&Card> {
+        self.definitions.get(def_id)
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&Card> {
+        self.definitions.values().find(|card| card.name == name)
+    }
+
+    // A new copy of a definition under its own instance id
+    pub fn instantiate(&self, def_id: &Uuid, instance_id: Uuid) -> Option<Card> {
+        self.get(def_id).map(|definition| Card {
+            id: instance_id,
+            ..definition.clone()
+        })
+    }
+
+    pub fn definitions(&self) -> impl Iterator<Item = &Card> {
+        self.definitions.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod cards_tests {
+    use super::*;
+    use crate::testing::CardBuilder;
+
+    #[test]
+    fn test_instantiate_keeps_instance_id() {
+        let definition = CardBuilder::new("Ice Golem").cost(5).power(6).build();
+        let def_id = definition.id;
+        let registry = CardRegistry::new([definition]);
+        let instance_id = Uuid::new_v4();
+
+        let card = registry.instantiate(&def_id, instance_id).unwrap();
+        assert_eq!(
+            (card.id, card.name.as_str(), card.power),
+            (instance_id, "Ice Golem", 6)
+        );
+        assert!(registry.instantiate(&Uuid::new_v4(), instance_id).is_none());
+    }
+}
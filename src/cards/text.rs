@@ -0,0 +1,549 @@
+// src/cards/text.rs
+// Rules text built from a card's effects, so the wording on a card can't
+// drift from what it does. Every phrase has a stable key and an English
+// template with `{name}` placeholders; a Localizer can swap in its own
+// template for any key and falls back to English for the rest.
+use crate::effects::{
+    BoostType, CostFilter, DrawFilter, Duration, Effect, EffectTarget, EffectValue, FieldRule,
+    ResurrectDestination, ScalingFactor, StatusKind, TargetCondition, Trigger,
+};
+use crate::models::Card;
+use crate::view::statuses::{lookup, status_id, StatusCategory};
+
+pub trait Localizer {
+    // The template for `key` in this locale, or None to use English
+    fn template(&self, key: &str) -> Option<&str>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+impl Localizer for English {
+    fn template(&self, _key: &str) -> Option<&str> {
+        None
+    }
+}
+
+fn fill(template: &str, args: &[(&str, String)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+pub struct RulesText<'a> {
+    localizer: &'a dyn Localizer,
+}
+
+impl<'a> RulesText<'a> {
+    pub fn new(localizer: &'a dyn Localizer) -> Self {
+        Self { localizer }
+    }
+
+    fn phrase(&self, key: &str, english: &str, args: &[(&str, String)]) -> String {
+        fill(self.localizer.template(key).unwrap_or(english), args)
+    }
+
+    // Singular and plural are separate keys, `<key>.one` and `<key>.many`
+    fn counted(&self, key: &str, count: u32, one: &str, many: &str) -> String {
+        if count == 1 {
+            self.phrase(&format!("{}.one", key), one, &[])
+        } else {
+            self.phrase(
+                &format!("{}.many", key),
+                many,
+                &[("count", count.to_string())],
+            )
+        }
+    }
+
+    // One sentence per effect, in the order they are listed on the card
+    pub fn card(&self, card: &Card) -> String {
+        card.effects
+            .iter()
+            .map(|effect| self.effect(effect))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn effect(&self, effect: &Effect) -> String {
+        match effect {
+            Effect::Damage(damage) => {
+                let mut text = self.phrase(
+                    "effect.damage",
+                    "Deal {amount} damage to {target}.",
+                    &[
+                        ("amount", self.amount(&damage.value)),
+                        ("target", self.target(&damage.target)),
+                    ],
+                );
+                if damage.penetrating {
+                    text.push(' ');
+                    text.push_str(&self.phrase(
+                        "effect.damage.penetrating",
+                        "Ignores shields.",
+                        &[],
+                    ));
+                }
+                if damage.ranged {
+                    text.push(' ');
+                    text.push_str(&self.phrase(
+                        "effect.damage.ranged",
+                        "Needs line of sight.",
+                        &[],
+                    ));
+                }
+                text
+            }
+            Effect::Heal(heal) => {
+                let (key, english) = if heal.over_heal {
+                    (
+                        "effect.heal.over",
+                        "Heal {target} for {amount}; healing past full becomes shield.",
+                    )
+                } else {
+                    ("effect.heal", "Heal {target} for {amount}.")
+                };
+                self.phrase(
+                    key,
+                    english,
+                    &[
+                        ("amount", self.amount(&heal.value)),
+                        ("target", self.target(&heal.target)),
+                    ],
+                )
+            }
+            Effect::Draw(draw) => {
+                let count = self.counted("count.cards", draw.cards, "a card", "{count} cards");
+                let target = self.target(&draw.target);
+                match &draw.filter {
+                    Some(filter) => self.phrase(
+                        "effect.draw.filtered",
+                        "Draw {count} with {filter} for {target}.",
+                        &[
+                            ("count", count),
+                            ("filter", self.filter(filter)),
+                            ("target", target),
+                        ],
+                    ),
+                    None => self.phrase(
+                        "effect.draw",
+                        "Draw {count} for {target}.",
+                        &[("count", count), ("target", target)],
+                    ),
+                }
+            }
+            Effect::Boost(boost) => {
+                let stat = match boost.stat {
+                    BoostType::Power => self.phrase("stat.power", "power", &[]),
+                    BoostType::Health => self.phrase("stat.health", "health", &[]),
+                    BoostType::Both => self.phrase("stat.both", "power and health", &[]),
+                };
+                self.phrase(
+                    "effect.boost",
+                    "Give {target} +{amount} {stat} {duration}.",
+                    &[
+                        ("target", self.target(&boost.target)),
+                        ("amount", self.amount(&boost.value)),
+                        ("stat", stat),
+                        ("duration", self.duration(&boost.duration)),
+                    ],
+                )
+            }
+            Effect::BuffStats(buff) => self.phrase(
+                "effect.buff",
+                "Give {target} {power} power and {health} health {duration}.",
+                &[
+                    ("target", self.target(&buff.target)),
+                    ("power", format!("{:+}", buff.power)),
+                    ("health", format!("{:+}", buff.health)),
+                    ("duration", self.duration(&buff.duration)),
+                ],
+            ),
+            Effect::ApplyStatus(status) => {
+                let id = status_id(&status.kind);
+                let is_keyword =
+                    lookup(id).is_some_and(|info| info.category == StatusCategory::Keyword);
+                // A keyword the card gives itself for good is just its name
+                if is_keyword
+                    && status.target == EffectTarget::Self_
+                    && status.duration == Duration::Permanent
+                {
+                    return format!("{}.", self.status(&status.kind));
+                }
+                self.phrase(
+                    "effect.status",
+                    "Apply {status} to {target} {duration}.",
+                    &[
+                        ("status", self.status(&status.kind)),
+                        ("target", self.target(&status.target)),
+                        ("duration", self.duration(&status.duration)),
+                    ],
+                )
+            }
+            Effect::Cleanse(cleanse) => {
+                let target = self.target(&cleanse.target);
+                match cleanse.immunity_turns {
+                    Some(turns) => self.phrase(
+                        "effect.cleanse.immunity",
+                        "Remove debuffs from {target}, who can't be debuffed {duration}.",
+                        &[
+                            ("target", target),
+                            ("duration", self.duration(&Duration::Temporary(turns))),
+                        ],
+                    ),
+                    None => self.phrase(
+                        "effect.cleanse",
+                        "Remove debuffs from {target}.",
+                        &[("target", target)],
+                    ),
+                }
+            }
+            Effect::ModifyMaxHealth(max_health) => self.phrase(
+                "effect.max_health",
+                "Give {target} {delta} max health {duration}.",
+                &[
+                    ("target", self.target(&max_health.target)),
+                    ("delta", format!("{:+}", max_health.delta)),
+                    ("duration", self.duration(&max_health.duration)),
+                ],
+            ),
+            Effect::ModifyCost(cost) => {
+                let cards = match &cost.filter {
+                    Some(filter) => self.phrase(
+                        "cards.filtered",
+                        "Cards with {filter}",
+                        &[("filter", self.filter(filter))],
+                    ),
+                    None => self.phrase("cards.all", "Cards", &[]),
+                };
+                self.phrase(
+                    "effect.cost",
+                    "{cards} in {owner} hand cost {delta} {duration}.",
+                    &[
+                        ("cards", cards),
+                        ("owner", self.owner(&cost.target)),
+                        ("delta", format!("{:+}", cost.delta)),
+                        ("duration", self.duration(&cost.duration)),
+                    ],
+                )
+            }
+            Effect::Transform(_) => self.phrase("effect.transform", "Transform a card.", &[]),
+            Effect::Mill(mill) => self.phrase(
+                "effect.mill",
+                "Mill {count} from {owner} deck.",
+                &[
+                    (
+                        "count",
+                        self.counted("count.cards", mill.count, "a card", "{count} cards"),
+                    ),
+                    ("owner", self.owner(&mill.target)),
+                ],
+            ),
+            Effect::Resurrect(resurrect) => {
+                let (key, english) = match resurrect.destination {
+                    ResurrectDestination::Hand => (
+                        "effect.resurrect.hand",
+                        "Return a card from {owner} graveyard to hand.",
+                    ),
+                    ResurrectDestination::Board => (
+                        "effect.resurrect.board",
+                        "Return a climber from {owner} graveyard to the board.",
+                    ),
+                };
+                self.phrase(key, english, &[("owner", self.owner(&resurrect.target))])
+            }
+            Effect::ExtraTurn(turn) => self.phrase(
+                "effect.extra_turn",
+                "Grant {target} an extra turn.",
+                &[("target", self.target(&turn.target))],
+            ),
+            Effect::SkipTurn(turn) => self.phrase(
+                "effect.skip_turn",
+                "Make {target} skip a turn.",
+                &[("target", self.target(&turn.target))],
+            ),
+            Effect::Scry(scry) => self.phrase(
+                "effect.scry",
+                "Look at the top {count} of {owner} deck, then put them back in any order or on the bottom.",
+                &[
+                    (
+                        "count",
+                        self.counted("count.top_cards", scry.count, "card", "{count} cards"),
+                    ),
+                    ("owner", self.owner(&scry.target)),
+                ],
+            ),
+            Effect::Overload(overload) => self.phrase(
+                "effect.overload",
+                "Overload {crystals}.",
+                &[("crystals", overload.crystals.to_string())],
+            ),
+            Effect::Field(rule) => {
+                let (key, fallback, delta) = match rule {
+                    FieldRule::Movement(delta) => (
+                        "field.movement",
+                        "While in play, every player gets {delta} movement.",
+                        delta,
+                    ),
+                    FieldRule::Power(delta) => (
+                        "field.power",
+                        "While in play, every player gets {delta} power.",
+                        delta,
+                    ),
+                    FieldRule::Cost(delta) => (
+                        "field.cost",
+                        "While in play, cards cost {delta} mana.",
+                        delta,
+                    ),
+                };
+                self.phrase(key, fallback, &[("delta", format!("{delta:+}"))])
+            }
+            Effect::DestroyExpedition(_) => {
+                self.phrase("effect.destroy_expedition", "Destroy the expedition in play.", &[])
+            }
+            Effect::Triggered(triggered) => match triggered.trigger {
+                Trigger::ComboThreshold(cards) => self.phrase(
+                    "trigger.combo",
+                    "Combo {cards}: {effect}",
+                    &[
+                        ("cards", cards.to_string()),
+                        ("effect", self.effect(&triggered.effect)),
+                    ],
+                ),
+            },
+        }
+    }
+
+    fn target(&self, target: &EffectTarget) -> String {
+        match target {
+            EffectTarget::Self_ => self.phrase("target.self", "you", &[]),
+            EffectTarget::Specific(_) => self.phrase("target.specific", "a chosen player", &[]),
+            EffectTarget::Multiple(_) => self.phrase("target.multiple", "chosen players", &[]),
+            EffectTarget::AllPlayers(_) => self.phrase("target.all", "all players", &[]),
+            EffectTarget::Random(count) => self.counted(
+                "target.random",
+                *count,
+                "a random player",
+                "{count} random players",
+            ),
+            EffectTarget::Adjacent => self.phrase("target.adjacent", "adjacent players", &[]),
+            EffectTarget::Area { radius, .. } => self.phrase(
+                "target.area",
+                "everyone within {radius} tiles",
+                &[("radius", radius.to_string())],
+            ),
+            EffectTarget::Conditional {
+                condition,
+                max_targets,
+            } => {
+                let players = match condition {
+                    TargetCondition::PowerGreaterThan(power) => self.phrase(
+                        "target.power_above",
+                        "players with more than {power} power",
+                        &[("power", power.to_string())],
+                    ),
+                    TargetCondition::PowerLessThan(power) => self.phrase(
+                        "target.power_below",
+                        "players with less than {power} power",
+                        &[("power", power.to_string())],
+                    ),
+                    TargetCondition::HasEffect(effect) => self.phrase(
+                        "target.has_effect",
+                        "players affected by {effect}",
+                        &[("effect", format!("{:?}", effect).to_lowercase())],
+                    ),
+                    TargetCondition::IsRarity(rarity) => self.phrase(
+                        "target.holds_rarity",
+                        "players holding a {rarity} card",
+                        &[("rarity", format!("{:?}", rarity).to_lowercase())],
+                    ),
+                };
+                match max_targets {
+                    Some(max) => self.phrase(
+                        "target.up_to",
+                        "up to {max} {players}",
+                        &[("max", max.to_string()), ("players", players)],
+                    ),
+                    None => players,
+                }
+            }
+            EffectTarget::Allies => self.phrase("target.allies", "you and your allies", &[]),
+            EffectTarget::Enemies => self.phrase("target.enemies", "each enemy", &[]),
+        }
+    }
+
+    // "your" or "<target>'s", for whose hand, deck or graveyard
+    fn owner(&self, target: &EffectTarget) -> String {
+        match target {
+            EffectTarget::Self_ => self.phrase("owner.self", "your", &[]),
+            other => self.phrase(
+                "owner.other",
+                "{target}'s",
+                &[("target", self.target(other))],
+            ),
+        }
+    }
+
+    fn amount(&self, value: &EffectValue) -> String {
+        let base = value.base.to_string();
+        let Some(scaling) = &value.scaling else {
+            return base;
+        };
+        let (key, english, factor) = match scaling {
+            ScalingFactor::MountainLevel(factor) => (
+                "amount.per_level",
+                "{base} plus {factor} per mountain level",
+                factor,
+            ),
+            ScalingFactor::CardsInHand(factor) => (
+                "amount.per_card_in_hand",
+                "{base} plus {factor} per card in hand",
+                factor,
+            ),
+            ScalingFactor::CardsPlayed(factor) => (
+                "amount.per_card_played",
+                "{base} plus {factor} per card played this turn",
+                factor,
+            ),
+            ScalingFactor::ManaSpent(factor) => (
+                "amount.per_mana_spent",
+                "{base} plus {factor} per mana spent this turn",
+                factor,
+            ),
+        };
+        self.phrase(
+            key,
+            english,
+            &[("base", base), ("factor", factor.to_string())],
+        )
+    }
+
+    fn duration(&self, duration: &Duration) -> String {
+        match duration {
+            Duration::Temporary(turns) => {
+                self.counted("duration.turns", *turns, "for a turn", "for {count} turns")
+            }
+            Duration::UntilMountainLevel(level) => self.phrase(
+                "duration.until_level",
+                "until reaching level {level}",
+                &[("level", level.to_string())],
+            ),
+            Duration::Permanent => {
+                self.phrase("duration.permanent", "for the rest of the game", &[])
+            }
+        }
+    }
+
+    fn filter(&self, filter: &DrawFilter) -> String {
+        match filter {
+            DrawFilter::Cost(CostFilter::Equal(cost)) => self.phrase(
+                "filter.cost_equal",
+                "cost {cost}",
+                &[("cost", cost.to_string())],
+            ),
+            DrawFilter::Cost(CostFilter::LessThan(cost)) => self.phrase(
+                "filter.cost_below",
+                "cost under {cost}",
+                &[("cost", cost.to_string())],
+            ),
+            DrawFilter::Cost(CostFilter::GreaterThan(cost)) => self.phrase(
+                "filter.cost_above",
+                "cost over {cost}",
+                &[("cost", cost.to_string())],
+            ),
+            DrawFilter::Type(card_type) => self.phrase(
+                "filter.type",
+                "type {card_type}",
+                &[("card_type", format!("{:?}", card_type).to_lowercase())],
+            ),
+            DrawFilter::Rarity(rarity) => self.phrase(
+                "filter.rarity",
+                "rarity {rarity}",
+                &[("rarity", format!("{:?}", rarity).to_lowercase())],
+            ),
+        }
+    }
+
+    // Status names come from the status bar registry so cards and the bar
+    // call a status the same thing
+    fn status(&self, kind: &StatusKind) -> String {
+        let id = status_id(kind);
+        let name = lookup(id).map_or(id, |info| info.name);
+        let key = format!("status.{}", id);
+        match kind {
+            StatusKind::Poison { damage: amount }
+            | StatusKind::Weakened { power: amount }
+            | StatusKind::Slowed { movement: amount }
+            | StatusKind::Hasted { movement: amount } => self.phrase(
+                &key,
+                &format!("{} {{amount}}", name),
+                &[("amount", amount.to_string())],
+            ),
+            StatusKind::DebuffImmunity | StatusKind::Guard | StatusKind::Stealth => {
+                self.phrase(&key, name, &[])
+            }
+        }
+    }
+}
+
+// The card's text in English
+pub fn rules_text(card: &Card) -> String {
+    RulesText::new(&English).card(card)
+}
+
+// TESTS
+#[cfg(test)]
+mod text_tests {
+    use super::*;
+    use crate::effects::{DamageEffect, StatusEffect, TriggeredEffect};
+    use crate::testing::CardBuilder;
+    use std::collections::HashMap;
+
+    struct Catalog(HashMap<&'static str, &'static str>);
+
+    impl Localizer for Catalog {
+        fn template(&self, key: &str) -> Option<&str> {
+            self.0.get(key).copied()
+        }
+    }
+
+    #[test]
+    fn test_text_follows_effects_and_localizes_by_key() {
+        let card = CardBuilder::new("Sentinel")
+            .effect(Effect::ApplyStatus(StatusEffect {
+                kind: StatusKind::Guard,
+                target: EffectTarget::Self_,
+                duration: Duration::Permanent,
+                priority: 0,
+            }))
+            .effect(Effect::Triggered(TriggeredEffect {
+                trigger: Trigger::ComboThreshold(3),
+                effect: Box::new(Effect::Damage(DamageEffect {
+                    value: EffectValue {
+                        base: 2,
+                        scaling: Some(ScalingFactor::CardsPlayed(1.0)),
+                    },
+                    target: EffectTarget::Random(2),
+                    penetrating: false,
+                    ranged: true,
+                    priority: 0,
+                })),
+            }))
+            .build();
+
+        assert_eq!(
+            rules_text(&card),
+            "Guard. Combo 3: Deal 2 plus 1 per card played this turn damage to 2 random \
+             players. Needs line of sight."
+        );
+
+        let german = Catalog(HashMap::from([
+            ("status.guard", "Wache"),
+            ("target.random.many", "{count} zufällige Spieler"),
+        ]));
+        let text = RulesText::new(&german).card(&card);
+        assert!(text.starts_with("Wache. Combo 3:"));
+        assert!(text.contains("damage to 2 zufällige Spieler."));
+    }
+}
@@ -0,0 +1,172 @@
+// src/cards/versions.rs
+// Card data published under a version name so several can be live at once.
+// A balance patch is published as a new version and becomes the current one
+// for new games, while games already running (correspondence games can run
+// for weeks) keep the version they were pinned to when they started. Each
+// game holds its own copy of its pool and its version tag.
+use crate::cards::errata::ErrataTable;
+use crate::cards::CardRegistry;
+use crate::errors::CardDataError;
+use crate::game_state::GameState;
+use crate::models::Deck;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+// One published version: the card pool with its errata baked in, and the
+// errata themselves for bringing decks up to date
+#[derive(Debug, Clone)]
+pub struct CardData {
+    pub version: String,
+    pub registry: Arc<CardRegistry>,
+    pub errata: ErrataTable,
+}
+
+impl CardData {
+    // Pins a new game to this version and deals its decks with it
+    pub fn pin(&self, game_state: GameState) -> GameState {
+        let mut game_state = game_state.with_card_data(&self.version, self.registry.clone());
+        for player in game_state.players.values_mut() {
+            self.apply_to_deck(&mut player.deck);
+            for card in player.hand.iter_mut() {
+                self.errata.apply(card);
+            }
+            player.invalidate_stats();
+        }
+        game_state
+    }
+
+    pub fn apply_to_deck(&self, deck: &mut Deck) {
+        self.errata.apply_to_deck(deck);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CardDataLibrary {
+    versions: BTreeMap<String, CardData>,
+    current: Option<String>,
+}
+
+impl CardDataLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Bakes the errata into a copy of the printed pool and makes the result
+    // the version new games start on
+    pub fn publish(
+        &mut self,
+        version: &str,
+        printed: &CardRegistry,
+        errata: &ErrataTable,
+    ) -> Result<&CardData, CardDataError> {
+        if self.versions.contains_key(version) {
+            return Err(CardDataError::VersionExists(version.to_string()));
+        }
+        let data = CardData {
+            version: version.to_string(),
+            registry: Arc::new(errata.registry(printed)),
+            errata: errata.clone(),
+        };
+        self.versions.insert(version.to_string(), data);
+        self.current = Some(version.to_string());
+        Ok(&self.versions[version])
+    }
+
+    pub fn current(&self) -> Option<&CardData> {
+        self.current
+            .as_ref()
+            .and_then(|version| self.versions.get(version))
+    }
+
+    pub fn get(&self, version: &str) -> Option<&CardData> {
+        self.versions.get(version)
+    }
+
+    pub fn versions(&self) -> impl Iterator<Item = &str> {
+        self.versions.keys().map(String::as_str)
+    }
+
+    // Drops an old version once no running game is pinned to it. The
+    // current version can't be retired.
+    pub fn retire<'a>(
+        &mut self,
+        version: &str,
+        running: impl IntoIterator<Item = &'a GameState>,
+    ) -> Result<(), CardDataError> {
+        if !self.versions.contains_key(version) || self.current.as_deref() == Some(version) {
+            return Err(CardDataError::UnknownVersion(version.to_string()));
+        }
+        let games = running
+            .into_iter()
+            .filter(|game_state| game_state.rules_version.card_data == version)
+            .count();
+        if games > 0 {
+            return Err(CardDataError::InUse {
+                version: version.to_string(),
+                games,
+            });
+        }
+        self.versions.remove(version);
+        Ok(())
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod versions_tests {
+    use super::*;
+    use crate::cards::errata::CardErrata;
+    use crate::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
+
+    #[test]
+    fn test_patch_leaves_pinned_games_on_their_version() {
+        let golem = CardBuilder::new("Ice Golem").cost(5).power(6).build();
+        let printed = CardRegistry::new([golem.clone()]);
+        let mut errata = ErrataTable::new();
+        let mut library = CardDataLibrary::new();
+        library.publish("2026.1", &printed, &errata).unwrap();
+        let deal = || {
+            GameStateBuilder::new()
+                .player(
+                    PlayerBuilder::new("Alice")
+                        .deck(vec![golem.clone()])
+                        .build(),
+                )
+                .build()
+        };
+        let ladder_game = library.current().unwrap().pin(deal());
+
+        errata.amend(
+            "Ice Golem",
+            Some(CardErrata {
+                cost: Some(7),
+                ..CardErrata::default()
+            }),
+        );
+        library.publish("2026.2", &printed, &errata).unwrap();
+        let patched_game = library.current().unwrap().pin(deal());
+
+        let golem_cost = |game: &GameState| game.cards.by_name("Ice Golem").unwrap().cost;
+        assert_eq!(ladder_game.rules_version.card_data, "2026.1");
+        assert_eq!(golem_cost(&ladder_game), 5);
+        assert_eq!(golem_cost(&patched_game), 7);
+        let alice = patched_game.turn_order[0];
+        assert_eq!(patched_game.players[&alice].deck.cards[0].cost, 7);
+
+        assert_eq!(
+            library.retire("2026.1", [&ladder_game, &patched_game]),
+            Err(CardDataError::InUse {
+                version: "2026.1".to_string(),
+                games: 1
+            })
+        );
+        library.retire("2026.1", [&patched_game]).unwrap();
+        assert!(library.get("2026.1").is_none());
+        // The game carries its own copy of the pool
+        assert_eq!(golem_cost(&ladder_game), 5);
+        assert!(matches!(
+            library.publish("2026.2", &printed, &errata),
+            Err(CardDataError::VersionExists(_))
+        ));
+    }
+}
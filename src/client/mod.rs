@@ -0,0 +1,269 @@
+// src/client/mod.rs
+// Typed async client for the game protocol, for bots and integration tests.
+// Works over any byte stream: TCP in production, an in-memory duplex in
+// tests. The Hello/Welcome exchange always goes as uncompressed JSON;
+// everything after it uses whatever was negotiated.
+use crate::errors::{ClientError, ProtocolError};
+use crate::events::GameEvent;
+use crate::game_state::GameAction;
+use crate::networking::{
+    decode, encode, pack, read_frame, unpack, write_frame, Channel, ClientMessage, Compression,
+    ServerMessage, WireFormat, PROTOCOL_VERSION,
+};
+use std::collections::VecDeque;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpStream, ToSocketAddrs};
+use uuid::Uuid;
+
+pub struct AscentClient<S> {
+    stream: S,
+    format: WireFormat,
+    compression: Compression,
+    player_id: Option<Uuid>,
+    game_id: Option<Uuid>,
+    // The rest of a batch whose first event has been handed out
+    batched: VecDeque<GameEvent>,
+}
+
+impl AscentClient<TcpStream> {
+    pub async fn connect(addr: impl ToSocketAddrs, client_build: u32) -> Result<Self, ClientError> {
+        let stream = TcpStream::connect(addr)
+            .await
+            .map_err(|e| ClientError::Protocol(ProtocolError::Io(e.to_string())))?;
+        Self::handshake(stream, client_build).await
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AscentClient<S> {
+    pub async fn handshake(stream: S, client_build: u32) -> Result<Self, ClientError> {
+        let mut client = Self {
+            stream,
+            format: WireFormat::Json,
+            compression: Compression::None,
+            player_id: None,
+            game_id: None,
+            batched: VecDeque::new(),
+        };
+        client
+            .send(&ClientMessage::Hello {
+                protocol_version: PROTOCOL_VERSION,
+                client_build,
+                formats: WireFormat::supported(),
+                compression: vec![Compression::Deflate],
+            })
+            .await?;
+        match client.recv().await? {
+            ServerMessage::Welcome {
+                format,
+                compression,
+                ..
+            } => {
+                client.format = format;
+                client.compression = compression;
+                Ok(client)
+            }
+            ServerMessage::UpdateRequired { download_url, .. } => {
+                Err(ClientError::UpdateRequired { download_url })
+            }
+            other => Err(ClientError::Unexpected(Box::new(other))),
+        }
+    }
+
+    pub fn player_id(&self) -> Option<Uuid> {
+        self.player_id
+    }
+
+    pub fn game_id(&self) -> Option<Uuid> {
+        self.game_id
+    }
+
+    pub async fn send(&mut self, message: &ClientMessage) -> Result<(), ClientError> {
+        let bytes = encode(message, self.format)
+            .and_then(|bytes| pack(bytes, self.compression))
+            .map_err(ClientError::Protocol)?;
+        write_frame(&mut self.stream, &bytes)
+            .await
+            .map_err(ClientError::Protocol)
+    }
+
+    // Next message of any kind; a Rejected reply comes back as an error
+    pub async fn recv(&mut self) -> Result<ServerMessage, ClientError> {
+        let frame = read_frame(&mut self.stream)
+            .await
+            .map_err(ClientError::Protocol)?;
+        let message = unpack(&frame)
+            .and_then(|bytes| decode(&bytes, self.format))
+            .map_err(ClientError::Protocol)?;
+        match message {
+            ServerMessage::Rejected { message } => Err(ClientError::Rejected(message)),
+            message => Ok(message),
+        }
+    }
+
+    pub async fn authenticate(&mut self, token: &str) -> Result<Uuid, ClientError> {
+        self.send(&ClientMessage::Authenticate {
+            token: token.to_string(),
+        })
+        .await?;
+        match self.recv().await? {
+            ServerMessage::Authenticated { player_id } => {
+                self.player_id = Some(player_id);
+                Ok(player_id)
+            }
+            other => Err(ClientError::Unexpected(Box::new(other))),
+        }
+    }
+
+    // Joins matchmaking and waits until a game is found
+    pub async fn queue(&mut self) -> Result<Uuid, ClientError> {
+        self.send(&ClientMessage::JoinQueue).await?;
+        loop {
+            match self.recv().await? {
+                ServerMessage::Queued | ServerMessage::Announcement { .. } => {}
+                ServerMessage::GameStarted { game_id, .. } => {
+                    self.game_id = Some(game_id);
+                    return Ok(game_id);
+                }
+                other => return Err(ClientError::Unexpected(Box::new(other))),
+            }
+        }
+    }
+
+    // The server only sends each player what they are allowed to see, so
+    // the event stream is already redacted. Social traffic is skipped;
+    // None means the game was halted. Batches come out one event at a time;
+    // use `recv` to see where a batch starts and ends.
+    pub async fn next_event(&mut self) -> Result<Option<GameEvent>, ClientError> {
+        if let Some(event) = self.batched.pop_front() {
+            return Ok(Some(event));
+        }
+        loop {
+            match self.recv().await? {
+                ServerMessage::Event(event) => return Ok(Some(event)),
+                ServerMessage::EventBatch { events, .. } => {
+                    self.batched = events.into();
+                    if let Some(event) = self.batched.pop_front() {
+                        return Ok(Some(event));
+                    }
+                }
+                ServerMessage::GameHalted { .. } => return Ok(None),
+                ServerMessage::Ping { nonce, .. } => {
+                    self.send(&ClientMessage::Pong { nonce }).await?;
+                }
+                ServerMessage::TurnClock { .. } => {}
+                message if message.channel() == Channel::Social => {}
+                other => return Err(ClientError::Unexpected(Box::new(other))),
+            }
+        }
+    }
+
+    // Sends an action under a fresh request id and returns the id. Resend
+    // with `resubmit` and the same id after a timeout; the server applies
+    // it at most once.
+    pub async fn submit(&mut self, action: GameAction) -> Result<Uuid, ClientError> {
+        let request_id = Uuid::new_v4();
+        self.resubmit(request_id, action).await?;
+        Ok(request_id)
+    }
+
+    pub async fn resubmit(
+        &mut self,
+        request_id: Uuid,
+        action: GameAction,
+    ) -> Result<(), ClientError> {
+        self.send(&ClientMessage::Action { request_id, action })
+            .await
+    }
+
+    // Sends actions to be applied together or not at all, under a fresh
+    // request id that also names the batch of events they produce
+    pub async fn submit_batch(&mut self, actions: Vec<GameAction>) -> Result<Uuid, ClientError> {
+        let request_id = Uuid::new_v4();
+        self.send(&ClientMessage::ActionBatch {
+            request_id,
+            actions,
+        })
+        .await?;
+        Ok(request_id)
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod client_tests {
+    use super::*;
+    use crate::networking::{handshake, VersionPolicy};
+
+    async fn reply<S: AsyncRead + AsyncWrite + Unpin>(stream: &mut S, message: ServerMessage) {
+        let bytes = pack(
+            encode(&message, WireFormat::Json).unwrap(),
+            Compression::None,
+        )
+        .unwrap();
+        write_frame(stream, &bytes).await.unwrap();
+    }
+
+    async fn request<S: AsyncRead + AsyncWrite + Unpin>(stream: &mut S) -> ClientMessage {
+        let frame = read_frame(stream).await.unwrap();
+        decode(&unpack(&frame).unwrap(), WireFormat::Json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_client_authenticates_queues_and_plays() {
+        let (client_end, mut server) = tokio::io::duplex(4096);
+        let (player_id, game_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let server_task = tokio::spawn(async move {
+            let hello = request(&mut server).await;
+            let welcome = match handshake(&hello, &VersionPolicy::default()).unwrap() {
+                ServerMessage::Welcome { .. } => ServerMessage::Welcome {
+                    protocol_version: PROTOCOL_VERSION,
+                    format: WireFormat::Json,
+                    compression: Compression::None,
+                },
+                other => other,
+            };
+            reply(&mut server, welcome).await;
+            assert!(matches!(
+                request(&mut server).await,
+                ClientMessage::Authenticate { .. }
+            ));
+            reply(&mut server, ServerMessage::Authenticated { player_id }).await;
+            assert_eq!(request(&mut server).await, ClientMessage::JoinQueue);
+            reply(&mut server, ServerMessage::Queued).await;
+            reply(
+                &mut server,
+                ServerMessage::GameStarted {
+                    game_id,
+                    players: vec![],
+                },
+            )
+            .await;
+            reply(
+                &mut server,
+                ServerMessage::Event(GameEvent::TurnStarted {
+                    player_id,
+                    turn_number: 1,
+                }),
+            )
+            .await;
+            request(&mut server).await
+        });
+
+        let mut client = AscentClient::handshake(client_end, 1).await.unwrap();
+        assert_eq!(client.authenticate("secret").await.unwrap(), player_id);
+        assert_eq!(client.queue().await.unwrap(), game_id);
+        assert!(matches!(
+            client.next_event().await.unwrap(),
+            Some(GameEvent::TurnStarted { .. })
+        ));
+        let request_id = client.submit(GameAction::EndTurn).await.unwrap();
+        assert_eq!(
+            server_task.await.unwrap(),
+            ClientMessage::Action {
+                request_id,
+                action: GameAction::EndTurn
+            }
+        );
+    }
+}
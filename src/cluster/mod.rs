@@ -0,0 +1,295 @@
+// src/cluster/mod.rs
+// Running more than one server node. A registry shared by every node (Redis
+// or the database in production) records which instance hosts each game,
+// the gateway routes players there, and a draining instance hands its games
+// to peers before it shuts down.
+use crate::errors::ClusterError;
+use crate::sessions::SessionManager;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceInfo {
+    pub id: Uuid,
+    pub address: String,
+    pub draining: bool,
+}
+
+// Backing store for cluster membership and game placement. Every call is a
+// single atomic update so nodes never see a game owned by two instances.
+pub trait GameRegistry: Send + Sync {
+    fn register(&self, instance: InstanceInfo);
+    fn deregister(&self, instance_id: Uuid);
+    fn set_draining(&self, instance_id: Uuid, draining: bool) -> Result<(), ClusterError>;
+    fn instances(&self) -> Vec<InstanceInfo>;
+    fn assign(&self, game_id: Uuid, instance_id: Uuid) -> Result<(), ClusterError>;
+    // Moves the game to `to` only while `from` still owns it
+    fn reassign(&self, game_id: Uuid, from: Uuid, to: Uuid) -> Result<(), ClusterError>;
+    fn release(&self, game_id: Uuid);
+    fn owner(&self, game_id: Uuid) -> Option<Uuid>;
+    fn games_on(&self, instance_id: Uuid) -> Vec<Uuid>;
+}
+
+#[derive(Debug, Default)]
+struct Placement {
+    instances: HashMap<Uuid, InstanceInfo>,
+    games: HashMap<Uuid, Uuid>,
+}
+
+// Single-process registry, for one-node deployments and tests
+#[derive(Debug, Default)]
+pub struct InMemoryRegistry {
+    placement: Mutex<Placement>,
+}
+
+impl InMemoryRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl GameRegistry for InMemoryRegistry {
+    fn register(&self, instance: InstanceInfo) {
+        let mut placement = self.placement.lock().unwrap();
+        placement.instances.insert(instance.id, instance);
+    }
+
+    fn deregister(&self, instance_id: Uuid) {
+        let mut placement = self.placement.lock().unwrap();
+        placement.instances.remove(&instance_id);
+        placement.games.retain(|_, owner| *owner != instance_id);
+    }
+
+    fn set_draining(&self, instance_id: Uuid, draining: bool) -> Result<(), ClusterError> {
+        let mut placement = self.placement.lock().unwrap();
+        let instance = placement
+            .instances
+            .get_mut(&instance_id)
+            .ok_or(ClusterError::UnknownInstance)?;
+        instance.draining = draining;
+        Ok(())
+    }
+
+    fn instances(&self) -> Vec<InstanceInfo> {
+        let placement = self.placement.lock().unwrap();
+        placement.instances.values().cloned().collect()
+    }
+
+    fn assign(&self, game_id: Uuid, instance_id: Uuid) -> Result<(), ClusterError> {
+        let mut placement = self.placement.lock().unwrap();
+        if !placement.instances.contains_key(&instance_id) {
+            return Err(ClusterError::UnknownInstance);
+        }
+        placement.games.insert(game_id, instance_id);
+        Ok(())
+    }
+
+    fn reassign(&self, game_id: Uuid, from: Uuid, to: Uuid) -> Result<(), ClusterError> {
+        let mut placement = self.placement.lock().unwrap();
+        if !placement.instances.contains_key(&to) {
+            return Err(ClusterError::UnknownInstance);
+        }
+        match placement.games.get_mut(&game_id) {
+            Some(owner) if *owner == from => {
+                *owner = to;
+                Ok(())
+            }
+            _ => Err(ClusterError::UnknownGame),
+        }
+    }
+
+    fn release(&self, game_id: Uuid) {
+        self.placement.lock().unwrap().games.remove(&game_id);
+    }
+
+    fn owner(&self, game_id: Uuid) -> Option<Uuid> {
+        self.placement.lock().unwrap().games.get(&game_id).copied()
+    }
+
+    fn games_on(&self, instance_id: Uuid) -> Vec<Uuid> {
+        let placement = self.placement.lock().unwrap();
+        placement
+            .games
+            .iter()
+            .filter(|(_, owner)| **owner == instance_id)
+            .map(|(game_id, _)| *game_id)
+            .collect()
+    }
+}
+
+// A game moving from a draining instance to a peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Handoff {
+    pub game_id: Uuid,
+    pub from: Uuid,
+    pub to: Uuid,
+}
+
+pub struct Gateway<R: GameRegistry> {
+    registry: R,
+}
+
+impl<R: GameRegistry> Gateway<R> {
+    pub fn new(registry: R) -> Self {
+        Self { registry }
+    }
+
+    pub fn registry(&self) -> &R {
+        &self.registry
+    }
+
+    // Where a player in this game should connect
+    pub fn route(&self, game_id: Uuid) -> Result<InstanceInfo, ClusterError> {
+        let owner = self
+            .registry
+            .owner(game_id)
+            .ok_or(ClusterError::UnknownGame)?;
+        self.registry
+            .instances()
+            .into_iter()
+            .find(|instance| instance.id == owner)
+            .ok_or(ClusterError::UnknownInstance)
+    }
+
+    // New games go to the accepting instance hosting the fewest
+    pub fn place(&self, game_id: Uuid) -> Result<InstanceInfo, ClusterError> {
+        let instance = self.least_loaded(None)?;
+        self.registry.assign(game_id, instance.id)?;
+        Ok(instance)
+    }
+
+    // Stops new games landing on the instance and picks a peer for each
+    // game it hosts. The instance sends each session on and calls
+    // `complete_handoff` once the peer has taken it. If no peer can take
+    // them the instance is left as it was.
+    pub fn drain(&self, instance_id: Uuid) -> Result<Vec<Handoff>, ClusterError> {
+        let was_draining = self
+            .registry
+            .instances()
+            .iter()
+            .find(|instance| instance.id == instance_id)
+            .ok_or(ClusterError::UnknownInstance)?
+            .draining;
+        // Draining first, so no game lands here while peers are chosen
+        self.registry.set_draining(instance_id, true)?;
+        let handoffs = self.plan_handoffs(instance_id);
+        if handoffs.is_err() {
+            self.registry.set_draining(instance_id, was_draining)?;
+        }
+        handoffs
+    }
+
+    fn plan_handoffs(&self, instance_id: Uuid) -> Result<Vec<Handoff>, ClusterError> {
+        let mut load = self.load();
+        let mut handoffs = Vec::new();
+        for game_id in self.registry.games_on(instance_id) {
+            let to = self.least_loaded_in(&load, Some(instance_id))?.id;
+            *load.entry(to).or_default() += 1;
+            handoffs.push(Handoff {
+                game_id,
+                from: instance_id,
+                to,
+            });
+        }
+        Ok(handoffs)
+    }
+
+    pub fn complete_handoff(&self, handoff: Handoff) -> Result<(), ClusterError> {
+        self.registry
+            .reassign(handoff.game_id, handoff.from, handoff.to)
+    }
+
+    fn load(&self) -> HashMap<Uuid, usize> {
+        self.registry
+            .instances()
+            .into_iter()
+            .map(|instance| (instance.id, self.registry.games_on(instance.id).len()))
+            .collect()
+    }
+
+    fn least_loaded(&self, excluding: Option<Uuid>) -> Result<InstanceInfo, ClusterError> {
+        self.least_loaded_in(&self.load(), excluding)
+    }
+
+    fn least_loaded_in(
+        &self,
+        load: &HashMap<Uuid, usize>,
+        excluding: Option<Uuid>,
+    ) -> Result<InstanceInfo, ClusterError> {
+        self.registry
+            .instances()
+            .into_iter()
+            .filter(|instance| !instance.draining && Some(instance.id) != excluding)
+            .min_by_key(|instance| (load.get(&instance.id).copied().unwrap_or(0), instance.id))
+            .ok_or(ClusterError::NoInstanceAvailable)
+    }
+}
+
+// Moves a hosted session between two session managers, for the node that
+// receives a handoff in-process or from its persisted form
+pub fn transfer(
+    handoff: Handoff,
+    from: &mut SessionManager,
+    to: &mut SessionManager,
+) -> Result<(), ClusterError> {
+    let session = from
+        .remove(handoff.game_id)
+        .ok_or(ClusterError::UnknownGame)?;
+    to.adopt(session);
+    Ok(())
+}
+
+// TESTS
+#[cfg(test)]
+mod cluster_tests {
+    use super::*;
+    use crate::testing::GameStateBuilder;
+
+    fn instance(address: &str) -> InstanceInfo {
+        InstanceInfo {
+            id: Uuid::new_v4(),
+            address: address.to_string(),
+            draining: false,
+        }
+    }
+
+    #[test]
+    fn test_drained_instance_hands_games_to_peer() {
+        let (a, b) = (instance("10.0.0.1:7000"), instance("10.0.0.2:7000"));
+        let gateway = Gateway::new(InMemoryRegistry::new());
+        gateway.registry().register(a.clone());
+
+        let mut on_a = SessionManager::new();
+        let mut on_b = SessionManager::new();
+        let game_id = on_a.start(GameStateBuilder::new().build());
+        assert_eq!(gateway.place(game_id).unwrap(), a);
+        assert_eq!(gateway.drain(a.id), Err(ClusterError::NoInstanceAvailable));
+        // A drain that found no peer leaves the instance taking games
+        let meanwhile = Uuid::new_v4();
+        assert_eq!(gateway.place(meanwhile).unwrap(), a);
+        gateway.registry().release(meanwhile);
+
+        gateway.registry().register(b.clone());
+        let handoffs = gateway.drain(a.id).unwrap();
+        assert_eq!(
+            handoffs,
+            [Handoff {
+                game_id,
+                from: a.id,
+                to: b.id
+            }]
+        );
+        transfer(handoffs[0], &mut on_a, &mut on_b).unwrap();
+        gateway.complete_handoff(handoffs[0]).unwrap();
+        // Already moved, so a repeat can't take it back from the peer
+        assert_eq!(
+            gateway.complete_handoff(handoffs[0]),
+            Err(ClusterError::UnknownGame)
+        );
+
+        assert!(on_b.get(game_id).is_some());
+        assert_eq!(gateway.route(game_id).unwrap().address, b.address);
+        assert_eq!(gateway.place(Uuid::new_v4()).unwrap().id, b.id);
+    }
+}
@@ -0,0 +1,493 @@
+// src/collections/bulk.rs
+// Collection chores that touch many cards at once. Each one checks
+// everything before changing anything, so it lands as a whole or not at
+// all, and says card by card what it did. The `_stored` versions run them
+// against storage, writing the collection and wallet back in one save.
+use crate::cards::CardRegistry;
+use crate::collections::provenance::{Acquisition, OwnedItem};
+use crate::collections::{transact, Collection};
+use crate::database::CollectionStorage;
+use crate::errors::{BulkError, WalletError};
+use crate::models::{Card, Deck, Format, Rarity};
+use crate::wallet::{Currency, TransactionReason, Wallet};
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+pub fn disenchant_value(rarity: &Rarity) -> u64 {
+    match rarity {
+        Rarity::Common => 5,
+        Rarity::Uncommon => 20,
+        Rarity::Rare => 100,
+        Rarity::Legendary => 400,
+    }
+}
+
+pub fn craft_cost(rarity: &Rarity) -> u64 {
+    match rarity {
+        Rarity::Common => 40,
+        Rarity::Uncommon => 100,
+        Rarity::Rare => 400,
+        Rarity::Legendary => 1600,
+    }
+}
+
+// One "<copies> <card name>" per line, e.g. "3 Ice Golem". A name given on
+// several lines has its copies added up into one entry. Codes adding up to
+// more than a constructed deck are turned away here, before anything is
+// priced or crafted from them.
+pub fn parse_deck_code(code: &str) -> Result<Vec<(String, usize)>, BulkError> {
+    let deck_size = Format::Constructed.deck_size();
+    let mut total: usize = 0;
+    let mut entries: Vec<(String, usize)> = Vec::new();
+    for line in code.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let invalid = || BulkError::InvalidDeckCode(line.to_string());
+        let (count, name) = line.split_once(' ').ok_or_else(invalid)?;
+        let count: usize = count.parse().map_err(|_| invalid())?;
+        if count == 0 || count > deck_size {
+            return Err(invalid());
+        }
+        total = total
+            .checked_add(count)
+            .filter(|total| *total <= deck_size)
+            .ok_or_else(invalid)?;
+        let name = name.trim();
+        match entries.iter_mut().find(|(seen, _)| seen == name) {
+            Some((_, copies)) => *copies += count,
+            None => entries.push((name.to_string(), count)),
+        }
+    }
+    Ok(entries)
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DisenchantReport {
+    // Card name and the copies turned into dust
+    pub destroyed: Vec<(String, Vec<Uuid>)>,
+    // Extra copies left alone because they're foil, in a deck or in a trade
+    pub kept: Vec<Uuid>,
+    pub dust: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CraftPreview {
+    // Card name and how many more copies are needed
+    pub missing: Vec<(String, usize)>,
+    pub dust_cost: u64,
+    pub affordable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeckCraftReport {
+    pub deck_name: String,
+    pub crafted: Vec<Card>,
+    pub dust_spent: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeenReport {
+    pub marked: usize,
+    pub already_seen: usize,
+    pub not_owned: Vec<Uuid>,
+}
+
+impl Collection {
+    // Everything past the constructed copy limit for each card. `inventory`
+    // is the owner's cards in full, since the collection only keeps ids.
+    pub fn disenchant_extras(
+        &mut self,
+        inventory: &[Card],
+        wallet: &mut Wallet,
+    ) -> DisenchantReport {
+        let in_decks: HashSet<Uuid> = self
+            .decks
+            .values()
+            .flat_map(|deck| deck.cards.iter().map(|card| card.id))
+            .collect();
+        let mut by_name: HashMap<&str, Vec<&Card>> = HashMap::new();
+        for card in inventory
+            .iter()
+            .filter(|card| self.cards.contains(&card.id))
+        {
+            by_name.entry(card.name.as_str()).or_default().push(card);
+        }
+
+        let mut report = DisenchantReport::default();
+        let mut names: Vec<&str> = by_name.keys().copied().collect();
+        names.sort_unstable();
+        for name in names {
+            let mut copies = by_name.remove(name).unwrap_or_default();
+            let Some(limit) = Format::Constructed.max_copies(&copies[0].rarity) else {
+                continue;
+            };
+            // Copies that can't or shouldn't go first, so the plain ones are
+            // the extras
+            copies.sort_by_key(|card| {
+                (
+                    !self.foils.contains(&card.id),
+                    !in_decks.contains(&card.id),
+                    !self.locked.contains(&card.id),
+                    card.id,
+                )
+            });
+            let mut destroyed = Vec::new();
+            for card in copies.into_iter().skip(limit) {
+                if self.foils.contains(&card.id)
+                    || in_decks.contains(&card.id)
+                    || self.locked.contains(&card.id)
+                {
+                    report.kept.push(card.id);
+                } else {
+                    report.dust += disenchant_value(&card.rarity);
+                    destroyed.push(card.id);
+                }
+            }
+            if !destroyed.is_empty() {
+                report.destroyed.push((name.to_string(), destroyed));
+            }
+        }
+
+        if report.dust > 0 {
+            // Crediting only fails on overflow; then nothing is destroyed
+            if wallet
+                .credit(Currency::Dust, report.dust, TransactionReason::Disenchant)
+                .is_err()
+            {
+                return DisenchantReport::default();
+            }
+        }
+        for (_, destroyed) in &report.destroyed {
+            for card in destroyed {
+                self.cards.remove(card);
+                self.seen.remove(card);
+            }
+        }
+        report
+    }
+
+    pub fn preview_deck_craft(
+        &self,
+        code: &str,
+        inventory: &[Card],
+        registry: &CardRegistry,
+        wallet: &Wallet,
+    ) -> Result<CraftPreview, BulkError> {
+        let mut preview = CraftPreview::default();
+        for (name, count) in parse_deck_code(code)? {
+            let definition = registry
+                .by_name(&name)
+                .ok_or_else(|| BulkError::UnknownCard(name.clone()))?;
+            let owned = inventory
+                .iter()
+                .filter(|card| card.name == name && self.cards.contains(&card.id))
+                .count();
+            if Format::Constructed
+                .max_copies(&definition.rarity)
+                .is_some_and(|max| count > max)
+            {
+                return Err(BulkError::TooManyCopies(name));
+            }
+            let missing = count.saturating_sub(owned);
+            if missing > 0 {
+                preview.dust_cost = craft_cost(&definition.rarity)
+                    .checked_mul(missing as u64)
+                    .and_then(|cost| preview.dust_cost.checked_add(cost))
+                    .ok_or(BulkError::Wallet(WalletError::Overflow))?;
+                preview.missing.push((name, missing));
+            }
+        }
+        preview.affordable = wallet.balance(Currency::Dust) >= preview.dust_cost;
+        Ok(preview)
+    }
+
+    // Crafts whatever the deck code needs that isn't owned yet and saves the
+    // deck under `deck_name`
+    pub fn craft_deck(
+        &mut self,
+        deck_name: &str,
+        code: &str,
+        inventory: &[Card],
+        registry: &CardRegistry,
+        wallet: &mut Wallet,
+        now: SystemTime,
+    ) -> Result<DeckCraftReport, BulkError> {
+        if self.decks.contains_key(deck_name) {
+            return Err(BulkError::DeckNameTaken);
+        }
+        let preview = self.preview_deck_craft(code, inventory, registry, wallet)?;
+        let definitions = preview
+            .missing
+            .iter()
+            .map(|(name, missing)| {
+                registry
+                    .by_name(name)
+                    .map(|definition| (definition, *missing))
+                    .ok_or_else(|| BulkError::UnknownCard(name.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // Paid for before a single copy is made
+        wallet
+            .debit(
+                Currency::Dust,
+                preview.dust_cost,
+                TransactionReason::Crafting,
+            )
+            .map_err(BulkError::Wallet)?;
+        let crafted: Vec<Card> = definitions
+            .into_iter()
+            .flat_map(|(definition, missing)| {
+                (0..missing).map(|_| Card {
+                    id: Uuid::new_v4(),
+                    ..definition.clone()
+                })
+            })
+            .collect();
+
+        let owned: Vec<&Card> = inventory
+            .iter()
+            .filter(|card| self.cards.contains(&card.id))
+            .chain(&crafted)
+            .collect();
+        let mut cards = Vec::new();
+        for (name, count) in parse_deck_code(code)? {
+            cards.extend(
+                owned
+                    .iter()
+                    .filter(|card| card.name == name)
+                    .take(count)
+                    .map(|card| (*card).clone()),
+            );
+        }
+        for card in &crafted {
+            self.grant(OwnedItem::Card(card.id), Acquisition::Craft, now);
+        }
+        self.save_deck(
+            deck_name.to_string(),
+            Deck {
+                cards: cards.into(),
+                owner_id: self.owner_id,
+            },
+            Format::Constructed,
+        );
+        Ok(DeckCraftReport {
+            deck_name: deck_name.to_string(),
+            crafted,
+            dust_spent: preview.dust_cost,
+        })
+    }
+
+    pub fn mark_seen(&mut self, cards: &[Uuid]) -> SeenReport {
+        let mut report = SeenReport::default();
+        for card in cards {
+            if !self.cards.contains(card) {
+                report.not_owned.push(*card);
+            } else if self.seen.insert(*card) {
+                report.marked += 1;
+            } else {
+                report.already_seen += 1;
+            }
+        }
+        report
+    }
+}
+
+pub fn disenchant_extras_stored(
+    storage: &mut impl CollectionStorage,
+    owner_id: Uuid,
+    inventory: &[Card],
+) -> DisenchantReport {
+    let Ok(report) = transact(storage, owner_id, |collection, wallet| {
+        Ok::<_, Infallible>(collection.disenchant_extras(inventory, wallet))
+    });
+    report
+}
+
+pub fn craft_deck_stored(
+    storage: &mut impl CollectionStorage,
+    owner_id: Uuid,
+    deck_name: &str,
+    code: &str,
+    inventory: &[Card],
+    registry: &CardRegistry,
+    now: SystemTime,
+) -> Result<DeckCraftReport, BulkError> {
+    transact(storage, owner_id, |collection, wallet| {
+        collection.craft_deck(deck_name, code, inventory, registry, wallet, now)
+    })
+}
+
+pub fn mark_seen_stored(
+    storage: &mut impl CollectionStorage,
+    owner_id: Uuid,
+    cards: &[Uuid],
+) -> SeenReport {
+    let Ok(report) = transact(storage, owner_id, |collection, _| {
+        Ok::<_, Infallible>(collection.mark_seen(cards))
+    });
+    report
+}
+
+// TESTS
+#[cfg(test)]
+mod bulk_tests {
+    use super::*;
+    use crate::database::MemoryStorage;
+    use crate::testing::CardBuilder;
+
+    #[test]
+    fn test_extras_become_dust_and_deck_code_crafts_the_rest() {
+        let golems: Vec<Card> = (0..5)
+            .map(|_| CardBuilder::new("Ice Golem").build())
+            .collect();
+        let mut collection = Collection::new(Uuid::new_v4());
+        collection.cards.extend(golems.iter().map(|card| card.id));
+        collection
+            .foils
+            .extend([golems[1].id, golems[2].id, golems[4].id]);
+        collection.locked.insert(golems[3].id);
+        let mut wallet = Wallet::new(collection.owner_id);
+        let now = SystemTime::UNIX_EPOCH;
+
+        let report = collection.disenchant_extras(&golems, &mut wallet);
+        let rarity = &golems[0].rarity;
+        assert_eq!(
+            report.destroyed,
+            vec![("Ice Golem".to_string(), vec![golems[0].id])]
+        );
+        assert_eq!(report.kept, vec![golems[3].id]);
+        assert_eq!(wallet.balance(Currency::Dust), disenchant_value(rarity));
+        assert_eq!(collection.cards.len(), 4);
+        assert!(collection.cards.contains(&golems[4].id));
+
+        let rope = CardBuilder::new("Rope").build();
+        let registry = CardRegistry::new([golems[0].clone(), rope.clone()]);
+        let code = "3 Ice Golem\n2 Rope";
+        let preview = collection
+            .preview_deck_craft(code, &golems, &registry, &wallet)
+            .unwrap();
+        assert_eq!(preview.missing, vec![("Rope".to_string(), 2)]);
+        assert_eq!(preview.dust_cost, craft_cost(&rope.rarity) * 2);
+        assert!(!preview.affordable);
+        assert!(matches!(
+            collection.preview_deck_craft("1000000000000 Rope", &golems, &registry, &wallet),
+            Err(BulkError::InvalidDeckCode(_))
+        ));
+        assert!(matches!(
+            collection.preview_deck_craft("4 Rope", &golems, &registry, &wallet),
+            Err(BulkError::TooManyCopies(name)) if name == "Rope"
+        ));
+        assert_eq!(
+            parse_deck_code("2 Rope\n1 Ice Golem\n1 Rope").unwrap(),
+            vec![("Rope".to_string(), 3), ("Ice Golem".to_string(), 1)]
+        );
+        assert!(matches!(
+            collection.preview_deck_craft("3 Rope\n3 Rope", &golems, &registry, &wallet),
+            Err(BulkError::TooManyCopies(name)) if name == "Rope"
+        ));
+        let balance = wallet.balance(Currency::Dust);
+        assert!(matches!(
+            collection.craft_deck("Climb", code, &golems, &registry, &mut wallet, now),
+            Err(BulkError::Wallet(_))
+        ));
+        assert!(collection.decks.is_empty());
+
+        wallet
+            .credit(Currency::Dust, preview.dust_cost, TransactionReason::Reward)
+            .unwrap();
+        let crafted = collection
+            .craft_deck("Climb", code, &golems, &registry, &mut wallet, now)
+            .unwrap();
+        assert_eq!(crafted.crafted.len(), 2);
+        assert_eq!(wallet.balance(Currency::Dust), balance);
+        assert_eq!(collection.decks["Climb"].cards.len(), 5);
+
+        let inventory: Vec<Card> = golems.iter().chain(&crafted.crafted).cloned().collect();
+        let split = collection
+            .craft_deck(
+                "Split",
+                "1 Rope\n1 Rope",
+                &inventory,
+                &registry,
+                &mut wallet,
+                now,
+            )
+            .unwrap();
+        assert!(split.crafted.is_empty());
+        let ids: HashSet<Uuid> = collection.decks["Split"]
+            .cards
+            .iter()
+            .map(|card| card.id)
+            .collect();
+        assert_eq!(ids.len(), 2);
+
+        let seen = collection.mark_seen(&[crafted.crafted[0].id, crafted.crafted[0].id, rope.id]);
+        assert_eq!((seen.marked, seen.already_seen), (1, 1));
+        assert_eq!(seen.not_owned, vec![rope.id]);
+    }
+
+    #[test]
+    fn test_deck_code_counts_are_checked_before_adding_up() {
+        assert!(matches!(
+            parse_deck_code("1 Rope\n18446744073709551615 Rope"),
+            Err(BulkError::InvalidDeckCode(line)) if line == "18446744073709551615 Rope"
+        ));
+        assert!(matches!(
+            parse_deck_code("20 Rope\n20 Ice Golem"),
+            Err(BulkError::InvalidDeckCode(_))
+        ));
+    }
+
+    #[test]
+    fn test_stored_chores_save_only_what_went_through() {
+        let owner_id = Uuid::new_v4();
+        let rope = CardBuilder::new("Rope").build();
+        let registry = CardRegistry::new([rope.clone()]);
+        let mut storage = MemoryStorage::default();
+        let mut wallet = Wallet::new(owner_id);
+        wallet
+            .credit(
+                Currency::Dust,
+                craft_cost(&rope.rarity),
+                TransactionReason::Reward,
+            )
+            .unwrap();
+        storage.save_holdings(Collection::new(owner_id), wallet);
+        let now = SystemTime::UNIX_EPOCH;
+
+        // Two copies cost more dust than there is; nothing is written
+        assert!(matches!(
+            craft_deck_stored(
+                &mut storage,
+                owner_id,
+                "Climb",
+                "2 Rope",
+                &[],
+                &registry,
+                now
+            ),
+            Err(BulkError::Wallet(_))
+        ));
+        assert!(storage.collections[&owner_id].cards.is_empty());
+
+        let report = craft_deck_stored(
+            &mut storage,
+            owner_id,
+            "Climb",
+            "1 Rope",
+            &[],
+            &registry,
+            now,
+        )
+        .unwrap();
+        let crafted = report.crafted[0].id;
+        assert_eq!(storage.wallets[&owner_id].balance(Currency::Dust), 0);
+        assert!(storage.collections[&owner_id].decks.contains_key("Climb"));
+
+        let seen = mark_seen_stored(&mut storage, owner_id, &[crafted]);
+        assert_eq!(seen.marked, 1);
+        assert!(storage.collections[&owner_id].seen.contains(&crafted));
+        let disenchanted = disenchant_extras_stored(&mut storage, owner_id, &report.crafted);
+        assert_eq!(disenchanted, DisenchantReport::default());
+    }
+}
@@ -0,0 +1,175 @@
+// src/collections/deck_stats.rs
+// A player's own record with each of their saved decks: games, wins, how
+// long games run and how the deck does against each opposing archetype.
+// Folded in from finished games and served with the deck list, so players
+// can judge their builds without an outside tracker.
+use crate::collections::Collection;
+use crate::models::Deck;
+use crate::replay::ReplaySummary;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchupRecord {
+    pub games: u32,
+    pub wins: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeckStats {
+    pub games: u32,
+    pub wins: u32,
+    pub total_turns: u64,
+    pub total_duration: Duration,
+    // By the opponent's archetype; games against unlabelled decks only
+    // count towards the totals
+    pub matchups: BTreeMap<String, MatchupRecord>,
+}
+
+impl DeckStats {
+    pub fn record(&mut self, won: bool, turns: u32, duration: Duration, opponent: Option<&str>) {
+        self.games += 1;
+        self.wins += won as u32;
+        self.total_turns += u64::from(turns);
+        self.total_duration += duration;
+        if let Some(archetype) = opponent {
+            let matchup = self.matchups.entry(archetype.to_string()).or_default();
+            matchup.games += 1;
+            matchup.wins += won as u32;
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64
+        }
+    }
+
+    pub fn average_turns(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.total_turns as f64 / self.games as f64
+        }
+    }
+
+    pub fn average_duration(&self) -> Duration {
+        self.total_duration
+            .checked_div(self.games)
+            .unwrap_or_default()
+    }
+}
+
+// One entry of the deck list endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct DeckListing {
+    pub name: String,
+    pub deck: Deck,
+    pub stats: DeckStats,
+    pub win_rate: f64,
+    pub average_turns: f64,
+    pub average_duration_secs: u64,
+}
+
+impl Collection {
+    // Credits a finished game to the saved deck the owner played it with.
+    // `opponent_archetype` labels the other side's deck when it is known.
+    pub fn record_deck_result(
+        &mut self,
+        deck_name: &str,
+        summary: &ReplaySummary,
+        opponent_archetype: Option<&str>,
+    ) {
+        if !self.decks.contains_key(deck_name) {
+            return;
+        }
+        self.deck_stats
+            .entry(deck_name.to_string())
+            .or_default()
+            .record(
+                summary.winner == Some(self.owner_id),
+                summary.turns,
+                summary.duration,
+                opponent_archetype,
+            );
+    }
+
+    // Saved decks by name, each with its record so far
+    pub fn deck_list(&self) -> Vec<DeckListing> {
+        let mut listings: Vec<DeckListing> = self
+            .decks
+            .iter()
+            .map(|(name, deck)| {
+                let stats = self.deck_stats.get(name).cloned().unwrap_or_default();
+                DeckListing {
+                    name: name.clone(),
+                    deck: deck.clone(),
+                    win_rate: stats.win_rate(),
+                    average_turns: stats.average_turns(),
+                    average_duration_secs: stats.average_duration().as_secs(),
+                    stats,
+                }
+            })
+            .collect();
+        listings.sort_by(|a, b| a.name.cmp(&b.name));
+        listings
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod deck_stats_tests {
+    use super::*;
+    use crate::testing::CardBuilder;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_results_build_up_on_the_deck_list() {
+        let (owner, opponent) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut collection = Collection::new(owner);
+        for name in ["Slide", "Climb"] {
+            collection.decks.insert(
+                name.to_string(),
+                Deck {
+                    cards: vec![CardBuilder::new("Rope").build()].into(),
+                    owner_id: owner,
+                },
+            );
+        }
+        let game = |winner, turns, minutes: u64| ReplaySummary {
+            game_id: Uuid::new_v4(),
+            players: vec![(owner, "Alice".to_string()), (opponent, "Bob".to_string())],
+            decks: HashMap::new(),
+            winner: Some(winner),
+            turns,
+            duration: Duration::from_secs(minutes * 60),
+        };
+        collection.record_deck_result("Slide", &game(owner, 10, 8), Some("Burn"));
+        collection.record_deck_result("Slide", &game(opponent, 14, 12), Some("Burn"));
+        collection.record_deck_result("Slide", &game(owner, 12, 10), None);
+        collection.record_deck_result("Deleted", &game(owner, 9, 7), None);
+
+        let list = collection.deck_list();
+        assert_eq!(
+            list.iter()
+                .map(|listing| listing.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Climb", "Slide"]
+        );
+        assert_eq!(list[0].stats, DeckStats::default());
+        let slide = &list[1];
+        assert_eq!((slide.stats.games, slide.stats.wins), (3, 2));
+        assert_eq!(slide.average_turns, 12.0);
+        assert_eq!(slide.average_duration_secs, 600);
+        assert_eq!(
+            slide.stats.matchups["Burn"],
+            MatchupRecord { games: 2, wins: 1 }
+        );
+        assert!(serde_json::to_value(slide).unwrap()["win_rate"].is_number());
+        assert!(!collection.deck_stats.contains_key("Deleted"));
+    }
+}
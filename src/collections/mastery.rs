@@ -0,0 +1,195 @@
+// src/collections/mastery.rs
+// Card mastery: every time a player plays a card it earns XP on their
+// collection entry for that card, counted by name so all copies share it.
+// Levels unlock card frames, and in casual games a small stat upgrade that
+// is applied when the deck is dealt into a new game.
+use crate::database::MasteryStorage;
+use crate::events::GameEvent;
+use crate::game_state::GameState;
+use crate::models::Deck;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+pub const XP_PER_PLAY: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MasteryReward {
+    Frame(&'static str),
+    // Extra power, only in casual games
+    Power(u32),
+}
+
+// XP needed for each level, and what reaching it unlocks
+pub const MASTERY_LEVELS: &[(u32, MasteryReward)] = &[
+    (100, MasteryReward::Frame("frame_bronze")),
+    (300, MasteryReward::Power(1)),
+    (700, MasteryReward::Frame("frame_silver")),
+    (1500, MasteryReward::Frame("frame_gold")),
+];
+
+pub fn level_for(xp: u32) -> u32 {
+    MASTERY_LEVELS
+        .iter()
+        .take_while(|(threshold, _)| xp >= *threshold)
+        .count() as u32
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MasteryUnlock {
+    pub card: String,
+    pub level: u32,
+    pub reward: MasteryReward,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mastery {
+    pub owner_id: Uuid,
+    // XP by card name
+    pub xp: BTreeMap<String, u32>,
+}
+
+impl Mastery {
+    pub fn new(owner_id: Uuid) -> Self {
+        Self {
+            owner_id,
+            xp: BTreeMap::new(),
+        }
+    }
+
+    pub fn level(&self, card: &str) -> u32 {
+        level_for(self.xp.get(card).copied().unwrap_or(0))
+    }
+
+    // The best frame unlocked for a card, if any
+    pub fn frame(&self, card: &str) -> Option<&'static str> {
+        MASTERY_LEVELS[..self.level(card) as usize]
+            .iter()
+            .rev()
+            .find_map(|(_, reward)| match reward {
+                MasteryReward::Frame(frame) => Some(*frame),
+                MasteryReward::Power(_) => None,
+            })
+    }
+
+    pub fn power_bonus(&self, card: &str) -> u32 {
+        MASTERY_LEVELS[..self.level(card) as usize]
+            .iter()
+            .map(|(_, reward)| match reward {
+                MasteryReward::Power(power) => *power,
+                MasteryReward::Frame(_) => 0,
+            })
+            .sum()
+    }
+
+    // Credits the owner's plays from a finished game and returns any levels
+    // they reached
+    pub fn record_game(&mut self, game_state: &GameState) -> Vec<MasteryUnlock> {
+        let mut unlocks = Vec::new();
+        for event in &game_state.events {
+            let GameEvent::CardPlayed {
+                player_id, name, ..
+            } = event
+            else {
+                continue;
+            };
+            if *player_id != self.owner_id {
+                continue;
+            }
+            let xp = self.xp.entry(name.clone()).or_default();
+            let before = level_for(*xp);
+            *xp = xp.saturating_add(XP_PER_PLAY);
+            for level in before..level_for(*xp) {
+                unlocks.push(MasteryUnlock {
+                    card: name.clone(),
+                    level: level + 1,
+                    reward: MASTERY_LEVELS[level as usize].1,
+                });
+            }
+        }
+        unlocks
+    }
+
+    // Run on a copy of the deck as it goes into a new game. Ranked games
+    // keep the printed stats, so upgrades only land when `casual` is set.
+    pub fn apply_to_deck(&self, deck: &mut Deck, casual: bool) {
+        if !casual {
+            return;
+        }
+        for card in deck.cards.iter_mut() {
+            card.power = card.power.saturating_add(self.power_bonus(&card.name));
+        }
+    }
+}
+
+// Folds a finished game into every seat's stored mastery
+pub fn record_game(
+    storage: &mut impl MasteryStorage,
+    game_state: &GameState,
+) -> HashMap<Uuid, Vec<MasteryUnlock>> {
+    let mut unlocks = HashMap::new();
+    for player_id in &game_state.turn_order {
+        let mut mastery = storage
+            .load_mastery(*player_id)
+            .unwrap_or_else(|| Mastery::new(*player_id));
+        unlocks.insert(*player_id, mastery.record_game(game_state));
+        storage.save_mastery(mastery);
+    }
+    unlocks
+}
+
+// TESTS
+#[cfg(test)]
+mod mastery_tests {
+    use super::*;
+    use crate::database::MemoryStorage;
+    use crate::testing::{CardBuilder, GameStateBuilder};
+
+    #[test]
+    fn test_plays_level_up_frames_and_casual_power() {
+        let mut game_state = GameStateBuilder::new().build();
+        let owner = game_state.turn_order[0];
+        for _ in 0..30 {
+            game_state.events.push(GameEvent::CardPlayed {
+                player_id: owner,
+                card_id: Uuid::new_v4(),
+                name: "Ice Golem".to_string(),
+            });
+        }
+        game_state.events.push(GameEvent::CardPlayed {
+            player_id: game_state.turn_order[1],
+            card_id: Uuid::new_v4(),
+            name: "Rope".to_string(),
+        });
+
+        let mut storage = MemoryStorage::default();
+        let unlocks = record_game(&mut storage, &game_state);
+        let mastery = storage.load_mastery(owner).unwrap();
+        assert_eq!(
+            unlocks[&owner]
+                .iter()
+                .map(|unlock| (unlock.level, unlock.reward))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, MasteryReward::Frame("frame_bronze")),
+                (2, MasteryReward::Power(1)),
+            ]
+        );
+        assert_eq!(mastery.frame("Ice Golem"), Some("frame_bronze"));
+        assert_eq!(mastery.level("Rope"), 0);
+        assert_eq!(
+            storage.load_mastery(game_state.turn_order[1]).unwrap().xp["Rope"],
+            XP_PER_PLAY
+        );
+
+        let golem = CardBuilder::new("Ice Golem").power(6).build();
+        let mut deck = Deck {
+            cards: vec![golem].into(),
+            owner_id: owner,
+        };
+        mastery.apply_to_deck(&mut deck, false);
+        assert_eq!(deck.cards[0].power, 6);
+        mastery.apply_to_deck(&mut deck, true);
+        assert_eq!(deck.cards[0].power, 7);
+    }
+}
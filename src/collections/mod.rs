@@ -1,13 +1,38 @@
 // src/collections/mod.rs
-use crate::models::Deck;
-use std::collections::{HashMap, HashSet};
+use crate::collections::deck_stats::DeckStats;
+use crate::collections::provenance::ProvenanceRecord;
+use crate::database::CollectionStorage;
+use crate::errors::TradeError;
+use crate::models::{Card, Deck, Format};
+use crate::wallet::Wallet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use uuid::Uuid;
 
-#[derive(Debug)]
+pub mod bulk;
+pub mod deck_stats;
+pub mod mastery;
+pub mod provenance;
+
+#[derive(Debug, Clone)]
 pub struct Collection {
     pub owner_id: Uuid,
     pub cards: HashSet<Uuid>,
     pub decks: HashMap<String, Deck>,
+    // What each saved deck was built for, by deck name; see `deck_format`
+    pub deck_formats: HashMap<String, Format>,
+    // Cards held in escrow by a pending trade; still owned, but can't be
+    // offered again until the trade settles
+    pub locked: HashSet<Uuid>,
+    // Card instances upgraded to their foil finish
+    pub foils: HashSet<Uuid>,
+    // Alternate art unlocked through crafting, by art id
+    pub alt_arts: HashSet<String>,
+    // Cards the owner has looked at since getting them; the rest show as new
+    pub seen: HashSet<Uuid>,
+    // The owner's record with each saved deck, by deck name
+    pub deck_stats: HashMap<String, DeckStats>,
+    // How everything owned was come by, oldest first
+    pub provenance: Vec<ProvenanceRecord>,
 }
 
 impl Collection {
@@ -16,6 +41,129 @@ impl Collection {
             owner_id,
             cards: HashSet::new(),
             decks: HashMap::new(),
+            deck_formats: HashMap::new(),
+            locked: HashSet::new(),
+            foils: HashSet::new(),
+            alt_arts: HashSet::new(),
+            seen: HashSet::new(),
+            deck_stats: HashMap::new(),
+            provenance: Vec::new(),
+        }
+    }
+
+    // Saves a deck under `name`, replacing any deck already called that
+    pub fn save_deck(&mut self, name: String, deck: Deck, format: Format) {
+        self.deck_formats.insert(name.clone(), format);
+        self.decks.insert(name, deck);
+    }
+
+    // Decks saved without a recorded format are constructed ones
+    pub fn deck_format(&self, name: &str) -> Format {
+        self.deck_formats
+            .get(name)
+            .copied()
+            .unwrap_or(Format::Constructed)
+    }
+
+    // Card name and how many more copies the deck needs than the owner has.
+    // `inventory` is the owner's cards in full, since the collection only
+    // keeps ids.
+    pub fn missing_cards(&self, deck: &Deck, inventory: &[Card]) -> Vec<(String, usize)> {
+        let mut needed: BTreeMap<&str, usize> = BTreeMap::new();
+        for card in deck.cards.iter() {
+            *needed.entry(card.name.as_str()).or_default() += 1;
+        }
+        needed
+            .into_iter()
+            .filter_map(|(name, count)| {
+                let owned = self
+                    .owned(inventory)
+                    .filter(|card| card.name == name)
+                    .count();
+                (count > owned).then(|| (name.to_string(), count - owned))
+            })
+            .collect()
+    }
+
+    // The owner's own copies of the deck's cards, one per card in the deck,
+    // or None if any are missing
+    pub fn own_copies(&self, deck: &Deck, inventory: &[Card]) -> Option<Vec<Card>> {
+        let mut by_name: HashMap<&str, Vec<&Card>> = HashMap::new();
+        for card in self.owned(inventory) {
+            by_name.entry(card.name.as_str()).or_default().push(card);
         }
+        deck.cards
+            .iter()
+            .map(|card| {
+                by_name
+                    .get_mut(card.name.as_str())
+                    .and_then(Vec::pop)
+                    .cloned()
+            })
+            .collect()
     }
+
+    fn owned<'a>(&'a self, inventory: &'a [Card]) -> impl Iterator<Item = &'a Card> + 'a {
+        inventory
+            .iter()
+            .filter(|card| self.cards.contains(&card.id))
+    }
+
+    // Owned and not already promised to another trade
+    pub fn check_tradable(&self, cards: &[Uuid]) -> Result<(), TradeError> {
+        for card in cards {
+            if !self.cards.contains(card) {
+                return Err(TradeError::NotOwned(*card));
+            }
+            if self.locked.contains(card) {
+                return Err(TradeError::Locked(*card));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn lock(&mut self, cards: &[Uuid]) -> Result<(), TradeError> {
+        self.check_tradable(cards)?;
+        self.locked.extend(cards);
+        Ok(())
+    }
+
+    pub fn release(&mut self, cards: &[Uuid]) {
+        for card in cards {
+            self.locked.remove(card);
+        }
+    }
+
+    // Hands cards over to someone else. Saved decks lose them too, so a
+    // deck never lists a card its owner no longer has.
+    pub fn give_up(&mut self, cards: &[Uuid]) {
+        for card in cards {
+            self.cards.remove(card);
+            self.seen.remove(card);
+        }
+        // Decks share their card lists, so only the ones touched are copied
+        for deck in self.decks.values_mut() {
+            if deck.cards.iter().any(|card| cards.contains(&card.id)) {
+                deck.cards.retain(|card| !cards.contains(&card.id));
+            }
+        }
+    }
+}
+
+// Runs `change` on the owner's stored collection and wallet and writes both
+// back only if it succeeds, so a refused change leaves storage as it was
+pub fn transact<T, E>(
+    storage: &mut impl CollectionStorage,
+    owner_id: Uuid,
+    change: impl FnOnce(&mut Collection, &mut Wallet) -> Result<T, E>,
+) -> Result<T, E> {
+    let mut collection = storage
+        .load_collection(owner_id)
+        .unwrap_or_else(|| Collection::new(owner_id));
+    let mut wallet = storage
+        .load_wallet(owner_id)
+        .unwrap_or_else(|| Wallet::new(owner_id));
+    let result = change(&mut collection, &mut wallet)?;
+    storage.save_holdings(collection, wallet);
+    Ok(result)
 }
@@ -0,0 +1,183 @@
+// src/collections/provenance.rs
+// Where each owned card and variant came from and when. The ledger is only
+// ever appended to, so a card that changed hands shows every owner's side
+// of it, and a grant of something already owned is still written down for
+// tracking duplicate grants. Support reads it when settling disputes and
+// deciding whether a trade can be rolled back.
+use crate::collections::Collection;
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OwnedItem {
+    Card(Uuid),
+    // The foil finish on one card instance
+    Foil(Uuid),
+    AltArt(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Acquisition {
+    Pack { product_id: String },
+    // Bought ready-made from the store, e.g. a starter deck
+    Purchase { product_id: String },
+    Craft,
+    Trade { trade_id: Uuid, from: Uuid },
+    Reward { reason: String },
+    Starter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvenanceRecord {
+    pub item: OwnedItem,
+    pub source: Acquisition,
+    pub at: SystemTime,
+    // False when the owner already had it
+    pub new: bool,
+}
+
+impl Collection {
+    // Adds the item to the collection and writes down how it got there.
+    // Returns false if it was already owned.
+    pub fn grant(&mut self, item: OwnedItem, source: Acquisition, at: SystemTime) -> bool {
+        let new = match &item {
+            OwnedItem::Card(id) => self.cards.insert(*id),
+            OwnedItem::Foil(id) => self.foils.insert(*id),
+            OwnedItem::AltArt(art) => self.alt_arts.insert(art.clone()),
+        };
+        if !new {
+            tracing::warn!(owner = %self.owner_id, ?item, ?source, "duplicate grant");
+        }
+        self.provenance.push(ProvenanceRecord {
+            item,
+            source,
+            at,
+            new,
+        });
+        new
+    }
+
+    // Every record for the item, oldest first
+    pub fn provenance_of<'a>(
+        &'a self,
+        item: &'a OwnedItem,
+    ) -> impl Iterator<Item = &'a ProvenanceRecord> + 'a {
+        self.provenance
+            .iter()
+            .filter(move |record| record.item == *item)
+    }
+
+    // How the owner came by what they hold now: the latest record for the
+    // item, if they still own it
+    pub fn acquired(&self, item: &OwnedItem) -> Option<&ProvenanceRecord> {
+        let owned = match item {
+            OwnedItem::Card(id) => self.cards.contains(id),
+            OwnedItem::Foil(id) => self.foils.contains(id),
+            OwnedItem::AltArt(art) => self.alt_arts.contains(art),
+        };
+        if !owned {
+            return None;
+        }
+        self.provenance
+            .iter()
+            .rfind(|record| record.item == *item && record.new)
+    }
+
+    // What the provenance endpoint serves: records in a time window, in
+    // the order they were written
+    pub fn provenance_between(&self, from: SystemTime, to: SystemTime) -> Vec<ProvenanceRecord> {
+        self.provenance
+            .iter()
+            .filter(|record| from <= record.at && record.at < to)
+            .cloned()
+            .collect()
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod provenance_tests {
+    use super::*;
+    use crate::crafting::{RecipeBook, DEFAULT_RECIPES};
+    use crate::models::Card;
+    use crate::testing::CardBuilder;
+    use crate::trading::TradeDesk;
+    use crate::wallet::{Currency, TransactionReason, Wallet};
+    use std::time::Duration;
+
+    #[test]
+    fn test_every_way_in_is_recorded() {
+        let start = SystemTime::UNIX_EPOCH;
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut alices = Collection::new(alice);
+        let mut bobs = Collection::new(bob);
+        let golems: Vec<Card> = (0..3)
+            .map(|_| CardBuilder::new("Ice Golem").build())
+            .collect();
+        for golem in &golems {
+            assert!(alices.grant(OwnedItem::Card(golem.id), Acquisition::Starter, start));
+        }
+        let rope = CardBuilder::new("Rope").build();
+        let pack = Acquisition::Pack {
+            product_id: "pack_standard".to_string(),
+        };
+        bobs.grant(OwnedItem::Card(rope.id), pack.clone(), start);
+        // The same card granted twice is kept as evidence
+        assert!(!bobs.grant(OwnedItem::Card(rope.id), pack, start));
+
+        let later = start + Duration::from_secs(60);
+        let mut desk = TradeDesk::default();
+        let trade = desk
+            .propose(&alices, bob, vec![golems[0].id], vec![rope.id])
+            .unwrap();
+        desk.accept(trade, &mut alices, &mut bobs, later).unwrap();
+        desk.confirm(trade, alice, &mut alices, &mut bobs, later)
+            .unwrap();
+        desk.confirm(trade, bob, &mut alices, &mut bobs, later)
+            .unwrap();
+        assert_eq!(
+            alices.acquired(&OwnedItem::Card(rope.id)).unwrap().source,
+            Acquisition::Trade {
+                trade_id: trade,
+                from: bob
+            }
+        );
+        assert_eq!(alices.acquired(&OwnedItem::Card(golems[0].id)), None);
+        assert_eq!(bobs.provenance_of(&OwnedItem::Card(rope.id)).count(), 2);
+
+        let extra = CardBuilder::new("Ice Golem").build();
+        let reward = Acquisition::Reward {
+            reason: "weekly_quest".to_string(),
+        };
+        alices.grant(OwnedItem::Card(extra.id), reward, later);
+        let mut wallet = Wallet::new(alice);
+        wallet
+            .credit(Currency::Dust, 100, TransactionReason::Reward)
+            .unwrap();
+        let crafted_at = later + Duration::from_secs(60);
+        RecipeBook::from_json(DEFAULT_RECIPES)
+            .unwrap()
+            .craft(
+                "foil_ice_golem",
+                &mut alices,
+                &mut wallet,
+                &golems[1],
+                &[golems[2].clone(), extra],
+                crafted_at,
+            )
+            .unwrap();
+        assert_eq!(
+            alices.provenance_between(crafted_at, SystemTime::now()),
+            vec![ProvenanceRecord {
+                item: OwnedItem::Foil(golems[1].id),
+                source: Acquisition::Craft,
+                at: crafted_at,
+                new: true,
+            }]
+        );
+        assert_eq!(alices.provenance_between(later, crafted_at).len(), 2);
+    }
+}
@@ -0,0 +1,275 @@
+// src/config/mod.rs
+// Server settings that can change while the server runs. Each reload swaps
+// in a whole new config. A game copies what it needs into its GameRules
+// when it is created, so a reload only affects games started after it.
+use crate::errors::ConfigError;
+use crate::game_state::{GameRules, MAX_TURN_TIME_LIMIT};
+use crate::matchmaking::MatchmakingParams;
+use crate::networking::{VersionPolicy, PROTOCOL_VERSION};
+use crate::webhooks::WebhookEndpoint;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct TimerSettings {
+    pub turn_time_limit_secs: Option<u64>,
+    pub afk_turns_before_concede: u32,
+    pub reconnect_window_secs: u64,
+    pub max_latency_allowance_ms: u64,
+}
+
+impl Default for TimerSettings {
+    fn default() -> Self {
+        let rules = GameRules::default();
+        Self {
+            turn_time_limit_secs: rules.turn_time_limit.map(|limit| limit.as_secs()),
+            afk_turns_before_concede: rules.afk_turns_before_concede,
+            reconnect_window_secs: rules.reconnect_window.as_secs(),
+            max_latency_allowance_ms: rules.max_latency_allowance.as_millis() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct AnnouncementSettings {
+    pub motd_enabled: bool,
+    // How often the server polls the board for announcements going live
+    pub poll_interval_secs: u64,
+}
+
+impl Default for AnnouncementSettings {
+    fn default() -> Self {
+        Self {
+            motd_enabled: true,
+            poll_interval_secs: 30,
+        }
+    }
+}
+
+// Background job intervals while the server is busy. With no games and no
+// connections for `idle_after_secs` they are stretched by `idle_multiplier`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct IdleSettings {
+    pub enabled: bool,
+    pub idle_after_secs: u64,
+    pub idle_multiplier: u32,
+    pub matchmaking_tick_ms: u64,
+    pub telemetry_flush_secs: u64,
+    pub stats_interval_secs: u64,
+}
+
+impl Default for IdleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_after_secs: 120,
+            idle_multiplier: 30,
+            matchmaking_tick_ms: 500,
+            telemetry_flush_secs: 10,
+            stats_interval_secs: 60,
+        }
+    }
+}
+
+// What is built ahead of readiness on start. The card data is always
+// checked; turning this off skips the rest for quicker dev restarts.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct WarmupSettings {
+    pub enabled: bool,
+    // Generated mountain sizes to build up front
+    pub mountain_levels: Vec<u32>,
+    pub prepare_statements: bool,
+}
+
+impl Default for WarmupSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mountain_levels: vec![3, 5, 7],
+            prepare_statements: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub timers: TimerSettings,
+    pub matchmaking: MatchmakingParams,
+    pub features: HashMap<String, bool>,
+    pub announcements: AnnouncementSettings,
+    pub clients: VersionPolicy,
+    pub webhooks: Vec<WebhookEndpoint>,
+    pub idle: IdleSettings,
+    pub warmup: WarmupSettings,
+}
+
+impl ServerConfig {
+    pub fn from_json(data: &str) -> Result<Self, ConfigError> {
+        let config: ServerConfig =
+            serde_json::from_str(data).map_err(|e| ConfigError::Invalid(e.to_string()))?;
+        let matchmaking = &config.matchmaking;
+        if matchmaking.base_window < 0.0 || matchmaking.max_window < matchmaking.base_window {
+            return Err(ConfigError::Invalid(
+                "matchmaking max_window must be at least base_window".to_string(),
+            ));
+        }
+        if matchmaking.max_latency_ms < matchmaking.base_latency_ms {
+            return Err(ConfigError::Invalid(
+                "matchmaking max_latency_ms must be at least base_latency_ms".to_string(),
+            ));
+        }
+        if config.idle.idle_multiplier == 0 {
+            return Err(ConfigError::Invalid(
+                "idle_multiplier must be at least 1".to_string(),
+            ));
+        }
+        if let Some(levels) = config
+            .warmup
+            .mountain_levels
+            .iter()
+            .find(|levels| !(1..=50).contains(*levels))
+        {
+            return Err(ConfigError::Invalid(format!(
+                "warmup mountain_levels {} is outside 1 to 50",
+                levels
+            )));
+        }
+        if let Some(limit) = config
+            .timers
+            .turn_time_limit_secs
+            .filter(|limit| !(1..=MAX_TURN_TIME_LIMIT.as_secs()).contains(limit))
+        {
+            return Err(ConfigError::Invalid(format!(
+                "turn_time_limit_secs {} is outside 1 to {}",
+                limit,
+                MAX_TURN_TIME_LIMIT.as_secs()
+            )));
+        }
+        if let Some(webhook) = config
+            .webhooks
+            .iter()
+            .find(|webhook| !webhook.url.starts_with("https://") || webhook.secret.is_empty())
+        {
+            return Err(ConfigError::Invalid(format!(
+                "webhook {} needs an https url and a secret",
+                webhook.url
+            )));
+        }
+        if config.clients.min_protocol_version > PROTOCOL_VERSION {
+            return Err(ConfigError::Invalid(
+                "min_protocol_version is newer than this server".to_string(),
+            ));
+        }
+        Ok(config)
+    }
+
+    pub fn feature(&self, name: &str) -> bool {
+        self.features.get(name).copied().unwrap_or(false)
+    }
+
+    // Rules for a game created now; the game keeps these for its lifetime
+    pub fn game_rules(&self) -> GameRules {
+        GameRules {
+            turn_time_limit: self.timers.turn_time_limit_secs.map(Duration::from_secs),
+            afk_turns_before_concede: self.timers.afk_turns_before_concede,
+            reconnect_window: Duration::from_secs(self.timers.reconnect_window_secs),
+            max_latency_allowance: Duration::from_millis(self.timers.max_latency_allowance_ms),
+            ..GameRules::default()
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ConfigHandle {
+    current: RwLock<Arc<ServerConfig>>,
+}
+
+impl ConfigHandle {
+    pub fn new(config: ServerConfig) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(config)),
+        }
+    }
+
+    // Readers hold on to the Arc for as long as they need a consistent view
+    pub fn current(&self) -> Arc<ServerConfig> {
+        self.current.read().unwrap().clone()
+    }
+
+    // The admin reload call. A config that fails validation leaves the
+    // running one in place.
+    pub fn reload(&self, data: &str) -> Result<(), ConfigError> {
+        let config = ServerConfig::from_json(data)?;
+        *self.current.write().unwrap() = Arc::new(config);
+        Ok(())
+    }
+
+    pub fn reload_file(&self, path: &Path) -> Result<(), ConfigError> {
+        let data = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(e.to_string()))?;
+        self.reload(&data)
+    }
+}
+
+// Re-reads the config file every time the process gets SIGHUP
+#[cfg(unix)]
+pub async fn reload_on_sighup(
+    handle: Arc<ConfigHandle>,
+    path: std::path::PathBuf,
+) -> Result<(), ConfigError> {
+    use tokio::signal::unix::{signal, SignalKind};
+    let mut hangups = signal(SignalKind::hangup()).map_err(|e| ConfigError::Io(e.to_string()))?;
+    while hangups.recv().await.is_some() {
+        match handle.reload_file(&path) {
+            Ok(()) => tracing::info!(path = %path.display(), "configuration reloaded"),
+            Err(error) => tracing::warn!(?error, "configuration reload rejected"),
+        }
+    }
+    Ok(())
+}
+
+// TESTS
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+    use crate::testing::GameStateBuilder;
+
+    #[test]
+    fn test_reload_leaves_running_games_alone() {
+        let handle = ConfigHandle::new(ServerConfig::default());
+        let game = GameStateBuilder::new()
+            .build()
+            .with_rules(handle.current().game_rules());
+
+        handle
+            .reload(
+                r#"{"timers": {"turn_time_limit_secs": 60},
+                    "matchmaking": {"base_window": 50, "growth_per_second": 10, "max_window": 400},
+                    "features": {"avalanches": true}}"#,
+            )
+            .unwrap();
+        let config = handle.current();
+        assert!(config.feature("avalanches"));
+        assert_eq!(config.matchmaking.base_window, 50.0);
+        assert_eq!(
+            config.game_rules().turn_time_limit,
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(game.rules.turn_time_limit, None);
+
+        assert!(handle
+            .reload(r#"{"timers": {"turn_time_limit_secs": 0}}"#)
+            .is_err());
+        assert!(handle
+            .reload(r#"{"timers": {"turn_time_limit_secs": 18446744073709551615}}"#)
+            .is_err());
+        assert!(handle.current().feature("avalanches"));
+    }
+}
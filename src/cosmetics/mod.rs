@@ -0,0 +1,155 @@
+// src/cosmetics/mod.rs
+use crate::errors::CosmeticError;
+use crate::game_state::GameState;
+use crate::networking::{PlayerPresentation, ServerMessage};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CosmeticSlot {
+    CardBack,
+    BoardSkin,
+    Avatar,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cosmetic {
+    pub id: String,
+    pub name: String,
+    pub slot: CosmeticSlot,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EquippedCosmetics {
+    pub card_back: Option<String>,
+    pub board_skin: Option<String>,
+    pub avatar: Option<String>,
+}
+
+// Kept apart from the card Collection: cosmetics never enter decks and are
+// never traded or disenchanted.
+#[derive(Debug, Clone)]
+pub struct CosmeticInventory {
+    pub owner_id: Uuid,
+    owned: HashMap<String, Cosmetic>,
+    equipped: HashMap<CosmeticSlot, String>,
+}
+
+impl CosmeticInventory {
+    pub fn new(owner_id: Uuid) -> Self {
+        Self {
+            owner_id,
+            owned: HashMap::new(),
+            equipped: HashMap::new(),
+        }
+    }
+
+    pub fn owns(&self, cosmetic_id: &str) -> bool {
+        self.owned.contains_key(cosmetic_id)
+    }
+
+    pub fn owned_in_slot(&self, slot: CosmeticSlot) -> Vec<&Cosmetic> {
+        self.owned
+            .values()
+            .filter(|cosmetic| cosmetic.slot == slot)
+            .collect()
+    }
+
+    pub fn grant(&mut self, cosmetic: Cosmetic) -> Result<(), CosmeticError> {
+        if self.owns(&cosmetic.id) {
+            return Err(CosmeticError::AlreadyOwned);
+        }
+        self.owned.insert(cosmetic.id.clone(), cosmetic);
+        Ok(())
+    }
+
+    pub fn equip(&mut self, cosmetic_id: &str) -> Result<(), CosmeticError> {
+        let cosmetic = self.owned.get(cosmetic_id).ok_or(CosmeticError::NotOwned)?;
+        self.equipped.insert(cosmetic.slot, cosmetic.id.clone());
+        Ok(())
+    }
+
+    pub fn unequip(&mut self, slot: CosmeticSlot) {
+        self.equipped.remove(&slot);
+    }
+
+    pub fn equipped(&self) -> EquippedCosmetics {
+        EquippedCosmetics {
+            card_back: self.equipped.get(&CosmeticSlot::CardBack).cloned(),
+            board_skin: self.equipped.get(&CosmeticSlot::BoardSkin).cloned(),
+            avatar: self.equipped.get(&CosmeticSlot::Avatar).cloned(),
+        }
+    }
+}
+
+// Players without an inventory on record are presented with the defaults.
+// Profile cards are added separately, see profiles::attach_cards.
+pub fn game_start_message(
+    game_state: &GameState,
+    inventories: &HashMap<Uuid, CosmeticInventory>,
+) -> ServerMessage {
+    let players = game_state
+        .turn_order
+        .iter()
+        .filter_map(|id| game_state.players.get(id))
+        .map(|player| PlayerPresentation {
+            player_id: player.id,
+            name: player.name.clone(),
+            cosmetics: inventories
+                .get(&player.id)
+                .map(CosmeticInventory::equipped)
+                .unwrap_or_default(),
+            profile: None,
+        })
+        .collect();
+
+    ServerMessage::GameStarted {
+        game_id: game_state.game_id,
+        players,
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod cosmetic_tests {
+    use super::*;
+    use crate::models::{Deck, Player};
+
+    #[test]
+    fn test_equip_and_game_start_metadata() {
+        let deck = || Deck {
+            cards: vec![].into(),
+            owner_id: Uuid::new_v4(),
+        };
+        let player1 = Player::new("Player 1".to_string(), deck());
+        let player2 = Player::new("Player 2".to_string(), deck());
+        let p1 = player1.id;
+        let game_state = GameState::new(player1, player2);
+
+        let mut inventory = CosmeticInventory::new(p1);
+        assert!(matches!(
+            inventory.equip("back_glacier"),
+            Err(CosmeticError::NotOwned)
+        ));
+        inventory
+            .grant(Cosmetic {
+                id: "back_glacier".to_string(),
+                name: "Glacier Card Back".to_string(),
+                slot: CosmeticSlot::CardBack,
+            })
+            .unwrap();
+        inventory.equip("back_glacier").unwrap();
+
+        let message = game_start_message(&game_state, &HashMap::from([(p1, inventory)]));
+        let ServerMessage::GameStarted { players, .. } = message else {
+            panic!("expected GameStarted");
+        };
+        assert_eq!(players[0].player_id, p1);
+        assert_eq!(
+            players[0].cosmetics.card_back.as_deref(),
+            Some("back_glacier")
+        );
+        assert_eq!(players[1].cosmetics, EquippedCosmetics::default());
+    }
+}
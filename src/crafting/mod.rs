@@ -0,0 +1,269 @@
+// src/crafting/mod.rs
+use crate::collections::provenance::{Acquisition, OwnedItem};
+use crate::collections::{self, Collection};
+use crate::database::CollectionStorage;
+use crate::errors::CraftingError;
+use crate::models::Card;
+use crate::wallet::{Currency, TransactionReason, Wallet};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+pub const DEFAULT_RECIPES: &str = include_str!("../../data/crafting_recipes.json");
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum CraftOutput {
+    Foil,           // Upgrades the target copy itself
+    AltArt(String), // Unlocks the art for every copy of the card
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Recipe {
+    pub id: String,
+    pub card: String,    // Card name the recipe applies to
+    pub duplicates: u32, // Extra copies of the card used up
+    pub dust: u64,
+    pub output: CraftOutput,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecipeBook {
+    pub recipes: Vec<Recipe>,
+}
+
+impl RecipeBook {
+    pub fn from_json(data: &str) -> Result<Self, CraftingError> {
+        let book: RecipeBook =
+            serde_json::from_str(data).map_err(|e| CraftingError::InvalidRecipes(e.to_string()))?;
+
+        let mut ids = HashSet::new();
+        let mut arts = HashSet::new();
+        for recipe in &book.recipes {
+            if !ids.insert(&recipe.id) {
+                return Err(CraftingError::InvalidRecipes(format!(
+                    "duplicate recipe {}",
+                    recipe.id
+                )));
+            }
+            if recipe.duplicates == 0 && recipe.dust == 0 {
+                return Err(CraftingError::InvalidRecipes(format!(
+                    "recipe {} costs nothing",
+                    recipe.id
+                )));
+            }
+            if let CraftOutput::AltArt(art) = &recipe.output {
+                if !arts.insert(art) {
+                    return Err(CraftingError::InvalidRecipes(format!(
+                        "art {} is unlocked by two recipes",
+                        art
+                    )));
+                }
+            }
+        }
+        Ok(book)
+    }
+
+    pub fn recipe(&self, recipe_id: &str) -> Option<&Recipe> {
+        self.recipes.iter().find(|recipe| recipe.id == recipe_id)
+    }
+
+    pub fn for_card(&self, name: &str) -> impl Iterator<Item = &Recipe> + '_ {
+        let name = name.to_string();
+        self.recipes
+            .iter()
+            .filter(move |recipe| recipe.card == name)
+    }
+
+    // Validates everything up front so a craft either fully happens or
+    // leaves the collection and wallet untouched
+    pub fn craft(
+        &self,
+        recipe_id: &str,
+        collection: &mut Collection,
+        wallet: &mut Wallet,
+        target: &Card,
+        duplicates: &[Card],
+        now: SystemTime,
+    ) -> Result<(), CraftingError> {
+        let recipe = self.recipe(recipe_id).ok_or(CraftingError::UnknownRecipe)?;
+        for card in std::iter::once(target).chain(duplicates) {
+            if card.name != recipe.card {
+                return Err(CraftingError::WrongCard(card.id));
+            }
+        }
+        let consumed: HashSet<Uuid> = duplicates.iter().map(|card| card.id).collect();
+        if consumed.len() != duplicates.len()
+            || consumed.contains(&target.id)
+            || duplicates.len() != recipe.duplicates as usize
+        {
+            return Err(CraftingError::WrongDuplicateCount {
+                needed: recipe.duplicates,
+                given: consumed.len(),
+            });
+        }
+        let owned: Vec<Uuid> = std::iter::once(target.id)
+            .chain(consumed.iter().copied())
+            .collect();
+        collection
+            .check_tradable(&owned)
+            .map_err(CraftingError::Unavailable)?;
+        let already = match &recipe.output {
+            CraftOutput::Foil => collection.foils.contains(&target.id),
+            CraftOutput::AltArt(art) => collection.alt_arts.contains(art),
+        };
+        if already {
+            return Err(CraftingError::AlreadyCrafted);
+        }
+
+        wallet
+            .debit(Currency::Dust, recipe.dust, TransactionReason::Crafting)
+            .map_err(CraftingError::Wallet)?;
+        // Everything in `owned` after the target is a consumed duplicate
+        collection.give_up(&owned[1..]);
+        let item = match &recipe.output {
+            CraftOutput::Foil => OwnedItem::Foil(target.id),
+            CraftOutput::AltArt(art) => OwnedItem::AltArt(art.clone()),
+        };
+        collection.grant(item, Acquisition::Craft, now);
+        Ok(())
+    }
+
+    // Crafts against the owner's stored collection and wallet, which are
+    // written back together only if the craft goes through
+    pub fn craft_stored(
+        &self,
+        recipe_id: &str,
+        storage: &mut impl CollectionStorage,
+        owner_id: Uuid,
+        target: &Card,
+        duplicates: &[Card],
+        now: SystemTime,
+    ) -> Result<(), CraftingError> {
+        collections::transact(storage, owner_id, |collection, wallet| {
+            self.craft(recipe_id, collection, wallet, target, duplicates, now)
+        })
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod crafting_tests {
+    use super::*;
+    use crate::database::MemoryStorage;
+    use crate::models::{Deck, Format};
+    use crate::testing::CardBuilder;
+
+    #[test]
+    fn test_foil_consumes_duplicates_and_dust() {
+        let book = RecipeBook::from_json(DEFAULT_RECIPES).unwrap();
+        let golems: Vec<Card> = (0..3)
+            .map(|_| CardBuilder::new("Ice Golem").build())
+            .collect();
+        let mut collection = Collection::new(Uuid::new_v4());
+        collection.cards.extend(golems.iter().map(|card| card.id));
+        let mut wallet = Wallet::new(collection.owner_id);
+        wallet
+            .credit(Currency::Dust, 100, TransactionReason::Reward)
+            .unwrap();
+
+        // Short on duplicates: nothing is spent
+        assert!(matches!(
+            book.craft(
+                "foil_ice_golem",
+                &mut collection,
+                &mut wallet,
+                &golems[0],
+                &golems[1..2],
+                SystemTime::UNIX_EPOCH
+            ),
+            Err(CraftingError::WrongDuplicateCount { needed: 2, .. })
+        ));
+        assert_eq!(wallet.balance(Currency::Dust), 100);
+
+        book.craft(
+            "foil_ice_golem",
+            &mut collection,
+            &mut wallet,
+            &golems[0],
+            &golems[1..],
+            SystemTime::UNIX_EPOCH,
+        )
+        .unwrap();
+        assert_eq!(wallet.balance(Currency::Dust), 0);
+        assert_eq!(collection.cards.len(), 1);
+        assert!(collection.foils.contains(&golems[0].id));
+    }
+
+    #[test]
+    fn test_stored_craft_prunes_decks_and_saves_once() {
+        let book = RecipeBook::from_json(DEFAULT_RECIPES).unwrap();
+        let golems: Vec<Card> = (0..3)
+            .map(|_| CardBuilder::new("Ice Golem").build())
+            .collect();
+        let owner_id = Uuid::new_v4();
+        let mut collection = Collection::new(owner_id);
+        collection.cards.extend(golems.iter().map(|card| card.id));
+        collection.seen.extend(golems.iter().map(|card| card.id));
+        collection.save_deck(
+            "Golems".to_string(),
+            Deck {
+                cards: golems.clone().into(),
+                owner_id,
+            },
+            Format::Constructed,
+        );
+        let mut wallet = Wallet::new(owner_id);
+        wallet
+            .credit(Currency::Dust, 100, TransactionReason::Reward)
+            .unwrap();
+        let mut storage = MemoryStorage::default();
+        storage.save_holdings(collection, wallet);
+
+        // Refused crafts write nothing back
+        assert!(book
+            .craft_stored(
+                "foil_ice_golem",
+                &mut storage,
+                owner_id,
+                &golems[0],
+                &golems[1..2],
+                SystemTime::UNIX_EPOCH
+            )
+            .is_err());
+        assert_eq!(storage.collections[&owner_id].cards.len(), 3);
+
+        book.craft_stored(
+            "foil_ice_golem",
+            &mut storage,
+            owner_id,
+            &golems[0],
+            &golems[1..],
+            SystemTime::UNIX_EPOCH,
+        )
+        .unwrap();
+        let collection = &storage.collections[&owner_id];
+        assert_eq!(storage.wallets[&owner_id].balance(Currency::Dust), 0);
+        assert_eq!(collection.cards.len(), 1);
+        assert_eq!(collection.seen.len(), 1);
+        let deck: Vec<Uuid> = collection.decks["Golems"]
+            .cards
+            .iter()
+            .map(|card| card.id)
+            .collect();
+        assert_eq!(deck, vec![golems[0].id]);
+        assert!(collection.foils.contains(&golems[0].id));
+    }
+
+    #[test]
+    fn test_duplicate_recipe_ids_are_rejected() {
+        let data = r#"{"recipes": [
+            {"id": "a", "card": "Boulder", "duplicates": 1, "dust": 0, "output": "Foil"},
+            {"id": "a", "card": "Rope", "duplicates": 1, "dust": 0, "output": "Foil"}
+        ]}"#;
+        assert!(matches!(
+            RecipeBook::from_json(data),
+            Err(CraftingError::InvalidRecipes(_))
+        ));
+    }
+}
@@ -1,3 +1,576 @@
 // src/database/mod.rs
-// Placeholder for database implementation
-pub struct DatabaseConnection;
+use crate::collections::mastery::Mastery;
+use crate::collections::Collection;
+use crate::draft::DraftRun;
+use crate::guilds::{Guild, GuildRegistry};
+use crate::profiles::Profile;
+use crate::summary::GameSummary;
+use crate::wallet::Wallet;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Primary,
+    Replica,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseConnection {
+    pub url: String,
+    pub role: Role,
+}
+
+// What a read is for, so each kind can say how stale it may be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryClass {
+    Leaderboard,
+    MatchHistory,
+    DeckBrowsing,
+    // Anything reading back what the caller just wrote
+    Account,
+}
+
+// Per-class staleness tolerance. Classes without an entry always read from
+// the primary.
+#[derive(Debug, Clone)]
+pub struct ReadPolicy {
+    pub max_staleness: HashMap<QueryClass, Duration>,
+}
+
+impl Default for ReadPolicy {
+    fn default() -> Self {
+        Self {
+            max_staleness: HashMap::from([
+                (QueryClass::Leaderboard, Duration::from_secs(30)),
+                (QueryClass::MatchHistory, Duration::from_secs(10)),
+                (QueryClass::DeckBrowsing, Duration::from_secs(60)),
+            ]),
+        }
+    }
+}
+
+// A statement prepared on each connection at startup, so the first match
+// after a deploy doesn't pay for planning every query it makes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreparedStatement {
+    pub name: &'static str,
+    pub sql: &'static str,
+    // Only ever sent to the primary
+    pub writes: bool,
+}
+
+pub const PREPARED_STATEMENTS: &[PreparedStatement] = &[
+    PreparedStatement {
+        name: "load_profile",
+        sql: "SELECT profile FROM profiles WHERE player_id = $1",
+        writes: false,
+    },
+    PreparedStatement {
+        name: "save_profile",
+        sql: "INSERT INTO profiles (player_id, profile) VALUES ($1, $2) \
+              ON CONFLICT (player_id) DO UPDATE SET profile = $2",
+        writes: true,
+    },
+    PreparedStatement {
+        name: "load_mastery",
+        sql: "SELECT mastery FROM mastery WHERE player_id = $1",
+        writes: false,
+    },
+    PreparedStatement {
+        name: "record_match",
+        sql: "INSERT INTO matches (game_id, players, winner, played_at, summary) \
+              VALUES ($1, $2, $3, $4, $5)",
+        writes: true,
+    },
+    PreparedStatement {
+        name: "match_history",
+        sql: "SELECT * FROM matches WHERE $1 = ANY(players) ORDER BY played_at DESC LIMIT $2",
+        writes: false,
+    },
+    PreparedStatement {
+        name: "leaderboard_page",
+        sql: "SELECT player_id, rating FROM ratings ORDER BY rating DESC LIMIT $1 OFFSET $2",
+        writes: false,
+    },
+];
+
+#[derive(Debug)]
+struct Replica {
+    connection: DatabaseConnection,
+    lag: Option<Duration>, // None until a health check has reported
+}
+
+// Routes queries across a primary and its read replicas. Writes always go to
+// the primary; reads go round-robin to replicas caught up enough for the
+// query class, falling back to the primary when none are.
+#[derive(Debug)]
+pub struct DatabaseCluster {
+    primary: DatabaseConnection,
+    replicas: Vec<Replica>,
+    pub policy: ReadPolicy,
+    next_replica: usize,
+}
+
+impl DatabaseCluster {
+    pub fn new(primary_url: &str) -> Self {
+        Self {
+            primary: DatabaseConnection {
+                url: primary_url.to_string(),
+                role: Role::Primary,
+            },
+            replicas: Vec::new(),
+            policy: ReadPolicy::default(),
+            next_replica: 0,
+        }
+    }
+
+    pub fn with_replica(mut self, url: &str) -> Self {
+        self.replicas.push(Replica {
+            connection: DatabaseConnection {
+                url: url.to_string(),
+                role: Role::Replica,
+            },
+            lag: None,
+        });
+        self
+    }
+
+    pub fn with_policy(mut self, policy: ReadPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    // Called by the replication health check
+    pub fn report_lag(&mut self, url: &str, lag: Duration) {
+        if let Some(replica) = self
+            .replicas
+            .iter_mut()
+            .find(|replica| replica.connection.url == url)
+        {
+            replica.lag = Some(lag);
+        }
+    }
+
+    pub fn write(&self) -> &DatabaseConnection {
+        &self.primary
+    }
+
+    // The primary first, then every replica whether caught up or not
+    pub fn connections(&self) -> impl Iterator<Item = &DatabaseConnection> {
+        std::iter::once(&self.primary).chain(self.replicas.iter().map(|r| &r.connection))
+    }
+
+    pub fn read(&mut self, class: QueryClass) -> &DatabaseConnection {
+        let tolerance = self.policy.max_staleness.get(&class).copied();
+        self.read_within(tolerance)
+    }
+
+    // For a single query that needs a tighter or looser bound than its class
+    pub fn read_within(&mut self, max_staleness: Option<Duration>) -> &DatabaseConnection {
+        let Some(tolerance) = max_staleness else {
+            return &self.primary;
+        };
+        let count = self.replicas.len();
+        for offset in 0..count {
+            let index = (self.next_replica + offset) % count;
+            if self.replicas[index].lag.is_some_and(|lag| lag <= tolerance) {
+                self.next_replica = index + 1;
+                return &self.replicas[index].connection;
+            }
+        }
+        &self.primary
+    }
+}
+
+// Data kept only for a while, each pruned on its own schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetainedData {
+    Replays,
+    Telemetry,
+    ChatLogs,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchRecord {
+    pub game_id: Uuid,
+    pub players: Vec<Uuid>,
+    pub winner: Option<Uuid>,
+    pub played_at: SystemTime,
+    pub summary: Option<GameSummary>,
+}
+
+// Personal details tied to an account
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerRecord {
+    pub player_id: Uuid,
+    pub email: String,
+    pub display_name: String,
+}
+
+// The operations erasure and retention need from the storage backend
+pub trait PlayerStorage {
+    // Swaps the player for `pseudonym` everywhere in match history, so
+    // opponents' records and stats stay intact
+    fn anonymize_matches(&mut self, player_id: Uuid, pseudonym: Uuid) -> usize;
+    fn delete_player_record(&mut self, player_id: Uuid) -> bool;
+    fn revoke_sessions(&mut self, player_id: Uuid) -> usize;
+    fn delete_owned(&mut self, player_id: Uuid, kind: RetainedData) -> usize;
+    fn prune(&mut self, kind: RetainedData, older_than: SystemTime) -> usize;
+}
+
+pub trait ProfileStorage {
+    fn load_profile(&self, player_id: Uuid) -> Option<Profile>;
+    fn save_profile(&mut self, profile: Profile);
+}
+
+pub trait MasteryStorage {
+    fn load_mastery(&self, player_id: Uuid) -> Option<Mastery>;
+    fn save_mastery(&mut self, mastery: Mastery);
+}
+
+// A player's run in progress; starting another replaces it
+pub trait DraftStorage {
+    fn load_draft(&self, player_id: Uuid) -> Option<DraftRun>;
+    fn save_draft(&mut self, run: DraftRun);
+}
+
+// A player's cards and wallet are written in one go, so a change that
+// spends from one and adds to the other lands whole or not at all
+pub trait CollectionStorage {
+    fn load_collection(&self, owner_id: Uuid) -> Option<Collection>;
+    fn load_wallet(&self, owner_id: Uuid) -> Option<Wallet>;
+    fn save_holdings(&mut self, collection: Collection, wallet: Wallet);
+}
+
+pub trait GuildStorage {
+    fn load_guilds(&self) -> Vec<Guild>;
+    fn save_guild(&mut self, guild: Guild);
+    fn delete_guild(&mut self, guild_id: Uuid) -> bool;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurgeReport {
+    pub pseudonym: Uuid,
+    pub matches_anonymized: usize,
+    pub record_deleted: bool,
+    pub sessions_revoked: usize,
+    pub chat_logs_deleted: usize,
+    pub left_guild: bool,
+    pub guild_messages_deleted: usize,
+}
+
+// Right to erasure: match history keeps its shape under a random
+// pseudonym, everything that identifies the player is deleted and any live
+// session is cut off. Guilds are changed in the running registry and saved
+// from there, so a later save can't put the player back.
+pub fn purge_player(
+    storage: &mut (impl PlayerStorage + GuildStorage),
+    guilds: &mut GuildRegistry,
+    player_id: Uuid,
+) -> PurgeReport {
+    let pseudonym = Uuid::new_v4();
+    let (left_guild, guild_messages_deleted) = guilds.purge_player(player_id);
+    guilds.save(storage);
+    PurgeReport {
+        pseudonym,
+        matches_anonymized: storage.anonymize_matches(player_id, pseudonym),
+        record_deleted: storage.delete_player_record(player_id),
+        sessions_revoked: storage.revoke_sessions(player_id),
+        chat_logs_deleted: storage.delete_owned(player_id, RetainedData::ChatLogs),
+        left_guild,
+        guild_messages_deleted,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetentionPolicy {
+    pub keep_for: HashMap<RetainedData, Duration>,
+    pub run_every: Duration,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        let day = Duration::from_secs(24 * 60 * 60);
+        Self {
+            keep_for: HashMap::from([
+                (RetainedData::Replays, day * 90),
+                (RetainedData::Telemetry, day * 30),
+                (RetainedData::ChatLogs, day * 14),
+            ]),
+            run_every: day,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RetentionJob {
+    pub policy: RetentionPolicy,
+    last_run: Option<SystemTime>,
+}
+
+impl RetentionJob {
+    pub fn new(policy: RetentionPolicy) -> Self {
+        Self {
+            policy,
+            last_run: None,
+        }
+    }
+
+    // Polled by the scheduler. Prunes everything past its retention period
+    // once per `run_every`, returning how much went of each kind.
+    pub fn run_if_due(
+        &mut self,
+        storage: &mut impl PlayerStorage,
+        now: SystemTime,
+    ) -> Option<HashMap<RetainedData, usize>> {
+        if self.last_run.is_some_and(|last| {
+            now.duration_since(last).unwrap_or_default() < self.policy.run_every
+        }) {
+            return None;
+        }
+        self.last_run = Some(now);
+        Some(
+            self.policy
+                .keep_for
+                .iter()
+                .map(|(kind, keep_for)| {
+                    let cutoff = now.checked_sub(*keep_for).unwrap_or(SystemTime::UNIX_EPOCH);
+                    (*kind, storage.prune(*kind, cutoff))
+                })
+                .collect(),
+        )
+    }
+}
+
+// Backend kept in memory, for a single node without a database and tests
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    pub matches: Vec<MatchRecord>,
+    pub players: HashMap<Uuid, PlayerRecord>,
+    pub profiles: HashMap<Uuid, Profile>,
+    pub mastery: HashMap<Uuid, Mastery>,
+    pub guilds: HashMap<Uuid, Guild>,
+    pub collections: HashMap<Uuid, Collection>,
+    pub wallets: HashMap<Uuid, Wallet>,
+    pub drafts: HashMap<Uuid, DraftRun>,
+    pub sessions: HashMap<Uuid, Uuid>, // Session token to player
+    pub retained: Vec<(RetainedData, Uuid, SystemTime)>, // Kind, owner, created
+}
+
+impl PlayerStorage for MemoryStorage {
+    fn anonymize_matches(&mut self, player_id: Uuid, pseudonym: Uuid) -> usize {
+        let mut changed = 0;
+        for record in &mut self.matches {
+            if !record.players.contains(&player_id) {
+                continue;
+            }
+            for player in &mut record.players {
+                if *player == player_id {
+                    *player = pseudonym;
+                }
+            }
+            if record.winner == Some(player_id) {
+                record.winner = Some(pseudonym);
+            }
+            if let Some(summary) = &mut record.summary {
+                summary.replace_player(player_id, pseudonym);
+            }
+            changed += 1;
+        }
+        changed
+    }
+
+    // The profile and card mastery go with the account
+    fn delete_player_record(&mut self, player_id: Uuid) -> bool {
+        self.profiles.remove(&player_id);
+        self.mastery.remove(&player_id);
+        self.players.remove(&player_id).is_some()
+    }
+
+    fn revoke_sessions(&mut self, player_id: Uuid) -> usize {
+        let before = self.sessions.len();
+        self.sessions.retain(|_, owner| *owner != player_id);
+        before - self.sessions.len()
+    }
+
+    fn delete_owned(&mut self, player_id: Uuid, kind: RetainedData) -> usize {
+        let before = self.retained.len();
+        self.retained
+            .retain(|(stored, owner, _)| !(*stored == kind && *owner == player_id));
+        before - self.retained.len()
+    }
+
+    fn prune(&mut self, kind: RetainedData, older_than: SystemTime) -> usize {
+        let before = self.retained.len();
+        self.retained
+            .retain(|(stored, _, created)| !(*stored == kind && *created < older_than));
+        before - self.retained.len()
+    }
+}
+
+impl ProfileStorage for MemoryStorage {
+    fn load_profile(&self, player_id: Uuid) -> Option<Profile> {
+        self.profiles.get(&player_id).cloned()
+    }
+
+    fn save_profile(&mut self, profile: Profile) {
+        self.profiles.insert(profile.player_id, profile);
+    }
+}
+
+impl MasteryStorage for MemoryStorage {
+    fn load_mastery(&self, player_id: Uuid) -> Option<Mastery> {
+        self.mastery.get(&player_id).cloned()
+    }
+
+    fn save_mastery(&mut self, mastery: Mastery) {
+        self.mastery.insert(mastery.owner_id, mastery);
+    }
+}
+
+impl DraftStorage for MemoryStorage {
+    fn load_draft(&self, player_id: Uuid) -> Option<DraftRun> {
+        self.drafts.get(&player_id).cloned()
+    }
+
+    fn save_draft(&mut self, run: DraftRun) {
+        self.drafts.insert(run.player_id, run);
+    }
+}
+
+impl CollectionStorage for MemoryStorage {
+    fn load_collection(&self, owner_id: Uuid) -> Option<Collection> {
+        self.collections.get(&owner_id).cloned()
+    }
+
+    fn load_wallet(&self, owner_id: Uuid) -> Option<Wallet> {
+        self.wallets.get(&owner_id).cloned()
+    }
+
+    fn save_holdings(&mut self, collection: Collection, wallet: Wallet) {
+        self.collections.insert(collection.owner_id, collection);
+        self.wallets.insert(wallet.owner_id, wallet);
+    }
+}
+
+impl GuildStorage for MemoryStorage {
+    fn load_guilds(&self) -> Vec<Guild> {
+        self.guilds.values().cloned().collect()
+    }
+
+    fn save_guild(&mut self, guild: Guild) {
+        self.guilds.insert(guild.id, guild);
+    }
+
+    fn delete_guild(&mut self, guild_id: Uuid) -> bool {
+        self.guilds.remove(&guild_id).is_some()
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod database_tests {
+    use super::*;
+    use crate::guilds::GuildRole;
+    use crate::moderation::ModerationService;
+
+    #[test]
+    fn test_reads_skip_lagging_replicas() {
+        let mut cluster = DatabaseCluster::new("primary")
+            .with_replica("replica-a")
+            .with_replica("replica-b");
+        assert_eq!(cluster.read(QueryClass::Leaderboard).url, "primary");
+
+        cluster.report_lag("replica-a", Duration::from_secs(20));
+        cluster.report_lag("replica-b", Duration::from_secs(1));
+        let leaderboard: Vec<String> = (0..2)
+            .map(|_| cluster.read(QueryClass::Leaderboard).url.clone())
+            .collect();
+        assert_eq!(leaderboard, ["replica-a", "replica-b"]);
+
+        assert_eq!(cluster.read(QueryClass::MatchHistory).url, "replica-b");
+        assert_eq!(cluster.read(QueryClass::Account).url, "primary");
+        assert_eq!(cluster.read_within(Some(Duration::ZERO)).url, "primary");
+        assert_eq!(cluster.write().role, Role::Primary);
+    }
+
+    #[test]
+    fn test_purge_anonymizes_history_and_revokes_sessions() {
+        let (player, opponent) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut storage = MemoryStorage::default();
+        storage.matches.push(MatchRecord {
+            game_id: Uuid::new_v4(),
+            players: vec![player, opponent],
+            winner: Some(player),
+            played_at: SystemTime::UNIX_EPOCH,
+            summary: None,
+        });
+        storage.players.insert(
+            player,
+            PlayerRecord {
+                player_id: player,
+                email: "climber@example.com".to_string(),
+                display_name: "Climber".to_string(),
+            },
+        );
+        storage.sessions.insert(Uuid::new_v4(), player);
+        storage.sessions.insert(Uuid::new_v4(), opponent);
+        let mut guilds = GuildRegistry::new();
+        let guild_id = guilds
+            .create_guild("Summiteers".to_string(), player)
+            .unwrap();
+        guilds.join(guild_id, opponent).unwrap();
+        let moderation = ModerationService::new();
+        for author in [player, opponent, player] {
+            guilds
+                .post_message(
+                    author,
+                    "hi".to_string(),
+                    &moderation,
+                    SystemTime::UNIX_EPOCH,
+                )
+                .unwrap();
+        }
+        guilds.save(&mut storage);
+
+        let report = purge_player(&mut storage, &mut guilds, player);
+        assert_eq!(report.matches_anonymized, 1);
+        assert!(report.record_deleted);
+        assert_eq!(report.sessions_revoked, 1);
+        assert!(report.left_guild);
+        assert_eq!(report.guild_messages_deleted, 2);
+        let record = &storage.matches[0];
+        assert_eq!(record.players, [report.pseudonym, opponent]);
+        assert_eq!(record.winner, Some(report.pseudonym));
+        assert_eq!(storage.sessions.len(), 1);
+
+        // Gone from storage as well as the running registry
+        let guild = &storage.guilds[&guild_id];
+        assert!(!guild.members.contains_key(&player));
+        assert_eq!(guild.role_of(opponent), Some(GuildRole::Leader));
+        assert!(guild.chat.iter().all(|message| message.author == opponent));
+        assert!(GuildRegistry::load(&storage).guild_of(player).is_none());
+    }
+
+    #[test]
+    fn test_retention_job_prunes_on_schedule() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let now = SystemTime::UNIX_EPOCH + day * 100;
+        let mut storage = MemoryStorage::default();
+        let owner = Uuid::new_v4();
+        storage.retained = vec![
+            (RetainedData::Replays, owner, now - day * 91),
+            (RetainedData::Replays, owner, now - day * 10),
+            (RetainedData::ChatLogs, owner, now - day * 15),
+        ];
+        let mut job = RetentionJob::new(RetentionPolicy::default());
+
+        let pruned = job.run_if_due(&mut storage, now).unwrap();
+        assert_eq!(pruned[&RetainedData::Replays], 1);
+        assert_eq!(pruned[&RetainedData::ChatLogs], 1);
+        assert_eq!(storage.retained.len(), 1);
+        assert!(job.run_if_due(&mut storage, now + day / 2).is_none());
+    }
+}
@@ -0,0 +1,316 @@
+// src/deck_sharing/mod.rs
+use crate::collections::Collection;
+use crate::errors::DeckShareError;
+use crate::models::{Card, Deck, Format};
+use std::collections::HashMap;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct PublishedDeck {
+    pub id: Uuid,
+    pub author_id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub deck: Deck,
+    pub published_at: SystemTime,
+    pub games: u32,
+    pub wins: u32,
+}
+
+impl PublishedDeck {
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeckSort {
+    Recent,
+    WinRate,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeckQuery {
+    pub text: Option<String>,
+    pub tags: Vec<String>,
+    pub sort: DeckSort,
+    pub limit: usize,
+}
+
+impl Default for DeckQuery {
+    fn default() -> Self {
+        Self {
+            text: None,
+            tags: Vec::new(),
+            sort: DeckSort::Recent,
+            limit: 50,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DeckLibrary {
+    decks: HashMap<Uuid, PublishedDeck>,
+}
+
+impl DeckLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, deck_id: Uuid) -> Option<&PublishedDeck> {
+        self.decks.get(&deck_id)
+    }
+
+    pub fn publish(
+        &mut self,
+        author_id: Uuid,
+        name: String,
+        description: String,
+        tags: Vec<String>,
+        deck: Deck,
+        now: SystemTime,
+    ) -> Result<Uuid, DeckShareError> {
+        deck.validate(Format::Constructed)
+            .map_err(DeckShareError::InvalidDeck)?;
+
+        let published = PublishedDeck {
+            id: Uuid::new_v4(),
+            author_id,
+            name,
+            description,
+            tags: tags.into_iter().map(|tag| tag.to_lowercase()).collect(),
+            deck,
+            published_at: now,
+            games: 0,
+            wins: 0,
+        };
+        let deck_id = published.id;
+        self.decks.insert(deck_id, published);
+        Ok(deck_id)
+    }
+
+    pub fn unpublish(&mut self, author_id: Uuid, deck_id: Uuid) -> Result<(), DeckShareError> {
+        let deck = self
+            .decks
+            .get(&deck_id)
+            .ok_or(DeckShareError::DeckNotFound)?;
+        if deck.author_id != author_id {
+            return Err(DeckShareError::NotAuthor);
+        }
+        self.decks.remove(&deck_id);
+        Ok(())
+    }
+
+    pub fn record_result(&mut self, deck_id: Uuid, won: bool) {
+        if let Some(deck) = self.decks.get_mut(&deck_id) {
+            deck.games += 1;
+            if won {
+                deck.wins += 1;
+            }
+        }
+    }
+
+    // Text matches name or description case-insensitively; every requested
+    // tag must be present on the deck.
+    pub fn search(&self, query: &DeckQuery) -> Vec<&PublishedDeck> {
+        let text = query.text.as_ref().map(|text| text.to_lowercase());
+        let mut results: Vec<&PublishedDeck> = self
+            .decks
+            .values()
+            .filter(|deck| {
+                text.as_ref().is_none_or(|text| {
+                    deck.name.to_lowercase().contains(text)
+                        || deck.description.to_lowercase().contains(text)
+                })
+            })
+            .filter(|deck| {
+                query
+                    .tags
+                    .iter()
+                    .all(|tag| deck.tags.contains(&tag.to_lowercase()))
+            })
+            .collect();
+
+        match query.sort {
+            DeckSort::Recent => results.sort_by_key(|deck| std::cmp::Reverse(deck.published_at)),
+            DeckSort::WinRate => results.sort_by(|a, b| {
+                b.win_rate()
+                    .total_cmp(&a.win_rate())
+                    .then(b.games.cmp(&a.games))
+            }),
+        }
+        results.truncate(query.limit);
+        results
+    }
+
+    // Saves a copy built from the copier's own cards, matched by name.
+    // `inventory` is the copier's cards in full.
+    pub fn copy_into(
+        &self,
+        deck_id: Uuid,
+        collection: &mut Collection,
+        inventory: &[Card],
+        name: Option<String>,
+    ) -> Result<String, DeckShareError> {
+        let published = self
+            .decks
+            .get(&deck_id)
+            .ok_or(DeckShareError::DeckNotFound)?;
+
+        let missing = collection.missing_cards(&published.deck, inventory);
+        if !missing.is_empty() {
+            return Err(DeckShareError::MissingCards(missing));
+        }
+        let cards = collection
+            .own_copies(&published.deck, inventory)
+            .ok_or(DeckShareError::MissingCards(Vec::new()))?;
+
+        let name = name.unwrap_or_else(|| published.name.clone());
+        if collection.decks.contains_key(&name) {
+            return Err(DeckShareError::NameTaken);
+        }
+        collection.save_deck(
+            name.clone(),
+            Deck {
+                cards: cards.into(),
+                owner_id: collection.owner_id,
+            },
+            Format::Constructed,
+        );
+        Ok(name)
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod deck_sharing_tests {
+    use super::*;
+    use crate::models::{Card, CardType, Rarity};
+    use std::time::Duration;
+
+    fn deck(owner_id: Uuid) -> Deck {
+        Deck {
+            cards: (0..30)
+                .map(|i| Card {
+                    id: Uuid::new_v4(),
+                    name: format!("Card {}", i),
+                    cost: 1,
+                    power: 1,
+                    rarity: Rarity::Common,
+                    effects: vec![],
+                    card_type: CardType::Climber,
+                })
+                .collect(),
+            owner_id,
+        }
+    }
+
+    #[test]
+    fn test_search_and_sort() {
+        let mut library = DeckLibrary::new();
+        let author = Uuid::new_v4();
+        let now = SystemTime::now();
+
+        let aggro = library
+            .publish(
+                author,
+                "Summit Rush".to_string(),
+                "Fast climbers".to_string(),
+                vec!["Aggro".to_string()],
+                deck(author),
+                now,
+            )
+            .unwrap();
+        let control = library
+            .publish(
+                author,
+                "Glacier Wall".to_string(),
+                "Slow and steady".to_string(),
+                vec!["control".to_string()],
+                deck(author),
+                now + Duration::from_secs(10),
+            )
+            .unwrap();
+        library.record_result(aggro, true);
+        library.record_result(control, false);
+
+        let recent = library.search(&DeckQuery::default());
+        assert_eq!(recent[0].id, control);
+
+        let by_win_rate = library.search(&DeckQuery {
+            sort: DeckSort::WinRate,
+            ..DeckQuery::default()
+        });
+        assert_eq!(by_win_rate[0].id, aggro);
+
+        let tagged = library.search(&DeckQuery {
+            tags: vec!["aggro".to_string()],
+            ..DeckQuery::default()
+        });
+        assert_eq!(tagged.len(), 1);
+
+        let text = library.search(&DeckQuery {
+            text: Some("steady".to_string()),
+            ..DeckQuery::default()
+        });
+        assert_eq!(text[0].id, control);
+    }
+
+    #[test]
+    fn test_copy_requires_owned_cards() {
+        let mut library = DeckLibrary::new();
+        let author = Uuid::new_v4();
+        let shared = deck(author);
+        let deck_id = library
+            .publish(
+                author,
+                "Summit Rush".to_string(),
+                String::new(),
+                vec![],
+                shared.clone(),
+                SystemTime::now(),
+            )
+            .unwrap();
+
+        let mut collection = Collection::new(Uuid::new_v4());
+        // The copier's own copies of the same cards, under their own ids
+        let inventory: Vec<Card> = shared
+            .cards
+            .iter()
+            .map(|card| Card {
+                id: Uuid::new_v4(),
+                ..card.clone()
+            })
+            .collect();
+        collection
+            .cards
+            .extend(inventory[1..].iter().map(|card| card.id));
+        assert!(matches!(
+            library.copy_into(deck_id, &mut collection, &inventory, None),
+            Err(DeckShareError::MissingCards(missing)) if missing == vec![("Card 0".to_string(), 1)]
+        ));
+
+        collection.cards.insert(inventory[0].id);
+        let name = library
+            .copy_into(deck_id, &mut collection, &inventory, None)
+            .unwrap();
+        let copied = &collection.decks[&name];
+        assert_eq!(copied.owner_id, collection.owner_id);
+        assert_eq!(copied.cards.len(), 30);
+        assert!(copied
+            .cards
+            .iter()
+            .all(|card| collection.cards.contains(&card.id)));
+        assert!(matches!(
+            library.copy_into(deck_id, &mut collection, &inventory, None),
+            Err(DeckShareError::NameTaken)
+        ));
+    }
+}
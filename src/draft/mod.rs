@@ -0,0 +1,243 @@
+// src/draft/mod.rs
+use crate::errors::{DraftError, ValidationError};
+use crate::models::{Card, Deck, Format, Rarity};
+use crate::rating::Rating;
+use crate::wallet::Currency;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const DRAFT_ROUNDS: usize = 30;
+pub const PICKS_PER_OFFER: usize = 3;
+pub const MAX_LOSSES: u32 = 3;
+pub const MAX_WINS: u32 = 12;
+
+// Relative odds of each rarity appearing in an offering slot
+const RARITY_WEIGHTS: [(Rarity, u32); 4] = [
+    (Rarity::Common, 70),
+    (Rarity::Uncommon, 20),
+    (Rarity::Rare, 8),
+    (Rarity::Legendary, 2),
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DraftPhase {
+    Drafting { offering: Vec<Card> },
+    Playing,
+    Complete,
+}
+
+// Saved after every pick and result, so a run outlives the session it
+// started in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DraftRun {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub phase: DraftPhase,
+    pub picks: Vec<Card>,
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl DraftRun {
+    pub fn start(player_id: Uuid, pool: &[Card], rng: &mut impl Rng) -> Result<Self, DraftError> {
+        let offering = generate_offering(pool, rng)?;
+        Ok(Self {
+            id: Uuid::new_v4(),
+            player_id,
+            phase: DraftPhase::Drafting { offering },
+            picks: Vec::new(),
+            wins: 0,
+            losses: 0,
+        })
+    }
+
+    pub fn offering(&self) -> Option<&[Card]> {
+        match &self.phase {
+            DraftPhase::Drafting { offering } => Some(offering),
+            _ => None,
+        }
+    }
+
+    pub fn pick(
+        &mut self,
+        index: usize,
+        pool: &[Card],
+        rng: &mut impl Rng,
+    ) -> Result<(), DraftError> {
+        let DraftPhase::Drafting { offering } = &mut self.phase else {
+            return Err(DraftError::NotDrafting);
+        };
+        if index >= offering.len() {
+            return Err(DraftError::InvalidPick);
+        }
+
+        self.picks.push(offering.swap_remove(index));
+        self.phase = if self.picks.len() == DRAFT_ROUNDS {
+            DraftPhase::Playing
+        } else {
+            DraftPhase::Drafting {
+                offering: generate_offering(pool, rng)?,
+            }
+        };
+        Ok(())
+    }
+
+    pub fn deck(&self) -> Result<Deck, ValidationError> {
+        let deck = Deck {
+            cards: self.picks.clone().into(),
+            owner_id: self.player_id,
+        };
+        deck.validate(Format::Draft)?;
+        Ok(deck)
+    }
+
+    pub fn record_result(&mut self, won: bool) -> Result<(), DraftError> {
+        if self.phase != DraftPhase::Playing {
+            return Err(DraftError::NotPlaying);
+        }
+
+        if won {
+            self.wins += 1;
+        } else {
+            self.losses += 1;
+        }
+        if self.wins >= MAX_WINS || self.losses >= MAX_LOSSES {
+            self.phase = DraftPhase::Complete;
+        }
+        Ok(())
+    }
+
+    // Draft players are paired by record rather than ladder rating, so a run
+    // at 5-1 meets other strong runs instead of fresh 0-0 decks.
+    pub fn matchmaking_rating(&self) -> Rating {
+        let record = self.wins as f64 - self.losses as f64;
+        Rating {
+            rating: crate::rating::DEFAULT_RATING + record * 100.0,
+            ..Rating::default()
+        }
+    }
+
+    pub fn rewards(&self) -> Vec<(Currency, u64)> {
+        let mut rewards = vec![(Currency::Gold, 50 + 25 * self.wins as u64)];
+        if self.wins >= 7 {
+            rewards.push((Currency::Gems, 10 * (self.wins as u64 - 6)));
+        }
+        rewards
+    }
+}
+
+fn generate_offering(pool: &[Card], rng: &mut impl Rng) -> Result<Vec<Card>, DraftError> {
+    if pool.is_empty() {
+        return Err(DraftError::EmptyPool);
+    }
+
+    let mut offering = Vec::with_capacity(PICKS_PER_OFFER);
+    for _ in 0..PICKS_PER_OFFER {
+        let rarity = roll_rarity(rng);
+        let candidates: Vec<&Card> = pool.iter().filter(|card| card.rarity == rarity).collect();
+        // Fall back to the whole pool when it has nothing of the rolled rarity
+        let card = match candidates.choose(rng) {
+            Some(card) => *card,
+            None => pool.choose(rng).ok_or(DraftError::EmptyPool)?,
+        };
+        offering.push(Card {
+            id: Uuid::new_v4(),
+            ..card.clone()
+        });
+    }
+    Ok(offering)
+}
+
+fn roll_rarity(rng: &mut impl Rng) -> Rarity {
+    let total: u32 = RARITY_WEIGHTS.iter().map(|(_, weight)| weight).sum();
+    let mut roll = rng.random_range(0..total);
+    for (rarity, weight) in RARITY_WEIGHTS.iter() {
+        if roll < *weight {
+            return rarity.clone();
+        }
+        roll -= weight;
+    }
+    Rarity::Common
+}
+
+// TESTS
+#[cfg(test)]
+mod draft_tests {
+    use super::*;
+    use crate::database::{DraftStorage, MemoryStorage};
+    use crate::models::CardType;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn pool() -> Vec<Card> {
+        [
+            Rarity::Common,
+            Rarity::Uncommon,
+            Rarity::Rare,
+            Rarity::Legendary,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, rarity)| Card {
+            id: Uuid::new_v4(),
+            name: format!("Card {}", i),
+            cost: i as u32,
+            power: 1,
+            rarity,
+            effects: vec![],
+            card_type: CardType::Climber,
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_full_draft_run() {
+        let pool = pool();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut run = DraftRun::start(Uuid::new_v4(), &pool, &mut rng).unwrap();
+
+        for _ in 0..DRAFT_ROUNDS {
+            assert_eq!(run.offering().unwrap().len(), PICKS_PER_OFFER);
+            run.pick(0, &pool, &mut rng).unwrap();
+        }
+
+        assert_eq!(run.phase, DraftPhase::Playing);
+        assert_eq!(run.deck().unwrap().cards.len(), DRAFT_ROUNDS);
+        assert!(matches!(
+            run.pick(0, &pool, &mut rng),
+            Err(DraftError::NotDrafting)
+        ));
+
+        run.record_result(true).unwrap();
+        for _ in 0..MAX_LOSSES {
+            run.record_result(false).unwrap();
+        }
+
+        assert_eq!(run.phase, DraftPhase::Complete);
+        assert_eq!(run.rewards(), vec![(Currency::Gold, 75)]);
+        assert!(run.record_result(true).is_err());
+    }
+
+    #[test]
+    fn test_run_round_trips_through_storage() {
+        let pool = pool();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut run = DraftRun::start(Uuid::new_v4(), &pool, &mut rng).unwrap();
+        run.pick(1, &pool, &mut rng).unwrap();
+
+        let json = serde_json::to_string(&run).unwrap();
+        assert_eq!(serde_json::from_str::<DraftRun>(&json).unwrap(), run);
+
+        let mut storage = MemoryStorage::default();
+        assert_eq!(storage.load_draft(run.player_id), None);
+        storage.save_draft(run.clone());
+        let mut loaded = storage.load_draft(run.player_id).unwrap();
+        assert_eq!(loaded, run);
+
+        // Picking up where it was left off
+        loaded.pick(0, &pool, &mut rng).unwrap();
+        assert_eq!(loaded.picks.len(), 2);
+    }
+}
@@ -1,12 +1,19 @@
 // src/effects/mod.rs
 use crate::errors::GameError;
-use crate::game_state::GameState;
-use crate::models::{CardType, Rarity};
+use crate::events::GameEvent;
+use crate::game_state::{GameState, ScryChoice};
+use crate::models::{Boost, Card, CardType, Player, Rarity, TileContent};
 use rand::prelude::IndexedRandom;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
 use std::collections::HashSet;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, PartialEq)]
+// Most effects hit one or two players, so target lists stay inline
+pub type TargetList = SmallVec<[Uuid; 4]>;
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum EffectType {
     Damage,
     Heal,
@@ -15,22 +22,67 @@ pub enum EffectType {
     Buff,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Effect {
     Damage(DamageEffect),
     Heal(HealEffect),
     Draw(DrawEffect),
     Boost(BoostEffect),
     BuffStats(BuffEffect),
+    ApplyStatus(StatusEffect),
+    Cleanse(CleanseEffect),
+    ModifyMaxHealth(MaxHealthEffect),
+    ModifyCost(CostEffect),
+    Transform(TransformEffect),
+    Mill(MillEffect),
+    Resurrect(ResurrectEffect),
+    ExtraTurn(TurnEffect),
+    SkipTurn(TurnEffect),
+    Triggered(TriggeredEffect),
+    Overload(OverloadEffect),
+    Scry(ScryEffect),
+    Field(FieldRule),
+    DestroyExpedition(DestroyExpeditionEffect),
+}
+
+// Conditions checked when a triggered effect resolves
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Trigger {
+    // At least this many cards played this turn, counting this one
+    ComboThreshold(u32),
+}
+
+impl Trigger {
+    pub fn is_met(&self, game_state: &GameState, controller: Uuid) -> bool {
+        let Some(player) = game_state.players.get(&controller) else {
+            return false;
+        };
+        match self {
+            Trigger::ComboThreshold(cards) => player.cards_played_this_turn >= *cards,
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+// An effect that only happens if its trigger is met; otherwise the card
+// resolves without it
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggeredEffect {
+    pub trigger: Trigger,
+    pub effect: Box<Effect>,
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EffectValue {
     pub base: u32,
     pub scaling: Option<ScalingFactor>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ScalingFactor {
     MountainLevel(f32), // Scales with mountain level
     CardsInHand(f32),   // Scales with number of cards in hand
@@ -38,7 +90,8 @@ pub enum ScalingFactor {
     ManaSpent(f32),     // Scales with mana spent this turn
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EffectTarget {
     Self_,                   // The card that played the effect
     Specific(Uuid),          // A specific target by UUID
@@ -56,9 +109,23 @@ pub enum EffectTarget {
         condition: TargetCondition,
         max_targets: Option<u32>,
     },
+    Allies,  // The source and its teammates
+    Enemies, // Everyone on another side
+}
+
+impl EffectTarget {
+    // Targets that catch whoever happens to be there, which is what the
+    // friendly-fire rule applies to
+    pub fn is_area(&self) -> bool {
+        matches!(
+            self,
+            EffectTarget::Adjacent | EffectTarget::Area { .. } | EffectTarget::AllPlayers(_)
+        )
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TargetCondition {
     PowerGreaterThan(u32),
     PowerLessThan(u32),
@@ -66,78 +133,426 @@ pub enum TargetCondition {
     IsRarity(Rarity),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DamageEffect {
     pub value: EffectValue,
     pub target: EffectTarget,
     pub penetrating: bool, // Ignores shields/armor
+    #[serde(default)]
+    pub ranged: bool, // Needs line of sight to each target
+    #[serde(default)]
+    pub priority: i32, // Higher resolves first among simultaneous effects
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HealEffect {
     pub value: EffectValue,
     pub target: EffectTarget,
     pub over_heal: bool, // Can heal beyond max health
+    #[serde(default)]
+    pub priority: i32, // Higher resolves first among simultaneous effects
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DrawEffect {
     pub cards: u32,
     pub target: EffectTarget,
     pub filter: Option<DrawFilter>,
+    #[serde(default)]
+    pub priority: i32, // Higher resolves first among simultaneous effects
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BoostEffect {
     pub value: EffectValue,
     pub target: EffectTarget,
     pub stat: BoostType,
     pub duration: Duration,
+    #[serde(default)]
+    pub on_source_removed: SourceRemoval,
+    #[serde(default)]
+    pub priority: i32, // Higher resolves first among simultaneous effects
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BuffEffect {
     pub power: i32, // Can be negative for debuffs
     pub health: i32,
     pub target: EffectTarget,
     pub duration: Duration,
+    #[serde(default)]
+    pub on_source_removed: SourceRemoval,
+    #[serde(default)]
+    pub priority: i32, // Higher resolves first among simultaneous effects
+}
+
+// What happens to a lasting effect when whatever applied it leaves play
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SourceRemoval {
+    #[default]
+    Persist,
+    Expire,
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub target: EffectTarget,
+    pub duration: Duration,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResurrectEffect {
+    pub target: EffectTarget, // Whose graveyard to take from
+    pub filter: Option<DrawFilter>,
+    pub destination: ResurrectDestination,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ResurrectDestination {
+    Hand,
+    Board, // Climbers only, onto an empty tile next to their owner
+}
+
+// Lets the card be played with up to `crystals` mana the player doesn't
+// have yet. Whatever is borrowed is locked at their next turn start.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverloadEffect {
+    pub crystals: u32,
+}
+
+// A rule an Expedition card puts on every player for as long as it holds
+// the expedition zone
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldRule {
+    Movement(i32),
+    Power(i32),
+    Cost(i32),
+}
+
+// Sends the expedition in play, if any, to its owner's graveyard
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DestroyExpeditionEffect {
+    #[serde(default)]
+    pub priority: i32,
+}
+
+// Queues an extra or skipped turn for each target
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnEffect {
+    pub target: EffectTarget,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+// Looks at the top `count` cards of each target's deck. The player who
+// played it then puts them back in any order, some on the bottom, with
+// GameAction::ResolveScry; nobody else learns which cards they were.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScryEffect {
+    pub target: EffectTarget,
+    pub count: u32,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MillEffect {
+    pub target: EffectTarget,
+    pub count: u32,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransformEffect {
+    pub card_id: Uuid,     // The card instance to transform, in hand or on the board
+    pub into_def_id: Uuid, // Registry definition it becomes
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaxHealthEffect {
+    pub delta: i32, // Negative lowers max health and clamps current health
+    pub target: EffectTarget,
+    pub duration: Duration,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+// Only counts while this holds for the card's owner
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CostCondition {
+    AboveLevel(u32),
+    FirstCardThisTurn,
+}
+
+// Changes what matching cards in hand cost to play. Card.cost itself is
+// never touched; the effective cost is worked out when a card is played.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostModifier {
+    pub delta: i32, // Negative makes cards cheaper
+    pub filter: Option<DrawFilter>,
+    pub condition: Option<CostCondition>,
+    pub duration: Duration,
+    pub source: Option<Uuid>,
+    pub on_source_removed: SourceRemoval,
+}
+
+impl CostModifier {
+    pub fn applies(&self, player: &Player, card: &Card) -> bool {
+        let condition = match self.condition {
+            None => true,
+            Some(CostCondition::AboveLevel(level)) => player.position.level > level,
+            Some(CostCondition::FirstCardThisTurn) => player.cards_played_this_turn == 0,
+        };
+        condition
+            && self
+                .filter
+                .as_ref()
+                .is_none_or(|filter| filter.matches(card))
+    }
+
+    pub(crate) fn expires_with(&self, source: Uuid) -> bool {
+        self.source == Some(source) && self.on_source_removed == SourceRemoval::Expire
+    }
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostEffect {
+    pub delta: i32,
+    #[serde(default)]
+    pub filter: Option<DrawFilter>,
+    #[serde(default)]
+    pub condition: Option<CostCondition>,
+    pub target: EffectTarget,
+    pub duration: Duration,
+    #[serde(default)]
+    pub on_source_removed: SourceRemoval,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CleanseEffect {
+    pub target: EffectTarget,
+    #[serde(default)]
+    pub immunity_turns: Option<u32>, // Blocks new debuffs for this many turns
+    #[serde(default)]
+    pub priority: i32,
+}
+
+// Lasting conditions on a player. Periodic ones tick at the start of their
+// owner's turn.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StatusKind {
+    Poison { damage: u32 },  // Periodic damage
+    Weakened { power: u32 }, // Lowers power
+    DebuffImmunity,
+    Guard,   // Nearby enemies have to target this player first
+    Stealth, // Can't be picked out by others until this player acts
+    Slowed { movement: u32 },
+    Hasted { movement: u32 },
+}
+
+impl StatusKind {
+    pub fn is_debuff(&self) -> bool {
+        matches!(
+            self,
+            StatusKind::Poison { .. } | StatusKind::Weakened { .. } | StatusKind::Slowed { .. }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Status {
+    pub kind: StatusKind,
+    pub duration: Duration,
+    pub source: Option<Uuid>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum BoostType {
     Power,
     Health,
     Both,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Duration {
     Temporary(u32),          // Lasts for X turns
     UntilMountainLevel(u32), // Lasts until reaching specific mountain level
     Permanent,               // Lasts for the rest of the game
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DrawFilter {
     Cost(CostFilter),
     Type(CardType),
     Rarity(Rarity),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CostFilter {
     Equal(u32),
     LessThan(u32),
     GreaterThan(u32),
 }
 
+impl DrawFilter {
+    pub fn matches(&self, card: &Card) -> bool {
+        match self {
+            DrawFilter::Cost(cost_filter) => match cost_filter {
+                CostFilter::Equal(cost) => card.cost == *cost,
+                CostFilter::LessThan(cost) => card.cost < *cost,
+                CostFilter::GreaterThan(cost) => card.cost > *cost,
+            },
+            DrawFilter::Type(card_type) => card.card_type == *card_type,
+            DrawFilter::Rarity(rarity) => card.rarity == *rarity,
+        }
+    }
+}
+
 impl Effect {
+    pub fn priority(&self) -> i32 {
+        match self {
+            Effect::Damage(effect) => effect.priority,
+            Effect::Heal(effect) => effect.priority,
+            Effect::Draw(effect) => effect.priority,
+            Effect::Boost(effect) => effect.priority,
+            Effect::BuffStats(effect) => effect.priority,
+            Effect::ApplyStatus(effect) => effect.priority,
+            Effect::Cleanse(effect) => effect.priority,
+            Effect::ModifyMaxHealth(effect) => effect.priority,
+            Effect::ModifyCost(effect) => effect.priority,
+            Effect::Transform(effect) => effect.priority,
+            Effect::Mill(effect) => effect.priority,
+            Effect::Scry(effect) => effect.priority,
+            Effect::Resurrect(effect) => effect.priority,
+            Effect::ExtraTurn(effect) | Effect::SkipTurn(effect) => effect.priority,
+            Effect::Triggered(triggered) => triggered.effect.priority(),
+            Effect::DestroyExpedition(effect) => effect.priority,
+            Effect::Overload(_) | Effect::Field(_) => 0,
+        }
+    }
+
+    // Mana a card with this effect may borrow from the next turn
+    pub fn overload(&self) -> u32 {
+        match self {
+            Effect::Overload(overload) => overload.crystals,
+            _ => 0,
+        }
+    }
+
+    // The global rule this effect sets while its Expedition is in play
+    pub fn field_rule(&self) -> Option<FieldRule> {
+        match self {
+            Effect::Field(rule) => Some(*rule),
+            _ => None,
+        }
+    }
+
+    // The target this effect resolves against
+    fn target(&self) -> Option<&EffectTarget> {
+        match self {
+            Effect::Damage(effect) => Some(&effect.target),
+            Effect::Heal(effect) => Some(&effect.target),
+            Effect::Draw(effect) => Some(&effect.target),
+            Effect::Boost(effect) => Some(&effect.target),
+            Effect::BuffStats(effect) => Some(&effect.target),
+            Effect::ApplyStatus(effect) => Some(&effect.target),
+            Effect::Cleanse(effect) => Some(&effect.target),
+            Effect::ModifyMaxHealth(effect) => Some(&effect.target),
+            Effect::ModifyCost(effect) => Some(&effect.target),
+            Effect::Mill(effect) => Some(&effect.target),
+            Effect::Scry(effect) => Some(&effect.target),
+            Effect::Resurrect(effect) => Some(&effect.target),
+            Effect::ExtraTurn(effect) | Effect::SkipTurn(effect) => Some(&effect.target),
+            // A triggered effect may never fire, so its target isn't
+            // held against the play
+            Effect::Triggered(_)
+            | Effect::Transform(_)
+            | Effect::Overload(_)
+            | Effect::Field(_)
+            | Effect::DestroyExpedition(_) => None,
+        }
+    }
+
+    // Checks a single chosen target against the state before the card is
+    // paid for, so a play refused for its target costs nothing
+    pub fn check_target(&self, game_state: &GameState, source: Uuid) -> Result<(), GameError> {
+        let Some(EffectTarget::Specific(target)) = self.target() else {
+            return Ok(());
+        };
+        if !is_targetable(game_state, source, *target) {
+            return Err(GameError::InvalidTarget);
+        }
+        check_guard(game_state, source, *target)?;
+        if let Effect::Damage(effect) = self {
+            if effect.ranged {
+                require_line_of_sight(&effect.target, game_state, source, &mut TargetList::new())?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn apply(&self, game_state: &mut GameState, source: Uuid) -> Result<(), GameError> {
         match self {
             Effect::Damage(damage_effect) => {
-                let targets = resolve_targets(&damage_effect.target, game_state, source)?;
+                let mut targets = resolve_targets(&damage_effect.target, game_state, source)?;
+                if !game_state.rules.friendly_fire && damage_effect.target.is_area() {
+                    targets.retain(|id| game_state.are_enemies(source, *id));
+                }
+                if damage_effect.ranged {
+                    require_line_of_sight(&damage_effect.target, game_state, source, &mut targets)?;
+                }
                 for target in targets {
-                    apply_damage(game_state, target, &damage_effect.value)?;
+                    let value = EffectValue {
+                        base: game_state.elevation_adjusted(
+                            source,
+                            target,
+                            damage_effect.value.base,
+                        ),
+                        ..damage_effect.value.clone()
+                    };
+                    apply_damage(
+                        game_state,
+                        source,
+                        target,
+                        &value,
+                        damage_effect.penetrating,
+                    )?;
                 }
             }
             Effect::Heal(heal_effect) => {
@@ -145,6 +560,7 @@ impl Effect {
                 for target in targets {
                     apply_heal(
                         game_state,
+                        source,
                         target,
                         &heal_effect.value,
                         heal_effect.over_heal,
@@ -160,13 +576,154 @@ impl Effect {
             Effect::Boost(boost_effect) => {
                 let targets = resolve_targets(&boost_effect.target, game_state, source)?;
                 for target in targets {
-                    apply_boost(game_state, target, boost_effect)?;
+                    apply_boost(game_state, source, target, boost_effect)?;
                 }
             }
             Effect::BuffStats(buff_effect) => {
                 let targets = resolve_targets(&buff_effect.target, game_state, source)?;
                 for target in targets {
-                    apply_buff(game_state, target, buff_effect)?;
+                    apply_buff(game_state, source, target, buff_effect)?;
+                }
+            }
+            Effect::ApplyStatus(status_effect) => {
+                let targets = resolve_targets(&status_effect.target, game_state, source)?;
+                for target in targets {
+                    apply_status(game_state, source, target, status_effect)?;
+                }
+            }
+            Effect::Cleanse(cleanse_effect) => {
+                let targets = resolve_targets(&cleanse_effect.target, game_state, source)?;
+                for target in targets {
+                    apply_cleanse(game_state, source, target, cleanse_effect)?;
+                }
+            }
+            Effect::Transform(transform_effect) => apply_transform(game_state, transform_effect)?,
+            Effect::Resurrect(resurrect_effect) => {
+                let targets = resolve_targets(&resurrect_effect.target, game_state, source)?;
+                for target in targets {
+                    apply_resurrect(game_state, target, resurrect_effect)?;
+                }
+            }
+            Effect::ExtraTurn(turn_effect) => {
+                let targets = resolve_targets(&turn_effect.target, game_state, source)?;
+                for target in targets {
+                    let player = game_state
+                        .players
+                        .get_mut(&target)
+                        .ok_or(GameError::PlayerNotFound)?;
+                    player.extra_turns = player.extra_turns.saturating_add(1);
+                    game_state
+                        .events
+                        .push(GameEvent::ExtraTurnGranted { player_id: target });
+                }
+            }
+            Effect::SkipTurn(turn_effect) => {
+                let targets = resolve_targets(&turn_effect.target, game_state, source)?;
+                for target in targets {
+                    let player = game_state
+                        .players
+                        .get_mut(&target)
+                        .ok_or(GameError::PlayerNotFound)?;
+                    player.skipped_turns = player.skipped_turns.saturating_add(1);
+                    game_state
+                        .events
+                        .push(GameEvent::SkipTurnQueued { player_id: target });
+                }
+            }
+            Effect::Triggered(triggered) => {
+                if triggered.trigger.is_met(game_state, source) {
+                    let cards_played = game_state
+                        .players
+                        .get(&source)
+                        .map_or(0, |player| player.cards_played_this_turn);
+                    game_state.events.push(GameEvent::ComboTriggered {
+                        player_id: source,
+                        cards_played,
+                    });
+                    triggered.effect.apply(game_state, source)?;
+                }
+            }
+            // Settled in play_card before the card resolves
+            Effect::Overload(_) | Effect::Field(_) => {}
+            Effect::DestroyExpedition(_) => {
+                game_state.destroy_expedition();
+            }
+            Effect::Scry(scry_effect) => {
+                let targets = resolve_targets(&scry_effect.target, game_state, source)?;
+                for target in targets {
+                    let player = game_state
+                        .players
+                        .get(&target)
+                        .ok_or(GameError::PlayerNotFound)?;
+                    let cards: Vec<Uuid> = player
+                        .deck
+                        .cards
+                        .iter()
+                        .take(scry_effect.count as usize)
+                        .map(|card| card.id)
+                        .collect();
+                    if cards.is_empty() {
+                        continue;
+                    }
+                    game_state.events.push(GameEvent::ScryStarted {
+                        player_id: source,
+                        deck_owner: target,
+                        count: cards.len() as u32,
+                    });
+                    game_state.pending_scry.push(ScryChoice {
+                        chooser: source,
+                        deck_owner: target,
+                        cards,
+                    });
+                }
+            }
+            Effect::Mill(mill_effect) => {
+                let targets = resolve_targets(&mill_effect.target, game_state, source)?;
+                for target in targets {
+                    let player = game_state
+                        .players
+                        .get_mut(&target)
+                        .ok_or(GameError::PlayerNotFound)?;
+                    let count = player.mill(mill_effect.count);
+                    game_state.events.push(GameEvent::CardsMilled {
+                        player_id: target,
+                        count,
+                    });
+                }
+            }
+            Effect::ModifyMaxHealth(max_health_effect) => {
+                let targets = resolve_targets(&max_health_effect.target, game_state, source)?;
+                for target in targets {
+                    let player = game_state
+                        .players
+                        .get_mut(&target)
+                        .ok_or(GameError::PlayerNotFound)?;
+                    player.modify_max_health(max_health_effect.delta, max_health_effect.duration);
+                    let max_health = player.max_health();
+                    game_state.events.push(GameEvent::MaxHealthChanged {
+                        player_id: target,
+                        max_health,
+                    });
+                }
+            }
+            Effect::ModifyCost(cost_effect) => {
+                let targets = resolve_targets(&cost_effect.target, game_state, source)?;
+                for target in targets {
+                    let player = game_state
+                        .players
+                        .get_mut(&target)
+                        .ok_or(GameError::PlayerNotFound)?;
+                    player.cost_modifiers.push(CostModifier {
+                        delta: cost_effect.delta,
+                        filter: cost_effect.filter.clone(),
+                        condition: cost_effect.condition,
+                        duration: cost_effect.duration,
+                        source: Some(source),
+                        on_source_removed: cost_effect.on_source_removed,
+                    });
+                    game_state
+                        .events
+                        .push(GameEvent::CostsChanged { player_id: target });
                 }
             }
         }
@@ -174,24 +731,154 @@ impl Effect {
     }
 }
 
-fn resolve_targets(
+// An effect waiting to resolve alongside others that fired at the same time
+#[derive(Debug, Clone)]
+pub struct PendingEffect {
+    pub effect: Effect,
+    pub controller: Uuid,
+    pub sequence: u32, // Play order; lower was played first
+}
+
+// Simultaneous effects resolve by:
+//   1. priority, highest first
+//   2. controller, starting with the active player and then in turn order
+//   3. play order
+// Controllers who are not seated go after everyone else.
+pub fn resolve_simultaneous(
+    game_state: &mut GameState,
+    mut pending: Vec<PendingEffect>,
+) -> Result<(), GameError> {
+    let active_seat = game_state
+        .turn_order
+        .iter()
+        .position(|id| *id == game_state.active_player)
+        .unwrap_or(0);
+    let seats = game_state.turn_order.len();
+    let seat_distance = |controller: &Uuid| {
+        game_state
+            .turn_order
+            .iter()
+            .position(|id| id == controller)
+            .map_or(usize::MAX, |seat| (seat + seats - active_seat) % seats)
+    };
+
+    pending.sort_by_key(|entry| {
+        (
+            std::cmp::Reverse(entry.effect.priority()),
+            seat_distance(&entry.controller),
+            entry.sequence,
+        )
+    });
+
+    for entry in pending {
+        entry.effect.apply(game_state, entry.controller)?;
+    }
+    Ok(())
+}
+
+// Enemies with Guard within this many tiles of the source must be targeted
+// before any other enemy
+pub const GUARD_RANGE: u32 = 2;
+
+fn check_guard(game_state: &GameState, source: Uuid, target: Uuid) -> Result<(), GameError> {
+    let (Some(source_player), Some(target_player)) = (
+        game_state.players.get(&source),
+        game_state.players.get(&target),
+    ) else {
+        return Ok(());
+    };
+    let is_guard = |player: &Player| player.has_status(|kind| *kind == StatusKind::Guard);
+    if !game_state.are_enemies(source, target) || is_guard(target_player) {
+        return Ok(());
+    }
+
+    // A stealthed guard can't be singled out, so it protects nobody
+    let guarded = game_state.seated_players().any(|player| {
+        game_state.are_enemies(source, player.id)
+            && is_guard(player)
+            && is_targetable(game_state, source, player.id)
+            && game_state
+                .mountain
+                .calculate_distance(source_player.position, player.position)
+                <= GUARD_RANGE
+    });
+    if guarded {
+        return Err(GameError::MustTargetGuard);
+    }
+    Ok(())
+}
+
+// Stealthed players can still target themselves and be caught by area or
+// adjacency effects, but can't be singled out by anyone else
+fn is_targetable(game_state: &GameState, source: Uuid, target: Uuid) -> bool {
+    target == source
+        || game_state
+            .players
+            .get(&target)
+            .is_none_or(|player| !player.has_status(|kind| *kind == StatusKind::Stealth))
+}
+
+// A single chosen target out of sight is an illegal play; wider targeting
+// just skips whoever can't be seen
+fn require_line_of_sight(
     target: &EffectTarget,
     game_state: &GameState,
     source: Uuid,
-) -> Result<Vec<Uuid>, GameError> {
+    targets: &mut TargetList,
+) -> Result<(), GameError> {
+    let Some(from) = game_state
+        .players
+        .get(&source)
+        .map(|player| player.position)
+    else {
+        return Ok(());
+    };
+    let visible = |id: &Uuid| {
+        *id == source
+            || game_state
+                .players
+                .get(id)
+                .is_none_or(|player| game_state.mountain.has_line_of_sight(from, player.position))
+    };
+
+    if let EffectTarget::Specific(id) = target {
+        if !visible(id) {
+            return Err(GameError::NoLineOfSight);
+        }
+    }
+    targets.retain(|id| visible(id));
+    Ok(())
+}
+
+fn resolve_targets(
+    target: &EffectTarget,
+    game_state: &mut GameState,
+    source: Uuid,
+) -> Result<TargetList, GameError> {
     match target {
-        EffectTarget::Self_ => Ok(vec![source]),
-        EffectTarget::Specific(uuid) => Ok(vec![*uuid]),
+        EffectTarget::Self_ => Ok(smallvec![source]),
+        EffectTarget::Specific(uuid) => {
+            if !is_targetable(game_state, source, *uuid) {
+                return Err(GameError::InvalidTarget);
+            }
+            check_guard(game_state, source, *uuid)?;
+            Ok(smallvec![*uuid])
+        }
         EffectTarget::Multiple(targets) => Ok(targets.iter().copied().collect()),
-        EffectTarget::AllPlayers(players) => Ok(players.clone()),
+        EffectTarget::AllPlayers(players) => Ok(players.iter().copied().collect()),
         EffectTarget::Random(n) => {
-            let available_targets: Vec<_> = game_state.players.keys().copied().collect();
+            // Seat order rather than map order, so a seeded game picks the
+            // same targets every time
+            let available_targets: TargetList = game_state
+                .seated_players()
+                .map(|player| player.id)
+                .filter(|id| is_targetable(game_state, source, *id))
+                .collect();
             if available_targets.len() < *n as usize {
                 return Err(GameError::InvalidTarget);
             }
-            let mut rng = rand::rng();
             Ok(available_targets
-                .choose_multiple(&mut rng, *n as usize)
+                .choose_multiple(game_state.rng.as_mut(), *n as usize)
                 .copied()
                 .collect())
         }
@@ -208,10 +895,9 @@ fn resolve_targets(
                     .get_neighbors(source_pos.x, source_pos.y, source_pos.z);
 
             Ok(game_state
-                .players
-                .iter()
-                .filter(|(_, player)| adjacent_positions.contains(&player.position))
-                .map(|(id, _)| *id)
+                .seated_players()
+                .filter(|player| adjacent_positions.contains(&player.position))
+                .map(|player| player.id)
                 .collect())
         }
         EffectTarget::Area { center, radius } => {
@@ -222,48 +908,57 @@ fn resolve_targets(
                 .position;
 
             Ok(game_state
-                .players
-                .iter()
-                .filter(|(_, player)| {
+                .seated_players()
+                .filter(|player| {
                     game_state
                         .mountain
                         .calculate_distance(center_pos, player.position)
                         <= *radius
                 })
-                .map(|(id, _)| *id)
+                .map(|player| player.id)
                 .collect())
         }
+        EffectTarget::Allies => Ok(game_state
+            .seated_players()
+            .filter(|player| game_state.are_allies(source, player.id))
+            .map(|player| player.id)
+            .collect()),
+        EffectTarget::Enemies => Ok(game_state
+            .seated_players()
+            .filter(|player| {
+                game_state.are_enemies(source, player.id)
+                    && is_targetable(game_state, source, player.id)
+            })
+            .map(|player| player.id)
+            .collect()),
         EffectTarget::Conditional {
             condition,
             max_targets,
         } => {
-            let mut valid_targets: Vec<Uuid> = match condition {
+            let mut valid_targets: TargetList = match condition {
                 TargetCondition::PowerGreaterThan(threshold) => game_state
-                    .players
-                    .iter()
-                    .filter(|(_, player)| player.get_power() > *threshold)
-                    .map(|(id, _)| *id)
+                    .seated_players()
+                    .filter(|player| player.get_power() > *threshold)
+                    .map(|player| player.id)
                     .collect(),
                 TargetCondition::PowerLessThan(threshold) => game_state
-                    .players
-                    .iter()
-                    .filter(|(_, player)| player.get_power() < *threshold)
-                    .map(|(id, _)| *id)
+                    .seated_players()
+                    .filter(|player| player.get_power() < *threshold)
+                    .map(|player| player.id)
                     .collect(),
                 TargetCondition::HasEffect(effect_type) => game_state
-                    .players
-                    .iter()
-                    .filter(|(_, player)| player.has_effect(effect_type))
-                    .map(|(id, _)| *id)
+                    .seated_players()
+                    .filter(|player| player.has_effect(effect_type))
+                    .map(|player| player.id)
                     .collect(),
                 TargetCondition::IsRarity(rarity) => game_state
-                    .players
-                    .iter()
-                    .filter(|(_, player)| player.hand.iter().any(|card| card.rarity >= *rarity))
-                    .map(|(id, _)| *id)
+                    .seated_players()
+                    .filter(|player| player.hand.iter().any(|card| card.rarity >= *rarity))
+                    .map(|player| player.id)
                     .collect(),
             };
 
+            valid_targets.retain(|id| is_targetable(game_state, source, *id));
             if let Some(max) = max_targets {
                 valid_targets.truncate(*max as usize);
             }
@@ -275,6 +970,7 @@ fn resolve_targets(
 
 fn apply_heal(
     game_state: &mut GameState,
+    source: Uuid,
     target: Uuid,
     value: &EffectValue,
     over_heal: bool,
@@ -290,30 +986,76 @@ fn apply_heal(
         return Ok(());
     }
 
-    if over_heal {
-        target_player.health += heal;
+    // Healing past max health turns the surplus into a shield that decays
+    // each turn, rather than raising health above the cap
+    let before = target_player.health;
+    let max_health = target_player.max_health();
+    let healed = target_player.health.saturating_add(heal);
+    target_player.health = healed.min(max_health);
+    let surplus = if over_heal {
+        healed.saturating_sub(max_health)
     } else {
-        target_player.health = (target_player.health + heal).min(target_player.max_health());
+        0
+    };
+    target_player.shield = target_player.shield.saturating_add(surplus);
+
+    let amount = target_player.health.saturating_sub(before);
+    game_state.events.push(GameEvent::Healed {
+        source,
+        target,
+        amount,
+    });
+    game_state.annotate(Some(target), Some(heal));
+    if surplus > 0 {
+        game_state.events.push(GameEvent::ShieldGained {
+            player_id: target,
+            amount: surplus,
+        });
     }
     Ok(())
 }
 
+// Drawing from an empty deck deals fatigue damage instead, one more each
+// time, so a milled-out player eventually loses
 fn apply_draw(
     game_state: &mut GameState,
     target: Uuid,
     cards: u32,
     filter: &Option<DrawFilter>,
 ) -> Result<(), GameError> {
-    let player = game_state
-        .players
-        .get_mut(&target)
-        .ok_or(GameError::PlayerNotFound)?;
-
     for _ in 0..cards {
-        if let Some(filter) = filter {
-            player.draw_filtered(filter)?;
-        } else {
-            player.draw_card()?;
+        let player = game_state
+            .players
+            .get_mut(&target)
+            .ok_or(GameError::PlayerNotFound)?;
+
+        match filter {
+            Some(filter) => {
+                player.draw_filtered(filter)?;
+                game_state
+                    .events
+                    .push(GameEvent::CardDrawn { player_id: target });
+            }
+            // Nothing left to lose once fatigue has finished them off
+            None if player.deck.cards.is_empty() && player.health == 0 => break,
+            None if player.deck.cards.is_empty() => {
+                player.fatigue += 1;
+                let value = EffectValue {
+                    base: player.fatigue,
+                    scaling: None,
+                };
+                game_state.events.push(GameEvent::Fatigued {
+                    player_id: target,
+                    damage: value.base,
+                });
+                apply_damage(game_state, target, target, &value, true)?;
+            }
+            None => {
+                player.draw_card()?;
+                game_state
+                    .events
+                    .push(GameEvent::CardDrawn { player_id: target });
+            }
         }
     }
 
@@ -322,6 +1064,7 @@ fn apply_draw(
 
 fn apply_boost(
     game_state: &mut GameState,
+    source: Uuid,
     target: Uuid,
     boost_effect: &BoostEffect,
 ) -> Result<(), GameError> {
@@ -332,12 +1075,14 @@ fn apply_boost(
         .get_mut(&target)
         .ok_or(GameError::PlayerNotFound)?;
 
+    let boost = Boost::new(boost_amount, boost_effect.duration)
+        .from_source(source, boost_effect.on_source_removed);
     match boost_effect.stat {
-        BoostType::Power => player.add_power_boost(boost_amount, boost_effect.duration.clone()),
-        BoostType::Health => player.add_health_boost(boost_amount, boost_effect.duration.clone()),
+        BoostType::Power => player.push_power_boost(boost),
+        BoostType::Health => player.push_health_boost(boost),
         BoostType::Both => {
-            player.add_power_boost(boost_amount, boost_effect.duration.clone());
-            player.add_health_boost(boost_amount, boost_effect.duration.clone());
+            player.push_power_boost(boost);
+            player.push_health_boost(boost);
         }
     }
 
@@ -346,6 +1091,7 @@ fn apply_boost(
 
 fn apply_buff(
     game_state: &mut GameState,
+    source: Uuid,
     target: Uuid,
     buff_effect: &BuffEffect,
 ) -> Result<(), GameError> {
@@ -354,12 +1100,195 @@ fn apply_buff(
         .get_mut(&target)
         .ok_or(GameError::PlayerNotFound)?;
 
-    player.add_buff(
-        buff_effect.power,
-        buff_effect.health,
-        buff_effect.duration.clone(),
-    );
+    let boost = |amount: i32| {
+        Boost::new(amount as u32, buff_effect.duration)
+            .from_source(source, buff_effect.on_source_removed)
+    };
+    if buff_effect.power > 0 {
+        player.push_power_boost(boost(buff_effect.power));
+    }
+    if buff_effect.health > 0 {
+        player.push_health_boost(boost(buff_effect.health));
+    }
+
+    Ok(())
+}
+
+fn apply_status(
+    game_state: &mut GameState,
+    source: Uuid,
+    target: Uuid,
+    status_effect: &StatusEffect,
+) -> Result<(), GameError> {
+    let player = game_state
+        .players
+        .get_mut(&target)
+        .ok_or(GameError::PlayerNotFound)?;
+
+    let kind = status_effect.kind;
+    if kind.is_debuff() && player.has_status(|status| *status == StatusKind::DebuffImmunity) {
+        game_state.events.push(GameEvent::StatusResisted {
+            player_id: target,
+            kind,
+        });
+        return Ok(());
+    }
+
+    player.add_status(Status {
+        kind,
+        duration: status_effect.duration,
+        source: Some(source),
+    });
+    game_state.events.push(GameEvent::StatusApplied {
+        player_id: target,
+        kind,
+    });
+    Ok(())
+}
+
+fn apply_cleanse(
+    game_state: &mut GameState,
+    source: Uuid,
+    target: Uuid,
+    cleanse_effect: &CleanseEffect,
+) -> Result<(), GameError> {
+    let player = game_state
+        .players
+        .get_mut(&target)
+        .ok_or(GameError::PlayerNotFound)?;
 
+    let removed = player.remove_statuses(|kind| kind.is_debuff());
+    if let Some(turns) = cleanse_effect.immunity_turns {
+        player.add_status(Status {
+            kind: StatusKind::DebuffImmunity,
+            duration: Duration::Temporary(turns),
+            source: Some(source),
+        });
+    }
+    game_state.events.push(GameEvent::Cleansed {
+        player_id: target,
+        removed,
+    });
+    Ok(())
+}
+
+// The card keeps its instance id and stays where it is, in the same hand
+// slot or on the same tile; only its definition changes
+fn apply_transform(
+    game_state: &mut GameState,
+    transform_effect: &TransformEffect,
+) -> Result<(), GameError> {
+    let card_id = transform_effect.card_id;
+    let into_def_id = transform_effect.into_def_id;
+    let replacement = game_state
+        .cards
+        .instantiate(&into_def_id, card_id)
+        .ok_or(GameError::UnknownCardDefinition)?;
+    let name = replacement.name.clone();
+
+    let holder = game_state
+        .players
+        .values()
+        .find(|player| player.hand.iter().any(|card| card.id == card_id))
+        .map(|player| player.id);
+    if let Some(player_id) = holder {
+        let player = game_state
+            .players
+            .get_mut(&player_id)
+            .ok_or(GameError::PlayerNotFound)?;
+        if let Some(card) = player.hand.iter_mut().find(|card| card.id == card_id) {
+            *card = replacement;
+        }
+        player.invalidate_stats();
+    } else {
+        let index = game_state
+            .mountain
+            .tiles
+            .iter()
+            .position(|tile| match &tile.content {
+                TileContent::Card(card) | TileContent::Trap(card) => card.id == card_id,
+                _ => false,
+            })
+            .ok_or(GameError::InvalidTarget)?;
+        if let TileContent::Card(card) | TileContent::Trap(card) =
+            &mut game_state.mountain.tiles[index].content
+        {
+            *card = replacement;
+        }
+    }
+
+    game_state.events.push(GameEvent::CardTransformed {
+        card_id,
+        into_def_id,
+        name,
+    });
+    Ok(())
+}
+
+// Brings back the most recently buried card that passes the filter
+fn apply_resurrect(
+    game_state: &mut GameState,
+    target: Uuid,
+    resurrect_effect: &ResurrectEffect,
+) -> Result<(), GameError> {
+    let player = game_state
+        .players
+        .get(&target)
+        .ok_or(GameError::PlayerNotFound)?;
+    let board = resurrect_effect.destination == ResurrectDestination::Board;
+    let index = player
+        .graveyard
+        .iter()
+        .rposition(|card| {
+            resurrect_effect
+                .filter
+                .as_ref()
+                .is_none_or(|filter| filter.matches(card))
+                && (!board || card.card_type == CardType::Climber)
+        })
+        .ok_or(GameError::NoValidCard)?;
+
+    let position = if board {
+        let owner = player.position;
+        let tile = game_state
+            .mountain
+            .tile_index(owner.x, owner.y, owner.z)
+            .and_then(|index| {
+                game_state
+                    .mountain
+                    .neighbor_indices(index)
+                    .iter()
+                    .copied()
+                    .find(|neighbor| {
+                        // Players who haven't moved yet aren't marked on
+                        // their tile, so ask where everyone stands too
+                        let tile = &game_state.mountain.tiles[*neighbor];
+                        tile.content == TileContent::Empty
+                            && game_state.player_at(tile.position(), Uuid::nil()).is_none()
+                    })
+            })
+            .ok_or(GameError::InvalidTarget)?;
+        Some((tile, game_state.mountain.tiles[tile].position()))
+    } else {
+        None
+    };
+
+    let player = game_state
+        .players
+        .get_mut(&target)
+        .ok_or(GameError::PlayerNotFound)?;
+    let card = player.graveyard.remove(index);
+    let card_id = card.id;
+    match position {
+        Some((tile, _)) => game_state.mountain.tiles[tile].content = TileContent::Card(card),
+        None => player.add_to_hand(card),
+    }
+
+    game_state.events.push(GameEvent::CardResurrected {
+        player_id: target,
+        card_id,
+        position: position.map(|(_, position)| position),
+    });
     Ok(())
 }
 
@@ -391,27 +1320,59 @@ fn calculate_value(value: &EffectValue, game_state: &GameState, target: Uuid) ->
                 }
             }
         };
-        base + scaling_factor as u32
+        base.saturating_add(scaling_factor as u32)
     } else {
         base
     }
 }
 
-fn apply_damage(
+pub(crate) fn apply_damage(
     game_state: &mut GameState,
+    source: Uuid,
     target: Uuid,
     value: &EffectValue,
+    penetrating: bool,
 ) -> Result<(), GameError> {
     let target_player = game_state
         .players
         .get_mut(&target)
         .ok_or(GameError::PlayerNotFound)?;
-    let damage = value.base;
+    let mut damage = value.base;
     if damage == 0 {
         return Ok(());
     }
+
+    let absorbed = if penetrating {
+        0
+    } else {
+        damage.min(target_player.shield)
+    };
+    target_player.shield -= absorbed;
+    damage -= absorbed;
+
     // Use saturating_sub to prevent underflow
+    let before = target_player.health;
     target_player.health = target_player.health.saturating_sub(damage);
+    let defeated = before > 0 && target_player.health == 0;
+
+    if absorbed > 0 {
+        game_state.events.push(GameEvent::ShieldAbsorbed {
+            player_id: target,
+            amount: absorbed,
+        });
+    }
+    game_state.events.push(GameEvent::DamageDealt {
+        source,
+        target,
+        amount: before - target_player.health,
+    });
+    game_state.annotate(Some(target), Some(value.base));
+    if defeated {
+        game_state
+            .events
+            .push(GameEvent::PlayerDefeated { player_id: target });
+        game_state.remove_source(target);
+    }
     Ok(())
 }
 
@@ -419,122 +1380,26 @@ fn apply_damage(
 #[cfg(test)]
 mod effect_tests {
     use super::*;
-    use crate::models::{Card, Deck, Player, Position, Rarity};
+    use crate::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
 
     #[test]
     fn test_apply_damage() {
-        let player1 = Player {
-            id: Uuid::new_v4(),
-            name: "Player 1".to_string(),
-            health: 30,
-            hand: vec![],
-            deck: Deck {
-                cards: vec![],
-                owner_id: Uuid::new_v4(),
-            },
-            mana: 0,
-            position: Position {
-                x: 0,
-                y: 0,
-                z: 0,
-                level: 0,
-            },
-            max_health: 30,
-            active_effects: vec![],
-            cards_played_this_turn: 0,
-            health_boosts: vec![],
-            power_boosts: vec![],
-            mana_spent_this_turn: 0,
-        };
-        let player2 = Player {
-            id: Uuid::new_v4(),
-            name: "Player 2".to_string(),
-            health: 30,
-            hand: vec![],
-            deck: Deck {
-                cards: vec![],
-                owner_id: Uuid::new_v4(),
-            },
-            mana: 0,
-            position: Position {
-                x: 0,
-                y: 0,
-                z: 0,
-                level: 0,
-            },
-            max_health: 30,
-            active_effects: vec![],
-            cards_played_this_turn: 0,
-            health_boosts: vec![],
-            power_boosts: vec![],
-            mana_spent_this_turn: 0,
-        };
-        let mut game_state = GameState::new(player1, player2);
-        let card = Card {
-            id: Uuid::new_v4(),
-            name: "Test Card".to_string(),
-            cost: 1,
-            power: 1,
-            rarity: Rarity::Common,
-            effects: vec![],
-            card_type: CardType::Spell,
-        };
+        let mut game_state = GameStateBuilder::new().build();
+        let card = CardBuilder::new("Test Card").build();
 
         let player_id = Uuid::new_v4();
         let target_id = Uuid::new_v4();
 
         game_state.players.insert(
             player_id,
-            Player {
-                id: player_id,
-                name: "Test Player".to_string(),
-                health: 30,
-                hand: vec![card.clone()],
-                deck: Deck {
-                    cards: vec![],
-                    owner_id: player_id,
-                },
-                mana: 0,
-                position: Position {
-                    x: 0,
-                    y: 0,
-                    z: 0,
-                    level: 0,
-                },
-                max_health: 30,
-                active_effects: vec![],
-                cards_played_this_turn: 0,
-                health_boosts: vec![],
-                power_boosts: vec![],
-                mana_spent_this_turn: 0,
-            },
+            PlayerBuilder::new("Test Player")
+                .id(player_id)
+                .hand(vec![card])
+                .build(),
         );
-
         game_state.players.insert(
             target_id,
-            Player {
-                id: target_id,
-                name: "Target Player".to_string(),
-                health: 30,
-                hand: vec![],
-                deck: Deck {
-                    cards: vec![],
-                    owner_id: target_id,
-                },
-                mana: 0,
-                position: Position {
-                    x: 0,
-                    y: 0,
-                    z: 0,
-                    level: 0,
-                },
-                max_health: 30,
-                active_effects: vec![],
-                cards_played_this_turn: 0,
-                health_boosts: vec![],
-                power_boosts: vec![],
-                mana_spent_this_turn: 0,
-            },
+            PlayerBuilder::new("Target Player").id(target_id).build(),
         );
 
         let damage_effect = DamageEffect {
@@ -544,6 +1409,8 @@ mod effect_tests {
             },
             target: EffectTarget::Specific(target_id),
             penetrating: false,
+            ranged: false,
+            priority: 0,
         };
 
         let effect = Effect::Damage(damage_effect);
@@ -552,4 +1419,562 @@ mod effect_tests {
 
         assert_eq!(game_state.players[&target_id].health, 25);
     }
+
+    #[test]
+    fn test_resolve_simultaneous_order() {
+        let mut game_state = GameStateBuilder::new().build();
+        let active = game_state.turn_order[0];
+        let other = game_state.turn_order[1];
+        let hit = |target, priority, base| {
+            Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base,
+                    scaling: None,
+                },
+                target: EffectTarget::Specific(target),
+                penetrating: false,
+                ranged: false,
+                priority,
+            })
+        };
+        let pending = vec![
+            PendingEffect {
+                effect: hit(active, 0, 1),
+                controller: other,
+                sequence: 0,
+            },
+            PendingEffect {
+                effect: hit(other, 0, 3),
+                controller: active,
+                sequence: 2,
+            },
+            PendingEffect {
+                effect: hit(other, 0, 2),
+                controller: active,
+                sequence: 1,
+            },
+            PendingEffect {
+                effect: hit(active, 5, 4),
+                controller: other,
+                sequence: 3,
+            },
+        ];
+
+        resolve_simultaneous(&mut game_state, pending).unwrap();
+
+        let hits: Vec<(Uuid, u32)> = game_state
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::DamageDealt { source, amount, .. } => Some((*source, *amount)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(hits, vec![(other, 4), (active, 2), (active, 3), (other, 1)]);
+    }
+
+    #[test]
+    fn test_overheal_becomes_decaying_shield() {
+        let mut game_state = GameStateBuilder::new().build();
+        let player_id = game_state.turn_order[0];
+        game_state.players.get_mut(&player_id).unwrap().health = 28;
+
+        Effect::Heal(HealEffect {
+            value: EffectValue {
+                base: 9,
+                scaling: None,
+            },
+            target: EffectTarget::Self_,
+            over_heal: true,
+            priority: 0,
+        })
+        .apply(&mut game_state, player_id)
+        .unwrap();
+
+        let player = &game_state.players[&player_id];
+        assert_eq!((player.health, player.shield), (30, 7));
+
+        let hit = |penetrating| {
+            Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base: 4,
+                    scaling: None,
+                },
+                target: EffectTarget::Self_,
+                penetrating,
+                ranged: false,
+                priority: 0,
+            })
+        };
+        hit(false).apply(&mut game_state, player_id).unwrap();
+        hit(true).apply(&mut game_state, player_id).unwrap();
+        let player = &game_state.players[&player_id];
+        assert_eq!((player.health, player.shield), (26, 3));
+
+        let player = game_state.players.get_mut(&player_id).unwrap();
+        player.update_turn();
+        assert_eq!(player.shield, 1);
+        assert!(game_state.validate_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_cleanse_removes_debuffs_and_grants_immunity() {
+        let mut game_state = GameStateBuilder::new().build();
+        let (caster, victim) = (game_state.turn_order[0], game_state.turn_order[1]);
+        let afflict = |kind| {
+            Effect::ApplyStatus(StatusEffect {
+                kind,
+                target: EffectTarget::Specific(victim),
+                duration: Duration::Temporary(3),
+                priority: 0,
+            })
+        };
+        game_state
+            .players
+            .get_mut(&victim)
+            .unwrap()
+            .add_power_boost(3, Duration::Permanent);
+        afflict(StatusKind::Poison { damage: 2 })
+            .apply(&mut game_state, caster)
+            .unwrap();
+        afflict(StatusKind::Weakened { power: 2 })
+            .apply(&mut game_state, caster)
+            .unwrap();
+        assert_eq!(game_state.players[&victim].get_power(), 1);
+
+        game_state.end_turn(caster).unwrap();
+        assert_eq!(game_state.players[&victim].health, 28);
+
+        Effect::Cleanse(CleanseEffect {
+            target: EffectTarget::Self_,
+            immunity_turns: Some(1),
+            priority: 0,
+        })
+        .apply(&mut game_state, victim)
+        .unwrap();
+        assert_eq!(game_state.players[&victim].get_power(), 3);
+        assert!(game_state.events.contains(&GameEvent::Cleansed {
+            player_id: victim,
+            removed: 2
+        }));
+
+        afflict(StatusKind::Poison { damage: 2 })
+            .apply(&mut game_state, caster)
+            .unwrap();
+        assert!(matches!(
+            game_state.events.last(),
+            Some(GameEvent::StatusResisted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_transform_in_hand_and_on_board() {
+        use crate::cards::CardRegistry;
+        use crate::models::TileContent;
+        use std::sync::Arc;
+
+        let golem = CardBuilder::new("Ice Golem").power(6).build();
+        let pebble = CardBuilder::new("Pebble").power(1).build();
+        let trap = CardBuilder::new("Snare").build();
+        let mut game_state = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Holder")
+                    .hand(vec![pebble.clone()])
+                    .build(),
+            )
+            .build()
+            .with_card_registry(Arc::new(CardRegistry::new([golem.clone()])));
+        let holder = game_state.turn_order[0];
+        game_state.mountain.get_tile_mut(1, 1, 0).unwrap().content =
+            TileContent::Trap(trap.clone());
+
+        let transform = |card_id| {
+            Effect::Transform(TransformEffect {
+                card_id,
+                into_def_id: golem.id,
+                priority: 0,
+            })
+        };
+        transform(pebble.id).apply(&mut game_state, holder).unwrap();
+        transform(trap.id).apply(&mut game_state, holder).unwrap();
+
+        let hand = &game_state.players[&holder].hand;
+        assert_eq!(
+            (hand[0].id, hand[0].name.as_str()),
+            (pebble.id, "Ice Golem")
+        );
+        assert_eq!(game_state.players[&holder].get_power(), 6);
+        assert!(matches!(
+            &game_state.mountain.get_tile(1, 1, 0).unwrap().content,
+            TileContent::Trap(card) if card.id == trap.id && card.name == "Ice Golem"
+        ));
+        assert!(matches!(
+            transform(Uuid::new_v4()).apply(&mut game_state, holder),
+            Err(GameError::InvalidTarget)
+        ));
+    }
+
+    #[test]
+    fn test_mill_then_fatigue() {
+        let deck = (0..3).map(|_| CardBuilder::new("Rope").build()).collect();
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Climber").deck(deck).build())
+            .build();
+        let player_id = game_state.turn_order[0];
+
+        Effect::Mill(MillEffect {
+            target: EffectTarget::Self_,
+            count: 2,
+            priority: 0,
+        })
+        .apply(&mut game_state, player_id)
+        .unwrap();
+        Effect::Draw(DrawEffect {
+            cards: 3,
+            target: EffectTarget::Self_,
+            filter: None,
+            priority: 0,
+        })
+        .apply(&mut game_state, player_id)
+        .unwrap();
+
+        let player = &game_state.players[&player_id];
+        assert_eq!(
+            (
+                player.graveyard.len(),
+                player.hand.len(),
+                player.deck.cards.len()
+            ),
+            (2, 1, 0)
+        );
+        assert_eq!((player.fatigue, player.health), (2, 27));
+        assert!(game_state.validate_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_resurrect_to_hand_and_board() {
+        use crate::models::TileContent;
+
+        let spell = CardBuilder::new("Ice Shard").build();
+        let climber = CardBuilder::new("Sherpa")
+            .card_type(CardType::Climber)
+            .build();
+        let mut game_state = GameStateBuilder::new().build();
+        let player_id = game_state.turn_order[0];
+        let player = game_state.players.get_mut(&player_id).unwrap();
+        player.graveyard = vec![climber.clone(), spell.clone()];
+
+        let resurrect = |filter, destination| {
+            Effect::Resurrect(ResurrectEffect {
+                target: EffectTarget::Self_,
+                filter,
+                destination,
+                priority: 0,
+            })
+        };
+        resurrect(
+            Some(DrawFilter::Type(CardType::Spell)),
+            ResurrectDestination::Hand,
+        )
+        .apply(&mut game_state, player_id)
+        .unwrap();
+        assert_eq!(game_state.players[&player_id].hand[0].id, spell.id);
+
+        // The first free-looking tile next to the owner has the opponent on it
+        let owner = game_state.players[&player_id].position;
+        let mountain = &game_state.mountain;
+        let first =
+            mountain.neighbor_indices(mountain.tile_index(owner.x, owner.y, owner.z).unwrap())[0];
+        let occupied = mountain.tiles[first].position();
+        let opponent = game_state.turn_order[1];
+        game_state.players.get_mut(&opponent).unwrap().position = occupied;
+
+        resurrect(None, ResurrectDestination::Board)
+            .apply(&mut game_state, player_id)
+            .unwrap();
+        let Some(GameEvent::CardResurrected {
+            position: Some(position),
+            ..
+        }) = game_state.events.last()
+        else {
+            panic!("climber was not summoned");
+        };
+        assert_ne!(*position, occupied);
+        assert!(matches!(
+            &game_state.mountain.get_tile(position.x, position.y, position.z).unwrap().content,
+            TileContent::Card(card) if card.id == climber.id
+        ));
+        assert!(game_state.players[&player_id].graveyard.is_empty());
+    }
+
+    #[test]
+    fn test_adjacent_hits_players_on_neighboring_tiles_at_their_level() {
+        use crate::models::Position;
+
+        let at = |x, y, z| Position {
+            x,
+            y,
+            z,
+            level: x.max(y).max(z),
+        };
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Source").position(at(0, 0, 0)).build())
+            .player(PlayerBuilder::new("Above").position(at(1, 1, 0)).build())
+            .player(PlayerBuilder::new("Far").position(at(2, 0, 2)).build())
+            .build();
+        let ids = game_state.turn_order.clone();
+
+        Effect::Damage(DamageEffect {
+            value: EffectValue {
+                base: 5,
+                scaling: None,
+            },
+            target: EffectTarget::Adjacent,
+            penetrating: false,
+            ranged: false,
+            priority: 0,
+        })
+        .apply(&mut game_state, ids[0])
+        .unwrap();
+        let health: Vec<u32> = ids.iter().map(|id| game_state.players[id].health).collect();
+        assert_eq!(health, vec![30, 25, 30]);
+    }
+
+    #[test]
+    fn test_guard_must_be_targeted_first() {
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Attacker").build())
+            .player(PlayerBuilder::new("Bystander").build())
+            .player(PlayerBuilder::new("Guard").build())
+            .build();
+        let (attacker, bystander, guard) = (
+            game_state.turn_order[0],
+            game_state.turn_order[1],
+            game_state.turn_order[2],
+        );
+        game_state
+            .players
+            .get_mut(&guard)
+            .unwrap()
+            .add_status(Status {
+                kind: StatusKind::Guard,
+                duration: Duration::Permanent,
+                source: None,
+            });
+
+        let hit = |target| {
+            Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base: 5,
+                    scaling: None,
+                },
+                target: EffectTarget::Specific(target),
+                penetrating: false,
+                ranged: false,
+                priority: 0,
+            })
+        };
+        assert!(matches!(
+            hit(bystander).apply(&mut game_state, attacker),
+            Err(GameError::MustTargetGuard)
+        ));
+        hit(guard).apply(&mut game_state, attacker).unwrap();
+        assert_eq!(game_state.players[&guard].health, 25);
+
+        // Out of range the guard no longer protects anyone
+        game_state.players.get_mut(&guard).unwrap().position = crate::models::Position {
+            x: 3,
+            y: 0,
+            z: 3,
+            level: 3,
+        };
+        hit(bystander).apply(&mut game_state, attacker).unwrap();
+        assert_eq!(game_state.players[&bystander].health, 25);
+
+        // Back in range but stealthed, it can't be targeted and doesn't
+        // force anyone to try
+        let guard_player = game_state.players.get_mut(&guard).unwrap();
+        guard_player.position = crate::models::Position::default();
+        guard_player.add_status(Status {
+            kind: StatusKind::Stealth,
+            duration: Duration::Permanent,
+            source: None,
+        });
+        hit(bystander).apply(&mut game_state, attacker).unwrap();
+        assert_eq!(game_state.players[&bystander].health, 20);
+        assert!(matches!(
+            hit(guard).apply(&mut game_state, attacker),
+            Err(GameError::InvalidTarget)
+        ));
+    }
+
+    #[test]
+    fn test_refused_target_leaves_the_card_unplayed() {
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Attacker").mana(5).build())
+            .player(PlayerBuilder::new("Bystander").build())
+            .player(PlayerBuilder::new("Guard").build())
+            .build();
+        let (attacker, bystander, guard) = (
+            game_state.turn_order[0],
+            game_state.turn_order[1],
+            game_state.turn_order[2],
+        );
+        game_state
+            .players
+            .get_mut(&guard)
+            .unwrap()
+            .add_status(Status {
+                kind: StatusKind::Guard,
+                duration: Duration::Permanent,
+                source: None,
+            });
+        let bolt = CardBuilder::new("Bolt")
+            .cost(2)
+            .effect(Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base: 5,
+                    scaling: None,
+                },
+                target: EffectTarget::Specific(bystander),
+                penetrating: false,
+                ranged: false,
+                priority: 0,
+            }))
+            .build();
+        game_state
+            .players
+            .get_mut(&attacker)
+            .unwrap()
+            .hand
+            .push(bolt.clone());
+        let before = game_state.players[&attacker].clone();
+        let events = game_state.events.len();
+
+        assert!(matches!(
+            game_state.play_card(attacker, bolt.id),
+            Err(GameError::MustTargetGuard)
+        ));
+        let after = &game_state.players[&attacker];
+        assert_eq!(after.hand, before.hand);
+        assert_eq!(after.mana, before.mana);
+        assert!(after.graveyard.is_empty());
+        assert_eq!(after.cards_played_this_turn, 0);
+        assert_eq!(game_state.events.len(), events);
+    }
+
+    #[test]
+    fn test_blocked_sight_leaves_the_card_unplayed() {
+        let mut game_state = GameStateBuilder::new().mountain_levels(4).build();
+        let from = game_state.mountain.get_tile(0, 0, 0).unwrap().position();
+        let to = game_state.mountain.get_tile(2, 0, 2).unwrap().position();
+        let (archer, target) = (game_state.turn_order[0], game_state.turn_order[1]);
+        game_state.players.get_mut(&archer).unwrap().position = from;
+        game_state.players.get_mut(&archer).unwrap().mana = 5;
+        game_state.players.get_mut(&target).unwrap().position = to;
+        game_state.mountain.get_tile_mut(1, 0, 1).unwrap().content =
+            TileContent::Card(CardBuilder::new("Ice Wall").build());
+        let arrow = CardBuilder::new("Arrow")
+            .cost(2)
+            .effect(Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base: 3,
+                    scaling: None,
+                },
+                target: EffectTarget::Specific(target),
+                penetrating: false,
+                ranged: true,
+                priority: 0,
+            }))
+            .build();
+        game_state
+            .players
+            .get_mut(&archer)
+            .unwrap()
+            .hand
+            .push(arrow.clone());
+        let before = game_state.players[&archer].clone();
+        let events = game_state.events.len();
+
+        assert!(matches!(
+            game_state.play_card(archer, arrow.id),
+            Err(GameError::NoLineOfSight)
+        ));
+        let after = &game_state.players[&archer];
+        assert_eq!(after.hand, before.hand);
+        assert_eq!(after.mana, before.mana);
+        assert!(after.graveyard.is_empty());
+        assert_eq!(game_state.events.len(), events);
+    }
+
+    #[test]
+    fn test_stealth_blocks_targeting_until_acting() {
+        let card = CardBuilder::new("Flare").cost(0).build();
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Scout").hand(vec![card.clone()]).build())
+            .build();
+        let (scout, other) = (game_state.turn_order[0], game_state.turn_order[1]);
+        game_state
+            .players
+            .get_mut(&scout)
+            .unwrap()
+            .add_status(Status {
+                kind: StatusKind::Stealth,
+                duration: Duration::Permanent,
+                source: None,
+            });
+
+        let mut targets =
+            resolve_targets(&EffectTarget::Random(1), &mut game_state, other).unwrap();
+        assert_eq!(targets.pop(), Some(other));
+        assert!(matches!(
+            resolve_targets(&EffectTarget::Specific(scout), &mut game_state, other),
+            Err(GameError::InvalidTarget)
+        ));
+
+        game_state.play_card(scout, card.id).unwrap();
+        assert!(resolve_targets(&EffectTarget::Specific(scout), &mut game_state, other).is_ok());
+    }
+
+    #[test]
+    fn test_cost_modifier_applies_only_while_condition_holds() {
+        let rope = CardBuilder::new("Rope")
+            .cost(3)
+            .card_type(CardType::Gear)
+            .build();
+        let flare = CardBuilder::new("Flare").cost(2).build();
+        let mut game_state = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Climber")
+                    .mana(2)
+                    .hand(vec![rope.clone(), flare.clone()])
+                    .build(),
+            )
+            .build();
+        let climber = game_state.turn_order[0];
+        Effect::ModifyCost(CostEffect {
+            delta: -1,
+            filter: Some(DrawFilter::Type(CardType::Gear)),
+            condition: Some(CostCondition::AboveLevel(3)),
+            target: EffectTarget::Self_,
+            duration: Duration::Permanent,
+            on_source_removed: SourceRemoval::Expire,
+            priority: 0,
+        })
+        .apply(&mut game_state, climber)
+        .unwrap();
+
+        let player = game_state.players.get_mut(&climber).unwrap();
+        assert_eq!(player.effective_cost(&rope), 3);
+        player.position.level = 4;
+        assert_eq!(player.effective_cost(&rope), 2);
+        assert_eq!(player.effective_cost(&flare), 2);
+
+        game_state.play_card(climber, rope.id).unwrap();
+        let player = &game_state.players[&climber];
+        assert_eq!((player.mana, rope.cost), (0, 3));
+
+        game_state.remove_source(climber);
+        assert!(game_state.players[&climber].cost_modifiers.is_empty());
+    }
 }
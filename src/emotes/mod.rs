@@ -0,0 +1,144 @@
+// src/emotes/mod.rs
+use crate::errors::EmoteError;
+use crate::networking::ServerMessage;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+pub const EMOTE_COOLDOWN: Duration = Duration::from_secs(4);
+pub const MAX_LOADOUT_SIZE: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Emote {
+    Greetings,
+    WellPlayed,
+    Thanks,
+    Oops,
+    Threaten,
+    Wow,
+}
+
+pub fn default_loadout() -> Vec<Emote> {
+    vec![
+        Emote::Greetings,
+        Emote::WellPlayed,
+        Emote::Thanks,
+        Emote::Oops,
+    ]
+}
+
+// Per-game emote state: who is playing, what each may send, when they last
+// sent, and who has muted their opponents.
+#[derive(Debug)]
+pub struct EmoteChannel {
+    loadouts: HashMap<Uuid, Vec<Emote>>,
+    last_sent: HashMap<Uuid, SystemTime>,
+    muted: HashSet<Uuid>,
+}
+
+impl EmoteChannel {
+    pub fn new(loadouts: HashMap<Uuid, Vec<Emote>>) -> Self {
+        Self {
+            loadouts,
+            last_sent: HashMap::new(),
+            muted: HashSet::new(),
+        }
+    }
+
+    pub fn set_loadout(&mut self, player_id: Uuid, loadout: Vec<Emote>) -> Result<(), EmoteError> {
+        if loadout.len() > MAX_LOADOUT_SIZE {
+            return Err(EmoteError::LoadoutTooLarge);
+        }
+        let slot = self
+            .loadouts
+            .get_mut(&player_id)
+            .ok_or(EmoteError::NotInGame)?;
+        *slot = loadout;
+        Ok(())
+    }
+
+    pub fn set_muted(&mut self, player_id: Uuid, muted: bool) {
+        if muted {
+            self.muted.insert(player_id);
+        } else {
+            self.muted.remove(&player_id);
+        }
+    }
+
+    // Returns the messages to deliver, one per recipient that hasn't muted
+    // emotes. The sender always sees their own emote.
+    pub fn send(
+        &mut self,
+        sender: Uuid,
+        emote: Emote,
+        now: SystemTime,
+    ) -> Result<Vec<(Uuid, ServerMessage)>, EmoteError> {
+        let loadout = self.loadouts.get(&sender).ok_or(EmoteError::NotInGame)?;
+        if !loadout.contains(&emote) {
+            return Err(EmoteError::NotInLoadout);
+        }
+
+        if let Some(last) = self.last_sent.get(&sender) {
+            let elapsed = now.duration_since(*last).unwrap_or_default();
+            if elapsed < EMOTE_COOLDOWN {
+                return Err(EmoteError::OnCooldown {
+                    remaining: EMOTE_COOLDOWN - elapsed,
+                });
+            }
+        }
+        self.last_sent.insert(sender, now);
+
+        Ok(self
+            .loadouts
+            .keys()
+            .filter(|recipient| **recipient == sender || !self.muted.contains(recipient))
+            .map(|recipient| {
+                (
+                    *recipient,
+                    ServerMessage::Emote {
+                        player_id: sender,
+                        emote,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod emote_tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown_and_mute() {
+        let sender = Uuid::new_v4();
+        let opponent = Uuid::new_v4();
+        let mut channel = EmoteChannel::new(HashMap::from([
+            (sender, default_loadout()),
+            (opponent, default_loadout()),
+        ]));
+        let now = SystemTime::now();
+
+        assert_eq!(
+            channel.send(sender, Emote::Greetings, now).unwrap().len(),
+            2
+        );
+        assert!(matches!(
+            channel.send(sender, Emote::Thanks, now + Duration::from_secs(1)),
+            Err(EmoteError::OnCooldown { remaining }) if remaining == Duration::from_secs(3)
+        ));
+        assert!(matches!(
+            channel.send(opponent, Emote::Threaten, now),
+            Err(EmoteError::NotInLoadout)
+        ));
+
+        channel.set_muted(opponent, true);
+        let deliveries = channel
+            .send(sender, Emote::WellPlayed, now + EMOTE_COOLDOWN)
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].0, sender);
+    }
+}
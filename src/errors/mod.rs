@@ -8,11 +8,463 @@ pub enum GameError {
     EmptyDeck,
     InvalidTarget,
     NoValidCard,
+    NotYourTurn,
+    InsufficientMana,
+    CardNotInHand,
+    UnknownCardDefinition,
+    MustTargetGuard,
+    TileOccupied,
+    NoLineOfSight,
+    InvalidFootprint,
+    NotEnoughMovement,
+    GameAlreadyStarted,
+    GamePaused,
+    PauseNotAllowed,
+    GameHalted,
+    ActionLimitReached,
+    // A scry has to be answered before anything else happens
+    ChoicePending,
+    NoChoicePending,
+    EmptyBatch,
 }
 
-#[derive(Debug)]
+impl GameError {
+    // Stable name for metrics and client error codes
+    pub fn code(&self) -> &'static str {
+        match self {
+            GameError::InvalidMove => "invalid_move",
+            GameError::PlayerNotFound => "player_not_found",
+            GameError::DeckInvalid => "deck_invalid",
+            GameError::GameNotFound => "game_not_found",
+            GameError::EmptyDeck => "empty_deck",
+            GameError::InvalidTarget => "invalid_target",
+            GameError::NoValidCard => "no_valid_card",
+            GameError::NotYourTurn => "not_your_turn",
+            GameError::InsufficientMana => "insufficient_mana",
+            GameError::CardNotInHand => "card_not_in_hand",
+            GameError::UnknownCardDefinition => "unknown_card_definition",
+            GameError::MustTargetGuard => "must_target_guard",
+            GameError::TileOccupied => "tile_occupied",
+            GameError::NoLineOfSight => "no_line_of_sight",
+            GameError::InvalidFootprint => "invalid_footprint",
+            GameError::NotEnoughMovement => "not_enough_movement",
+            GameError::GameAlreadyStarted => "game_already_started",
+            GameError::GamePaused => "game_paused",
+            GameError::PauseNotAllowed => "pause_not_allowed",
+            GameError::GameHalted => "game_halted",
+            GameError::ActionLimitReached => "action_limit_reached",
+            GameError::ChoicePending => "choice_pending",
+            GameError::NoChoicePending => "no_choice_pending",
+            GameError::EmptyBatch => "empty_batch",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
 pub enum ValidationError {
     InvalidDeckSize,
     InvalidCardCount,
     InvalidPlayerState,
+    // Pulled from play by an admin; carries the card's name
+    DisabledCard(String),
+    // An experimental card not allowed in this format or match
+    LabsCard(String),
+    // Against the format's ban list; both carry the card's name
+    BannedCard(String),
+    RestrictedCard(String),
+}
+
+impl ValidationError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValidationError::InvalidDeckSize => "invalid_deck_size",
+            ValidationError::InvalidCardCount => "invalid_card_count",
+            ValidationError::InvalidPlayerState => "invalid_player_state",
+            ValidationError::DisabledCard(_) => "disabled_card",
+            ValidationError::LabsCard(_) => "labs_card",
+            ValidationError::BannedCard(_) => "banned_card",
+            ValidationError::RestrictedCard(_) => "restricted_card",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum GuildError {
+    GuildNotFound,
+    NameTaken,
+    AlreadyInGuild,
+    NotInGuild,
+    GuildFull,
+    InsufficientRole,
+    #[cfg(feature = "server")]
+    Moderation(ModerationError),
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug)]
+pub enum ModerationError {
+    Sanctioned(crate::moderation::Sanction),
+    SanctionNotFound,
+    CannotReportSelf,
+    DuplicateReport,
+    ReportNotFound,
+}
+
+#[cfg(feature = "server")]
+impl ModerationError {
+    pub fn code(&self) -> &'static str {
+        use crate::moderation::SanctionKind;
+        match self {
+            ModerationError::Sanctioned(sanction) => match sanction.kind {
+                SanctionKind::ChatMute => "chat_muted",
+                SanctionKind::MatchmakingBan => "matchmaking_banned",
+                SanctionKind::FullBan => "banned",
+            },
+            ModerationError::SanctionNotFound => "sanction_not_found",
+            ModerationError::CannotReportSelf => "cannot_report_self",
+            ModerationError::DuplicateReport => "duplicate_report",
+            ModerationError::ReportNotFound => "report_not_found",
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug)]
+pub enum WalletError {
+    InsufficientFunds {
+        currency: crate::wallet::Currency,
+        balance: u64,
+        requested: u64,
+    },
+    Overflow,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TradeError {
+    NotFound,
+    NotParticipant,
+    WrongCollection,
+    NotOwned(uuid::Uuid),
+    Locked(uuid::Uuid),
+    InvalidState,
+    Expired,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug)]
+pub enum CraftingError {
+    InvalidRecipes(String),
+    UnknownRecipe,
+    WrongCard(uuid::Uuid),
+    WrongDuplicateCount { needed: u32, given: usize },
+    Unavailable(TradeError),
+    AlreadyCrafted,
+    Wallet(WalletError),
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug)]
+pub enum BulkError {
+    InvalidDeckCode(String),
+    UnknownCard(String),
+    // More copies of the card than a constructed deck allows
+    TooManyCopies(String),
+    DeckNameTaken,
+    Wallet(WalletError),
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug)]
+pub enum StoreError {
+    ItemNotFound,
+    NotOnOffer,
+    InvalidCatalog(String),
+    Wallet(WalletError),
+    // A request id already used for a different item
+    RequestMismatch,
+    // A cosmetic the buyer has, or a deck under a name they already use
+    AlreadyOwned,
+    // A card a deck lists, missing from the card pool
+    UnknownCard(String),
+}
+
+#[derive(Debug)]
+pub enum DraftError {
+    NotDrafting,
+    NotPlaying,
+    InvalidPick,
+    EmptyPool,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    InvalidData(String),
+    ActionRejected { index: usize, error: GameError },
+    // The replay only has its event log, not the actions behind it
+    NotReproducible,
+    // Re-running the actions logged something else at this event, so the
+    // rules have changed since the game was played
+    Diverged { event_index: usize },
+    EventOutOfRange { event_index: usize, len: usize },
+}
+
+#[derive(Debug)]
+pub enum PuzzleError {
+    InvalidData(String),
+}
+
+#[derive(Debug)]
+pub enum ScenarioError {
+    InvalidData(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CardDataError {
+    UnknownVersion(String),
+    // Published versions never change; publish a new one instead
+    VersionExists(String),
+    // Games are still being played on it
+    InUse { version: String, games: usize },
+}
+
+#[derive(Debug)]
+pub enum ObjectiveError {
+    InvalidData(String),
+    // Objectives stay secret until someone has won
+    GameInProgress,
+}
+
+impl ObjectiveError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ObjectiveError::InvalidData(_) => "invalid_data",
+            ObjectiveError::GameInProgress => "game_in_progress",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TutorialError {
+    InvalidScript(String),
+    ActionNotAllowed { hint: String },
+    AlreadyComplete,
+    Game(GameError),
+}
+
+#[derive(Debug)]
+pub enum DeckShareError {
+    DeckNotFound,
+    NotAuthor,
+    NameTaken,
+    // Card name and how many more copies are needed
+    MissingCards(Vec<(String, usize)>),
+    InvalidDeck(ValidationError),
+}
+
+#[derive(Debug)]
+pub enum EmoteError {
+    NotInGame,
+    NotInLoadout,
+    LoadoutTooLarge,
+    OnCooldown { remaining: std::time::Duration },
+}
+
+#[derive(Debug)]
+pub enum CosmeticError {
+    NotOwned,
+    AlreadyOwned,
+}
+
+#[derive(Debug)]
+pub enum ProtocolError {
+    Encode(String),
+    Decode(String),
+    UnsupportedFormat,
+    FrameTooLarge(usize),
+    Io(String),
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug)]
+pub enum ClientError {
+    Protocol(ProtocolError),
+    UpdateRequired { download_url: String },
+    Rejected(crate::messages::LocalizedMessage),
+    Unexpected(Box<crate::networking::ServerMessage>),
+}
+
+#[derive(Debug)]
+pub enum InvariantViolation {
+    UnknownActivePlayer(uuid::Uuid),
+    TurnOrderMismatch,
+    PositionOffBoard {
+        player_id: uuid::Uuid,
+        position: crate::models::Position,
+    },
+    DuplicateCardId(uuid::Uuid),
+    HealthAboveMax {
+        player_id: uuid::Uuid,
+        health: u32,
+        max_health: u32,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SessionError {
+    UnknownGame,
+    NotAPlayer,
+    GameInProgress,
+    // The game's command queue is full; try again shortly
+    Busy,
+    // The game's task has stopped
+    Closed,
+    // A seat's deck can't be played in this match, with whose it is
+    InvalidDeck(uuid::Uuid, ValidationError),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MaintenanceError {
+    EndsBeforeStart,
+    StartingSoon { starts_in_secs: u64 },
+    Down { ends_in_secs: Option<u64> },
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Invalid(String),
+    Io(String),
+}
+
+#[derive(Debug)]
+pub enum AdminError {
+    UnknownGame,
+    NotAPlayer,
+    GameOver,
+    NothingToRollBack,
+    // Resume sent to a game that isn't halted
+    NotHalted,
+    // A card pool change sent to a single game, or the other way round
+    WrongScope,
+    // A turn timer of zero or longer than MAX_TURN_TIME_LIMIT
+    InvalidTurnTimer,
+    Game(GameError),
+    Recovery(RecoveryError),
+    #[cfg(feature = "server")]
+    Moderation(ModerationError),
+}
+
+#[derive(Debug)]
+pub enum RecoveryError {
+    Io(String),
+    InvalidData(String),
+    // Written by a newer server than this one
+    UnsupportedVersion(u32),
+    // The snapshot holds a game this instance is already hosting
+    AlreadyHosted(uuid::Uuid),
+    // A game started on another rules engine version than this server's
+    EngineMismatch { game_id: uuid::Uuid, engine: u32 },
+}
+
+// Why a player couldn't join the matchmaking queue
+#[cfg(feature = "server")]
+#[derive(Debug)]
+pub enum QueueError {
+    Moderation(ModerationError),
+    InvalidDeck(ValidationError),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum WarmupError {
+    // Nothing has been published for new games to start on
+    NoCardData,
+    // A Transform effect names a definition the registry doesn't have
+    UnknownDefinition {
+        card: String,
+        def_id: uuid::Uuid,
+    },
+    Statement {
+        name: &'static str,
+        url: String,
+        reason: String,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ClusterError {
+    UnknownGame,
+    UnknownInstance,
+    NoInstanceAvailable,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ResumeError {
+    UnknownToken,
+    Expired,
+    AheadOfServer,
+    TooFarBehind,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AnnouncementError {
+    EndsBeforeStart,
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ShuffleError {
+    WrongGame,
+    // The revealed seed doesn't reproduce the deck order the replay recorded
+    OrderMismatch { player_id: uuid::Uuid },
+    CommitmentMismatch,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CoachError {
+    NotAPlayer,
+    CoachIsPlaying,
+    NotGranted,
+    GameInProgress,
+}
+
+impl CoachError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            CoachError::NotAPlayer => "not_a_player",
+            CoachError::CoachIsPlaying => "coach_is_playing",
+            CoachError::NotGranted => "not_granted",
+            CoachError::GameInProgress => "game_in_progress",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LobbyError {
+    NotFound,
+    WrongPassword,
+    SeatsFull,
+    SpectatorsFull,
+    AlreadyJoined,
+    NotInLobby,
+    NotSeated,
+    ChatDisabled,
+    EmotesDisabled,
+    // The player is under a chat mute
+    Muted,
+}
+
+impl LobbyError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            LobbyError::NotFound => "lobby_not_found",
+            LobbyError::WrongPassword => "wrong_password",
+            LobbyError::SeatsFull => "seats_full",
+            LobbyError::SpectatorsFull => "spectators_full",
+            LobbyError::AlreadyJoined => "already_joined",
+            LobbyError::NotInLobby => "not_in_lobby",
+            LobbyError::NotSeated => "not_seated",
+            LobbyError::ChatDisabled => "chat_disabled",
+            LobbyError::EmotesDisabled => "emotes_disabled",
+            LobbyError::Muted => "chat_muted",
+        }
+    }
 }
@@ -0,0 +1,154 @@
+// src/events/bus.rs
+// Fan-out of game events to the systems that react to them (telemetry,
+// broadcast, replay recording and so on). The rules engine only appends to
+// its own log; whoever drives the game publishes that log to the bus after
+// each change, so the engine never calls into server code.
+use crate::events::GameEvent;
+use crate::game_state::GameState;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use uuid::Uuid;
+
+pub trait EventSubscriber: Send {
+    fn on_event(&mut self, game_id: Uuid, event: &GameEvent);
+
+    // Called once per game, after the events that decided it
+    fn on_game_over(&mut self, _game_state: &GameState) {}
+}
+
+impl<F: FnMut(Uuid, &GameEvent) + Send> EventSubscriber for F {
+    fn on_event(&mut self, game_id: Uuid, event: &GameEvent) {
+        self(game_id, event)
+    }
+}
+
+// Forwards events to another thread; dropped by the bus once the
+// receiving end hangs up
+struct ChannelSubscriber(Sender<(Uuid, GameEvent)>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Vec<(SubscriptionId, Box<dyn EventSubscriber>)>,
+    channels: Vec<(SubscriptionId, ChannelSubscriber)>,
+    next_id: u64,
+    // How far into each game's log has been published
+    cursors: HashMap<Uuid, usize>,
+    // Games whose end has been announced, so a republish doesn't repeat it
+    finished: HashSet<Uuid>,
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBus")
+            .field(
+                "subscribers",
+                &(self.subscribers.len() + self.channels.len()),
+            )
+            .field("games", &self.cursors.len())
+            .finish()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn next_id(&mut self) -> SubscriptionId {
+        self.next_id += 1;
+        SubscriptionId(self.next_id)
+    }
+
+    pub fn subscribe(&mut self, subscriber: impl EventSubscriber + 'static) -> SubscriptionId {
+        let id = self.next_id();
+        self.subscribers.push((id, Box::new(subscriber)));
+        id
+    }
+
+    pub fn subscribe_channel(&mut self) -> (SubscriptionId, Receiver<(Uuid, GameEvent)>) {
+        let id = self.next_id();
+        let (sender, receiver) = channel();
+        self.channels.push((id, ChannelSubscriber(sender)));
+        (id, receiver)
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) {
+        self.subscribers
+            .retain(|(subscription, _)| *subscription != id);
+        self.channels
+            .retain(|(subscription, _)| *subscription != id);
+    }
+
+    // Delivers the events the game has logged since it was last published,
+    // in order, and returns how many went out. A log that got shorter was
+    // rolled back; nothing is resent and publishing picks up from there.
+    pub fn publish(&mut self, game_state: &GameState) -> usize {
+        let game_id = game_state.game_id;
+        let cursor = self.cursors.entry(game_id).or_default();
+        let start = (*cursor).min(game_state.events.len());
+        *cursor = game_state.events.len();
+
+        let events = &game_state.events[start..];
+        for event in events {
+            for (_, subscriber) in &mut self.subscribers {
+                subscriber.on_event(game_id, event);
+            }
+            self.channels
+                .retain(|(_, channel)| channel.0.send((game_id, event.clone())).is_ok());
+        }
+        if game_state.winner().is_some() && self.finished.insert(game_id) {
+            for (_, subscriber) in &mut self.subscribers {
+                subscriber.on_game_over(game_state);
+            }
+        }
+        events.len()
+    }
+
+    // Forget a finished game's cursor
+    pub fn close(&mut self, game_id: Uuid) {
+        self.cursors.remove(&game_id);
+        self.finished.remove(&game_id);
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod bus_tests {
+    use super::*;
+    use crate::game_state::GameAction;
+    use crate::testing::GameStateBuilder;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_each_event_reaches_every_subscriber_once() {
+        let mut game_state = GameStateBuilder::new().build();
+        let mut bus = EventBus::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let recorder = bus.subscribe(move |_: Uuid, event: &GameEvent| {
+            log.lock().unwrap().push(event.clone());
+        });
+        let (_, receiver) = bus.subscribe_channel();
+
+        assert_eq!(bus.publish(&game_state), 1);
+        let player_id = game_state.active_player;
+        game_state
+            .apply_action(player_id, &GameAction::EndTurn)
+            .unwrap();
+        assert_eq!(bus.publish(&game_state), 1);
+        assert_eq!(bus.publish(&game_state), 0);
+
+        assert_eq!(*seen.lock().unwrap(), game_state.events);
+        assert_eq!(receiver.try_iter().count(), 2);
+
+        bus.unsubscribe(recorder);
+        drop(receiver);
+        game_state.events.push(GameEvent::GamePaused);
+        assert_eq!(bus.publish(&game_state), 1);
+        assert_eq!(seen.lock().unwrap().len(), 2);
+    }
+}
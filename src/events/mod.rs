@@ -0,0 +1,184 @@
+// src/events/mod.rs
+use crate::effects::StatusKind;
+use crate::game_state::ConcedeReason;
+use crate::models::Position;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub mod bus;
+
+// Everything observable that happens during a game, in resolution order.
+// GameState appends to its log as rules resolve; replays, summaries and
+// clients all read from it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GameEvent {
+    InitiativeDecided {
+        player_id: Uuid,
+    },
+    TurnStarted {
+        player_id: Uuid,
+        turn_number: u32,
+    },
+    CardPlayed {
+        player_id: Uuid,
+        card_id: Uuid,
+        name: String,
+    },
+    DamageDealt {
+        source: Uuid,
+        target: Uuid,
+        amount: u32,
+    },
+    Healed {
+        source: Uuid,
+        target: Uuid,
+        amount: u32,
+    },
+    ShieldGained {
+        player_id: Uuid,
+        amount: u32,
+    },
+    ShieldAbsorbed {
+        player_id: Uuid,
+        amount: u32,
+    },
+    PlayerMoved {
+        player_id: Uuid,
+        from: Position,
+        to: Position,
+    },
+    PlayerFell {
+        player_id: Uuid,
+        levels: u32,
+        damage: u32,
+    },
+    CampRested {
+        player_id: Uuid,
+        amount: u32,
+    },
+    PlayerRespawned {
+        player_id: Uuid,
+        position: Position,
+    },
+    AvalancheWarning {
+        path: Vec<Position>,
+    },
+    AvalancheStruck {
+        path: Vec<Position>,
+    },
+    ExtraTurnGranted {
+        player_id: Uuid,
+    },
+    SkipTurnQueued {
+        player_id: Uuid,
+    },
+    TurnSkipped {
+        player_id: Uuid,
+    },
+    SummitReached {
+        player_id: Uuid,
+    },
+    PauseRequested {
+        player_id: Uuid,
+    },
+    GamePaused,
+    GameResumed,
+    TurnTimedOut {
+        player_id: Uuid,
+        consecutive: u32,
+    },
+    PlayerConceded {
+        player_id: Uuid,
+        reason: ConcedeReason,
+    },
+    PlayerDefeated {
+        player_id: Uuid,
+    },
+    BoostsExpired {
+        player_id: Uuid,
+        source: Uuid,
+    },
+    StatusApplied {
+        player_id: Uuid,
+        kind: StatusKind,
+    },
+    StatusResisted {
+        player_id: Uuid,
+        kind: StatusKind,
+    },
+    StealthBroken {
+        player_id: Uuid,
+    },
+    Cleansed {
+        player_id: Uuid,
+        removed: u32,
+    },
+    // Which card stays hidden; only the count is public
+    CardDrawn {
+        player_id: Uuid,
+    },
+    CardsMilled {
+        player_id: Uuid,
+        count: u32,
+    },
+    Fatigued {
+        player_id: Uuid,
+        damage: u32,
+    },
+    CardResurrected {
+        player_id: Uuid,
+        card_id: Uuid,
+        position: Option<Position>, // None when returned to hand
+    },
+    CardTransformed {
+        card_id: Uuid,
+        into_def_id: Uuid,
+        name: String,
+    },
+    MaxHealthChanged {
+        player_id: Uuid,
+        max_health: u32,
+    },
+    // A cost modifier was added; costs in hand may have changed
+    CostsChanged {
+        player_id: Uuid,
+    },
+    // A combo effect went off on the `cards_played`th card of the turn
+    ComboTriggered {
+        player_id: Uuid,
+        cards_played: u32,
+    },
+    // Only counts: which cards were looked at stays with the chooser
+    ScryStarted {
+        player_id: Uuid,
+        deck_owner: Uuid,
+        count: u32,
+    },
+    ScryResolved {
+        player_id: Uuid,
+        deck_owner: Uuid,
+        top: u32,
+        bottom: u32,
+    },
+    // An Expedition card took the shared zone
+    ExpeditionStarted {
+        player_id: Uuid,
+        card_id: Uuid,
+        name: String,
+    },
+    // Replaced or destroyed; the card went to `player_id`'s graveyard
+    ExpeditionEnded {
+        player_id: Uuid,
+        card_id: Uuid,
+    },
+}
+
+// Presentation hints for one event, kept out of the event itself so the
+// log stays small. Only recorded when a game has annotations turned on.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventAnnotation {
+    pub event_index: usize,
+    pub source_card: Option<Uuid>, // The card being resolved, if any
+    pub target_position: Option<Position>,
+    pub amount_before_mitigation: Option<u32>, // Before shields and caps
+}
@@ -0,0 +1,210 @@
+// src/ffi/mod.rs
+// C ABI over the rules engine so non-Rust clients can run the same rules
+// locally. Everything crosses the boundary as UTF-8 JSON strings. Build the
+// shared library with
+//   cargo rustc --release --no-default-features --features ffi --crate-type cdylib
+// Strings returned from here must be released with `ascent_string_free`,
+// games with `ascent_game_free`.
+use crate::errors::GameError;
+use crate::game_state::{GameAction, GameState};
+use crate::models::{Deck, Player};
+use crate::runtime::seeded_rng;
+use crate::view::PlayerView;
+use serde::{Deserialize, Serialize};
+use std::ffi::{c_char, CStr, CString};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct SeatConfig {
+    pub name: String,
+    // The deck owner becomes the player's id
+    pub deck: Deck,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GameConfig {
+    pub seats: [SeatConfig; 2],
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Outcome {
+    Ok { ok: bool },
+    Err { error: String },
+}
+
+pub struct AscentGame {
+    state: GameState,
+}
+
+impl AscentGame {
+    pub fn from_config(config: GameConfig) -> AscentGame {
+        let [first, second] = config.seats.map(|seat| {
+            let mut player = Player::new(seat.name, seat.deck);
+            player.id = player.deck.owner_id;
+            player
+        });
+        let mut state = GameState::new(first, second);
+        if let Some(seed) = config.seed {
+            state = state.with_rng(seeded_rng(seed));
+        }
+        AscentGame { state }
+    }
+
+    pub fn apply(&mut self, player_id: Uuid, action: &GameAction) -> Result<(), GameError> {
+        self.state.apply_action(player_id, action)
+    }
+
+    pub fn view(&self, viewer: Uuid) -> PlayerView {
+        self.state.view_for(viewer)
+    }
+}
+
+unsafe fn read_str<'a>(ptr: *const c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    CStr::from_ptr(ptr).to_str().ok()
+}
+
+fn into_c_string<T: Serialize>(value: &T) -> *mut c_char {
+    serde_json::to_string(value)
+        .ok()
+        .and_then(|json| CString::new(json).ok())
+        .map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Creates a game from a JSON `GameConfig`. Returns null if the config
+/// can't be read.
+///
+/// # Safety
+/// `config_json` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ascent_game_new(config_json: *const c_char) -> *mut AscentGame {
+    let Some(config) = read_str(config_json).and_then(|json| serde_json::from_str(json).ok())
+    else {
+        return std::ptr::null_mut();
+    };
+    Box::into_raw(Box::new(AscentGame::from_config(config)))
+}
+
+/// # Safety
+/// `game` must be null or a pointer from `ascent_game_new` not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn ascent_game_free(game: *mut AscentGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+/// Applies a JSON `GameAction` for `player_id`. Returns `{"ok":true}` or
+/// `{"error":"<code>"}` using the same codes the server sends.
+///
+/// # Safety
+/// `game` must be a live game; the strings must be null or NUL-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn ascent_game_apply(
+    game: *mut AscentGame,
+    player_id: *const c_char,
+    action_json: *const c_char,
+) -> *mut c_char {
+    let Some(game) = game.as_mut() else {
+        return std::ptr::null_mut();
+    };
+    let player_id = read_str(player_id).and_then(|id| Uuid::parse_str(id).ok());
+    let action = read_str(action_json).and_then(|json| serde_json::from_str(json).ok());
+    let outcome = match (player_id, action) {
+        (Some(player_id), Some(action)) => match game.apply(player_id, &action) {
+            Ok(()) => Outcome::Ok { ok: true },
+            Err(error) => Outcome::Err {
+                error: error.code().to_string(),
+            },
+        },
+        _ => Outcome::Err {
+            error: "bad_request".to_string(),
+        },
+    };
+    into_c_string(&outcome)
+}
+
+/// The game as `viewer_id` sees it, as a JSON `PlayerView`.
+///
+/// # Safety
+/// `game` must be a live game; `viewer_id` must be null or NUL-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn ascent_game_view(
+    game: *const AscentGame,
+    viewer_id: *const c_char,
+) -> *mut c_char {
+    let Some(game) = game.as_ref() else {
+        return std::ptr::null_mut();
+    };
+    let viewer = read_str(viewer_id)
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .unwrap_or_default();
+    into_c_string(&game.view(viewer))
+}
+
+/// Events from index `since` on, as a JSON array.
+///
+/// # Safety
+/// `game` must be a live game.
+#[no_mangle]
+pub unsafe extern "C" fn ascent_game_events(game: *const AscentGame, since: usize) -> *mut c_char {
+    let Some(game) = game.as_ref() else {
+        return std::ptr::null_mut();
+    };
+    into_c_string(&game.state.events.get(since..).unwrap_or_default())
+}
+
+/// # Safety
+/// `s` must be null or a string returned by this library, not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn ascent_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod ffi_tests {
+    use super::*;
+
+    fn call(ptr: *mut c_char) -> serde_json::Value {
+        let json = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+        unsafe { ascent_string_free(ptr) };
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_game_round_trips_through_the_c_abi() {
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let config = serde_json::json!({
+            "seats": [
+                {"name": "Alice", "deck": {"cards": [], "owner_id": alice}},
+                {"name": "Bob", "deck": {"cards": [], "owner_id": bob}},
+            ],
+            "seed": 7,
+        });
+        let config = CString::new(config.to_string()).unwrap();
+        let game = unsafe { ascent_game_new(config.as_ptr()) };
+        assert!(!game.is_null());
+
+        let bob_id = CString::new(bob.to_string()).unwrap();
+        let end_turn = CString::new(r#""EndTurn""#).unwrap();
+        let outcome = call(unsafe { ascent_game_apply(game, bob_id.as_ptr(), end_turn.as_ptr()) });
+        assert_eq!(outcome["error"], "not_your_turn");
+
+        let view = call(unsafe { ascent_game_view(game, bob_id.as_ptr()) });
+        assert_eq!(view["active_player"], alice.to_string());
+        assert!(view["seats"][0]["hand"].is_null());
+        assert!(view["seats"][1]["hand"].is_array());
+
+        let events = call(unsafe { ascent_game_events(game, 0) });
+        assert_eq!(events.as_array().unwrap().len(), 1);
+        unsafe { ascent_game_free(game) };
+        assert!(unsafe { ascent_game_new(std::ptr::null()) }.is_null());
+    }
+}
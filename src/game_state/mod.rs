@@ -1,32 +1,669 @@
 // src/game_state/mod.rs
-use crate::errors::GameError;
-use crate::models::{Mountain, Player, Position};
-use std::collections::HashMap;
+use crate::cards::CardRegistry;
+use crate::effects::{
+    apply_damage, resolve_simultaneous, Effect, EffectValue, FieldRule, PendingEffect, StatusKind,
+};
+use crate::errors::{GameError, InvariantViolation};
+use crate::events::{EventAnnotation, GameEvent};
+use crate::models::{Card, CardType, Mountain, Player, Position, TileContent};
+use crate::runtime::{os_rng, GameClock, GameRng};
+use rand::prelude::IndexedRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use uuid::Uuid;
 
-#[derive(Debug)]
+pub const MAX_MANA: u32 = 10;
+// Longest batch of actions a client can send as one play
+pub const MAX_BATCH_ACTIONS: usize = 8;
+// Longest turn timer a game can be given
+pub const MAX_TURN_TIME_LIMIT: Duration = Duration::from_secs(60 * 60);
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GameAction {
+    PlayCard { card_id: Uuid },
+    Move { position: Position },
+    EndTurn,
+    // Answers the oldest pending scry: every looked-at card goes in exactly
+    // one list, `top` first card first, `bottom` last card last
+    ResolveScry { top: Vec<Uuid>, bottom: Vec<Uuid> },
+}
+
+// Cards a player has looked at and not yet put back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScryChoice {
+    pub chooser: Uuid,
+    pub deck_owner: Uuid,
+    pub cards: Vec<Uuid>,
+}
+
+// The card holding the shared expedition zone and who played it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Expedition {
+    pub card: Card,
+    pub owner: Uuid,
+}
+
+impl Expedition {
+    pub fn rules(&self) -> Vec<FieldRule> {
+        self.card
+            .effects
+            .iter()
+            .filter_map(Effect::field_rule)
+            .collect()
+    }
+}
+
+// wasm32-unknown-unknown has no system time to read, so there a game's
+// clock stands still until the client hands it one with `with_clock`
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn system_clock() -> Arc<dyn GameClock> {
+    Arc::new(crate::runtime::SystemClock)
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn system_clock() -> Arc<dyn GameClock> {
+    crate::runtime::ManualClock::new(SystemTime::UNIX_EPOCH)
+}
+
+fn serialize_registry<S: serde::Serializer>(
+    cards: &Arc<CardRegistry>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    cards.as_ref().serialize(serializer)
+}
+
+fn deserialize_registry<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Arc<CardRegistry>, D::Error> {
+    CardRegistry::deserialize(deserializer).map(Arc::new)
+}
+
+fn same_tile(a: Position, b: Position) -> bool {
+    (a.x, a.y, a.z) == (b.x, b.y, b.z)
+}
+
+// What happens when a player moves onto a tile another player stands on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollisionRule {
+    #[default]
+    Block,
+    Swap,     // The two players trade places
+    Displace, // The occupant is pushed to a free tile next to it
+}
+
+// Damage changes per level of height difference between attacker and
+// target, counting at most `max_levels` levels. Off unless a mode sets it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighGroundRules {
+    pub bonus_per_level: u32,
+    pub uphill_penalty_per_level: u32,
+    pub max_levels: u32,
+}
+
+// How the first player is picked when a game begins
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Initiative {
+    #[default]
+    SeatOrder, // Whoever was seated first
+    CoinFlip,
+    Altitude, // The lowest climber starts; ties are flipped for
+}
+
+// Compensation for the player who goes second
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SecondPlayerBonus {
+    None,
+    #[default]
+    ExtraCard,
+    BonusMana(u32), // Added on top of their first turn's mana
+}
+
+// How teammates take their turns in team games
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TeamTurns {
+    #[default]
+    Alternating, // Seats alternate between teams, one teammate at a time
+    Shared, // Either teammate may act while it is their team's turn
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConcedeReason {
+    Surrendered,
+    Inactive,     // Ran out the turn timer too many times in a row
+    Disconnected, // Stayed away longer than the reconnect window
+    Kicked,       // Removed by an admin
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Requested {
+        by: Uuid,
+    },
+    Paused {
+        since: SystemTime,
+    },
+}
+
+// Bumped whenever a rules change in this crate would play an existing game
+// differently. A server only hosts games started on its own engine version.
+pub const ENGINE_VERSION: u32 = 1;
+
+// Card data games get when nothing pins them to a published version
+pub const UNVERSIONED_CARD_DATA: &str = "unversioned";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RulesVersion {
+    pub engine: u32,
+    pub card_data: String,
+}
+
+impl Default for RulesVersion {
+    fn default() -> Self {
+        Self {
+            engine: ENGINE_VERSION,
+            card_data: UNVERSIONED_CARD_DATA.to_string(),
+        }
+    }
+}
+
+// Per-game rule knobs. Defaults are the standard ruleset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameRules {
+    pub collision: CollisionRule,
+    pub high_ground: HighGroundRules,
+    // Damage per level fallen beyond the first, for forced drops
+    pub falling_damage_per_level: u32,
+    // Healing for starting a turn on a camp
+    pub camp_heal: u32,
+    // Modes with unit death: defeated players come back at a camp after
+    // this many of their own turns. None means defeat is final.
+    pub respawn_after: Option<u32>,
+    pub avalanches: Option<AvalancheRules>,
+    // Most turns one player can take in a row through extra turns
+    pub max_consecutive_turns: u32,
+    pub initiative: Initiative,
+    pub second_player_bonus: SecondPlayerBonus,
+    // Turn timer; the turn is passed for a player who runs it out
+    pub turn_time_limit: Option<Duration>,
+    // Most extra time the turn timer gives for a slow connection
+    pub max_latency_allowance: Duration,
+    // Expired turns in a row before the player is conceded for
+    pub afk_turns_before_concede: u32,
+    // How long a dropped player has to come back
+    pub reconnect_window: Duration,
+    // Longest a pause may last before play resumes on its own. None for
+    // modes that can't be paused.
+    pub max_pause: Option<Duration>,
+    pub team_turns: TeamTurns,
+    // Whether area and adjacency damage also hits the source's own side
+    pub friendly_fire: bool,
+    // Action budget per turn; None leaves it unlimited. Caps combo decks
+    // that could otherwise chain cards forever.
+    pub max_cards_per_turn: Option<u32>,
+    pub max_moves_per_turn: Option<u32>,
+    // Team games: a team wins by having every standing member on the
+    // summit at the same time
+    pub summit_hold: bool,
+}
+
+// An avalanche path is announced every `interval` turns and comes down at
+// the start of the following turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AvalancheRules {
+    pub interval: u32,
+    pub damage: u32,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            collision: CollisionRule::default(),
+            high_ground: HighGroundRules::default(),
+            falling_damage_per_level: 3,
+            camp_heal: 2,
+            respawn_after: None,
+            avalanches: None,
+            max_consecutive_turns: 3,
+            initiative: Initiative::default(),
+            second_player_bonus: SecondPlayerBonus::default(),
+            turn_time_limit: None,
+            max_latency_allowance: Duration::from_secs(1),
+            afk_turns_before_concede: 3,
+            reconnect_window: Duration::from_secs(120),
+            max_pause: None,
+            team_turns: TeamTurns::default(),
+            friendly_fire: true,
+            summit_hold: false,
+            max_cards_per_turn: None,
+            max_moves_per_turn: None,
+        }
+    }
+}
+
+// Serializes everything but the RNG and clock. A game loaded back gets a
+// fresh OS-seeded RNG and the system clock, so from there on it won't roll
+// the same as the original would have.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
     pub game_id: Uuid,
     pub players: HashMap<Uuid, Player>,
+    pub turn_order: Vec<Uuid>,
     pub active_player: Uuid,
     pub turn_number: u32,
     pub mountain: Mountain,
+    pub events: Vec<GameEvent>,
+    #[serde(skip, default = "os_rng")]
+    pub rng: Box<dyn GameRng>,
+    #[serde(skip, default = "system_clock")]
+    pub clock: Arc<dyn GameClock>,
+    pub turn_started_at: SystemTime,
+    #[serde(
+        serialize_with = "serialize_registry",
+        deserialize_with = "deserialize_registry"
+    )]
+    pub cards: Arc<CardRegistry>,
+    // Engine and card data the game started with; it keeps both to the end
+    #[serde(default)]
+    pub rules_version: RulesVersion,
+    pub rules: GameRules,
+    // Own turns left before each downed player respawns
+    pub respawns: HashMap<Uuid, u32>,
+    // Tiles the telegraphed avalanche will sweep next turn
+    pub pending_avalanche: Option<Vec<Position>>,
+    // How many turns in a row the active player has had
+    pub consecutive_turns: u32,
+    // Expired turns in a row per player, cleared when they act
+    pub timeouts: HashMap<Uuid, u32>,
+    pub disconnected_since: HashMap<Uuid, SystemTime>,
+    // Extra turn time per player for their connection, measured by the
+    // server; capped by the rules when the timer is checked
+    pub latency_allowance: HashMap<Uuid, Duration>,
+    pub pause: PauseState,
+    // Client hints alongside `events`; None unless turned on
+    pub annotations: Option<Vec<EventAnnotation>>,
+    // The card whose effects are resolving right now
+    pub resolving_card: Option<Uuid>,
+    // Team per player; empty in free-for-all games
+    pub teams: HashMap<Uuid, u32>,
+    // Oldest first; play waits until they are answered
+    pub pending_scry: Vec<ScryChoice>,
+    // Shared by everyone; a new Expedition replaces the one in play
+    pub expedition: Option<Expedition>,
 }
 
 impl GameState {
     pub fn new(player1: Player, player2: Player) -> Self {
         let mut players = HashMap::new();
         let p1_id = player1.id;
+        let turn_order = vec![player1.id, player2.id];
         players.insert(player1.id, player1);
         players.insert(player2.id, player2);
+        let clock = system_clock();
 
         Self {
             game_id: Uuid::new_v4(),
             players,
+            turn_order,
             active_player: p1_id,
             turn_number: 1,
-            mountain: Mountain::new(7),
+            mountain: Mountain::preset(7),
+            events: vec![GameEvent::TurnStarted {
+                player_id: p1_id,
+                turn_number: 1,
+            }],
+            rng: os_rng(),
+            turn_started_at: clock.now(),
+            clock,
+            cards: Arc::default(),
+            rules_version: RulesVersion::default(),
+            rules: GameRules::default(),
+            respawns: HashMap::new(),
+            pending_avalanche: None,
+            consecutive_turns: 1,
+            timeouts: HashMap::new(),
+            disconnected_since: HashMap::new(),
+            latency_allowance: HashMap::new(),
+            pause: PauseState::Running,
+            annotations: None,
+            resolving_card: None,
+            teams: HashMap::new(),
+            pending_scry: Vec::new(),
+            expedition: None,
+        }
+    }
+
+    // A 2v2 game. Seats alternate between the teams, so with alternating
+    // turns nobody plays twice before the other side has answered.
+    pub fn new_teams(team_a: [Player; 2], team_b: [Player; 2]) -> Self {
+        let [a1, a2] = team_a;
+        let [b1, b2] = team_b;
+        let teams = [(a1.id, 0), (a2.id, 0), (b1.id, 1), (b2.id, 1)];
+        let mut game_state = GameState::new(a1, b1);
+        for player in [a2, b2] {
+            game_state.turn_order.push(player.id);
+            game_state.players.insert(player.id, player);
+        }
+        game_state.teams = teams.into_iter().collect();
+        game_state
+    }
+
+    pub fn with_annotations(mut self) -> Self {
+        self.annotations = Some(Vec::new());
+        self
+    }
+
+    // Attaches hints to the most recent event when annotations are on
+    pub fn annotate(&mut self, target: Option<Uuid>, amount_before_mitigation: Option<u32>) {
+        let Some(event_index) = self.events.len().checked_sub(1) else {
+            return;
+        };
+        let target_position = target
+            .and_then(|id| self.players.get(&id))
+            .map(|player| player.position);
+        let source_card = self.resolving_card;
+        if let Some(annotations) = &mut self.annotations {
+            annotations.push(EventAnnotation {
+                event_index,
+                source_card,
+                target_position,
+                amount_before_mitigation,
+            });
+        }
+    }
+
+    // Each event with its hints, for clients that animate the log
+    pub fn annotated_events(&self) -> impl Iterator<Item = (&GameEvent, Option<&EventAnnotation>)> {
+        let annotations = self.annotations.as_deref().unwrap_or_default();
+        self.events.iter().enumerate().map(move |(index, event)| {
+            let annotation = annotations
+                .iter()
+                .find(|annotation| annotation.event_index == index);
+            (event, annotation)
+        })
+    }
+
+    pub fn with_rules(mut self, rules: GameRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    // True once anything has happened beyond the opening TurnStarted
+    pub fn has_started(&self) -> bool {
+        self.turn_number != 1 || self.events.len() > 1
+    }
+
+    // Settles who goes first under the game's initiative rule and gives the
+    // second seat its compensation. Only meaningful before the first action;
+    // a new game otherwise starts in seat order with no bonus.
+    pub fn decide_initiative(&mut self) -> Result<(), GameError> {
+        if self.has_started() {
+            return Err(GameError::GameAlreadyStarted);
+        }
+
+        let seats = self.turn_order.len();
+        let first = match self.rules.initiative {
+            Initiative::SeatOrder => 0,
+            Initiative::CoinFlip => self.rng.random_range(0..seats),
+            Initiative::Altitude => {
+                let lowest = self
+                    .seated_players()
+                    .map(|player| player.position.level)
+                    .min()
+                    .unwrap_or_default();
+                let tied: SmallVec<[usize; 4]> = (0..seats)
+                    .filter(|seat| self.players[&self.turn_order[*seat]].position.level == lowest)
+                    .collect();
+                tied.choose(self.rng.as_mut()).copied().unwrap_or_default()
+            }
+        };
+        self.turn_order.rotate_left(first);
+        self.active_player = self.turn_order[0];
+
+        if let Some(player) = self
+            .turn_order
+            .get(1)
+            .and_then(|second| self.players.get_mut(second))
+        {
+            match self.rules.second_player_bonus {
+                SecondPlayerBonus::None => {}
+                SecondPlayerBonus::ExtraCard => {
+                    // An empty deck just means no card
+                    let _ = player.draw_card();
+                }
+                SecondPlayerBonus::BonusMana(amount) => player.mana_tokens = amount,
+            }
+        }
+
+        self.events = vec![
+            GameEvent::InitiativeDecided {
+                player_id: self.active_player,
+            },
+            GameEvent::TurnStarted {
+                player_id: self.active_player,
+                turn_number: 1,
+            },
+        ];
+        Ok(())
+    }
+
+    pub fn with_card_registry(mut self, cards: Arc<CardRegistry>) -> Self {
+        self.cards = cards;
+        self
+    }
+
+    // Pins the game to a published card data version, see CardDataLibrary
+    pub fn with_card_data(mut self, version: &str, cards: Arc<CardRegistry>) -> Self {
+        self.rules_version = RulesVersion {
+            engine: ENGINE_VERSION,
+            card_data: version.to_string(),
+        };
+        self.cards = cards;
+        self
+    }
+
+    pub fn with_rng(mut self, rng: Box<dyn GameRng>) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn GameClock>) -> Self {
+        self.turn_started_at = clock.now();
+        self.clock = clock;
+        self
+    }
+
+    // Cleanup pass for when a card, unit or player that applied lasting
+    // effects leaves play: boosts set to expire with it are removed, the
+    // rest persist.
+    pub fn remove_source(&mut self, source: Uuid) {
+        for seat in 0..self.turn_order.len() {
+            let id = self.turn_order[seat];
+            let Some(player) = self.players.get_mut(&id) else {
+                continue;
+            };
+            if player.remove_boosts_from(source) {
+                self.events.push(GameEvent::BoostsExpired {
+                    player_id: id,
+                    source,
+                });
+            }
+        }
+    }
+
+    // Damage from one player to another after the high-ground modifier.
+    // Attacking uphill never takes a hit below 1 damage.
+    pub fn elevation_adjusted(&self, source: Uuid, target: Uuid, amount: u32) -> u32 {
+        let (Some(attacker), Some(defender)) =
+            (self.players.get(&source), self.players.get(&target))
+        else {
+            return amount;
+        };
+        if amount == 0 || source == target {
+            return amount;
+        }
+
+        let rules = self.rules.high_ground;
+        let (high, low) = (attacker.position.level, defender.position.level);
+        if high > low {
+            let levels = (high - low).min(rules.max_levels);
+            amount.saturating_add(levels.saturating_mul(rules.bonus_per_level))
+        } else {
+            let levels = (low - high).min(rules.max_levels);
+            amount
+                .saturating_sub(levels.saturating_mul(rules.uphill_penalty_per_level))
+                .max(1)
+        }
+    }
+
+    pub fn team_of(&self, player_id: Uuid) -> Option<u32> {
+        self.teams.get(&player_id).copied()
+    }
+
+    // Without teams everyone is on their own side
+    pub fn are_allies(&self, a: Uuid, b: Uuid) -> bool {
+        a == b
+            || self
+                .team_of(a)
+                .is_some_and(|team| self.team_of(b) == Some(team))
+    }
+
+    pub fn are_enemies(&self, a: Uuid, b: Uuid) -> bool {
+        !self.are_allies(a, b)
+    }
+
+    // The active player, or with shared team turns any of their teammates
+    pub fn holds_turn(&self, player_id: Uuid) -> bool {
+        self.active_player == player_id
+            || (self.rules.team_turns == TeamTurns::Shared
+                && self.are_allies(self.active_player, player_id))
+    }
+
+    // Players in seat order. Anything that walks every player should use
+    // this rather than the map so results don't depend on hash order.
+    pub fn seated_players(&self) -> impl Iterator<Item = &Player> {
+        self.turn_order.iter().filter_map(|id| self.players.get(id))
+    }
+
+    // Time on the turn clock; it stands still while the game is paused
+    pub fn turn_elapsed(&self) -> Duration {
+        let now = match self.pause {
+            PauseState::Paused { since } => since,
+            _ => self.clock.now(),
+        };
+        now.duration_since(self.turn_started_at).unwrap_or_default()
+    }
+
+    // Either player may ask; the game only stops once an opponent agrees
+    pub fn request_pause(&mut self, player_id: Uuid) -> Result<(), GameError> {
+        if self.rules.max_pause.is_none() {
+            return Err(GameError::PauseNotAllowed);
+        }
+        if self.pause != PauseState::Running {
+            return Err(GameError::GamePaused);
+        }
+        if !self.players.contains_key(&player_id) {
+            return Err(GameError::PlayerNotFound);
+        }
+        self.pause = PauseState::Requested { by: player_id };
+        self.events.push(GameEvent::PauseRequested { player_id });
+        Ok(())
+    }
+
+    // Only a seated player on the other side can agree; a teammate of
+    // whoever asked doesn't count
+    pub fn accept_pause(&mut self, player_id: Uuid) -> Result<(), GameError> {
+        let PauseState::Requested { by } = self.pause else {
+            return Err(GameError::PauseNotAllowed);
+        };
+        if !self.seated_players().any(|player| player.id == player_id) {
+            return Err(GameError::PlayerNotFound);
+        }
+        if !self.are_enemies(by, player_id) {
+            return Err(GameError::PauseNotAllowed);
+        }
+        self.pause = PauseState::Paused {
+            since: self.clock.now(),
+        };
+        self.events.push(GameEvent::GamePaused);
+        Ok(())
+    }
+
+    // Server-imposed pause, e.g. for maintenance; no agreement needed
+    pub fn suspend(&mut self) {
+        if !matches!(self.pause, PauseState::Paused { .. }) {
+            self.pause = PauseState::Paused {
+                since: self.clock.now(),
+            };
+            self.events.push(GameEvent::GamePaused);
+        }
+    }
+
+    // Drops a pending request or ends a pause, handing the turn clock back
+    // the time it was frozen
+    pub fn resume(&mut self) {
+        if let PauseState::Paused { since } = self.pause {
+            let paused_for = self.clock.now().duration_since(since).unwrap_or_default();
+            self.turn_started_at += paused_for;
+            self.events.push(GameEvent::GameResumed);
+        }
+        self.pause = PauseState::Running;
+    }
+
+    // The last player standing wins; None while the game is still undecided.
+    // In team games this is the first seated member of the winning team.
+    pub fn winner(&self) -> Option<Uuid> {
+        if !self.teams.is_empty() {
+            let team = self.winning_team()?;
+            return self
+                .seated_players()
+                .find(|player| self.team_of(player.id) == Some(team))
+                .map(|player| player.id);
+        }
+        // With respawns on, nobody is ever knocked out for good
+        let mut alive = self
+            .players
+            .values()
+            .filter(|player| player.health > 0 || self.rules.respawn_after.is_some());
+        match (alive.next(), alive.next()) {
+            (Some(player), None) => Some(player.id),
+            _ => None,
+        }
+    }
+
+    // The last team with anyone standing, or with `summit_hold` a team
+    // whose standing members are all on the summit
+    pub fn winning_team(&self) -> Option<u32> {
+        let mut standing = self
+            .seated_players()
+            .filter(|player| player.health > 0 || self.rules.respawn_after.is_some())
+            .filter_map(|player| self.team_of(player.id));
+        let first = standing.next()?;
+        if standing.all(|team| team == first) {
+            return Some(first);
+        }
+
+        if !self.rules.summit_hold {
+            return None;
         }
+        let summit = self.mountain.summit_level();
+        let mut teams: Vec<u32> = self.teams.values().copied().collect();
+        teams.sort_unstable();
+        teams.dedup();
+        teams.into_iter().find(|team| {
+            let mut members = self
+                .seated_players()
+                .filter(|player| self.team_of(player.id) == Some(*team) && player.health > 0)
+                .peekable();
+            members.peek().is_some() && members.all(|player| player.position.level >= summit)
+        })
     }
 
     pub fn move_player(
@@ -34,23 +671,872 @@ impl GameState {
         player_id: Uuid,
         new_position: Position,
     ) -> Result<(), GameError> {
-        let current_position = self
+        let player = self
             .players
             .get(&player_id)
-            .ok_or(GameError::PlayerNotFound)?
-            .position;
+            .ok_or(GameError::PlayerNotFound)?;
+        let (current_position, movement) = (player.position, player.movement);
 
-        if !self.mountain.is_valid_move(current_position, new_position) {
-            return Err(GameError::InvalidMove);
+        // Routes go around units and other players; whoever stands on the
+        // destination itself is dealt with by the collision rule below
+        let (_, cost) = self
+            .mountain
+            .find_path(current_position, new_position, |tile| {
+                tile.content.unit_id().is_some()
+                    || self.player_at(tile.position(), player_id).is_some()
+            })
+            .filter(|(path, _)| !path.is_empty())
+            .ok_or(GameError::InvalidMove)?;
+        if cost > movement {
+            return Err(GameError::NotEnoughMovement);
+        }
+        if self.holds_unit(new_position) {
+            return Err(GameError::TileOccupied);
         }
 
+        if let Some(occupant) = self.player_at(new_position, player_id) {
+            match self.rules.collision {
+                CollisionRule::Block => return Err(GameError::TileOccupied),
+                CollisionRule::Swap => self.relocate(occupant, current_position),
+                CollisionRule::Displace => {
+                    let free = self
+                        .mountain
+                        .get_neighbors(new_position.x, new_position.y, new_position.z)
+                        .into_iter()
+                        .find(|position| {
+                            !same_tile(*position, current_position) && self.is_free(*position)
+                        })
+                        .ok_or(GameError::TileOccupied)?;
+                    self.relocate(occupant, free);
+                }
+            }
+        }
         if let Some(player) = self.players.get_mut(&player_id) {
-            player.position = new_position;
-            Ok(())
+            player.movement -= cost;
+        }
+        self.relocate(player_id, new_position);
+        Ok(())
+    }
+
+    // Tiles move_player would accept for this player right now
+    pub fn reachable_tiles(&self, player_id: Uuid) -> Vec<Position> {
+        let Some(player) = self.players.get(&player_id) else {
+            return Vec::new();
+        };
+        self.mountain
+            .reachable(player.position, player.movement, |tile| {
+                tile.content.unit_id().is_some()
+                    || self.player_at(tile.position(), player_id).is_some()
+            })
+            .into_iter()
+            .map(|(position, _)| position)
+            .filter(|position| !self.holds_unit(*position))
+            .filter(|position| match self.player_at(*position, player_id) {
+                None => true,
+                Some(_) => match self.rules.collision {
+                    CollisionRule::Block => false,
+                    CollisionRule::Swap => true,
+                    CollisionRule::Displace => self
+                        .mountain
+                        .get_neighbors(position.x, position.y, position.z)
+                        .into_iter()
+                        .any(|free| !same_tile(free, player.position) && self.is_free(free)),
+                },
+            })
+            .collect()
+    }
+
+    // Forced movement from knockback or a collapsing tile. Skips the
+    // adjacency check a normal move has, but the tile still has to be free.
+    pub fn displace(&mut self, player_id: Uuid, to: Position) -> Result<(), GameError> {
+        if !self.players.contains_key(&player_id) {
+            return Err(GameError::PlayerNotFound);
+        }
+        if self.mountain.get_tile(to.x, to.y, to.z).is_none() {
+            return Err(GameError::InvalidMove);
+        }
+        if self.player_at(to, player_id).is_some() || self.holds_unit(to) {
+            return Err(GameError::TileOccupied);
+        }
+        self.relocate(player_id, to);
+        Ok(())
+    }
+
+    // The seated player standing on a tile, other than `except`
+    pub fn player_at(&self, position: Position, except: Uuid) -> Option<Uuid> {
+        self.seated_players()
+            .find(|player| player.id != except && same_tile(player.position, position))
+            .map(|player| player.id)
+    }
+
+    fn holds_unit(&self, position: Position) -> bool {
+        self.mountain
+            .get_tile(position.x, position.y, position.z)
+            .is_some_and(|tile| tile.content.unit_id().is_some())
+    }
+
+    fn is_free(&self, position: Position) -> bool {
+        self.player_at(position, Uuid::nil()).is_none()
+            && self
+                .mountain
+                .get_tile(position.x, position.y, position.z)
+                .is_some_and(|tile| tile.content == TileContent::Empty)
+    }
+
+    // Moves a player and keeps the tiles they leave and enter in step.
+    // Traps stay hidden under whoever walks onto them.
+    fn relocate(&mut self, player_id: Uuid, to: Position) {
+        let Some(from) = self.place(player_id, to) else {
+            return;
+        };
+
+        self.events.push(GameEvent::PlayerMoved {
+            player_id,
+            from,
+            to,
+        });
+        if to.level >= self.mountain.summit_level() {
+            self.events.push(GameEvent::SummitReached { player_id });
+        }
+
+        // Stepping down one level is a normal climb; anything further is a fall
+        let levels = from.level.saturating_sub(to.level);
+        if levels > 1 {
+            let damage = (levels - 1).saturating_mul(self.rules.falling_damage_per_level);
+            self.events.push(GameEvent::PlayerFell {
+                player_id,
+                levels,
+                damage,
+            });
+            let value = EffectValue {
+                base: damage,
+                scaling: None,
+            };
+            // The player is known to exist, so this can't fail
+            let _ = apply_damage(self, player_id, player_id, &value, true);
+        }
+    }
+
+    // Puts a player on a tile with no movement rules or events, returning
+    // where they were
+    fn place(&mut self, player_id: Uuid, to: Position) -> Option<Position> {
+        let player = self.players.get_mut(&player_id)?;
+        let from = player.position;
+        player.position = to;
+
+        if let Some(tile) = self.mountain.get_tile_mut(from.x, from.y, from.z) {
+            if tile.content == TileContent::Player(player_id) {
+                tile.content = TileContent::Empty;
+            }
+        }
+        if let Some(tile) = self.mountain.get_tile_mut(to.x, to.y, to.z) {
+            if matches!(tile.content, TileContent::Empty | TileContent::Player(_)) {
+                tile.content = TileContent::Player(player_id);
+            }
+        }
+        Some(from)
+    }
+
+    pub fn apply_action(&mut self, player_id: Uuid, action: &GameAction) -> Result<(), GameError> {
+        if matches!(self.pause, PauseState::Paused { .. }) {
+            return Err(GameError::GamePaused);
+        }
+        if !self.pending_scry.is_empty() && !matches!(action, GameAction::ResolveScry { .. }) {
+            return Err(GameError::ChoicePending);
+        }
+        match action {
+            GameAction::ResolveScry { top, bottom } => self.resolve_scry(player_id, top, bottom),
+            GameAction::PlayCard { card_id } => self.play_card(player_id, *card_id),
+            GameAction::Move { position } => {
+                if !self.holds_turn(player_id) {
+                    return Err(GameError::NotYourTurn);
+                }
+                let moves = self
+                    .players
+                    .get(&player_id)
+                    .ok_or(GameError::PlayerNotFound)?
+                    .moves_this_turn;
+                if self
+                    .rules
+                    .max_moves_per_turn
+                    .is_some_and(|max| moves >= max)
+                {
+                    return Err(GameError::ActionLimitReached);
+                }
+                self.move_player(player_id, *position)?;
+                if let Some(player) = self.players.get_mut(&player_id) {
+                    player.moves_this_turn += 1;
+                }
+                Ok(())
+            }
+            GameAction::EndTurn => self.end_turn(player_id),
+        }?;
+        self.timeouts.remove(&player_id);
+        debug_assert!(
+            self.validate_invariants().is_ok(),
+            "{:?}",
+            self.validate_invariants()
+        );
+        Ok(())
+    }
+
+    // Applies several actions as one play, e.g. a card, the answer to its
+    // scry and a move. Either all of them go through or the game is left
+    // exactly as it was and the first failure is returned.
+    pub fn apply_batch(
+        &mut self,
+        player_id: Uuid,
+        actions: &[GameAction],
+    ) -> Result<(), GameError> {
+        if actions.is_empty() {
+            return Err(GameError::EmptyBatch);
+        }
+        if actions.len() > MAX_BATCH_ACTIONS {
+            return Err(GameError::ActionLimitReached);
+        }
+        let before = self.clone();
+        for action in actions {
+            if let Err(e) = self.apply_action(player_id, action) {
+                *self = before;
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    // Stable fingerprint of everything the rules decide. The game id is left
+    // out so the same log replayed into a fresh game hashes the same.
+    pub fn state_hash(&self) -> u64 {
+        let players: Vec<_> = self
+            .turn_order
+            .iter()
+            .filter_map(|id| self.players.get(id))
+            .map(|player| {
+                serde_json::json!({
+                    "id": player.id,
+                    "health": player.health,
+                    "max_health": player.max_health,
+                    "mana": player.mana,
+                    "position": player.position,
+                    "hand": player.hand.iter().map(|card| card.id).collect::<Vec<_>>(),
+                    "deck": player.deck.cards.iter().map(|card| card.id).collect::<Vec<_>>(),
+                    "graveyard": player.graveyard.iter().map(|card| card.id).collect::<Vec<_>>(),
+                    "fatigue": player.fatigue,
+                    "power_boosts": player.power_boosts.as_slice(),
+                    "health_boosts": player.health_boosts.as_slice(),
+                    "active_effects": player.active_effects.as_slice(),
+                    "statuses": player.statuses.as_slice(),
+                    "max_health_modifiers": player.max_health_modifiers.as_slice(),
+                    "cost_modifiers": player.cost_modifiers.as_slice(),
+                    "shield": player.shield,
+                    "movement": player.movement,
+                    "extra_turns": player.extra_turns,
+                    "skipped_turns": player.skipped_turns,
+                    "mana_tokens": player.mana_tokens,
+                    "overload": player.overload,
+                    "locked_mana": player.locked_mana,
+                    "respawn_in": self.respawns.get(&player.id),
+                })
+            })
+            .collect();
+        let snapshot = serde_json::json!({
+            "turn_number": self.turn_number,
+            "active_player": self.active_player,
+            "players": players,
+            "events": self.events,
+            "pending_avalanche": self.pending_avalanche,
+            "pending_scry": self.pending_scry,
+            "expedition": self.expedition.as_ref().map(|expedition| expedition.card.id),
+        });
+
+        // FNV-1a, so the value doesn't depend on std's hasher
+        snapshot
+            .to_string()
+            .bytes()
+            .fold(0xcbf29ce484222325, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+            })
+    }
+
+    // Structural checks that must hold between actions
+    pub fn validate_invariants(&self) -> Result<(), InvariantViolation> {
+        if !self.players.contains_key(&self.active_player) {
+            return Err(InvariantViolation::UnknownActivePlayer(self.active_player));
+        }
+
+        let seated: HashSet<Uuid> = self.turn_order.iter().copied().collect();
+        if seated.len() != self.turn_order.len()
+            || seated.len() != self.players.len()
+            || !self.players.keys().all(|id| seated.contains(id))
+            || !seated.contains(&self.active_player)
+        {
+            return Err(InvariantViolation::TurnOrderMismatch);
+        }
+
+        let mut card_ids = HashSet::new();
+        for player in self.players.values() {
+            let position = player.position;
+            if self
+                .mountain
+                .get_tile(position.x, position.y, position.z)
+                .is_none()
+            {
+                return Err(InvariantViolation::PositionOffBoard {
+                    player_id: player.id,
+                    position,
+                });
+            }
+
+            if player.health > player.max_health() {
+                return Err(InvariantViolation::HealthAboveMax {
+                    player_id: player.id,
+                    health: player.health,
+                    max_health: player.max_health(),
+                });
+            }
+
+            for card in player
+                .hand
+                .iter()
+                .chain(player.deck.cards.iter())
+                .chain(player.graveyard.iter())
+            {
+                if !card_ids.insert(card.id) {
+                    return Err(InvariantViolation::DuplicateCardId(card.id));
+                }
+            }
+        }
+
+        for tile in self.mountain.tiles.iter() {
+            if let TileContent::Card(card) | TileContent::Trap(card) = &tile.content {
+                if !card_ids.insert(card.id) {
+                    return Err(InvariantViolation::DuplicateCardId(card.id));
+                }
+            }
+        }
+        if let Some(expedition) = &self.expedition {
+            if !card_ids.insert(expedition.card.id) {
+                return Err(InvariantViolation::DuplicateCardId(expedition.card.id));
+            }
+        }
+        Ok(())
+    }
+
+    // Puts `card` in the expedition zone, sending whatever held it to its
+    // owner's graveyard
+    fn start_expedition(&mut self, owner: Uuid, card: Card) {
+        self.destroy_expedition();
+        self.events.push(GameEvent::ExpeditionStarted {
+            player_id: owner,
+            card_id: card.id,
+            name: card.name.clone(),
+        });
+        self.expedition = Some(Expedition { card, owner });
+        self.refresh_field();
+    }
+
+    pub fn destroy_expedition(&mut self) -> Option<Uuid> {
+        let Expedition { card, owner } = self.expedition.take()?;
+        let card_id = card.id;
+        self.events.push(GameEvent::ExpeditionEnded {
+            player_id: owner,
+            card_id,
+        });
+        if let Some(player) = self.players.get_mut(&owner) {
+            player.graveyard.push(card);
+        }
+        self.refresh_field();
+        Some(card_id)
+    }
+
+    // Copies the expedition's rules onto every player so their derived
+    // stats and costs pick them up
+    fn refresh_field(&mut self) {
+        let rules = self
+            .expedition
+            .as_ref()
+            .map(Expedition::rules)
+            .unwrap_or_default();
+        for player in self.players.values_mut() {
+            player.field_rules = rules.clone();
+            player.invalidate_stats();
+        }
+    }
+
+    fn resolve_scry(
+        &mut self,
+        player_id: Uuid,
+        top: &[Uuid],
+        bottom: &[Uuid],
+    ) -> Result<(), GameError> {
+        let choice = self
+            .pending_scry
+            .first()
+            .ok_or(GameError::NoChoicePending)?;
+        if choice.chooser != player_id {
+            return Err(GameError::NotYourTurn);
+        }
+        let mut answered: Vec<Uuid> = top.iter().chain(bottom).copied().collect();
+        let mut looked_at = choice.cards.clone();
+        answered.sort_unstable();
+        looked_at.sort_unstable();
+        if answered != looked_at {
+            return Err(GameError::InvalidTarget);
+        }
+
+        let choice = self.pending_scry.remove(0);
+        let owner = self
+            .players
+            .get_mut(&choice.deck_owner)
+            .ok_or(GameError::PlayerNotFound)?;
+        // Cards drawn or milled since they were looked at are skipped
+        let mut take = |ids: &[Uuid]| -> Vec<Card> {
+            ids.iter()
+                .filter_map(|id| {
+                    let index = owner.deck.cards.iter().position(|card| card.id == *id)?;
+                    Some(owner.deck.cards.remove(index))
+                })
+                .collect()
+        };
+        let (on_top, on_bottom) = (take(top), take(bottom));
+        let (top_count, bottom_count) = (on_top.len() as u32, on_bottom.len() as u32);
+        owner.deck.cards.splice(0..0, on_top);
+        owner.deck.cards.extend(on_bottom);
+        self.events.push(GameEvent::ScryResolved {
+            player_id,
+            deck_owner: choice.deck_owner,
+            top: top_count,
+            bottom: bottom_count,
+        });
+        Ok(())
+    }
+
+    // Mana refills at the start of each turn to the round number, capped at
+    // MAX_MANA, so both players gain their first crystal on their first turn.
+    // With shared team turns the whole incoming team is refilled.
+    pub fn end_turn(&mut self, player_id: Uuid) -> Result<(), GameError> {
+        if !self.holds_turn(player_id) {
+            return Err(GameError::NotYourTurn);
+        }
+        // A scry still open when the turn is forced over (a timeout, say)
+        // leaves the cards where they were
+        for choice in std::mem::take(&mut self.pending_scry) {
+            self.events.push(GameEvent::ScryResolved {
+                player_id: choice.chooser,
+                deck_owner: choice.deck_owner,
+                top: choice.cards.len() as u32,
+                bottom: 0,
+            });
+        }
+
+        let index = self
+            .turn_order
+            .iter()
+            .position(|id| *id == self.active_player)
+            .ok_or(GameError::PlayerNotFound)?;
+        let next = self.next_turn_holder(index);
+
+        self.turn_number += 1;
+        self.active_player = next;
+        self.turn_started_at = self.clock.now();
+
+        let side: Vec<Uuid> = match self.rules.team_turns {
+            TeamTurns::Shared => self
+                .turn_order
+                .iter()
+                .copied()
+                .filter(|id| self.are_allies(next, *id))
+                .collect(),
+            TeamTurns::Alternating => vec![next],
+        };
+        let round = self
+            .turn_number
+            .div_ceil((self.turn_order.len() / side.len()) as u32);
+        for id in &side {
+            let player = self.players.get_mut(id).ok_or(GameError::PlayerNotFound)?;
+            player.update_turn();
+            let refill = round
+                .min(MAX_MANA)
+                .saturating_add(std::mem::take(&mut player.mana_tokens));
+            player.locked_mana = std::mem::take(&mut player.overload).min(refill);
+            player.mana = refill - player.locked_mana;
+        }
+
+        self.events.push(GameEvent::TurnStarted {
+            player_id: next,
+            turn_number: self.turn_number,
+        });
+        for id in &side {
+            self.visit_camp(*id);
+        }
+        self.tick_hazards()?;
+        for id in side {
+            self.tick_statuses(id)?;
+        }
+        Ok(())
+    }
+
+    // Brings down the avalanche announced last turn, then maybe announces
+    // the next one
+    fn tick_hazards(&mut self) -> Result<(), GameError> {
+        let Some(rules) = self.rules.avalanches else {
+            return Ok(());
+        };
+
+        if let Some(path) = self.pending_avalanche.take() {
+            self.events
+                .push(GameEvent::AvalancheStruck { path: path.clone() });
+            let caught: Vec<Uuid> = self
+                .seated_players()
+                .filter(|player| path.iter().any(|tile| same_tile(*tile, player.position)))
+                .map(|player| player.id)
+                .collect();
+            for player_id in caught {
+                let value = EffectValue {
+                    base: rules.damage,
+                    scaling: None,
+                };
+                apply_damage(self, Uuid::nil(), player_id, &value, false)?;
+                let position = self.players[&player_id].position;
+                if let Some(below) = self.downhill_escape(position, &path) {
+                    self.relocate(player_id, below);
+                }
+            }
+            for tile in &path {
+                let Some(below) = self.downhill_escape(*tile, &path) else {
+                    continue;
+                };
+                let index = self.mountain.tile_index(tile.x, tile.y, tile.z);
+                // Large units are too big to be carried off
+                let Some(index) =
+                    index.filter(|index| match &self.mountain.tiles[*index].content {
+                        TileContent::Card(card) => self.mountain.unit_footprint(card.id).len() == 1,
+                        _ => false,
+                    })
+                else {
+                    continue;
+                };
+                let card =
+                    std::mem::replace(&mut self.mountain.tiles[index].content, TileContent::Empty);
+                if let Some(target) = self.mountain.get_tile_mut(below.x, below.y, below.z) {
+                    target.content = card;
+                }
+            }
+        }
+
+        if rules.interval > 0 && self.turn_number.is_multiple_of(rules.interval) {
+            let path = self.avalanche_path();
+            self.events
+                .push(GameEvent::AvalancheWarning { path: path.clone() });
+            self.pending_avalanche = Some(path);
+        }
+        Ok(())
+    }
+
+    // Starts on a random summit tile and runs straight down, picking a
+    // random lower neighbor at each step
+    fn avalanche_path(&mut self) -> Vec<Position> {
+        let summit = self.mountain.summit_level();
+        let tops: Vec<usize> = (0..self.mountain.tiles.len())
+            .filter(|index| self.mountain.tiles[*index].level == summit)
+            .collect();
+        let mut current = tops.choose(self.rng.as_mut()).copied();
+        let mut path = Vec::new();
+        while let Some(index) = current {
+            let tile = &self.mountain.tiles[index];
+            path.push(tile.position());
+            let lower: SmallVec<[usize; 8]> = self
+                .mountain
+                .neighbor_indices(index)
+                .iter()
+                .copied()
+                .filter(|neighbor| self.mountain.tiles[*neighbor].level < tile.level)
+                .collect();
+            current = lower.choose(self.rng.as_mut()).copied();
+        }
+        path
+    }
+
+    // The lowest free neighbor off the avalanche path
+    fn downhill_escape(&self, position: Position, path: &[Position]) -> Option<Position> {
+        self.mountain
+            .get_neighbors(position.x, position.y, position.z)
+            .into_iter()
+            .filter(|neighbor| {
+                neighbor.level < position.level
+                    && !path.iter().any(|tile| same_tile(*tile, *neighbor))
+                    && self.is_free(*neighbor)
+            })
+            .min_by_key(|neighbor| neighbor.level)
+    }
+
+    // Whoever plays after the seat at `index`. An owed extra turn comes
+    // first, up to the consecutive-turn cap; seats with skips queued are
+    // passed over, each using up one skip.
+    fn next_turn_holder(&mut self, index: usize) -> Uuid {
+        let current = self.turn_order[index];
+        if let Some(player) = self.players.get_mut(&current) {
+            if player.extra_turns > 0 && self.consecutive_turns < self.rules.max_consecutive_turns {
+                player.extra_turns -= 1;
+                self.consecutive_turns += 1;
+                return current;
+            }
+        }
+
+        self.consecutive_turns = 1;
+        let seats = self.turn_order.len();
+        for step in 1..=seats {
+            let candidate = self.turn_order[(index + step) % seats];
+            let Some(player) = self.players.get_mut(&candidate) else {
+                continue;
+            };
+            // Knocked-out teammates hold no seat of their own
+            if player.health == 0 && self.rules.respawn_after.is_none() && step < seats {
+                continue;
+            }
+            // Running out of seats means everyone was skipping; the last
+            // candidate plays anyway rather than the turn going nowhere
+            if player.skipped_turns == 0 || step == seats {
+                return candidate;
+            }
+            player.skipped_turns -= 1;
+            self.events.push(GameEvent::TurnSkipped {
+                player_id: candidate,
+            });
+        }
+        self.turn_order[(index + 1) % seats]
+    }
+
+    pub fn mark_disconnected(&mut self, player_id: Uuid) {
+        let now = self.clock.now();
+        self.disconnected_since.entry(player_id).or_insert(now);
+    }
+
+    pub fn mark_reconnected(&mut self, player_id: Uuid) {
+        self.disconnected_since.remove(&player_id);
+    }
+
+    // Called by the server on a timer. Passes the turn of a player who ran
+    // out the clock, and concedes for anyone who has been idle or gone too
+    // long.
+    pub fn enforce_timers(&mut self) -> Result<(), GameError> {
+        let now = self.clock.now();
+        if let PauseState::Paused { since } = self.pause {
+            let limit = self.rules.max_pause.unwrap_or_default();
+            if now.duration_since(since).unwrap_or_default() < limit {
+                return Ok(());
+            }
+            self.resume();
+        }
+
+        let gone: Vec<Uuid> = self
+            .turn_order
+            .iter()
+            .copied()
+            .filter(|id| {
+                self.disconnected_since.get(id).is_some_and(|since| {
+                    now.duration_since(*since).unwrap_or_default() >= self.rules.reconnect_window
+                })
+            })
+            .collect();
+        for player_id in gone {
+            self.disconnected_since.remove(&player_id);
+            self.concede(player_id, ConcedeReason::Disconnected)?;
+        }
+
+        let Some(limit) = self.rules.turn_time_limit else {
+            return Ok(());
+        };
+        let player_id = self.active_player;
+        let allowance = self
+            .latency_allowance
+            .get(&player_id)
+            .copied()
+            .unwrap_or_default()
+            .min(self.rules.max_latency_allowance);
+        if self.winner().is_some() || self.turn_elapsed() < limit.saturating_add(allowance) {
+            return Ok(());
+        }
+        let timeouts = self.timeouts.entry(player_id).or_default();
+        *timeouts += 1;
+        let timeouts = *timeouts;
+        self.events.push(GameEvent::TurnTimedOut {
+            player_id,
+            consecutive: timeouts,
+        });
+        if timeouts >= self.rules.afk_turns_before_concede {
+            self.concede(player_id, ConcedeReason::Inactive)
         } else {
-            Err(GameError::PlayerNotFound)
+            self.end_turn(player_id)
+        }
+    }
+
+    // Takes a player out of the game. If it was their turn, play moves on.
+    pub fn concede(&mut self, player_id: Uuid, reason: ConcedeReason) -> Result<(), GameError> {
+        let player = self
+            .players
+            .get_mut(&player_id)
+            .ok_or(GameError::PlayerNotFound)?;
+        player.health = 0;
+        self.timeouts.remove(&player_id);
+        self.events
+            .push(GameEvent::PlayerConceded { player_id, reason });
+        if self.active_player == player_id && self.winner().is_none() {
+            self.end_turn(player_id)?;
+        }
+        Ok(())
+    }
+
+    // Turn-start camp handling: downed players count down to a respawn,
+    // everyone else standing on a camp gets a little health back
+    fn visit_camp(&mut self, player_id: Uuid) {
+        let Some(player) = self.players.get(&player_id) else {
+            return;
+        };
+        let position = player.position;
+
+        if player.health == 0 {
+            let Some(delay) = self.rules.respawn_after else {
+                return;
+            };
+            let remaining = self.respawns.entry(player_id).or_insert(delay);
+            *remaining = remaining.saturating_sub(1);
+            if *remaining > 0 {
+                return;
+            }
+            self.respawns.remove(&player_id);
+
+            let camp = self
+                .mountain
+                .camps
+                .iter()
+                .copied()
+                .filter(|camp| {
+                    self.player_at(*camp, player_id).is_none() && !self.holds_unit(*camp)
+                })
+                .min_by_key(|camp| self.mountain.calculate_distance(position, *camp))
+                .unwrap_or(position);
+            self.place(player_id, camp);
+            if let Some(player) = self.players.get_mut(&player_id) {
+                player.health = player.max_health();
+            }
+            self.events.push(GameEvent::PlayerRespawned {
+                player_id,
+                position: camp,
+            });
+        } else if self.mountain.is_camp(position) {
+            let player = self.players.get_mut(&player_id).unwrap();
+            let before = player.health;
+            player.health = before
+                .saturating_add(self.rules.camp_heal)
+                .min(player.max_health());
+            let amount = player.health.saturating_sub(before);
+            if amount > 0 {
+                self.events
+                    .push(GameEvent::CampRested { player_id, amount });
+            }
         }
     }
+
+    // Periodic statuses fire once at the start of their owner's turn
+    fn tick_statuses(&mut self, player_id: Uuid) -> Result<(), GameError> {
+        let ticks: SmallVec<[(Uuid, u32); 4]> = self.players[&player_id]
+            .statuses
+            .iter()
+            .filter_map(|status| match status.kind {
+                StatusKind::Poison { damage } => Some((status.source.unwrap_or(player_id), damage)),
+                _ => None,
+            })
+            .collect();
+
+        for (source, damage) in ticks {
+            let value = EffectValue {
+                base: damage,
+                scaling: None,
+            };
+            apply_damage(self, source, player_id, &value, false)?;
+        }
+        Ok(())
+    }
+
+    pub fn play_card(&mut self, player_id: Uuid, card_id: Uuid) -> Result<(), GameError> {
+        if !self.holds_turn(player_id) {
+            return Err(GameError::NotYourTurn);
+        }
+
+        let player = self
+            .players
+            .get_mut(&player_id)
+            .ok_or(GameError::PlayerNotFound)?;
+        let index = player
+            .hand
+            .iter()
+            .position(|card| card.id == card_id)
+            .ok_or(GameError::CardNotInHand)?;
+        if self
+            .rules
+            .max_cards_per_turn
+            .is_some_and(|max| player.cards_played_this_turn >= max)
+        {
+            return Err(GameError::ActionLimitReached);
+        }
+        let cost = player.effective_cost(&player.hand[index]);
+        let borrowable: u32 = player.hand[index]
+            .effects
+            .iter()
+            .map(Effect::overload)
+            .sum();
+        let borrowed = cost.saturating_sub(player.mana);
+        if borrowed > borrowable {
+            return Err(GameError::InsufficientMana);
+        }
+        let effects = player.hand[index].effects.clone();
+        for effect in &effects {
+            effect.check_target(self, player_id)?;
+        }
+
+        let player = self
+            .players
+            .get_mut(&player_id)
+            .ok_or(GameError::PlayerNotFound)?;
+        let card = player.remove_from_hand(index);
+        player.mana -= cost - borrowed;
+        player.overload += borrowed;
+        player.mana_spent_this_turn += cost;
+        player.cards_played_this_turn += 1;
+        // Acting gives a stealthed player away
+        let revealed = player.remove_statuses(|kind| *kind == StatusKind::Stealth) > 0;
+
+        if revealed {
+            self.events.push(GameEvent::StealthBroken { player_id });
+        }
+        self.events.push(GameEvent::CardPlayed {
+            player_id,
+            card_id: card.id,
+            name: card.name.clone(),
+        });
+
+        if card.card_type == CardType::Expedition {
+            self.start_expedition(player_id, card);
+        } else {
+            player.graveyard.push(card);
+        }
+
+        let pending = effects
+            .into_iter()
+            .zip(0..)
+            .map(|(effect, sequence)| PendingEffect {
+                effect,
+                controller: player_id,
+                sequence,
+            })
+            .collect();
+        self.resolving_card = Some(card_id);
+        self.annotate(None, None);
+        let resolved = resolve_simultaneous(self, pending);
+        self.resolving_card = None;
+        resolved
+    }
 }
 
 //TESTS
@@ -58,20 +1544,23 @@ impl GameState {
 #[cfg(test)]
 mod game_state_tests {
     use super::*;
+    use crate::effects::Status;
+    use crate::models::{Shared, BASE_SPEED};
+    use crate::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
 
     #[test]
     fn test_new_game_state() {
         let player1 = Player::new(
             "Player 1".to_string(),
             crate::Deck {
-                cards: vec![],
+                cards: vec![].into(),
                 owner_id: Uuid::new_v4(),
             },
         );
         let player2 = Player::new(
             "Player 2".to_string(),
             crate::Deck {
-                cards: vec![],
+                cards: vec![].into(),
                 owner_id: Uuid::new_v4(),
             },
         );
@@ -81,4 +1570,849 @@ mod game_state_tests {
         assert_eq!(game_state.active_player, player1.id);
         assert_eq!(game_state.turn_number, 1);
     }
+
+    #[test]
+    fn test_end_turn_rotates_and_refills_mana() {
+        let deck = || crate::Deck {
+            cards: vec![].into(),
+            owner_id: Uuid::new_v4(),
+        };
+        let player1 = Player::new("Player 1".to_string(), deck());
+        let player2 = Player::new("Player 2".to_string(), deck());
+        let (p1, p2) = (player1.id, player2.id);
+        let mut game_state = GameState::new(player1, player2);
+
+        assert!(matches!(
+            game_state.apply_action(p2, &GameAction::EndTurn),
+            Err(GameError::NotYourTurn)
+        ));
+
+        game_state.apply_action(p1, &GameAction::EndTurn).unwrap();
+        assert_eq!(game_state.active_player, p2);
+        assert_eq!(game_state.players[&p2].mana, 1);
+
+        game_state.end_turn(p2).unwrap();
+        assert_eq!(game_state.active_player, p1);
+        assert_eq!(game_state.turn_number, 3);
+        assert_eq!(game_state.players[&p1].mana, 2);
+    }
+
+    #[test]
+    fn test_scry_waits_for_the_chooser_and_stays_hidden() {
+        let deck: Vec<Card> = ["Rope", "Piton", "Flare", "Drift"]
+            .map(|name| CardBuilder::new(name).build())
+            .to_vec();
+        let peek = CardBuilder::new("Spyglass")
+            .cost(0)
+            .effect(Effect::Scry(crate::effects::ScryEffect {
+                target: crate::effects::EffectTarget::Self_,
+                count: 3,
+                priority: 0,
+            }))
+            .build();
+        let mut game_state = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Seer")
+                    .hand(vec![peek.clone()])
+                    .deck(deck.clone())
+                    .build(),
+            )
+            .build();
+        let (seer, rival) = (game_state.turn_order[0], game_state.turn_order[1]);
+        game_state
+            .apply_action(seer, &GameAction::PlayCard { card_id: peek.id })
+            .unwrap();
+
+        assert!(matches!(
+            game_state.apply_action(seer, &GameAction::EndTurn),
+            Err(GameError::ChoicePending)
+        ));
+        let shown = game_state.view_for(seer).scry.unwrap();
+        assert_eq!(shown.cards, deck[..3]);
+        let hidden = game_state.view_for(rival);
+        assert_eq!((hidden.choosing, hidden.scry), (Some(seer), None));
+
+        let (top, bottom) = (vec![deck[2].id, deck[0].id], vec![deck[1].id]);
+        assert!(matches!(
+            game_state.apply_action(
+                rival,
+                &GameAction::ResolveScry {
+                    top: top.clone(),
+                    bottom: bottom.clone()
+                }
+            ),
+            Err(GameError::NotYourTurn)
+        ));
+        assert!(matches!(
+            game_state.apply_action(
+                seer,
+                &GameAction::ResolveScry {
+                    top: top.clone(),
+                    bottom: vec![]
+                }
+            ),
+            Err(GameError::InvalidTarget)
+        ));
+        game_state
+            .apply_action(seer, &GameAction::ResolveScry { top, bottom })
+            .unwrap();
+        let order: Vec<Uuid> = game_state.players[&seer]
+            .deck
+            .cards
+            .iter()
+            .map(|card| card.id)
+            .collect();
+        assert_eq!(order, [deck[2].id, deck[0].id, deck[3].id, deck[1].id]);
+        assert!(matches!(
+            game_state.events.last(),
+            Some(GameEvent::ScryResolved {
+                top: 2,
+                bottom: 1,
+                ..
+            })
+        ));
+        game_state.apply_action(seer, &GameAction::EndTurn).unwrap();
+    }
+
+    #[test]
+    fn test_overload_borrows_from_next_turn() {
+        let deck = || crate::Deck {
+            cards: vec![].into(),
+            owner_id: Uuid::new_v4(),
+        };
+        let surge = CardBuilder::new("Storm Surge")
+            .cost(3)
+            .effect(Effect::Overload(crate::effects::OverloadEffect {
+                crystals: 2,
+            }))
+            .build();
+        let greedy = CardBuilder::new("Avalanche").cost(4).build();
+        let mut player1 = Player::new("Player 1".to_string(), deck());
+        player1.hand = vec![surge.clone(), greedy.clone()];
+        let player2 = Player::new("Player 2".to_string(), deck());
+        let (p1, p2) = (player1.id, player2.id);
+        let mut game_state = GameState::new(player1, player2);
+        game_state.players.get_mut(&p1).unwrap().mana = 1;
+
+        assert!(matches!(
+            game_state.play_card(p1, greedy.id),
+            Err(GameError::InsufficientMana)
+        ));
+        game_state.play_card(p1, surge.id).unwrap();
+        let player = &game_state.players[&p1];
+        assert_eq!((player.mana, player.overload), (0, 2));
+        assert_eq!(game_state.view_for(p1).own_seat().unwrap().overload, 2);
+
+        game_state.end_turn(p1).unwrap();
+        game_state.end_turn(p2).unwrap();
+        let player = &game_state.players[&p1];
+        // Two crystals this round, both locked
+        assert_eq!(
+            (player.mana, player.locked_mana, player.overload),
+            (0, 2, 0)
+        );
+        game_state.end_turn(p1).unwrap();
+        game_state.end_turn(p2).unwrap();
+        assert_eq!(game_state.players[&p1].mana, 3);
+        assert_eq!(game_state.players[&p1].locked_mana, 0);
+    }
+
+    #[test]
+    fn test_expedition_holds_shared_zone_until_replaced() {
+        let base_camp = CardBuilder::new("Base Camp")
+            .cost(0)
+            .card_type(CardType::Expedition)
+            .effect(Effect::Field(FieldRule::Movement(1)))
+            .build();
+        let thin_air = CardBuilder::new("Thin Air")
+            .cost(0)
+            .card_type(CardType::Expedition)
+            .effect(Effect::Field(FieldRule::Cost(1)))
+            .build();
+        let rockfall = CardBuilder::new("Rockfall")
+            .effect(Effect::DestroyExpedition(
+                crate::effects::DestroyExpeditionEffect { priority: 0 },
+            ))
+            .build();
+        let mut game_state = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Alice")
+                    .hand(vec![base_camp.clone()])
+                    .build(),
+            )
+            .player(
+                PlayerBuilder::new("Bob")
+                    .hand(vec![thin_air.clone(), rockfall.clone()])
+                    .build(),
+            )
+            .build();
+        let (alice, bob) = (game_state.turn_order[0], game_state.turn_order[1]);
+
+        game_state.play_card(alice, base_camp.id).unwrap();
+        assert_eq!(game_state.expedition.as_ref().unwrap().owner, alice);
+        assert!(game_state.players[&alice].graveyard.is_empty());
+        assert_eq!(
+            game_state.players[&bob].derived_stats().speed,
+            BASE_SPEED + 1
+        );
+        assert!(game_state.view_for(bob).expedition.is_some());
+        game_state.validate_invariants().unwrap();
+
+        game_state.end_turn(alice).unwrap();
+        game_state.players.get_mut(&bob).unwrap().mana = 1;
+        game_state.play_card(bob, thin_air.id).unwrap();
+        assert_eq!(game_state.players[&alice].graveyard[0].id, base_camp.id);
+        assert_eq!(game_state.players[&bob].derived_stats().speed, BASE_SPEED);
+        // Rockfall now costs 2
+        assert!(matches!(
+            game_state.play_card(bob, rockfall.id),
+            Err(GameError::InsufficientMana)
+        ));
+        game_state.players.get_mut(&bob).unwrap().mana = 2;
+        game_state.play_card(bob, rockfall.id).unwrap();
+        assert!(game_state.expedition.is_none());
+        assert_eq!(game_state.players[&bob].effective_cost(&thin_air), 0);
+        assert!(game_state.events.contains(&GameEvent::ExpeditionEnded {
+            player_id: bob,
+            card_id: thin_air.id,
+        }));
+    }
+
+    #[test]
+    fn test_snapshot_shares_until_written() {
+        let deck = || crate::Deck {
+            cards: vec![].into(),
+            owner_id: Uuid::new_v4(),
+        };
+        let player1 = Player::new("Player 1".to_string(), deck());
+        let p1 = player1.id;
+        let mut game_state = GameState::new(player1, Player::new("Player 2".to_string(), deck()));
+
+        let snapshot = game_state.clone();
+        assert!(Shared::ptr_eq(
+            &game_state.mountain.tiles,
+            &snapshot.mountain.tiles
+        ));
+        assert!(Shared::ptr_eq(
+            &game_state.players[&p1].deck.cards,
+            &snapshot.players[&p1].deck.cards
+        ));
+
+        game_state.mountain.get_tile_mut(0, 0, 0).unwrap().content = TileContent::Player(p1);
+
+        assert!(!Shared::ptr_eq(
+            &game_state.mountain.tiles,
+            &snapshot.mountain.tiles
+        ));
+        assert_eq!(
+            snapshot.mountain.get_tile(0, 0, 0).unwrap().content,
+            TileContent::Empty
+        );
+        assert!(Shared::ptr_eq(
+            &game_state.players[&p1].deck.cards,
+            &snapshot.players[&p1].deck.cards
+        ));
+    }
+
+    #[test]
+    fn test_validate_invariants() {
+        let card = CardBuilder::new("Rope").build();
+        let mut game_state = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Player 1")
+                    .hand(vec![card.clone()])
+                    .build(),
+            )
+            .build();
+        assert!(game_state.validate_invariants().is_ok());
+
+        let other = game_state.turn_order[1];
+        game_state
+            .players
+            .get_mut(&other)
+            .unwrap()
+            .add_to_hand(card.clone());
+        assert!(matches!(
+            game_state.validate_invariants(),
+            Err(InvariantViolation::DuplicateCardId(id)) if id == card.id
+        ));
+
+        let player = game_state.players.get_mut(&other).unwrap();
+        player.remove_from_hand(0);
+        player.health = 40;
+        assert!(matches!(
+            game_state.validate_invariants(),
+            Err(InvariantViolation::HealthAboveMax { .. })
+        ));
+
+        let player = game_state.players.get_mut(&other).unwrap();
+        player.health = 30;
+        player.position = Position {
+            x: 99,
+            y: 0,
+            z: 0,
+            level: 0,
+        };
+        assert!(matches!(
+            game_state.validate_invariants(),
+            Err(InvariantViolation::PositionOffBoard { .. })
+        ));
+    }
+
+    #[test]
+    fn test_seeded_random_targeting_and_turn_timer() {
+        use crate::effects::{DamageEffect, Effect, EffectTarget, EffectValue};
+        use crate::runtime::ManualClock;
+
+        let bolt = Effect::Damage(DamageEffect {
+            value: EffectValue {
+                base: 1,
+                scaling: None,
+            },
+            target: EffectTarget::Random(1),
+            penetrating: false,
+            ranged: false,
+            priority: 0,
+        });
+        let struck = |seed| {
+            let mut game_state = GameStateBuilder::new()
+                .player(PlayerBuilder::new("A").id(Uuid::from_u128(1)).build())
+                .player(PlayerBuilder::new("B").id(Uuid::from_u128(2)).build())
+                .seed(seed)
+                .build();
+            let source = game_state.active_player;
+            for _ in 0..8 {
+                bolt.apply(&mut game_state, source).unwrap();
+            }
+            game_state.events
+        };
+        assert_eq!(struck(3), struck(3));
+
+        let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+        let mut game_state = GameStateBuilder::new().clock(clock.clone()).build();
+        clock.advance(Duration::from_secs(45));
+        assert_eq!(game_state.turn_elapsed(), Duration::from_secs(45));
+
+        let active = game_state.active_player;
+        game_state.end_turn(active).unwrap();
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(game_state.turn_elapsed(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_defeat_removes_expiring_boosts() {
+        use crate::effects::{
+            BoostEffect, BoostType, DamageEffect, Duration, Effect, EffectTarget, EffectValue,
+            SourceRemoval,
+        };
+
+        let mut game_state = GameStateBuilder::new().build();
+        let (first, second) = (game_state.turn_order[0], game_state.turn_order[1]);
+        let blessing = |on_source_removed| {
+            Effect::Boost(BoostEffect {
+                value: EffectValue {
+                    base: 10,
+                    scaling: None,
+                },
+                target: EffectTarget::Specific(second),
+                stat: BoostType::Health,
+                duration: Duration::Permanent,
+                on_source_removed,
+                priority: 0,
+            })
+        };
+        blessing(SourceRemoval::Expire)
+            .apply(&mut game_state, first)
+            .unwrap();
+        blessing(SourceRemoval::Persist)
+            .apply(&mut game_state, first)
+            .unwrap();
+        game_state.players.get_mut(&second).unwrap().health = 50;
+
+        game_state.players.get_mut(&first).unwrap().health = 1;
+        game_state.remove_source(Uuid::new_v4());
+        assert_eq!(game_state.players[&second].max_health(), 50);
+
+        let finisher = Effect::Damage(DamageEffect {
+            value: EffectValue {
+                base: 1,
+                scaling: None,
+            },
+            target: EffectTarget::Specific(first),
+            penetrating: true,
+            ranged: false,
+            priority: 0,
+        });
+        finisher.apply(&mut game_state, second).unwrap();
+
+        let survivor = &game_state.players[&second];
+        assert_eq!((survivor.max_health(), survivor.health), (40, 40));
+        assert_eq!(
+            game_state.events.last(),
+            Some(&GameEvent::BoostsExpired {
+                player_id: second,
+                source: first
+            })
+        );
+    }
+
+    #[test]
+    fn test_moving_onto_occupied_tile() {
+        let ledge = Position {
+            x: 1,
+            y: 0,
+            z: 1,
+            level: 1,
+        };
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Climber").build())
+            .player(PlayerBuilder::new("Blocker").position(ledge).build())
+            .build();
+        let (climber, blocker) = (game_state.turn_order[0], game_state.turn_order[1]);
+        let start = game_state.players[&climber].position;
+
+        assert!(matches!(
+            game_state.move_player(climber, ledge),
+            Err(GameError::TileOccupied)
+        ));
+
+        game_state.rules.collision = CollisionRule::Swap;
+        game_state.move_player(climber, ledge).unwrap();
+        assert_eq!(game_state.players[&climber].position, ledge);
+        assert_eq!(game_state.players[&blocker].position, start);
+        assert_eq!(
+            game_state.mountain.get_tile(1, 0, 1).unwrap().content,
+            TileContent::Player(climber)
+        );
+        assert_eq!(
+            game_state.mountain.get_tile(0, 0, 0).unwrap().content,
+            TileContent::Player(blocker)
+        );
+    }
+
+    #[test]
+    fn test_high_ground_damage() {
+        let ledge = Position {
+            x: 2,
+            y: 0,
+            z: 2,
+            level: 2,
+        };
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Above").position(ledge).build())
+            .build()
+            .with_rules(GameRules {
+                high_ground: HighGroundRules {
+                    bonus_per_level: 2,
+                    uphill_penalty_per_level: 3,
+                    max_levels: 1,
+                },
+                ..GameRules::default()
+            });
+        let (above, below) = (game_state.turn_order[0], game_state.turn_order[1]);
+
+        assert_eq!(game_state.elevation_adjusted(above, below, 5), 7);
+        assert_eq!(game_state.elevation_adjusted(below, above, 5), 2);
+        assert_eq!(game_state.elevation_adjusted(below, above, 2), 1);
+
+        game_state.rules = GameRules::default();
+        assert_eq!(game_state.elevation_adjusted(above, below, 5), 5);
+    }
+
+    #[test]
+    fn test_falling_damage() {
+        let ledge = Position {
+            x: 3,
+            y: 0,
+            z: 3,
+            level: 3,
+        };
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Faller").position(ledge).build())
+            .build();
+        let player_id = game_state.turn_order[0];
+        let below = game_state.mountain.get_tile(1, 0, 1).unwrap().position();
+
+        game_state.displace(player_id, below).unwrap();
+
+        assert_eq!(game_state.players[&player_id].position, below);
+        assert_eq!(game_state.players[&player_id].health, 27);
+        assert!(game_state.events.contains(&GameEvent::PlayerFell {
+            player_id,
+            levels: 2,
+            damage: 3,
+        }));
+    }
+
+    #[test]
+    fn test_camp_heals_and_respawns() {
+        let mut game_state = GameStateBuilder::new().build();
+        game_state.rules.respawn_after = Some(2);
+        let (first, second) = (game_state.turn_order[0], game_state.turn_order[1]);
+        let camp = game_state.mountain.camps[0];
+
+        let player = game_state.players.get_mut(&second).unwrap();
+        player.health = 20;
+        player.position = camp;
+        game_state.end_turn(first).unwrap();
+        assert_eq!(game_state.players[&second].health, 22);
+        game_state.players.get_mut(&second).unwrap().position = Position::default();
+
+        game_state.players.get_mut(&first).unwrap().health = 0;
+        game_state.end_turn(second).unwrap();
+        assert_eq!(game_state.players[&first].health, 0);
+        assert!(game_state.winner().is_none());
+
+        game_state.end_turn(first).unwrap();
+        game_state.end_turn(second).unwrap();
+        assert_eq!(game_state.players[&first].health, 30);
+        assert!(game_state.events.contains(&GameEvent::PlayerRespawned {
+            player_id: first,
+            position: game_state.mountain.camps[0],
+        }));
+    }
+
+    #[test]
+    fn test_avalanche_is_telegraphed_then_strikes() {
+        let mut game_state = GameStateBuilder::new().seed(7).build();
+        game_state.rules.avalanches = Some(AvalancheRules {
+            interval: 2,
+            damage: 4,
+        });
+        let (first, second) = (game_state.turn_order[0], game_state.turn_order[1]);
+
+        game_state.end_turn(first).unwrap();
+        let path = game_state.pending_avalanche.clone().unwrap();
+        assert_eq!(path[0].level, game_state.mountain.summit_level());
+        assert_eq!(path.last().unwrap().level, 0);
+
+        game_state.players.get_mut(&first).unwrap().position = path[1];
+        game_state.end_turn(second).unwrap();
+
+        let player = &game_state.players[&first];
+        assert_eq!(player.health, 26);
+        assert!(player.position.level < path[1].level);
+        assert!(game_state
+            .events
+            .contains(&GameEvent::AvalancheStruck { path }));
+    }
+
+    #[test]
+    fn test_movement_points_and_pathing() {
+        let mut game_state = GameStateBuilder::new().build();
+        let (first, second) = (game_state.turn_order[0], game_state.turn_order[1]);
+        let at = |game_state: &GameState, x, y, z| {
+            game_state.mountain.get_tile(x, y, z).unwrap().position()
+        };
+
+        // Two levels up costs four points, more than a turn's worth
+        let high = at(&game_state, 2, 1, 1);
+        assert!(matches!(
+            game_state.move_player(first, high),
+            Err(GameError::NotEnoughMovement)
+        ));
+
+        let ledge = at(&game_state, 1, 0, 1);
+        game_state.move_player(first, ledge).unwrap();
+        assert_eq!(game_state.players[&first].movement, 0);
+
+        game_state.end_turn(first).unwrap();
+        game_state
+            .players
+            .get_mut(&second)
+            .unwrap()
+            .add_status(Status {
+                kind: StatusKind::Hasted { movement: 2 },
+                duration: crate::effects::Duration::Temporary(2),
+                source: None,
+            });
+        game_state.end_turn(second).unwrap();
+        game_state.end_turn(first).unwrap();
+        assert_eq!(game_state.players[&second].movement, 4);
+        game_state.move_player(second, high).unwrap();
+        assert_eq!(game_state.players[&second].position, high);
+    }
+
+    #[test]
+    fn test_extra_and_skipped_turns() {
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("First").build())
+            .player(PlayerBuilder::new("Second").build())
+            .player(PlayerBuilder::new("Third").build())
+            .build();
+        game_state.rules.max_consecutive_turns = 2;
+        let [first, second, third] = [0, 1, 2].map(|seat| game_state.turn_order[seat]);
+
+        game_state.players.get_mut(&first).unwrap().extra_turns = 5;
+        game_state.players.get_mut(&second).unwrap().skipped_turns = 1;
+
+        game_state.end_turn(first).unwrap();
+        assert_eq!(game_state.active_player, first);
+        // The cap stops a third turn in a row, and Second's skip is used up
+        game_state.end_turn(first).unwrap();
+        assert_eq!(game_state.active_player, third);
+        assert!(game_state
+            .events
+            .contains(&GameEvent::TurnSkipped { player_id: second }));
+        assert_eq!(game_state.players[&first].extra_turns, 4);
+    }
+
+    #[test]
+    fn test_initiative_and_second_player_bonus() {
+        let low = PlayerBuilder::new("Low").deck(vec![CardBuilder::new("Rope").build()]);
+        let high = PlayerBuilder::new("High").position(Position {
+            x: 1,
+            y: 0,
+            z: 1,
+            level: 1,
+        });
+        let (low, high) = (low.build(), high.build());
+        let (low_id, high_id) = (low.id, high.id);
+        let mut game_state = GameStateBuilder::new().player(high).player(low).build();
+        game_state.rules.initiative = Initiative::Altitude;
+        game_state.rules.second_player_bonus = SecondPlayerBonus::BonusMana(1);
+
+        game_state.decide_initiative().unwrap();
+        assert_eq!(game_state.active_player, low_id);
+        assert_eq!(game_state.turn_order, vec![low_id, high_id]);
+
+        game_state.end_turn(low_id).unwrap();
+        assert_eq!(game_state.players[&high_id].mana, 2);
+        assert!(matches!(
+            game_state.decide_initiative(),
+            Err(GameError::GameAlreadyStarted)
+        ));
+    }
+
+    #[test]
+    fn test_turn_timer_passes_then_concedes() {
+        use crate::runtime::ManualClock;
+
+        let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+        let mut game_state = GameStateBuilder::new().clock(clock.clone()).build();
+        game_state.rules.turn_time_limit = Some(Duration::from_secs(60));
+        game_state.rules.afk_turns_before_concede = 2;
+        let (idle, other) = (game_state.turn_order[0], game_state.turn_order[1]);
+
+        clock.advance(Duration::from_secs(30));
+        game_state.enforce_timers().unwrap();
+        assert_eq!(game_state.active_player, idle);
+
+        clock.advance(Duration::from_secs(30));
+        game_state.enforce_timers().unwrap();
+        assert_eq!(game_state.active_player, other);
+        game_state
+            .apply_action(other, &GameAction::EndTurn)
+            .unwrap();
+
+        clock.advance(Duration::from_secs(60));
+        game_state.enforce_timers().unwrap();
+        assert_eq!(game_state.winner(), Some(other));
+        assert!(game_state.events.contains(&GameEvent::PlayerConceded {
+            player_id: idle,
+            reason: ConcedeReason::Inactive,
+        }));
+    }
+
+    #[test]
+    fn test_pause_freezes_timer_and_resumes_on_its_own() {
+        use crate::runtime::ManualClock;
+
+        let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+        let mut game_state = GameStateBuilder::new().clock(clock.clone()).build();
+        let (first, second) = (game_state.turn_order[0], game_state.turn_order[1]);
+        assert!(matches!(
+            game_state.request_pause(first),
+            Err(GameError::PauseNotAllowed)
+        ));
+
+        game_state.rules.max_pause = Some(Duration::from_secs(300));
+        game_state.request_pause(first).unwrap();
+        assert!(game_state.accept_pause(first).is_err());
+        game_state.accept_pause(second).unwrap();
+
+        clock.advance(Duration::from_secs(10));
+        game_state.enforce_timers().unwrap();
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(game_state.turn_elapsed(), Duration::ZERO);
+        assert!(matches!(
+            game_state.apply_action(first, &GameAction::EndTurn),
+            Err(GameError::GamePaused)
+        ));
+
+        clock.advance(Duration::from_secs(300));
+        game_state.enforce_timers().unwrap();
+        assert_eq!(game_state.pause, PauseState::Running);
+        assert_eq!(game_state.turn_elapsed(), Duration::ZERO);
+        game_state
+            .apply_action(first, &GameAction::EndTurn)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_pause_needs_a_seated_opponent_to_agree() {
+        let [a1, a2, b1, b2] =
+            ["A1", "A2", "B1", "B2"].map(|name| PlayerBuilder::new(name).build());
+        let (a1_id, a2_id, b1_id) = (a1.id, a2.id, b1.id);
+        let mut game_state = GameState::new_teams([a1, a2], [b1, b2]);
+        game_state.rules.max_pause = Some(Duration::from_secs(300));
+        game_state.request_pause(a1_id).unwrap();
+
+        assert!(matches!(
+            game_state.accept_pause(Uuid::new_v4()),
+            Err(GameError::PlayerNotFound)
+        ));
+        assert!(matches!(
+            game_state.accept_pause(a2_id),
+            Err(GameError::PauseNotAllowed)
+        ));
+        assert_eq!(game_state.pause, PauseState::Requested { by: a1_id });
+
+        game_state.accept_pause(b1_id).unwrap();
+        assert!(matches!(game_state.pause, PauseState::Paused { .. }));
+    }
+
+    #[test]
+    fn test_annotations_carry_card_and_raw_amount() {
+        use crate::effects::{DamageEffect, Effect, EffectTarget, EffectValue};
+
+        let bolt = CardBuilder::new("Bolt")
+            .cost(0)
+            .effect(Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base: 5,
+                    scaling: None,
+                },
+                target: EffectTarget::Random(1),
+                penetrating: false,
+                ranged: false,
+                priority: 0,
+            }))
+            .build();
+        let mut game_state = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Caster")
+                    .hand(vec![bolt.clone()])
+                    .build(),
+            )
+            .build()
+            .with_annotations();
+        for player in game_state.players.values_mut() {
+            player.shield = 2;
+        }
+        let caster = game_state.active_player;
+
+        game_state.play_card(caster, bolt.id).unwrap();
+
+        let (event, annotation) = game_state
+            .annotated_events()
+            .find(|(event, _)| matches!(event, GameEvent::DamageDealt { .. }))
+            .unwrap();
+        let annotation = annotation.unwrap();
+        assert!(matches!(event, GameEvent::DamageDealt { amount: 3, .. }));
+        assert_eq!(annotation.source_card, Some(bolt.id));
+        assert_eq!(annotation.amount_before_mitigation, Some(5));
+        assert!(annotation.target_position.is_some());
+        assert_eq!(game_state.resolving_card, None);
+    }
+
+    #[test]
+    fn test_team_turns_friendly_fire_and_team_win() {
+        use crate::effects::{DamageEffect, Effect, EffectTarget, EffectValue};
+
+        let [a1, a2, b1, b2] =
+            ["A1", "A2", "B1", "B2"].map(|name| PlayerBuilder::new(name).build());
+        let ids = [a1.id, a2.id, b1.id, b2.id];
+        let mut game_state = GameState::new_teams([a1, a2], [b1, b2]);
+        game_state.rules.team_turns = TeamTurns::Shared;
+        game_state.rules.friendly_fire = false;
+        assert_eq!(game_state.turn_order, [ids[0], ids[2], ids[1], ids[3]]);
+        assert!(game_state.are_allies(ids[0], ids[1]));
+        assert!(game_state.are_enemies(ids[1], ids[2]));
+
+        // A2 ends the turn A1 was given, and both of team B are refilled
+        game_state.end_turn(ids[1]).unwrap();
+        assert_eq!(game_state.active_player, ids[2]);
+        assert!(matches!(
+            game_state.end_turn(ids[0]),
+            Err(GameError::NotYourTurn)
+        ));
+        assert_eq!(game_state.players[&ids[3]].mana, 1);
+        game_state.end_turn(ids[3]).unwrap();
+
+        let avalanche = Effect::Damage(DamageEffect {
+            value: EffectValue {
+                base: 100,
+                scaling: None,
+            },
+            target: EffectTarget::Area {
+                center: ids[0],
+                radius: 20,
+            },
+            penetrating: true,
+            ranged: false,
+            priority: 0,
+        });
+        avalanche.apply(&mut game_state, ids[1]).unwrap();
+        assert!(game_state.players[&ids[0]].health > 0);
+        assert_eq!(game_state.players[&ids[2]].health, 0);
+        assert_eq!(game_state.winning_team(), Some(0));
+        assert_eq!(game_state.winner(), Some(ids[0]));
+    }
+
+    #[test]
+    fn test_action_budget_and_combo_trigger() {
+        use crate::effects::{
+            DamageEffect, Effect, EffectTarget, EffectValue, Trigger, TriggeredEffect,
+        };
+
+        let finisher = Effect::Triggered(TriggeredEffect {
+            trigger: Trigger::ComboThreshold(3),
+            effect: Box::new(Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base: 4,
+                    scaling: None,
+                },
+                target: EffectTarget::Enemies,
+                penetrating: true,
+                ranged: false,
+                priority: 0,
+            })),
+        });
+        let hand: Vec<_> = (0..4)
+            .map(|_| {
+                CardBuilder::new("Flurry")
+                    .cost(0)
+                    .effect(finisher.clone())
+                    .build()
+            })
+            .collect();
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Combo").hand(hand.clone()).build())
+            .player(PlayerBuilder::new("Target").build())
+            .build();
+        game_state.rules.max_cards_per_turn = Some(3);
+        game_state.rules.max_moves_per_turn = Some(0);
+        let (combo, target) = (game_state.turn_order[0], game_state.turn_order[1]);
+
+        game_state.play_card(combo, hand[0].id).unwrap();
+        game_state.play_card(combo, hand[1].id).unwrap();
+        assert_eq!(game_state.players[&target].health, 30);
+        game_state.play_card(combo, hand[2].id).unwrap();
+        assert_eq!(game_state.players[&target].health, 26);
+        assert!(game_state.events.contains(&GameEvent::ComboTriggered {
+            player_id: combo,
+            cards_played: 3,
+        }));
+
+        assert!(matches!(
+            game_state.play_card(combo, hand[3].id),
+            Err(GameError::ActionLimitReached)
+        ));
+        let position = game_state.players[&combo].position;
+        assert!(matches!(
+            game_state.apply_action(combo, &GameAction::Move { position }),
+            Err(GameError::ActionLimitReached)
+        ));
+    }
 }
@@ -0,0 +1,675 @@
+// src/guilds/mod.rs
+// Guilds live in the registry while the server runs. Every change marks the
+// guild so `save` writes it to storage, and `load` rebuilds the registry at
+// startup. Members read their guild over the protocol with GetGuild.
+// Objectives move as games are played, through GuildProgress on the event
+// bus.
+use crate::database::GuildStorage;
+use crate::errors::GuildError;
+use crate::events::bus::EventSubscriber;
+use crate::events::GameEvent;
+use crate::game_state::GameState;
+use crate::moderation::ModerationService;
+use crate::networking::{ClientMessage, ServerMessage};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use uuid::Uuid;
+
+pub const MAX_GUILD_MEMBERS: usize = 50;
+pub const MAX_CHAT_HISTORY: usize = 200;
+pub const XP_PER_LEVEL: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GuildRole {
+    Member,
+    Officer,
+    Leader,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildMember {
+    pub player_id: Uuid,
+    pub role: GuildRole,
+    pub weekly_contribution: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildMessage {
+    pub author: Uuid,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ObjectiveKind {
+    GamesPlayed,
+    GamesWon,
+    DamageDealt,
+    LevelsClimbed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildObjective {
+    pub kind: ObjectiveKind,
+    pub target: u32,
+    pub progress: u32,
+    pub reward_xp: u32,
+}
+
+impl GuildObjective {
+    pub fn new(kind: ObjectiveKind, target: u32, reward_xp: u32) -> Self {
+        Self {
+            kind,
+            target,
+            progress: 0,
+            reward_xp,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.progress >= self.target
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Guild {
+    pub id: Uuid,
+    pub name: String,
+    pub members: HashMap<Uuid, GuildMember>,
+    pub chat: Vec<GuildMessage>,
+    pub objectives: Vec<GuildObjective>,
+    pub experience: u32,
+}
+
+impl Guild {
+    pub fn new(name: String, leader: Uuid) -> Self {
+        let mut members = HashMap::new();
+        members.insert(
+            leader,
+            GuildMember {
+                player_id: leader,
+                role: GuildRole::Leader,
+                weekly_contribution: 0,
+            },
+        );
+
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            members,
+            chat: Vec::new(),
+            objectives: Vec::new(),
+            experience: 0,
+        }
+    }
+
+    pub fn level(&self) -> u32 {
+        self.experience / XP_PER_LEVEL + 1
+    }
+
+    pub fn role_of(&self, player_id: Uuid) -> Option<GuildRole> {
+        self.members.get(&player_id).map(|member| member.role)
+    }
+
+    // Progress counts toward every matching objective; rewards are granted
+    // once, at the moment an objective crosses its target.
+    pub fn record_progress(&mut self, player_id: Uuid, kind: ObjectiveKind, amount: u32) {
+        let Some(member) = self.members.get_mut(&player_id) else {
+            return;
+        };
+        member.weekly_contribution = member.weekly_contribution.saturating_add(amount);
+
+        for objective in self.objectives.iter_mut().filter(|o| o.kind == kind) {
+            let was_complete = objective.is_complete();
+            objective.progress = objective.progress.saturating_add(amount);
+            if !was_complete && objective.is_complete() {
+                self.experience = self.experience.saturating_add(objective.reward_xp);
+            }
+        }
+    }
+
+    pub fn start_week(&mut self, objectives: Vec<GuildObjective>) {
+        self.objectives = objectives;
+        for member in self.members.values_mut() {
+            member.weekly_contribution = 0;
+        }
+    }
+
+    // Highest role, then most contributed this week; a tie goes to the
+    // lowest player id so every node picks the same successor
+    fn successor(&self) -> Option<Uuid> {
+        self.members
+            .values()
+            .max_by_key(|member| {
+                (
+                    member.role,
+                    member.weekly_contribution,
+                    std::cmp::Reverse(member.player_id),
+                )
+            })
+            .map(|member| member.player_id)
+    }
+
+    pub fn view(&self) -> GuildView {
+        let mut members: Vec<GuildMember> = self.members.values().cloned().collect();
+        members.sort_by_key(|member| {
+            (
+                std::cmp::Reverse(member.role),
+                std::cmp::Reverse(member.weekly_contribution),
+                member.player_id,
+            )
+        });
+        GuildView {
+            id: self.id,
+            name: self.name.clone(),
+            level: self.level(),
+            experience: self.experience,
+            members,
+            objectives: self.objectives.clone(),
+            chat: self.chat.clone(),
+        }
+    }
+}
+
+// What a member is sent for their guild, members by rank
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuildView {
+    pub id: Uuid,
+    pub name: String,
+    pub level: u32,
+    pub experience: u32,
+    pub members: Vec<GuildMember>,
+    pub objectives: Vec<GuildObjective>,
+    pub chat: Vec<GuildMessage>,
+}
+
+#[derive(Debug, Default)]
+pub struct GuildRegistry {
+    pub guilds: HashMap<Uuid, Guild>,
+    memberships: HashMap<Uuid, Uuid>,
+    // Guilds changed or disbanded since the last save
+    dirty: HashSet<Uuid>,
+}
+
+impl GuildRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Every stored guild, with its members' memberships rebuilt
+    pub fn load(storage: &impl GuildStorage) -> Self {
+        let mut registry = Self::new();
+        for guild in storage.load_guilds() {
+            for player_id in guild.members.keys() {
+                registry.memberships.insert(*player_id, guild.id);
+            }
+            registry.guilds.insert(guild.id, guild);
+        }
+        registry
+    }
+
+    // Writes out every guild changed since the last save and deletes the
+    // disbanded ones, returning how many were written or deleted
+    pub fn save(&mut self, storage: &mut impl GuildStorage) -> usize {
+        let saved = self.dirty.len();
+        for guild_id in self.dirty.drain() {
+            match self.guilds.get(&guild_id) {
+                Some(guild) => storage.save_guild(guild.clone()),
+                None => {
+                    storage.delete_guild(guild_id);
+                }
+            }
+        }
+        saved
+    }
+
+    // Answers a connection's GetGuild request
+    pub fn handle_request(
+        &self,
+        player_id: Uuid,
+        message: &ClientMessage,
+    ) -> Option<ServerMessage> {
+        let ClientMessage::GetGuild = message else {
+            return None;
+        };
+        Some(ServerMessage::Guild {
+            guild: self.guild_of(player_id).map(Guild::view),
+        })
+    }
+
+    pub fn guild_of(&self, player_id: Uuid) -> Option<&Guild> {
+        self.memberships
+            .get(&player_id)
+            .and_then(|guild_id| self.guilds.get(guild_id))
+    }
+
+    pub fn create_guild(&mut self, name: String, leader: Uuid) -> Result<Uuid, GuildError> {
+        if self.memberships.contains_key(&leader) {
+            return Err(GuildError::AlreadyInGuild);
+        }
+        if self
+            .guilds
+            .values()
+            .any(|guild| guild.name.eq_ignore_ascii_case(&name))
+        {
+            return Err(GuildError::NameTaken);
+        }
+
+        let guild = Guild::new(name, leader);
+        let guild_id = guild.id;
+        self.guilds.insert(guild_id, guild);
+        self.memberships.insert(leader, guild_id);
+        self.dirty.insert(guild_id);
+        Ok(guild_id)
+    }
+
+    pub fn join(&mut self, guild_id: Uuid, player_id: Uuid) -> Result<(), GuildError> {
+        if self.memberships.contains_key(&player_id) {
+            return Err(GuildError::AlreadyInGuild);
+        }
+        let guild = self
+            .guilds
+            .get_mut(&guild_id)
+            .ok_or(GuildError::GuildNotFound)?;
+        if guild.members.len() >= MAX_GUILD_MEMBERS {
+            return Err(GuildError::GuildFull);
+        }
+
+        guild.members.insert(
+            player_id,
+            GuildMember {
+                player_id,
+                role: GuildRole::Member,
+                weekly_contribution: 0,
+            },
+        );
+        self.memberships.insert(player_id, guild_id);
+        self.dirty.insert(guild_id);
+        Ok(())
+    }
+
+    // A departing leader hands the guild to the highest-ranked remaining
+    // member; the last member out disbands it.
+    pub fn leave(&mut self, player_id: Uuid) -> Result<(), GuildError> {
+        let guild_id = self
+            .memberships
+            .remove(&player_id)
+            .ok_or(GuildError::NotInGuild)?;
+        let guild = self
+            .guilds
+            .get_mut(&guild_id)
+            .ok_or(GuildError::GuildNotFound)?;
+        self.dirty.insert(guild_id);
+
+        let departed = guild.members.remove(&player_id);
+        if guild.members.is_empty() {
+            self.guilds.remove(&guild_id);
+            return Ok(());
+        }
+
+        if departed.map(|member| member.role) == Some(GuildRole::Leader) {
+            if let Some(successor) = guild.successor() {
+                if let Some(member) = guild.members.get_mut(&successor) {
+                    member.role = GuildRole::Leader;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_role(
+        &mut self,
+        actor: Uuid,
+        target: Uuid,
+        role: GuildRole,
+    ) -> Result<(), GuildError> {
+        let guild = self.guild_of_mut(actor)?;
+        let actor_role = guild.role_of(actor).ok_or(GuildError::NotInGuild)?;
+        let target_role = guild.role_of(target).ok_or(GuildError::NotInGuild)?;
+
+        // Only strictly higher ranks may change a role, and never above their own
+        if actor_role <= target_role || role >= actor_role {
+            return Err(GuildError::InsufficientRole);
+        }
+
+        if let Some(member) = guild.members.get_mut(&target) {
+            member.role = role;
+        }
+        Ok(())
+    }
+
+    // Muted players can still read the guild chat, just not post to it
+    pub fn post_message(
+        &mut self,
+        author: Uuid,
+        content: String,
+        moderation: &ModerationService,
+        now: SystemTime,
+    ) -> Result<(), GuildError> {
+        let guild = self.guild_of_mut(author)?;
+        moderation
+            .check_chat(author, now)
+            .map_err(GuildError::Moderation)?;
+        guild.chat.push(GuildMessage { author, content });
+        if guild.chat.len() > MAX_CHAT_HISTORY {
+            let overflow = guild.chat.len() - MAX_CHAT_HISTORY;
+            guild.chat.drain(..overflow);
+        }
+        Ok(())
+    }
+
+    pub fn start_week(
+        &mut self,
+        guild_id: Uuid,
+        objectives: Vec<GuildObjective>,
+    ) -> Result<(), GuildError> {
+        let guild = self
+            .guilds
+            .get_mut(&guild_id)
+            .ok_or(GuildError::GuildNotFound)?;
+        guild.start_week(objectives);
+        self.dirty.insert(guild_id);
+        Ok(())
+    }
+
+    pub fn record_progress(&mut self, player_id: Uuid, kind: ObjectiveKind, amount: u32) {
+        if let Ok(guild) = self.guild_of_mut(player_id) {
+            guild.record_progress(player_id, kind, amount);
+        }
+    }
+
+    // Takes an erased player out of their guild and deletes everything they
+    // wrote in any guild's chat. Returns whether they were in a guild and
+    // how many messages went.
+    pub fn purge_player(&mut self, player_id: Uuid) -> (bool, usize) {
+        let left = self.leave(player_id).is_ok();
+        let mut deleted = 0;
+        for guild in self.guilds.values_mut() {
+            let before = guild.chat.len();
+            guild.chat.retain(|message| message.author != player_id);
+            if guild.chat.len() < before {
+                deleted += before - guild.chat.len();
+                self.dirty.insert(guild.id);
+            }
+        }
+        (left, deleted)
+    }
+
+    // The player's guild, marked for the next save
+    fn guild_of_mut(&mut self, player_id: Uuid) -> Result<&mut Guild, GuildError> {
+        let guild_id = self
+            .memberships
+            .get(&player_id)
+            .ok_or(GuildError::NotInGuild)?;
+        let guild = self
+            .guilds
+            .get_mut(guild_id)
+            .ok_or(GuildError::GuildNotFound)?;
+        self.dirty.insert(guild.id);
+        Ok(guild)
+    }
+}
+
+// Bus subscriber feeding guild objectives from live games. The registry is
+// shared with whatever serves and saves guilds.
+pub struct GuildProgress(pub Arc<Mutex<GuildRegistry>>);
+
+impl EventSubscriber for GuildProgress {
+    fn on_event(&mut self, _game_id: Uuid, event: &GameEvent) {
+        let mut guilds = self.0.lock().unwrap();
+        match event {
+            GameEvent::DamageDealt {
+                source,
+                target,
+                amount,
+            } if source != target => {
+                guilds.record_progress(*source, ObjectiveKind::DamageDealt, *amount);
+            }
+            GameEvent::PlayerMoved {
+                player_id,
+                from,
+                to,
+            } if to.level > from.level => {
+                let climbed = to.level - from.level;
+                guilds.record_progress(*player_id, ObjectiveKind::LevelsClimbed, climbed);
+            }
+            _ => {}
+        }
+    }
+
+    fn on_game_over(&mut self, game_state: &GameState) {
+        let mut guilds = self.0.lock().unwrap();
+        let winner = game_state.winner();
+        for player in game_state.seated_players() {
+            guilds.record_progress(player.id, ObjectiveKind::GamesPlayed, 1);
+            if winner.is_some_and(|winner| game_state.are_allies(winner, player.id)) {
+                guilds.record_progress(player.id, ObjectiveKind::GamesWon, 1);
+            }
+        }
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod guild_tests {
+    use super::*;
+    use crate::events::bus::EventBus;
+    use crate::models::Position;
+    use crate::moderation::SanctionKind;
+    use crate::testing::GameStateBuilder;
+
+    #[test]
+    fn test_join_and_leave_transfers_leadership() {
+        let mut registry = GuildRegistry::new();
+        let leader = Uuid::new_v4();
+        let officer = Uuid::new_v4();
+        let member = Uuid::new_v4();
+
+        let guild_id = registry
+            .create_guild("Summiteers".to_string(), leader)
+            .unwrap();
+        registry.join(guild_id, officer).unwrap();
+        registry.join(guild_id, member).unwrap();
+        registry
+            .set_role(leader, officer, GuildRole::Officer)
+            .unwrap();
+
+        assert!(matches!(
+            registry.join(guild_id, member),
+            Err(GuildError::AlreadyInGuild)
+        ));
+        assert!(matches!(
+            registry.set_role(member, officer, GuildRole::Member),
+            Err(GuildError::InsufficientRole)
+        ));
+
+        let mut moderation = ModerationService::new();
+        let now = SystemTime::UNIX_EPOCH;
+        registry
+            .post_message(member, "hi".to_string(), &moderation, now)
+            .unwrap();
+        moderation.issue(
+            leader,
+            member,
+            SanctionKind::FullBan,
+            None,
+            "spam".to_string(),
+            now,
+        );
+        assert!(matches!(
+            registry.post_message(member, "hi again".to_string(), &moderation, now),
+            Err(GuildError::Moderation(_))
+        ));
+        assert_eq!(registry.guild_of(member).unwrap().chat.len(), 1);
+
+        registry.leave(leader).unwrap();
+        let guild = registry.guild_of(member).unwrap();
+        assert_eq!(guild.role_of(officer), Some(GuildRole::Leader));
+        assert_eq!(guild.members.len(), 2);
+    }
+
+    #[test]
+    fn test_objectives_aggregate_member_progress() {
+        let mut registry = GuildRegistry::new();
+        let leader = Uuid::new_v4();
+        let member = Uuid::new_v4();
+        let guild_id = registry.create_guild("Ropes".to_string(), leader).unwrap();
+        registry.join(guild_id, member).unwrap();
+
+        registry
+            .start_week(
+                guild_id,
+                vec![GuildObjective::new(ObjectiveKind::GamesWon, 3, 500)],
+            )
+            .unwrap();
+
+        registry.record_progress(leader, ObjectiveKind::GamesWon, 2);
+        registry.record_progress(member, ObjectiveKind::GamesWon, 1);
+        registry.record_progress(member, ObjectiveKind::GamesWon, 1);
+
+        let guild = &registry.guilds[&guild_id];
+        assert!(guild.objectives[0].is_complete());
+        assert_eq!(guild.experience, 500);
+        assert_eq!(guild.members[&member].weekly_contribution, 2);
+    }
+
+    #[test]
+    fn test_guilds_survive_a_restart_and_are_served_to_members() {
+        use crate::database::MemoryStorage;
+
+        let mut storage = MemoryStorage::default();
+        let mut registry = GuildRegistry::new();
+        let leader = Uuid::new_v4();
+        let (low, high) = {
+            let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+            (a.min(b), a.max(b))
+        };
+        let guild_id = registry.create_guild("Belay".to_string(), leader).unwrap();
+        registry.join(guild_id, high).unwrap();
+        registry.join(guild_id, low).unwrap();
+        let disbanded = registry.create_guild("Gone".to_string(), Uuid::new_v4());
+        registry
+            .start_week(
+                guild_id,
+                vec![GuildObjective::new(
+                    ObjectiveKind::DamageDealt,
+                    10,
+                    u32::MAX,
+                )],
+            )
+            .unwrap();
+        registry.record_progress(leader, ObjectiveKind::DamageDealt, u32::MAX);
+        registry.record_progress(leader, ObjectiveKind::DamageDealt, 1);
+        assert_eq!(registry.save(&mut storage), 2);
+        assert_eq!(registry.save(&mut storage), 0);
+
+        let mut registry = GuildRegistry::load(&storage);
+        let guild = registry.guild_of(leader).unwrap();
+        assert_eq!(guild.members[&leader].weekly_contribution, u32::MAX);
+        assert_eq!(guild.experience, u32::MAX);
+        let gone = registry.guilds[&disbanded.unwrap()]
+            .members
+            .keys()
+            .copied()
+            .next();
+        registry.leave(gone.unwrap()).unwrap();
+
+        // Equal role and contribution: the lower player id takes over
+        registry.leave(leader).unwrap();
+        registry.save(&mut storage);
+        let registry = GuildRegistry::load(&storage);
+        assert_eq!(storage.guilds.len(), 1);
+        assert_eq!(
+            registry.guild_of(high).unwrap().role_of(low),
+            Some(GuildRole::Leader)
+        );
+
+        let Some(ServerMessage::Guild { guild: Some(view) }) =
+            registry.handle_request(high, &ClientMessage::GetGuild)
+        else {
+            panic!("no guild sent");
+        };
+        assert_eq!(view.name, "Belay");
+        assert_eq!(view.members[0].player_id, low);
+        assert_eq!(
+            registry.handle_request(leader, &ClientMessage::GetGuild),
+            Some(ServerMessage::Guild { guild: None })
+        );
+    }
+
+    #[test]
+    fn test_games_on_the_bus_move_guild_objectives() {
+        let mut game_state = GameStateBuilder::new().build();
+        let (member, rival) = (game_state.turn_order[0], game_state.turn_order[1]);
+        let mut registry = GuildRegistry::new();
+        let guild_id = registry
+            .create_guild("Summiteers".to_string(), member)
+            .unwrap();
+        let kinds = [
+            ObjectiveKind::GamesPlayed,
+            ObjectiveKind::GamesWon,
+            ObjectiveKind::DamageDealt,
+            ObjectiveKind::LevelsClimbed,
+        ];
+        registry
+            .start_week(
+                guild_id,
+                kinds
+                    .iter()
+                    .map(|kind| GuildObjective::new(*kind, 100, 10))
+                    .collect(),
+            )
+            .unwrap();
+        let guilds = Arc::new(Mutex::new(registry));
+        let mut bus = EventBus::new();
+        bus.subscribe(GuildProgress(guilds.clone()));
+
+        let at = |level| Position {
+            x: 0,
+            y: 0,
+            z: 0,
+            level,
+        };
+        game_state.events.extend([
+            GameEvent::DamageDealt {
+                source: member,
+                target: rival,
+                amount: 7,
+            },
+            GameEvent::DamageDealt {
+                source: rival,
+                target: member,
+                amount: 3,
+            },
+            GameEvent::PlayerMoved {
+                player_id: member,
+                from: at(1),
+                to: at(3),
+            },
+            GameEvent::PlayerMoved {
+                player_id: member,
+                from: at(3),
+                to: at(2),
+            },
+        ]);
+        bus.publish(&game_state);
+        game_state.players.get_mut(&rival).unwrap().health = 0;
+        game_state
+            .events
+            .push(GameEvent::PlayerDefeated { player_id: rival });
+        bus.publish(&game_state);
+        // Publishing a finished game again counts nothing twice
+        bus.publish(&game_state);
+
+        let guilds = guilds.lock().unwrap();
+        let guild = guilds.guild_of(member).unwrap();
+        let progress: Vec<u32> = guild
+            .objectives
+            .iter()
+            .map(|objective| objective.progress)
+            .collect();
+        assert_eq!(progress, [1, 1, 7, 2]);
+        assert_eq!(guild.members[&member].weekly_contribution, 11);
+    }
+}
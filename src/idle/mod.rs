@@ -0,0 +1,226 @@
+// src/idle/mod.rs
+// Idle mode for quiet servers, mostly self-hosted and dev ones. Once there
+// have been no games and no connections for a while, background jobs
+// (matchmaking ticks, telemetry flushes, stats jobs) run far less often.
+// A new connection wakes every sleeping job at once instead of leaving it
+// to the next slow tick.
+use crate::config::IdleSettings;
+use serde::Serialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tokio::sync::Notify;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundJob {
+    Matchmaking,
+    TelemetryFlush,
+    Stats,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityState {
+    Active,
+    Idle,
+}
+
+// What the metrics endpoint reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct IdleStats {
+    pub state: ActivityState,
+    pub connections: usize,
+    // Switches between the two states since start
+    pub transitions: u64,
+    // Total time spent idle, the current stretch included
+    pub idle_secs: u64,
+}
+
+#[derive(Debug)]
+struct Tracker {
+    state: ActivityState,
+    // When games and connections last both dropped to zero
+    quiet_since: Option<SystemTime>,
+    idle_since: Option<SystemTime>,
+    transitions: u64,
+    idle_total: Duration,
+}
+
+#[derive(Debug)]
+pub struct IdleMonitor {
+    settings: IdleSettings,
+    connections: AtomicUsize,
+    tracker: Mutex<Tracker>,
+    wake: Notify,
+}
+
+impl IdleMonitor {
+    // Starts out active; a server with nothing to do goes idle after the
+    // usual quiet time
+    pub fn new(settings: IdleSettings, now: SystemTime) -> Self {
+        Self {
+            settings,
+            connections: AtomicUsize::new(0),
+            tracker: Mutex::new(Tracker {
+                state: ActivityState::Active,
+                quiet_since: Some(now),
+                idle_since: None,
+                transitions: 0,
+                idle_total: Duration::ZERO,
+            }),
+            wake: Notify::new(),
+        }
+    }
+
+    pub fn state(&self) -> ActivityState {
+        self.tracker.lock().unwrap().state
+    }
+
+    pub fn connection_opened(&self, now: SystemTime) {
+        self.connections.fetch_add(1, Ordering::Relaxed);
+        let mut tracker = self.tracker.lock().unwrap();
+        tracker.quiet_since = None;
+        self.set_state(&mut tracker, ActivityState::Active, now);
+    }
+
+    pub fn connection_closed(&self) {
+        let _ = self
+            .connections
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                count.checked_sub(1)
+            });
+    }
+
+    // Called from each tick with the number of hosted games
+    pub fn observe(&self, games: usize, now: SystemTime) -> ActivityState {
+        let busy = games > 0 || self.connections.load(Ordering::Relaxed) > 0;
+        let mut tracker = self.tracker.lock().unwrap();
+        if busy {
+            tracker.quiet_since = None;
+            self.set_state(&mut tracker, ActivityState::Active, now);
+        } else {
+            let quiet_since = *tracker.quiet_since.get_or_insert(now);
+            let quiet = now.duration_since(quiet_since).unwrap_or_default();
+            if self.settings.enabled && quiet >= Duration::from_secs(self.settings.idle_after_secs)
+            {
+                self.set_state(&mut tracker, ActivityState::Idle, now);
+            }
+        }
+        tracker.state
+    }
+
+    fn set_state(&self, tracker: &mut Tracker, state: ActivityState, now: SystemTime) {
+        if tracker.state == state {
+            return;
+        }
+        tracker.state = state;
+        tracker.transitions += 1;
+        match state {
+            ActivityState::Idle => tracker.idle_since = Some(now),
+            ActivityState::Active => {
+                if let Some(since) = tracker.idle_since.take() {
+                    tracker.idle_total += now.duration_since(since).unwrap_or_default();
+                }
+                self.wake.notify_waiters();
+            }
+        }
+        tracing::info!(?state, "server activity changed");
+    }
+
+    pub fn interval(&self, job: BackgroundJob) -> Duration {
+        let active = match job {
+            BackgroundJob::Matchmaking => Duration::from_millis(self.settings.matchmaking_tick_ms),
+            BackgroundJob::TelemetryFlush => {
+                Duration::from_secs(self.settings.telemetry_flush_secs)
+            }
+            BackgroundJob::Stats => Duration::from_secs(self.settings.stats_interval_secs),
+        };
+        match self.state() {
+            ActivityState::Active => active,
+            ActivityState::Idle => active.saturating_mul(self.settings.idle_multiplier.max(1)),
+        }
+    }
+
+    // Sleeps until the job's next run, cut short if the server wakes up
+    pub async fn wait(&self, job: BackgroundJob) {
+        let woken = self.wake.notified();
+        tokio::select! {
+            _ = tokio::time::sleep(self.interval(job)) => {}
+            _ = woken => {}
+        }
+    }
+
+    pub fn stats(&self, now: SystemTime) -> IdleStats {
+        let tracker = self.tracker.lock().unwrap();
+        let current = tracker
+            .idle_since
+            .map(|since| now.duration_since(since).unwrap_or_default())
+            .unwrap_or_default();
+        IdleStats {
+            state: tracker.state,
+            connections: self.connections.load(Ordering::Relaxed),
+            transitions: tracker.transitions,
+            idle_secs: (tracker.idle_total + current).as_secs(),
+        }
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod idle_tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_quiet_server_downshifts_and_wakes_on_connection() {
+        let start = SystemTime::UNIX_EPOCH;
+        let settings = IdleSettings {
+            idle_after_secs: 60,
+            matchmaking_tick_ms: 500,
+            idle_multiplier: 10_000,
+            ..IdleSettings::default()
+        };
+        let monitor = Arc::new(IdleMonitor::new(settings, start));
+
+        assert_eq!(monitor.observe(1, start), ActivityState::Active);
+        assert_eq!(
+            monitor.observe(0, start + Duration::from_secs(30)),
+            ActivityState::Active
+        );
+        let idle_at = start + Duration::from_secs(90);
+        assert_eq!(monitor.observe(0, idle_at), ActivityState::Idle);
+        assert_eq!(
+            monitor.interval(BackgroundJob::Matchmaking),
+            Duration::from_millis(5_000_000)
+        );
+
+        // A matchmaking job asleep on the idle interval
+        let sleeper = tokio::spawn({
+            let monitor = monitor.clone();
+            async move { monitor.wait(BackgroundJob::Matchmaking).await }
+        });
+        tokio::task::yield_now().await;
+        monitor.connection_opened(idle_at + Duration::from_secs(20));
+        tokio::time::timeout(Duration::from_secs(5), sleeper)
+            .await
+            .expect("woken straight away")
+            .unwrap();
+        assert_eq!(
+            monitor.interval(BackgroundJob::Matchmaking),
+            Duration::from_millis(500)
+        );
+
+        monitor.connection_closed();
+        let stats = monitor.stats(idle_at + Duration::from_secs(60));
+        assert_eq!(
+            stats,
+            IdleStats {
+                state: ActivityState::Active,
+                connections: 0,
+                transitions: 2,
+                idle_secs: 20,
+            }
+        );
+    }
+}
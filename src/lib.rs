@@ -1,14 +1,108 @@
+#[cfg(feature = "server")]
+pub mod admin;
+#[cfg(feature = "server")]
+pub mod announcements;
+#[cfg(feature = "server")]
+pub mod bots;
+pub mod cards;
+#[cfg(feature = "server")]
+pub mod client;
+#[cfg(feature = "server")]
+pub mod cluster;
+#[cfg(feature = "server")]
 pub mod collections;
+#[cfg(feature = "server")]
+pub mod config;
+#[cfg(feature = "server")]
+pub mod cosmetics;
+#[cfg(feature = "server")]
+pub mod crafting;
+#[cfg(feature = "server")]
 pub mod database;
+#[cfg(feature = "server")]
+pub mod deck_sharing;
+#[cfg(feature = "server")]
+pub mod draft;
 pub mod effects;
+#[cfg(feature = "server")]
+pub mod emotes;
 pub mod errors;
+pub mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod game_state;
+#[cfg(feature = "server")]
+pub mod guilds;
+#[cfg(feature = "server")]
+pub mod idle;
+#[cfg(feature = "server")]
+pub mod lobbies;
+#[cfg(feature = "server")]
+pub mod maintenance;
+#[cfg(feature = "server")]
+pub mod matchmaking;
+#[cfg(feature = "server")]
+pub mod messages;
+#[cfg(feature = "server")]
+pub mod metrics;
 pub mod models;
+#[cfg(feature = "server")]
+pub mod moderation;
+#[cfg(feature = "server")]
 pub mod networking;
+#[cfg(feature = "server")]
+pub mod objectives;
+#[cfg(feature = "server")]
+pub mod practice;
+#[cfg(feature = "server")]
+pub mod profiles;
+#[cfg(feature = "server")]
+pub mod puzzles;
+#[cfg(feature = "server")]
+pub mod rating;
+#[cfg(feature = "server")]
+pub mod recovery;
+#[cfg(feature = "server")]
+pub mod replay;
+pub mod runtime;
+#[cfg(feature = "server")]
+pub mod scenarios;
+#[cfg(feature = "server")]
+pub mod sessions;
+#[cfg(feature = "server")]
+pub mod shuffle;
+#[cfg(feature = "server")]
+pub mod simulation;
+#[cfg(feature = "server")]
+pub mod stats;
+#[cfg(feature = "server")]
+pub mod store;
+#[cfg(feature = "server")]
+pub mod suggestions;
+#[cfg(feature = "server")]
+pub mod summary;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "server")]
+pub mod trading;
+#[cfg(feature = "server")]
+pub mod tutorial;
+pub mod view;
+#[cfg(feature = "server")]
+pub mod wallet;
+#[cfg(feature = "server")]
+pub mod warmup;
+#[cfg(feature = "server")]
+pub mod webhooks;
+
+// Without `server` only the rules engine is built
+#[cfg(not(feature = "core-rules"))]
+compile_error!("enable `server` or `core-rules`");
 
 // Re-export commonly used items
+#[cfg(feature = "server")]
+pub use collections::Collection;
 pub use {
-    collections::Collection,
     effects::{Effect, EffectTarget},
     errors::GameError,
     game_state::GameState,
@@ -0,0 +1,319 @@
+// src/lobbies/mod.rs
+// Private lobbies a host sets up before a game: two player seats, an
+// optional spectator cap and password, and switches for chat and emotes.
+// Every join request goes through here, so the limits hold whatever the
+// client shows.
+use crate::errors::LobbyError;
+use crate::metrics::ErrorTelemetry;
+use crate::moderation::ModerationService;
+use crate::networking::{ClientMessage, ServerMessage};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+pub const PLAYER_SEATS: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct LobbySettings {
+    // None leaves spectating open to anyone; Some(0) turns it off
+    pub max_spectators: Option<usize>,
+    pub password: Option<String>,
+    pub chat_enabled: bool,
+    pub emotes_enabled: bool,
+}
+
+impl Default for LobbySettings {
+    fn default() -> Self {
+        Self {
+            max_spectators: None,
+            password: None,
+            chat_enabled: true,
+            emotes_enabled: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LobbyRole {
+    Player,
+    Spectator,
+}
+
+#[derive(Debug, Clone)]
+pub struct Lobby {
+    pub id: Uuid,
+    pub host: Uuid,
+    pub max_spectators: Option<usize>,
+    pub chat_enabled: bool,
+    pub emotes_enabled: bool,
+    // Only a salted hash of the password is kept
+    password_hash: Option<String>,
+    pub players: Vec<Uuid>,
+    pub spectators: HashSet<Uuid>,
+}
+
+fn password_hash(lobby_id: Uuid, password: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(lobby_id.as_bytes());
+    hasher.update(password.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+impl Lobby {
+    pub fn has_password(&self) -> bool {
+        self.password_hash.is_some()
+    }
+
+    pub fn role_of(&self, player_id: Uuid) -> Option<LobbyRole> {
+        if self.players.contains(&player_id) {
+            Some(LobbyRole::Player)
+        } else if self.spectators.contains(&player_id) {
+            Some(LobbyRole::Spectator)
+        } else {
+            None
+        }
+    }
+
+    pub fn join(
+        &mut self,
+        player_id: Uuid,
+        role: LobbyRole,
+        password: Option<&str>,
+    ) -> Result<(), LobbyError> {
+        if self.role_of(player_id).is_some() {
+            return Err(LobbyError::AlreadyJoined);
+        }
+        // The host never needs the password for their own lobby
+        if let Some(expected) = &self.password_hash {
+            let given = password.map(|password| password_hash(self.id, password));
+            if player_id != self.host && given.as_ref() != Some(expected) {
+                return Err(LobbyError::WrongPassword);
+            }
+        }
+        match role {
+            LobbyRole::Player => {
+                if self.players.len() >= PLAYER_SEATS {
+                    return Err(LobbyError::SeatsFull);
+                }
+                self.players.push(player_id);
+            }
+            LobbyRole::Spectator => {
+                if self
+                    .max_spectators
+                    .is_some_and(|max| self.spectators.len() >= max)
+                {
+                    return Err(LobbyError::SpectatorsFull);
+                }
+                self.spectators.insert(player_id);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn leave(&mut self, player_id: Uuid) {
+        self.players.retain(|id| *id != player_id);
+        self.spectators.remove(&player_id);
+    }
+
+    pub fn check_chat(
+        &self,
+        player_id: Uuid,
+        moderation: &ModerationService,
+        now: SystemTime,
+    ) -> Result<(), LobbyError> {
+        self.role_of(player_id).ok_or(LobbyError::NotInLobby)?;
+        if !self.chat_enabled {
+            return Err(LobbyError::ChatDisabled);
+        }
+        moderation
+            .check_chat(player_id, now)
+            .map_err(|_| LobbyError::Muted)
+    }
+
+    // Spectators watch; only seated players send emotes
+    pub fn check_emote(&self, player_id: Uuid) -> Result<(), LobbyError> {
+        match self.role_of(player_id) {
+            None => return Err(LobbyError::NotInLobby),
+            Some(LobbyRole::Spectator) => return Err(LobbyError::NotSeated),
+            Some(LobbyRole::Player) => {}
+        }
+        if !self.emotes_enabled {
+            return Err(LobbyError::EmotesDisabled);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LobbyManager {
+    lobbies: HashMap<Uuid, Lobby>,
+    telemetry: Arc<ErrorTelemetry>,
+}
+
+impl LobbyManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Counts refused joins alongside the server's other rejections
+    pub fn with_telemetry(mut self, telemetry: Arc<ErrorTelemetry>) -> Self {
+        self.telemetry = telemetry;
+        self
+    }
+
+    // The host takes the first player seat
+    pub fn create(&mut self, host: Uuid, settings: LobbySettings) -> Uuid {
+        let id = Uuid::new_v4();
+        let lobby = Lobby {
+            id,
+            host,
+            max_spectators: settings.max_spectators,
+            chat_enabled: settings.chat_enabled,
+            emotes_enabled: settings.emotes_enabled,
+            password_hash: settings
+                .password
+                .as_deref()
+                .map(|password| password_hash(id, password)),
+            players: vec![host],
+            spectators: HashSet::new(),
+        };
+        self.lobbies.insert(id, lobby);
+        id
+    }
+
+    pub fn get(&self, lobby_id: Uuid) -> Option<&Lobby> {
+        self.lobbies.get(&lobby_id)
+    }
+
+    pub fn get_mut(&mut self, lobby_id: Uuid) -> Option<&mut Lobby> {
+        self.lobbies.get_mut(&lobby_id)
+    }
+
+    pub fn close(&mut self, lobby_id: Uuid) -> Option<Lobby> {
+        self.lobbies.remove(&lobby_id)
+    }
+
+    // Answers a connection's JoinLobby request
+    pub fn handle_join(
+        &mut self,
+        player_id: Uuid,
+        message: &ClientMessage,
+    ) -> Option<ServerMessage> {
+        let ClientMessage::JoinLobby {
+            lobby_id,
+            spectate,
+            password,
+        } = message
+        else {
+            return None;
+        };
+        let role = if *spectate {
+            LobbyRole::Spectator
+        } else {
+            LobbyRole::Player
+        };
+        let joined = self
+            .lobbies
+            .get_mut(lobby_id)
+            .ok_or(LobbyError::NotFound)
+            .and_then(|lobby| lobby.join(player_id, role, password.as_deref()));
+        Some(match joined {
+            Ok(()) => ServerMessage::LobbyJoined {
+                lobby_id: *lobby_id,
+                spectating: *spectate,
+            },
+            Err(error) => {
+                self.telemetry.lobby_error(&error, "lobby_join");
+                ServerMessage::Rejected {
+                    message: error.message(),
+                }
+            }
+        })
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod lobby_tests {
+    use super::*;
+    use crate::messages::LocalizedMessage;
+    use crate::moderation::SanctionKind;
+
+    #[test]
+    fn test_password_and_spectator_cap_are_enforced() {
+        let telemetry = Arc::new(ErrorTelemetry::new());
+        let mut lobbies = LobbyManager::new().with_telemetry(telemetry.clone());
+        let host = Uuid::new_v4();
+        let lobby_id = lobbies.create(
+            host,
+            LobbySettings {
+                max_spectators: Some(1),
+                password: Some("summit".to_string()),
+                emotes_enabled: false,
+                ..LobbySettings::default()
+            },
+        );
+        let join = |spectate, password: Option<&str>| ClientMessage::JoinLobby {
+            lobby_id,
+            spectate,
+            password: password.map(str::to_string),
+        };
+
+        let rejected = |code: &str| {
+            Some(ServerMessage::Rejected {
+                message: LocalizedMessage::error(code),
+            })
+        };
+        assert_eq!(
+            lobbies.handle_join(Uuid::new_v4(), &join(false, Some("valley"))),
+            rejected("wrong_password")
+        );
+        let rival = Uuid::new_v4();
+        assert!(matches!(
+            lobbies.handle_join(rival, &join(false, Some("summit"))),
+            Some(ServerMessage::LobbyJoined {
+                spectating: false,
+                ..
+            })
+        ));
+        assert_eq!(
+            lobbies.handle_join(Uuid::new_v4(), &join(false, Some("summit"))),
+            rejected("seats_full")
+        );
+        let watcher = Uuid::new_v4();
+        lobbies
+            .handle_join(watcher, &join(true, Some("summit")))
+            .unwrap();
+        assert_eq!(
+            lobbies.handle_join(Uuid::new_v4(), &join(true, Some("summit"))),
+            rejected("spectators_full")
+        );
+        let refused = telemetry.by_code();
+        assert_eq!(refused.len(), 3);
+        assert!(refused.values().all(|count| *count == 1));
+
+        let lobby = lobbies.get(lobby_id).unwrap();
+        assert_eq!(lobby.role_of(watcher), Some(LobbyRole::Spectator));
+        let mut moderation = ModerationService::new();
+        let now = SystemTime::UNIX_EPOCH;
+        assert_eq!(lobby.check_chat(watcher, &moderation, now), Ok(()));
+        moderation.issue(
+            host,
+            watcher,
+            SanctionKind::ChatMute,
+            None,
+            "spam".to_string(),
+            now,
+        );
+        assert_eq!(
+            lobby.check_chat(watcher, &moderation, now),
+            Err(LobbyError::Muted)
+        );
+        assert_eq!(lobby.check_emote(rival), Err(LobbyError::EmotesDisabled));
+        assert_eq!(lobby.check_emote(watcher), Err(LobbyError::NotSeated));
+    }
+}
@@ -1,6 +1,6 @@
-use tokio;
+use ascent::runtime::{GameClock, SystemClock};
+use std::sync::Arc;
 use tracing::{info, Level};
-use tracing_subscriber;
 
 mod config {
     pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -35,12 +35,15 @@ async fn setup_game_server() -> Result<GameServer, Box<dyn std::error::Error>> {
     }
 }
 
-async fn run_server(_server: GameServer) -> Result<(), Box<dyn std::error::Error>> {
+async fn run_server(server: GameServer) -> Result<(), Box<dyn std::error::Error>> {
     // TODO: Implement graceful shutdown handling
     // TODO: Setup signal handlers for SIGTERM, SIGINT
 
     tokio::signal::ctrl_c().await?;
-    info!("Shutdown signal received, initiating graceful shutdown");
+    info!(
+        "Shutdown signal received after {:?}, initiating graceful shutdown",
+        server.uptime()
+    );
 
     Ok(())
 }
@@ -53,11 +56,28 @@ struct GameServer {
     // - Player connections
     // - Game state manager
     // - ...
+    // Shared with every game the server creates
+    clock: Arc<dyn GameClock>,
+    started_at: std::time::SystemTime,
 }
 
 impl GameServer {
     fn new() -> Self {
-        Self {}
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    fn with_clock(clock: Arc<dyn GameClock>) -> Self {
+        Self {
+            started_at: clock.now(),
+            clock,
+        }
+    }
+
+    fn uptime(&self) -> std::time::Duration {
+        self.clock
+            .now()
+            .duration_since(self.started_at)
+            .unwrap_or_default()
     }
 
     fn is_valid(&self) -> bool {
@@ -0,0 +1,282 @@
+// src/maintenance/mod.rs
+// Scheduled downtime. Ahead of the window the server stops making new
+// matches so running games can finish. Games still going when it starts are
+// paused and set aside, then put back once maintenance is over. Set-aside
+// games are also written to disk, so a restart during the window keeps them.
+use crate::errors::{MaintenanceError, RecoveryError};
+use crate::messages::{LocalizedMessage, MessageCatalog};
+use crate::networking::ServerMessage;
+use crate::recovery::{load_suspended, save_suspended, SUSPENDED_FILE};
+use crate::sessions::{GameSession, SessionManager};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceWindow {
+    pub id: Uuid,
+    pub starts_at: SystemTime,
+    pub expected_end: Option<SystemTime>,
+    // How long before the start new matches stop, so games can finish
+    pub grace: Duration,
+}
+
+// What the status endpoint returns; clients show a banner from it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "state")]
+pub enum MaintenanceStatus {
+    Normal,
+    Scheduled { starts_in_secs: u64 },
+    Draining { starts_in_secs: u64 },
+    Down { ends_in_secs: Option<u64> },
+}
+
+#[derive(Debug, Default)]
+pub struct Maintenance {
+    window: Option<MaintenanceWindow>,
+    suspended: Vec<GameSession>,
+    // Where set-aside games are written; None keeps them in memory only
+    snapshot_dir: Option<PathBuf>,
+}
+
+fn secs_until(now: SystemTime, at: SystemTime) -> u64 {
+    at.duration_since(now).unwrap_or_default().as_secs()
+}
+
+impl Maintenance {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_snapshot_dir(mut self, dir: PathBuf) -> Self {
+        self.snapshot_dir = Some(dir);
+        self
+    }
+
+    // After a restart, picks up the games a previous run set aside so
+    // `finish` can hand them back. Returns how many there were.
+    pub fn recover(&mut self) -> Result<usize, RecoveryError> {
+        let Some(dir) = &self.snapshot_dir else {
+            return Ok(0);
+        };
+        let recovered = load_suspended(dir)?;
+        let count = recovered.len();
+        self.suspended.extend(recovered);
+        Ok(count)
+    }
+
+    pub fn schedule(
+        &mut self,
+        starts_at: SystemTime,
+        expected_end: Option<SystemTime>,
+        grace: Duration,
+    ) -> Result<Uuid, MaintenanceError> {
+        if expected_end.is_some_and(|end| end <= starts_at) {
+            return Err(MaintenanceError::EndsBeforeStart);
+        }
+        let window = MaintenanceWindow {
+            id: Uuid::new_v4(),
+            starts_at,
+            expected_end,
+            grace,
+        };
+        self.window = Some(window);
+        Ok(window.id)
+    }
+
+    pub fn cancel(&mut self) {
+        self.window = None;
+    }
+
+    pub fn status(&self, now: SystemTime) -> MaintenanceStatus {
+        let Some(window) = self.window else {
+            return MaintenanceStatus::Normal;
+        };
+        let starts_in_secs = secs_until(now, window.starts_at);
+        if now >= window.starts_at {
+            MaintenanceStatus::Down {
+                ends_in_secs: window.expected_end.map(|end| secs_until(now, end)),
+            }
+        } else if now
+            .checked_add(window.grace)
+            .is_none_or(|end_of_grace| end_of_grace >= window.starts_at)
+        {
+            MaintenanceStatus::Draining { starts_in_secs }
+        } else {
+            MaintenanceStatus::Scheduled { starts_in_secs }
+        }
+    }
+
+    // Gate for joining the matchmaking queue
+    pub fn check_matchmaking(&self, now: SystemTime) -> Result<(), MaintenanceError> {
+        match self.status(now) {
+            MaintenanceStatus::Normal | MaintenanceStatus::Scheduled { .. } => Ok(()),
+            MaintenanceStatus::Draining { starts_in_secs } => {
+                Err(MaintenanceError::StartingSoon { starts_in_secs })
+            }
+            MaintenanceStatus::Down { ends_in_secs } => {
+                Err(MaintenanceError::Down { ends_in_secs })
+            }
+        }
+    }
+
+    // Countdown broadcast while a window is coming up
+    pub fn notice(&self, now: SystemTime) -> Option<ServerMessage> {
+        let window = self.window?;
+        let (MaintenanceStatus::Scheduled { starts_in_secs }
+        | MaintenanceStatus::Draining { starts_in_secs }) = self.status(now)
+        else {
+            return None;
+        };
+        let message = LocalizedMessage::new("maintenance.countdown")
+            .with("minutes", starts_in_secs.div_ceil(60));
+        Some(ServerMessage::Announcement {
+            id: window.id,
+            text: MessageCatalog::server().render(&message),
+            message,
+        })
+    }
+
+    // Once the window has started, pauses every game still running and
+    // takes it out of the manager. Returns the ids set aside.
+    pub fn suspend_games(&mut self, sessions: &mut SessionManager, now: SystemTime) -> Vec<Uuid> {
+        if !matches!(self.status(now), MaintenanceStatus::Down { .. }) {
+            return Vec::new();
+        }
+        let running: Vec<Uuid> = sessions
+            .ids()
+            .filter(|game_id| {
+                sessions
+                    .get(*game_id)
+                    .is_some_and(|session| session.game.winner().is_none())
+            })
+            .collect();
+        for game_id in &running {
+            if let Some(mut session) = sessions.remove(*game_id) {
+                session.game.suspend();
+                self.suspended.push(session);
+            }
+        }
+        if let (Some(dir), false) = (&self.snapshot_dir, running.is_empty()) {
+            match save_suspended(&self.suspended, dir, now) {
+                Ok(path) => tracing::info!(path = %path.display(), "suspended games saved"),
+                Err(error) => tracing::error!(?error, "suspended games not saved"),
+            }
+        }
+        running
+    }
+
+    // Ends maintenance and hands the set-aside games back, timers running
+    // again from where they stopped
+    pub fn finish(&mut self, sessions: &mut SessionManager) -> Vec<Uuid> {
+        self.window = None;
+        if let Some(dir) = &self.snapshot_dir {
+            // Already missing is fine; nothing was suspended
+            let _ = std::fs::remove_file(dir.join(SUSPENDED_FILE));
+        }
+        self.suspended
+            .drain(..)
+            .map(|mut session| {
+                session.game.resume();
+                sessions.adopt(session)
+            })
+            .collect()
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod maintenance_tests {
+    use super::*;
+    use crate::game_state::PauseState;
+    use crate::testing::GameStateBuilder;
+
+    #[test]
+    fn test_window_blocks_queue_then_suspends_and_resumes_games() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(3600);
+        let mut maintenance = Maintenance::new();
+        maintenance
+            .schedule(
+                start,
+                Some(start + Duration::from_secs(1800)),
+                Duration::from_secs(900),
+            )
+            .unwrap();
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(GameStateBuilder::new().build());
+
+        let early = start - Duration::from_secs(1200);
+        assert!(maintenance.check_matchmaking(early).is_ok());
+        assert!(maintenance.notice(early).is_some());
+        assert!(matches!(
+            maintenance.check_matchmaking(start - Duration::from_secs(600)),
+            Err(MaintenanceError::StartingSoon {
+                starts_in_secs: 600
+            })
+        ));
+        assert!(maintenance
+            .suspend_games(&mut sessions, start - Duration::from_secs(1))
+            .is_empty());
+
+        assert_eq!(maintenance.suspend_games(&mut sessions, start), [game_id]);
+        assert!(sessions.get(game_id).is_none());
+        assert_eq!(
+            maintenance.status(start),
+            MaintenanceStatus::Down {
+                ends_in_secs: Some(1800)
+            }
+        );
+
+        assert_eq!(maintenance.finish(&mut sessions), [game_id]);
+        let game = &sessions.get(game_id).unwrap().game;
+        assert_eq!(game.pause, PauseState::Running);
+        assert_eq!(maintenance.status(start), MaintenanceStatus::Normal);
+    }
+
+    #[test]
+    fn test_endless_grace_drains_from_the_moment_it_is_scheduled() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(3600);
+        let mut maintenance = Maintenance::new();
+        maintenance.schedule(start, None, Duration::MAX).unwrap();
+
+        assert_eq!(
+            maintenance.status(SystemTime::UNIX_EPOCH),
+            MaintenanceStatus::Draining {
+                starts_in_secs: 3600
+            }
+        );
+    }
+
+    #[test]
+    fn test_suspended_games_survive_a_restart() {
+        let dir = std::env::temp_dir().join(format!("ascent-maintenance-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(3600);
+        let mut maintenance = Maintenance::new().with_snapshot_dir(dir.clone());
+        maintenance.schedule(start, None, Duration::ZERO).unwrap();
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(GameStateBuilder::new().build());
+        assert_eq!(maintenance.suspend_games(&mut sessions, start), [game_id]);
+        drop(maintenance);
+
+        // A fresh instance with nothing hosted
+        let mut maintenance = Maintenance::new().with_snapshot_dir(dir.clone());
+        let mut sessions = SessionManager::new();
+        assert_eq!(maintenance.recover().unwrap(), 1);
+        assert_eq!(maintenance.finish(&mut sessions), [game_id]);
+        assert_eq!(
+            sessions.get(game_id).unwrap().game.pause,
+            PauseState::Running
+        );
+        assert!(!dir.join(SUSPENDED_FILE).exists());
+        assert_eq!(
+            Maintenance::new()
+                .with_snapshot_dir(dir.clone())
+                .recover()
+                .unwrap(),
+            0
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,465 @@
+// src/matchmaking/mod.rs
+use crate::cards::bans::BanList;
+use crate::cards::errata::ErrataTable;
+use crate::errors::{ModerationError, QueueError};
+use crate::metrics::ErrorTelemetry;
+use crate::models::Deck;
+use crate::moderation::ModerationService;
+use crate::rating::Rating;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+// Acceptable rating gap when a player first queues, and how fast it widens
+pub const BASE_RATING_WINDOW: f64 = 100.0;
+pub const WINDOW_GROWTH_PER_SECOND: f64 = 5.0;
+pub const MAX_RATING_WINDOW: f64 = 600.0;
+
+// Round trip to the host both players may be asked to put up with, in
+// milliseconds, widening the same way so a player in a quiet region still
+// gets a game eventually
+pub const BASE_LATENCY_WINDOW_MS: u64 = 80;
+pub const LATENCY_GROWTH_MS_PER_SECOND: u64 = 2;
+pub const MAX_LATENCY_WINDOW_MS: u64 = 250;
+
+// The window constants above as live settings, so they can be retuned
+// without a restart
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct MatchmakingParams {
+    pub base_window: f64,
+    pub growth_per_second: f64,
+    pub max_window: f64,
+    pub base_latency_ms: u64,
+    pub latency_growth_ms_per_second: u64,
+    pub max_latency_ms: u64,
+}
+
+impl Default for MatchmakingParams {
+    fn default() -> Self {
+        Self {
+            base_window: BASE_RATING_WINDOW,
+            growth_per_second: WINDOW_GROWTH_PER_SECOND,
+            max_window: MAX_RATING_WINDOW,
+            base_latency_ms: BASE_LATENCY_WINDOW_MS,
+            latency_growth_ms_per_second: LATENCY_GROWTH_MS_PER_SECOND,
+            max_latency_ms: MAX_LATENCY_WINDOW_MS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueueEntry {
+    pub player_id: Uuid,
+    pub rating: Rating,
+    pub queued_at: SystemTime,
+    // Measured round trip to each candidate host region. Empty when the
+    // client hasn't reported any, which lets it be hosted anywhere.
+    pub latency: HashMap<String, Duration>,
+}
+
+impl QueueEntry {
+    // Uncertain ratings get a proportionally wider window so new players
+    // aren't stuck waiting for an exact match that may not exist.
+    pub fn rating_window(&self, now: SystemTime) -> f64 {
+        self.rating_window_with(&MatchmakingParams::default(), now)
+    }
+
+    pub fn rating_window_with(&self, params: &MatchmakingParams, now: SystemTime) -> f64 {
+        let waited = now
+            .duration_since(self.queued_at)
+            .unwrap_or(Duration::ZERO)
+            .as_secs_f64();
+        let window = params.base_window + self.rating.deviation + waited * params.growth_per_second;
+        window.min(params.max_window)
+    }
+
+    pub fn latency_window_with(&self, params: &MatchmakingParams, now: SystemTime) -> Duration {
+        let waited = now
+            .duration_since(self.queued_at)
+            .unwrap_or(Duration::ZERO)
+            .as_secs();
+        let window = params
+            .base_latency_ms
+            .saturating_add(waited.saturating_mul(params.latency_growth_ms_per_second));
+        Duration::from_millis(window.min(params.max_latency_ms))
+    }
+}
+
+// The host region that suits both players best, judged by whichever of
+// them is further from it, with that round trip. None when neither has
+// reported latency, or when they have no region in common.
+pub fn best_host(a: &QueueEntry, b: &QueueEntry) -> Option<(String, Duration)> {
+    let (known, other) = match (a.latency.is_empty(), b.latency.is_empty()) {
+        (true, true) => return None,
+        (true, false) => (b, None),
+        (false, true) => (a, None),
+        (false, false) => (a, Some(b)),
+    };
+    known
+        .latency
+        .iter()
+        .filter_map(|(region, rtt)| match other {
+            Some(other) => Some((region, (*rtt).max(*other.latency.get(region)?))),
+            None => Some((region, *rtt)),
+        })
+        .min_by(|(region_a, rtt_a), (region_b, rtt_b)| {
+            rtt_a.cmp(rtt_b).then_with(|| region_a.cmp(region_b))
+        })
+        .map(|(region, rtt)| (region.clone(), rtt))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pairing {
+    pub player1: Uuid,
+    pub player2: Uuid,
+    // Where to start the game and the worse of the two players' round
+    // trips to it. None when neither player reported latency.
+    pub host: Option<String>,
+    pub rtt: Option<Duration>,
+}
+
+// What a queue holds decks to: the current errata and, for a queue playing
+// a format's ban list, that list
+#[derive(Debug, Clone, Copy)]
+pub struct DeckRules<'a> {
+    pub errata: &'a ErrataTable,
+    pub bans: Option<&'a BanList>,
+}
+
+#[derive(Debug, Default)]
+pub struct MatchmakingQueue {
+    entries: Vec<QueueEntry>,
+    pub params: MatchmakingParams,
+    telemetry: Arc<ErrorTelemetry>,
+}
+
+impl MatchmakingQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Counts decks turned away alongside the server's other rejections
+    pub fn with_telemetry(mut self, telemetry: Arc<ErrorTelemetry>) -> Self {
+        self.telemetry = telemetry;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, player_id: Uuid) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.player_id == player_id)
+    }
+
+    // Anyone barred from matchmaking by a sanction is turned away
+    pub fn enqueue(
+        &mut self,
+        player_id: Uuid,
+        rating: Rating,
+        moderation: &ModerationService,
+        now: SystemTime,
+    ) -> Result<bool, ModerationError> {
+        self.enqueue_with_latency(player_id, rating, HashMap::new(), moderation, now)
+    }
+
+    // `latency` is the client's ping to each host region it can reach
+    pub fn enqueue_with_latency(
+        &mut self,
+        player_id: Uuid,
+        rating: Rating,
+        latency: HashMap<String, Duration>,
+        moderation: &ModerationService,
+        now: SystemTime,
+    ) -> Result<bool, ModerationError> {
+        moderation.check_matchmaking(player_id, now)?;
+        if self.contains(player_id) {
+            return Ok(false);
+        }
+        self.entries.push(QueueEntry {
+            player_id,
+            rating,
+            queued_at: now,
+            latency,
+        });
+        Ok(true)
+    }
+
+    // Decks holding a card that has been disabled since they were built, or
+    // one the queue's ban list rules out as it stands at `now`, are turned
+    // away before the player is queued
+    pub fn enqueue_with_deck(
+        &mut self,
+        player_id: Uuid,
+        rating: Rating,
+        deck: &Deck,
+        rules: DeckRules<'_>,
+        moderation: &ModerationService,
+        now: SystemTime,
+    ) -> Result<bool, QueueError> {
+        moderation
+            .check_matchmaking(player_id, now)
+            .map_err(QueueError::Moderation)?;
+        let checked = match rules.bans {
+            Some(bans) => bans.check_deck(deck, now),
+            None => Ok(()),
+        }
+        .and_then(|()| rules.errata.check_deck(deck));
+        if let Err(error) = checked {
+            self.telemetry.validation_error(&error, "queue");
+            return Err(QueueError::InvalidDeck(error));
+        }
+        self.enqueue(player_id, rating, moderation, now)
+            .map_err(QueueError::Moderation)
+    }
+
+    // Waiting players, for exporting the queue
+    pub fn entries(&self) -> &[QueueEntry] {
+        &self.entries
+    }
+
+    // Puts exported entries back, keeping their original queue times. Any
+    // player already queued here keeps their place instead.
+    pub fn restore(&mut self, entries: Vec<QueueEntry>) -> usize {
+        let before = self.entries.len();
+        for entry in entries {
+            if !self.contains(entry.player_id) {
+                self.entries.push(entry);
+            }
+        }
+        self.entries.len() - before
+    }
+
+    pub fn dequeue(&mut self, player_id: Uuid) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.player_id != player_id);
+        self.entries.len() != before
+    }
+
+    // Longest-waiting players are paired first. Of the opponents both
+    // sides' rating and latency windows accept, the one with the fastest
+    // shared host wins, then the closest rating.
+    pub fn find_pairings(&mut self, now: SystemTime) -> Vec<Pairing> {
+        self.entries.sort_by_key(|entry| entry.queued_at);
+
+        let mut pairings = Vec::new();
+        let mut i = 0;
+        while i < self.entries.len() {
+            let entry = &self.entries[i];
+            let opponent = self
+                .entries
+                .iter()
+                .enumerate()
+                .skip(i + 1)
+                .filter_map(|(j, other)| {
+                    let gap = (entry.rating.rating - other.rating.rating).abs();
+                    if gap > entry.rating_window_with(&self.params, now)
+                        || gap > other.rating_window_with(&self.params, now)
+                    {
+                        return None;
+                    }
+                    let host = best_host(entry, other);
+                    match &host {
+                        Some((_, rtt))
+                            if *rtt > entry.latency_window_with(&self.params, now)
+                                || *rtt > other.latency_window_with(&self.params, now) =>
+                        {
+                            None
+                        }
+                        // Both reported but share no region
+                        None if !entry.latency.is_empty() && !other.latency.is_empty() => None,
+                        _ => Some((j, gap, host)),
+                    }
+                })
+                .min_by(|(_, gap_a, host_a), (_, gap_b, host_b)| {
+                    let rtt =
+                        |host: &Option<(String, Duration)>| host.as_ref().map(|(_, rtt)| *rtt);
+                    rtt(host_a)
+                        .cmp(&rtt(host_b))
+                        .then_with(|| gap_a.total_cmp(gap_b))
+                });
+
+            match opponent {
+                Some((j, _, host)) => {
+                    let other = self.entries.remove(j);
+                    let entry = self.entries.remove(i);
+                    let (host, rtt) = host.unzip();
+                    pairings.push(Pairing {
+                        player1: entry.player_id,
+                        player2: other.player_id,
+                        host,
+                        rtt,
+                    });
+                }
+                None => i += 1,
+            }
+        }
+        pairings
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod matchmaking_tests {
+    use super::*;
+    use crate::moderation::SanctionKind;
+
+    fn settled(rating: f64) -> Rating {
+        Rating::new(rating, 50.0, 0.06)
+    }
+
+    #[test]
+    fn test_pairs_closest_ratings() {
+        let mut queue = MatchmakingQueue::new();
+        let now = SystemTime::now();
+        let low = Uuid::new_v4();
+        let mid = Uuid::new_v4();
+        let high = Uuid::new_v4();
+
+        let mut moderation = ModerationService::new();
+        let barred = Uuid::new_v4();
+        moderation.issue(
+            Uuid::new_v4(),
+            barred,
+            SanctionKind::MatchmakingBan,
+            Some(Duration::from_secs(3600)),
+            "leaving games".to_string(),
+            now,
+        );
+        for (player_id, rating) in [(low, 1400.0), (high, 1900.0), (mid, 1450.0)] {
+            assert!(queue
+                .enqueue(player_id, settled(rating), &moderation, now)
+                .unwrap());
+        }
+        assert!(matches!(
+            queue.enqueue(barred, settled(1420.0), &moderation, now),
+            Err(ModerationError::Sanctioned(sanction)) if sanction.player_id == barred
+        ));
+        assert!(!queue.contains(barred));
+
+        let pairings = queue.find_pairings(now);
+
+        assert_eq!(
+            pairings,
+            vec![Pairing {
+                player1: low,
+                player2: mid,
+                host: None,
+                rtt: None,
+            }]
+        );
+        assert!(queue.contains(high));
+    }
+
+    #[test]
+    fn test_window_widens_with_wait() {
+        let mut queue = MatchmakingQueue::new();
+        let start = SystemTime::now();
+        let moderation = ModerationService::new();
+        for rating in [1400.0, 1700.0] {
+            queue
+                .enqueue(Uuid::new_v4(), settled(rating), &moderation, start)
+                .unwrap();
+        }
+
+        assert!(queue.find_pairings(start).is_empty());
+        assert_eq!(
+            queue.find_pairings(start + Duration::from_secs(60)).len(),
+            1
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_prefers_low_latency_host_and_widens_for_far_players() {
+        let mut queue = MatchmakingQueue::new();
+        let start = SystemTime::now();
+        let moderation = ModerationService::new();
+        let ping = |regions: &[(&str, u64)]| -> HashMap<String, Duration> {
+            regions
+                .iter()
+                .map(|(region, ms)| (region.to_string(), Duration::from_millis(*ms)))
+                .collect()
+        };
+        let (eu, eu_close, us, far) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        queue
+            .enqueue_with_latency(
+                eu,
+                settled(1500.0),
+                ping(&[("eu", 20), ("us", 110)]),
+                &moderation,
+                start,
+            )
+            .unwrap();
+        // Closer in rating, but only a good match across the ocean
+        queue
+            .enqueue_with_latency(
+                us,
+                settled(1500.0),
+                ping(&[("eu", 120), ("us", 15)]),
+                &moderation,
+                start,
+            )
+            .unwrap();
+        queue
+            .enqueue_with_latency(
+                eu_close,
+                settled(1560.0),
+                ping(&[("eu", 30), ("us", 100)]),
+                &moderation,
+                start,
+            )
+            .unwrap();
+
+        let pairings = queue.find_pairings(start);
+        assert_eq!(
+            pairings,
+            vec![Pairing {
+                player1: eu,
+                player2: eu_close,
+                host: Some("eu".to_string()),
+                rtt: Some(Duration::from_millis(30)),
+            }]
+        );
+
+        queue
+            .enqueue_with_latency(
+                far,
+                settled(1500.0),
+                ping(&[("eu", 200)]),
+                &moderation,
+                start,
+            )
+            .unwrap();
+        // 200ms is too slow until both have waited a minute
+        assert!(queue.find_pairings(start).is_empty());
+        let pairings = queue.find_pairings(start + Duration::from_secs(60));
+        assert_eq!(pairings[0].rtt, Some(Duration::from_millis(200)));
+
+        let telemetry = crate::metrics::MatchRttTelemetry::new();
+        for measured in [190, 230] {
+            telemetry.record_match(
+                "eu",
+                Duration::from_millis(200),
+                Duration::from_millis(measured),
+            );
+        }
+        let stats = telemetry.host("eu");
+        assert_eq!((stats.matches, stats.p95), (2, Duration::from_millis(230)));
+        assert_eq!(stats.mean_excess, Duration::from_millis(15));
+    }
+}
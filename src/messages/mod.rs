@@ -0,0 +1,306 @@
+// src/messages/mod.rs
+// Anything the server tells a player in words goes out as a catalog key
+// and named parameters, never as finished English. Clients fetch the
+// catalog once, keep it by version and render each key for their own
+// locale, so rewording a message can't break a client that reads it. The
+// English templates here are the reference text and the fallback.
+use crate::errors::{
+    CoachError, GameError, LobbyError, ModerationError, ObjectiveError, ValidationError,
+};
+use crate::networking::ServerMessage;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalizedMessage {
+    pub key: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+}
+
+impl LocalizedMessage {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    // Errors are keyed by their stable code
+    pub fn error(code: &str) -> Self {
+        Self::new(&format!("error.{}", code))
+    }
+}
+
+// Every key the server sends, with its English template. Placeholders are
+// `{name}`, the same as card rules text.
+pub const CATALOG: &[(&str, &str)] = &[
+    ("error.invalid_move", "That move isn't allowed."),
+    ("error.player_not_found", "That player isn't in this game."),
+    ("error.deck_invalid", "That deck can't be used."),
+    ("error.game_not_found", "That game doesn't exist."),
+    ("error.empty_deck", "Your deck is empty."),
+    ("error.invalid_target", "That isn't a valid target."),
+    ("error.no_valid_card", "There's no card to choose."),
+    ("error.not_your_turn", "It isn't your turn."),
+    ("error.insufficient_mana", "You don't have enough mana."),
+    ("error.card_not_in_hand", "That card isn't in your hand."),
+    ("error.unknown_card_definition", "That card doesn't exist."),
+    (
+        "error.must_target_guard",
+        "A guard has to be targeted first.",
+    ),
+    ("error.tile_occupied", "Someone is already on that tile."),
+    ("error.no_line_of_sight", "You can't see that target."),
+    ("error.invalid_footprint", "That doesn't fit there."),
+    ("error.not_enough_movement", "You can't move that far."),
+    (
+        "error.game_already_started",
+        "The game has already started.",
+    ),
+    ("error.game_paused", "The game is paused."),
+    ("error.pause_not_allowed", "You can't pause right now."),
+    ("error.game_halted", "This game was stopped by an error."),
+    (
+        "error.action_limit_reached",
+        "You can't do that again this turn.",
+    ),
+    ("error.choice_pending", "Make your choice first."),
+    ("error.no_choice_pending", "There's nothing to choose."),
+    ("error.empty_batch", "Nothing was sent to play."),
+    (
+        "error.invalid_deck_size",
+        "The deck has the wrong number of cards.",
+    ),
+    (
+        "error.invalid_card_count",
+        "The deck has too many copies of a card.",
+    ),
+    (
+        "error.invalid_player_state",
+        "That player can't do this now.",
+    ),
+    ("error.disabled_card", "{card} is disabled for now."),
+    ("error.labs_card", "{card} isn't allowed in this match."),
+    ("error.banned_card", "{card} is banned in this format."),
+    (
+        "error.restricted_card",
+        "Only one copy of {card} is allowed in this format.",
+    ),
+    ("error.invalid_data", "That data couldn't be read."),
+    ("error.game_in_progress", "The game isn't over yet."),
+    ("error.not_a_player", "You aren't playing in this game."),
+    ("error.coach_is_playing", "Coaches can't also play."),
+    ("error.not_granted", "You haven't been given access."),
+    ("error.lobby_not_found", "That lobby doesn't exist."),
+    ("error.wrong_password", "Wrong password."),
+    ("error.seats_full", "All seats are taken."),
+    ("error.spectators_full", "No more spectators can join."),
+    ("error.already_joined", "You're already in this lobby."),
+    ("error.not_in_lobby", "You aren't in this lobby."),
+    ("error.not_seated", "You don't have a seat."),
+    ("error.chat_disabled", "Chat is off in this lobby."),
+    ("error.emotes_disabled", "Emotes are off in this lobby."),
+    ("error.chat_muted", "You're muted and can't chat right now."),
+    (
+        "error.matchmaking_banned",
+        "You're barred from matchmaking right now.",
+    ),
+    ("error.banned", "This account is banned."),
+    ("error.sanction_not_found", "That sanction doesn't exist."),
+    ("error.cannot_report_self", "You can't report yourself."),
+    (
+        "error.duplicate_report",
+        "You've already reported this player for this game.",
+    ),
+    ("error.report_not_found", "That report doesn't exist."),
+    (
+        "maintenance.countdown",
+        "Maintenance begins in {minutes} minutes",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub key: String,
+    pub english: String,
+    // Placeholder names in the order they appear
+    pub params: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageCatalog {
+    // Changes whenever any key or template does
+    pub version: String,
+    entries: BTreeMap<String, CatalogEntry>,
+}
+
+fn placeholders(template: &str) -> Vec<String> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+impl MessageCatalog {
+    pub fn new(templates: &[(&str, &str)]) -> Self {
+        let entries: BTreeMap<String, CatalogEntry> = templates
+            .iter()
+            .map(|(key, english)| {
+                let entry = CatalogEntry {
+                    key: key.to_string(),
+                    english: english.to_string(),
+                    params: placeholders(english),
+                };
+                (entry.key.clone(), entry)
+            })
+            .collect();
+        let mut hasher = Sha256::new();
+        for entry in entries.values() {
+            hasher.update(entry.key.as_bytes());
+            hasher.update([0]);
+            hasher.update(entry.english.as_bytes());
+            hasher.update([0]);
+        }
+        let version = format!("{:x}", hasher.finalize())[..16].to_string();
+        Self { version, entries }
+    }
+
+    pub fn server() -> Self {
+        Self::new(CATALOG)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&CatalogEntry> {
+        self.entries.get(key)
+    }
+
+    // The English text, for logs and for clients without the catalog. An
+    // unknown key comes back as itself.
+    pub fn render(&self, message: &LocalizedMessage) -> String {
+        let Some(entry) = self.get(&message.key) else {
+            return message.key.clone();
+        };
+        message
+            .params
+            .iter()
+            .fold(entry.english.clone(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), value)
+            })
+    }
+
+    // Answers a catalog request. A client already holding this version
+    // gets the version back and no entries.
+    pub fn to_message(&self, known_version: Option<&str>) -> ServerMessage {
+        let entries = if known_version == Some(self.version.as_str()) {
+            Vec::new()
+        } else {
+            self.entries.values().cloned().collect()
+        };
+        ServerMessage::MessageCatalog {
+            version: self.version.clone(),
+            entries,
+        }
+    }
+}
+
+impl GameError {
+    pub fn message(&self) -> LocalizedMessage {
+        LocalizedMessage::error(self.code())
+    }
+}
+
+impl ValidationError {
+    pub fn message(&self) -> LocalizedMessage {
+        let message = LocalizedMessage::error(self.code());
+        match self {
+            ValidationError::DisabledCard(card)
+            | ValidationError::LabsCard(card)
+            | ValidationError::BannedCard(card)
+            | ValidationError::RestrictedCard(card) => message.with("card", card),
+            _ => message,
+        }
+    }
+}
+
+impl LobbyError {
+    pub fn message(&self) -> LocalizedMessage {
+        LocalizedMessage::error(self.code())
+    }
+}
+
+impl CoachError {
+    pub fn message(&self) -> LocalizedMessage {
+        LocalizedMessage::error(self.code())
+    }
+}
+
+impl ObjectiveError {
+    pub fn message(&self) -> LocalizedMessage {
+        LocalizedMessage::error(self.code())
+    }
+}
+
+impl ModerationError {
+    pub fn message(&self) -> LocalizedMessage {
+        LocalizedMessage::error(self.code())
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod messages_tests {
+    use super::*;
+
+    #[test]
+    fn test_every_sent_key_is_in_the_catalog() {
+        let catalog = MessageCatalog::server();
+        let sent = [
+            GameError::NotYourTurn.message(),
+            GameError::EmptyBatch.message(),
+            ValidationError::InvalidCardCount.message(),
+            ValidationError::BannedCard("Avalanche".to_string()).message(),
+            LobbyError::WrongPassword.message(),
+            CoachError::NotGranted.message(),
+            ObjectiveError::GameInProgress.message(),
+            ModerationError::DuplicateReport.message(),
+            LobbyError::Muted.message(),
+            LocalizedMessage::new("maintenance.countdown").with("minutes", 5),
+        ];
+        for message in &sent {
+            let entry = catalog.get(&message.key).unwrap();
+            assert_eq!(
+                entry.params,
+                message.params.keys().cloned().collect::<Vec<_>>(),
+                "{}",
+                message.key
+            );
+        }
+        assert_eq!(
+            catalog.render(&sent[3]),
+            "Avalanche is banned in this format."
+        );
+        assert_eq!(catalog.render(&LocalizedMessage::new("unknown")), "unknown");
+
+        let ServerMessage::MessageCatalog { version, entries } = catalog.to_message(None) else {
+            panic!("not a catalog");
+        };
+        assert_eq!(entries.len(), CATALOG.len());
+        assert_eq!(
+            catalog.to_message(Some(&version)),
+            ServerMessage::MessageCatalog {
+                version,
+                entries: Vec::new()
+            }
+        );
+        let reworded = MessageCatalog::new(&[("error.not_your_turn", "Wait your turn.")]);
+        assert_ne!(reworded.version, catalog.version);
+    }
+}
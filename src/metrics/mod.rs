@@ -0,0 +1,287 @@
+// src/metrics/mod.rs
+// Counters for errors handed back to clients. Each rejection is counted by
+// error code, endpoint and game phase, and logged with a fingerprint, so a
+// spike after a patch shows up both on the dashboard and in traces.
+use crate::cards::labs::LabsTable;
+use crate::errors::{GameError, LobbyError, ValidationError};
+use crate::game_state::{GameState, PauseState};
+use crate::models::{Deck, Format};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamePhase {
+    NotStarted,
+    InProgress,
+    Paused,
+    Finished,
+}
+
+impl GamePhase {
+    pub fn of(game_state: &GameState) -> GamePhase {
+        if game_state.winner().is_some() {
+            GamePhase::Finished
+        } else if matches!(game_state.pause, PauseState::Paused { .. }) {
+            GamePhase::Paused
+        } else if !game_state.has_started() {
+            GamePhase::NotStarted
+        } else {
+            GamePhase::InProgress
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GamePhase::NotStarted => "not_started",
+            GamePhase::InProgress => "in_progress",
+            GamePhase::Paused => "paused",
+            GamePhase::Finished => "finished",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ErrorKey {
+    pub code: &'static str,
+    pub endpoint: &'static str, // e.g. "action", "deck_submit"
+    pub phase: Option<GamePhase>,
+}
+
+impl ErrorKey {
+    // Same inputs give the same value on every node and every build, so
+    // traces can be grouped by it
+    pub fn fingerprint(&self) -> String {
+        let phase = self.phase.map_or("none", |phase| phase.name());
+        let hash = [self.code, self.endpoint, phase]
+            .join("/")
+            .bytes()
+            .fold(0xcbf29ce484222325, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+            });
+        format!("{:016x}", hash)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ErrorTelemetry {
+    counts: Mutex<HashMap<ErrorKey, u64>>,
+}
+
+impl ErrorTelemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, key: ErrorKey) {
+        *self.counts.lock().unwrap().entry(key).or_default() += 1;
+        tracing::warn!(
+            code = key.code,
+            endpoint = key.endpoint,
+            phase = key.phase.map(|phase| phase.name()),
+            fingerprint = %key.fingerprint(),
+            "request rejected"
+        );
+    }
+
+    pub fn game_error(&self, error: &GameError, endpoint: &'static str, game_state: &GameState) {
+        self.record(ErrorKey {
+            code: error.code(),
+            endpoint,
+            phase: Some(GamePhase::of(game_state)),
+        });
+    }
+
+    pub fn validation_error(&self, error: &ValidationError, endpoint: &'static str) {
+        self.record(ErrorKey {
+            code: error.code(),
+            endpoint,
+            phase: None,
+        });
+    }
+
+    pub fn lobby_error(&self, error: &LobbyError, endpoint: &'static str) {
+        self.record(ErrorKey {
+            code: error.code(),
+            endpoint,
+            phase: None,
+        });
+    }
+
+    pub fn count(&self, key: &ErrorKey) -> u64 {
+        self.counts.lock().unwrap().get(key).copied().unwrap_or(0)
+    }
+
+    // Totals per error code across endpoints and phases, for dashboards
+    pub fn by_code(&self) -> HashMap<&'static str, u64> {
+        let mut totals = HashMap::new();
+        for (key, count) in self.counts.lock().unwrap().iter() {
+            *totals.entry(key.code).or_default() += count;
+        }
+        totals
+    }
+}
+
+// Games and wins for decks holding a labs card, next to the same counts
+// for decks without any, so a trial can be judged against the baseline
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LabsStats {
+    pub games: u64,
+    pub wins: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct LabsTelemetry {
+    by_card: Mutex<HashMap<(String, Format), LabsStats>>,
+    baseline: Mutex<HashMap<Format, LabsStats>>,
+}
+
+impl LabsTelemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Each seat's deck with the player it belongs to
+    pub fn record_match(
+        &self,
+        labs: &LabsTable,
+        format: Format,
+        decks: &[(Uuid, &Deck)],
+        winner: Option<Uuid>,
+    ) {
+        for (player_id, deck) in decks {
+            let won = winner == Some(*player_id);
+            let cards = labs.labs_cards(deck);
+            if cards.is_empty() {
+                let mut baseline = self.baseline.lock().unwrap();
+                let stats = baseline.entry(format).or_default();
+                stats.games += 1;
+                stats.wins += won as u64;
+                continue;
+            }
+            let mut by_card = self.by_card.lock().unwrap();
+            for name in cards {
+                tracing::info!(card = %name, won, "labs card played");
+                let stats = by_card.entry((name, format)).or_default();
+                stats.games += 1;
+                stats.wins += won as u64;
+            }
+        }
+    }
+
+    pub fn card(&self, name: &str, format: Format) -> LabsStats {
+        self.by_card
+            .lock()
+            .unwrap()
+            .get(&(name.to_string(), format))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn baseline(&self, format: Format) -> LabsStats {
+        self.baseline
+            .lock()
+            .unwrap()
+            .get(&format)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+// How many match round trips are kept per host region
+pub const RTT_SAMPLES: usize = 1_000;
+
+// Round trips measured once matches were under way, per host region, next
+// to what matchmaking expected when it paired them. Used to tune the
+// latency windows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RttStats {
+    pub matches: usize,
+    pub mean: Duration,
+    pub p95: Duration,
+    // How much slower than expected matches ran, on average
+    pub mean_excess: Duration,
+}
+
+#[derive(Debug, Default)]
+pub struct MatchRttTelemetry {
+    // (expected, measured), oldest first
+    by_host: Mutex<HashMap<String, VecDeque<(Duration, Duration)>>>,
+}
+
+impl MatchRttTelemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_match(&self, host: &str, expected: Duration, measured: Duration) {
+        tracing::info!(
+            host,
+            expected_ms = expected.as_millis() as u64,
+            measured_ms = measured.as_millis() as u64,
+            "match rtt"
+        );
+        let mut by_host = self.by_host.lock().unwrap();
+        let samples = by_host.entry(host.to_string()).or_default();
+        if samples.len() == RTT_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back((expected, measured));
+    }
+
+    pub fn host(&self, host: &str) -> RttStats {
+        let by_host = self.by_host.lock().unwrap();
+        let Some(samples) = by_host.get(host).filter(|samples| !samples.is_empty()) else {
+            return RttStats::default();
+        };
+        let mut measured: Vec<Duration> = samples.iter().map(|(_, measured)| *measured).collect();
+        measured.sort_unstable();
+        let count = measured.len() as u32;
+        let excess: Duration = samples
+            .iter()
+            .map(|(expected, measured)| measured.saturating_sub(*expected))
+            .sum();
+        RttStats {
+            matches: measured.len(),
+            mean: measured.iter().sum::<Duration>() / count,
+            p95: measured[(measured.len() * 95).div_ceil(100) - 1],
+            mean_excess: excess / count,
+        }
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod metrics_tests {
+    use super::*;
+    use crate::testing::GameStateBuilder;
+
+    #[test]
+    fn test_rejections_are_counted_by_code_endpoint_and_phase() {
+        let telemetry = ErrorTelemetry::new();
+        let game_state = GameStateBuilder::new().build();
+        let phase = GamePhase::of(&game_state);
+
+        telemetry.game_error(&GameError::InvalidTarget, "action", &game_state);
+        telemetry.game_error(&GameError::InvalidTarget, "action", &game_state);
+        telemetry.validation_error(&ValidationError::InvalidDeckSize, "deck_submit");
+
+        let key = ErrorKey {
+            code: "invalid_target",
+            endpoint: "action",
+            phase: Some(phase),
+        };
+        assert_eq!(telemetry.count(&key), 2);
+        assert_eq!(telemetry.by_code()["invalid_deck_size"], 1);
+        assert_eq!(key.fingerprint(), key.fingerprint());
+        assert_ne!(
+            key.fingerprint(),
+            ErrorKey {
+                endpoint: "deck_submit",
+                ..key
+            }
+            .fingerprint()
+        );
+    }
+}
@@ -1,10 +1,74 @@
 // src/models/mod.rs
-use crate::effects::{CostFilter, DrawFilter, Duration, Effect, EffectType};
-use crate::errors::GameError;
+use crate::effects::{
+    CostModifier, DrawFilter, Duration, Effect, EffectType, FieldRule, SourceRemoval, Status,
+    StatusKind,
+};
+use crate::errors::{GameError, ValidationError};
+use serde::{Deserialize, Serialize};
+use serde::{Deserializer, Serializer};
+use smallvec::SmallVec;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 
 use uuid::Uuid;
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Eq)]
+// Arc-backed storage that is only copied when written through, so cloning
+// a GameState for previews or AI rollouts shares untouched tiles and decks.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Shared<T: Clone>(Arc<T>);
+
+impl<T: Clone> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+impl<T: Clone> From<T> for Shared<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Clone> FromIterator<T> for Shared<Vec<T>> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<T: Clone> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Clone> DerefMut for Shared<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl<T: Clone + Serialize> Serialize for Shared<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Clone + Deserialize<'de>> Deserialize<'de> for Shared<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Shared::new)
+    }
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Serialize, Deserialize)]
 pub enum Rarity {
     Common,
     Uncommon,
@@ -12,33 +76,82 @@ pub enum Rarity {
     Legendary,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CardType {
     Climber,
     Spell,
     Weapon,
     Trap,
     Gear,
+    // Stays in the shared expedition zone, see GameState::expedition
+    Expedition,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Card {
+    #[serde(default = "Uuid::new_v4")]
     pub id: Uuid,
     pub name: String,
     pub cost: u32,
     pub power: u32,
     pub rarity: Rarity,
+    #[serde(default)]
     pub effects: Vec<Effect>,
     pub card_type: CardType,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Deck {
-    pub cards: Vec<Card>,
+    pub cards: Shared<Vec<Card>>,
     pub owner_id: Uuid,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Format {
+    Constructed,
+    Draft,
+}
+
+impl Format {
+    pub fn deck_size(&self) -> usize {
+        30
+    }
+
+    // Drafted decks may hold any number of copies of whatever was picked
+    pub fn max_copies(&self, rarity: &Rarity) -> Option<usize> {
+        match (self, rarity) {
+            (Format::Draft, _) => None,
+            (Format::Constructed, Rarity::Legendary) => Some(1),
+            (Format::Constructed, _) => Some(3),
+        }
+    }
+}
+
+impl Deck {
+    // Copies are counted by card name, since every card instance has its own id
+    pub fn validate(&self, format: Format) -> Result<(), ValidationError> {
+        if self.cards.len() != format.deck_size() {
+            return Err(ValidationError::InvalidDeckSize);
+        }
+
+        let mut copies: HashMap<&str, usize> = HashMap::new();
+        for card in self.cards.iter() {
+            let count = copies.entry(card.name.as_str()).or_default();
+            *count += 1;
+            if format
+                .max_copies(&card.rarity)
+                .is_some_and(|max| *count > max)
+            {
+                return Err(ValidationError::InvalidCardCount);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub struct Position {
     pub x: u32,
     pub y: u32,
@@ -46,21 +159,112 @@ pub struct Position {
     pub level: u32,
 }
 
-#[derive(Debug, Clone)]
+// A stat boost and where it came from. Boosts without a source are never
+// touched by source cleanup.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Boost {
+    pub amount: u32,
+    pub duration: Duration,
+    pub source: Option<Uuid>,
+    pub on_source_removed: SourceRemoval,
+}
+
+impl Boost {
+    pub fn new(amount: u32, duration: Duration) -> Self {
+        Self {
+            amount,
+            duration,
+            source: None,
+            on_source_removed: SourceRemoval::Persist,
+        }
+    }
+
+    pub fn from_source(mut self, source: Uuid, on_source_removed: SourceRemoval) -> Self {
+        self.source = Some(source);
+        self.on_source_removed = on_source_removed;
+        self
+    }
+
+    fn expires_with(&self, source: Uuid) -> bool {
+        self.source == Some(source) && self.on_source_removed == SourceRemoval::Expire
+    }
+}
+
+pub type BoostList = SmallVec<[Boost; 4]>;
+pub type ActiveEffectList = SmallVec<[(EffectType, Duration); 4]>;
+pub type StatusList = SmallVec<[Status; 4]>;
+pub type MaxHealthModifiers = SmallVec<[(i32, Duration); 2]>;
+pub type CostModifiers = SmallVec<[CostModifier; 2]>;
+
+// Stats derived from the hand and boost lists. They are computed on first
+// use and dropped whenever one of those inputs changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DerivedStats {
+    pub power: u32,
+    pub speed: u32, // Movement points at the start of each turn
+    pub bonus_health: u32,
+    pub max_health_delta: i64,
+}
+
+fn saturating_sum(values: impl Iterator<Item = u32>) -> u32 {
+    values.fold(0, u32::saturating_add)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatCache(OnceLock<DerivedStats>);
+
+impl StatCache {
+    fn get_or_compute(&self, compute: impl FnOnce() -> DerivedStats) -> DerivedStats {
+        *self.0.get_or_init(compute)
+    }
+
+    fn invalidate(&mut self) {
+        self.0.take();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
     pub id: Uuid,
     pub name: String,
     pub health: u32,
     pub hand: Vec<Card>,
     pub deck: Deck,
+    pub graveyard: Vec<Card>,
+    pub fatigue: u32, // Damage the next empty-deck draw deals
     pub mana: u32,
     pub position: Position,
     pub max_health: u32,
-    pub power_boosts: Vec<(u32, Duration)>,
-    pub health_boosts: Vec<(u32, Duration)>,
-    pub active_effects: Vec<(EffectType, Duration)>,
+    pub power_boosts: BoostList,
+    pub health_boosts: BoostList,
+    pub active_effects: ActiveEffectList,
+    pub statuses: StatusList,
+    // Temporary changes to base max health; permanent ones edit it directly
+    pub max_health_modifiers: MaxHealthModifiers,
+    pub cost_modifiers: CostModifiers,
     pub cards_played_this_turn: u32,
     pub mana_spent_this_turn: u32,
+    pub moves_this_turn: u32,
+    // Absorbs damage before health; halves at the start of each turn
+    pub shield: u32,
+    // Movement points left this turn
+    pub movement: u32,
+    // Turns owed to or taken from this player, used up by the turn order
+    pub extra_turns: u32,
+    pub skipped_turns: u32,
+    // Bonus mana waiting for this player's next turn start
+    pub mana_tokens: u32,
+    // Crystals borrowed by Overload cards this turn, locked at the next
+    // turn start
+    pub overload: u32,
+    // Crystals locked this turn by the last turn's overload
+    pub locked_mana: u32,
+    // Rules from the expedition in play, copied onto every player
+    pub field_rules: Vec<FieldRule>,
+    // Code that writes `hand` or the boost lists directly must call
+    // `invalidate_stats` afterwards.
+    #[serde(skip)]
+    pub stats: StatCache,
 }
 
 impl Player {
@@ -72,20 +276,102 @@ impl Player {
             max_health: 30,
             hand: Vec::new(),
             deck,
+            graveyard: Vec::new(),
+            fatigue: 0,
             mana: 0,
             position: Position::default(),
-            power_boosts: Vec::new(),
-            health_boosts: Vec::new(),
-            active_effects: Vec::new(),
+            power_boosts: SmallVec::new(),
+            health_boosts: SmallVec::new(),
+            active_effects: SmallVec::new(),
+            statuses: SmallVec::new(),
+            max_health_modifiers: SmallVec::new(),
+            cost_modifiers: SmallVec::new(),
             cards_played_this_turn: 0,
             mana_spent_this_turn: 0,
+            moves_this_turn: 0,
+            shield: 0,
+            movement: BASE_SPEED,
+            extra_turns: 0,
+            skipped_turns: 0,
+            mana_tokens: 0,
+            overload: 0,
+            locked_mana: 0,
+            field_rules: Vec::new(),
+            stats: StatCache::default(),
         }
     }
 
+    pub fn derived_stats(&self) -> DerivedStats {
+        self.stats.get_or_compute(|| {
+            let base_power = saturating_sum(self.hand.iter().map(|card| card.power));
+            let boost_power = saturating_sum(self.power_boosts.iter().map(|boost| boost.amount));
+            let weakened = saturating_sum(self.statuses.iter().map(|status| match status.kind {
+                StatusKind::Weakened { power } => power,
+                _ => 0,
+            }));
+            // Each piece of Gear carried adds a point of movement
+            let gear = self
+                .hand
+                .iter()
+                .filter(|card| card.card_type == CardType::Gear)
+                .count() as u32;
+            let (hasted, slowed) = self
+                .statuses
+                .iter()
+                .fold((0u32, 0u32), |(up, down), status| match status.kind {
+                    StatusKind::Hasted { movement } => (up.saturating_add(movement), down),
+                    StatusKind::Slowed { movement } => (up, down.saturating_add(movement)),
+                    _ => (up, down),
+                });
+            let (field_power, field_movement) =
+                self.field_rules
+                    .iter()
+                    .fold((0i32, 0i32), |(power, movement), rule| match rule {
+                        FieldRule::Power(delta) => (power.saturating_add(*delta), movement),
+                        FieldRule::Movement(delta) => (power, movement.saturating_add(*delta)),
+                        FieldRule::Cost(_) => (power, movement),
+                    });
+            DerivedStats {
+                power: base_power
+                    .saturating_add(boost_power)
+                    .saturating_sub(weakened)
+                    .saturating_add_signed(field_power),
+                speed: BASE_SPEED
+                    .saturating_add(gear)
+                    .saturating_add(hasted)
+                    .saturating_sub(slowed)
+                    .saturating_add_signed(field_movement),
+                bonus_health: saturating_sum(self.health_boosts.iter().map(|boost| boost.amount)),
+                max_health_delta: self
+                    .max_health_modifiers
+                    .iter()
+                    .map(|(delta, _)| i64::from(*delta))
+                    .sum(),
+            }
+        })
+    }
+
+    pub fn invalidate_stats(&mut self) {
+        self.stats.invalidate();
+    }
+
     pub fn get_power(&self) -> u32 {
-        let base_power: u32 = self.hand.iter().map(|card| card.power).sum();
-        let boost_power: u32 = self.power_boosts.iter().map(|(amount, _)| amount).sum();
-        base_power + boost_power
+        self.derived_stats().power
+    }
+
+    // What `card` costs this player to play right now, never below zero
+    pub fn effective_cost(&self, card: &Card) -> u32 {
+        let delta: i32 = self
+            .cost_modifiers
+            .iter()
+            .filter(|modifier| modifier.applies(self, card))
+            .map(|modifier| modifier.delta)
+            .chain(self.field_rules.iter().map(|rule| match rule {
+                FieldRule::Cost(delta) => *delta,
+                _ => 0,
+            }))
+            .sum();
+        card.cost.saturating_add_signed(delta)
     }
 
     pub fn has_effect(&self, effect_type: &EffectType) -> bool {
@@ -94,26 +380,65 @@ impl Player {
             .any(|(effect, _)| effect == effect_type)
     }
 
+    pub fn has_status(&self, matches: impl Fn(&StatusKind) -> bool) -> bool {
+        self.statuses.iter().any(|status| matches(&status.kind))
+    }
+
+    pub fn add_status(&mut self, status: Status) {
+        self.statuses.push(status);
+        self.invalidate_stats();
+    }
+
+    // Removes matching statuses and returns how many went
+    pub fn remove_statuses(&mut self, matches: impl Fn(&StatusKind) -> bool) -> u32 {
+        let before = self.statuses.len();
+        self.statuses.retain(|status| !matches(&status.kind));
+        self.invalidate_stats();
+        (before - self.statuses.len()) as u32
+    }
+
+    // Modifiers reshape the base first (never below 1); health boosts then
+    // stack on top, so a boost survives a max health reduction intact
     pub fn max_health(&self) -> u32 {
-        let boost_health: u32 = self.health_boosts.iter().map(|(amount, _)| amount).sum();
-        self.max_health + boost_health
+        let stats = self.derived_stats();
+        let base = (i64::from(self.max_health) + stats.max_health_delta).clamp(1, u32::MAX.into());
+        (base as u32).saturating_add(stats.bonus_health)
+    }
+
+    pub fn modify_max_health(&mut self, delta: i32, duration: Duration) {
+        match duration {
+            Duration::Permanent => {
+                self.max_health = self.max_health.saturating_add_signed(delta).max(1);
+            }
+            _ => self.max_health_modifiers.push((delta, duration)),
+        }
+        self.invalidate_stats();
+        self.clamp_health();
+    }
+
+    pub fn add_to_hand(&mut self, card: Card) {
+        self.hand.push(card);
+        self.invalidate_stats();
+    }
+
+    pub fn remove_from_hand(&mut self, index: usize) -> Card {
+        let card = self.hand.remove(index);
+        self.invalidate_stats();
+        card
+    }
+
+    pub fn set_hand(&mut self, hand: Vec<Card>) {
+        self.hand = hand;
+        self.invalidate_stats();
     }
 
     pub fn draw_filtered(&mut self, filter: &DrawFilter) -> Result<(), GameError> {
-        let card_position = self.deck.cards.iter().position(|card| match filter {
-            DrawFilter::Cost(cost_filter) => match cost_filter {
-                CostFilter::Equal(cost) => card.cost == *cost,
-                CostFilter::LessThan(cost) => card.cost < *cost,
-                CostFilter::GreaterThan(cost) => card.cost > *cost,
-            },
-            DrawFilter::Type(card_type) => card.card_type == *card_type,
-            DrawFilter::Rarity(rarity) => card.rarity == *rarity,
-        });
+        let card_position = self.deck.cards.iter().position(|card| filter.matches(card));
 
         match card_position {
             Some(pos) => {
                 let card = self.deck.cards.remove(pos);
-                self.hand.push(card);
+                self.add_to_hand(card);
                 Ok(())
             }
             None => Err(GameError::NoValidCard),
@@ -126,52 +451,96 @@ impl Player {
         }
 
         let card = self.deck.cards.remove(0);
-        self.hand.push(card);
+        self.add_to_hand(card);
         Ok(())
     }
 
+    // Moves up to `count` cards from the top of the deck to the graveyard
+    // and returns how many went
+    pub fn mill(&mut self, count: u32) -> u32 {
+        let count = (count as usize).min(self.deck.cards.len());
+        self.graveyard.extend(self.deck.cards.drain(..count));
+        count as u32
+    }
+
     pub fn add_power_boost(&mut self, amount: u32, duration: Duration) {
-        self.power_boosts.push((amount, duration));
+        self.push_power_boost(Boost::new(amount, duration));
     }
 
     pub fn add_health_boost(&mut self, amount: u32, duration: Duration) {
-        self.health_boosts.push((amount, duration));
+        self.push_health_boost(Boost::new(amount, duration));
+    }
 
-        let new_max = self.max_health();
-        self.health = self.health.min(new_max);
+    pub fn push_power_boost(&mut self, boost: Boost) {
+        self.power_boosts.push(boost);
+        self.invalidate_stats();
+    }
+
+    pub fn push_health_boost(&mut self, boost: Boost) {
+        self.health_boosts.push(boost);
+        self.invalidate_stats();
     }
 
     pub fn add_buff(&mut self, power: i32, health: i32, duration: Duration) {
         if power > 0 {
-            self.add_power_boost(power as u32, duration.clone());
+            self.add_power_boost(power as u32, duration);
         }
         if health > 0 {
             self.add_health_boost(health as u32, duration);
         }
     }
 
+    // Drops boosts that expire with `source`. Returns whether any went.
+    pub fn remove_boosts_from(&mut self, source: Uuid) -> bool {
+        let before = self.power_boosts.len() + self.health_boosts.len() + self.cost_modifiers.len();
+        self.power_boosts
+            .retain(|boost| !boost.expires_with(source));
+        self.health_boosts
+            .retain(|boost| !boost.expires_with(source));
+        self.cost_modifiers
+            .retain(|modifier| !modifier.expires_with(source));
+        if self.power_boosts.len() + self.health_boosts.len() + self.cost_modifiers.len() == before
+        {
+            return false;
+        }
+
+        self.invalidate_stats();
+        self.clamp_health();
+        true
+    }
+
+    // Losing a health boost lowers the cap; current health follows it down
+    fn clamp_health(&mut self) {
+        self.health = self.health.min(self.max_health());
+    }
+
     pub fn update_turn(&mut self) {
         self.cards_played_this_turn = 0;
         self.mana_spent_this_turn = 0;
+        self.moves_this_turn = 0;
+        self.shield /= 2;
 
         // Update durations and remove expired effects
         self.update_durations();
+        self.movement = self.derived_stats().speed;
     }
 
     fn update_durations(&mut self) {
         // Update power boosts
-        self.power_boosts.retain(|(_, duration)| match duration {
-            Duration::Temporary(turns) => *turns > 0,
-            Duration::UntilMountainLevel(_) => true,
-            Duration::Permanent => true,
-        });
+        self.power_boosts
+            .retain(|Boost { duration, .. }| match duration {
+                Duration::Temporary(turns) => *turns > 0,
+                Duration::UntilMountainLevel(_) => true,
+                Duration::Permanent => true,
+            });
 
         // Update health boosts
-        self.health_boosts.retain(|(_, duration)| match duration {
-            Duration::Temporary(turns) => *turns > 0,
-            Duration::UntilMountainLevel(_) => true,
-            Duration::Permanent => true,
-        });
+        self.health_boosts
+            .retain(|Boost { duration, .. }| match duration {
+                Duration::Temporary(turns) => *turns > 0,
+                Duration::UntilMountainLevel(_) => true,
+                Duration::Permanent => true,
+            });
 
         // Update active effects
         self.active_effects.retain(|(_, duration)| match duration {
@@ -181,13 +550,13 @@ impl Player {
         });
 
         // Decrease temporary durations separately for each type
-        for (_, duration) in self.power_boosts.iter_mut() {
+        for Boost { duration, .. } in self.power_boosts.iter_mut() {
             if let Duration::Temporary(turns) = duration {
                 *turns = turns.saturating_sub(1);
             }
         }
 
-        for (_, duration) in self.health_boosts.iter_mut() {
+        for Boost { duration, .. } in self.health_boosts.iter_mut() {
             if let Duration::Temporary(turns) = duration {
                 *turns = turns.saturating_sub(1);
             }
@@ -198,32 +567,156 @@ impl Player {
                 *turns = turns.saturating_sub(1);
             }
         }
+
+        self.max_health_modifiers
+            .retain(|(_, duration)| match duration {
+                Duration::Temporary(turns) => *turns > 0,
+                Duration::UntilMountainLevel(_) => true,
+                Duration::Permanent => true,
+            });
+        for (_, duration) in self.max_health_modifiers.iter_mut() {
+            if let Duration::Temporary(turns) = duration {
+                *turns = turns.saturating_sub(1);
+            }
+        }
+
+        self.cost_modifiers
+            .retain(|modifier| match modifier.duration {
+                Duration::Temporary(turns) => turns > 0,
+                Duration::UntilMountainLevel(_) => true,
+                Duration::Permanent => true,
+            });
+        for modifier in self.cost_modifiers.iter_mut() {
+            if let Duration::Temporary(turns) = &mut modifier.duration {
+                *turns = turns.saturating_sub(1);
+            }
+        }
+
+        self.statuses.retain(|status| match status.duration {
+            Duration::Temporary(turns) => turns > 0,
+            Duration::UntilMountainLevel(_) => true,
+            Duration::Permanent => true,
+        });
+        for status in self.statuses.iter_mut() {
+            if let Duration::Temporary(turns) = &mut status.duration {
+                *turns = turns.saturating_sub(1);
+            }
+        }
+
+        self.invalidate_stats();
+        self.clamp_health();
     }
 }
 
 // Mountain is our gameboard where the game is played
-// it is made up of hexagonal tiles in elevated stages
-#[derive(Debug, PartialEq)]
+// it is made up of hexagonal tiles in elevated stages. Level n is the ring
+// of tiles whose largest coordinate is n, and a tile's neighbours are the
+// tiles one step away under `calculate_distance`, the same tiles
+// `is_valid_move` accepts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "MountainLayout", into = "MountainLayout")]
 pub struct Mountain {
-    pub tiles: Vec<Tile>,
+    pub tiles: Shared<Vec<Tile>>,
     pub levels: u32,
+    pub camps: Vec<Position>,
+    tile_index: Shared<HashMap<(u32, u32, u32), usize>>,
+    neighbors: Shared<Vec<NeighborList>>,
+    rings: Shared<Vec<Vec<usize>>>,
+}
+
+pub type NeighborList = SmallVec<[usize; 8]>;
+
+// A mountain as stored; the lookup tables are rebuilt on load
+#[derive(Clone, Serialize, Deserialize)]
+struct MountainLayout {
+    levels: u32,
+    tiles: Vec<Tile>,
+    camps: Vec<Position>,
 }
 
-#[derive(Debug, PartialEq)]
+impl From<MountainLayout> for Mountain {
+    fn from(layout: MountainLayout) -> Self {
+        Mountain::from_tiles(layout.levels, layout.tiles, layout.camps)
+    }
+}
+
+impl From<Mountain> for MountainLayout {
+    fn from(mountain: Mountain) -> Self {
+        MountainLayout {
+            levels: mountain.levels,
+            tiles: mountain.tiles.to_vec(),
+            camps: mountain.camps,
+        }
+    }
+}
+
+// Movement points a player starts each turn with before Gear and statuses
+pub const BASE_SPEED: u32 = 2;
+
+// Every this many levels the climb has a camp, short of the summit
+pub const CAMP_INTERVAL: u32 = 3;
+
+// Generated mountains by level count, built once per process and handed out
+// as clones that share their tiles and lookup tables until written to
+static PRESETS: OnceLock<Mutex<HashMap<u32, Mountain>>> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TileContent {
     Empty,
     Card(Card),
     Trap(Card),
     Player(Uuid),
+    // Covered by a large unit whose card sits on its anchor tile
+    Part(Uuid),
+}
+
+impl TileContent {
+    // The unit standing here, whether this is its anchor tile or not
+    pub fn unit_id(&self) -> Option<Uuid> {
+        match self {
+            TileContent::Card(card) => Some(card.id),
+            TileContent::Part(id) => Some(*id),
+            _ => None,
+        }
+    }
+}
+
+// Ground under a tile, set by custom mountains
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Terrain {
+    #[default]
+    Rock,
+    Scree, // Loose ground; one extra movement point to step onto
+}
+
+impl Terrain {
+    pub fn extra_cost(&self) -> u32 {
+        match self {
+            Terrain::Rock => 0,
+            Terrain::Scree => 1,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Tile {
     pub x: u32,
     pub y: u32,
     pub z: u32,
     pub level: u32,
     pub content: TileContent,
+    pub terrain: Terrain,
+}
+
+impl Tile {
+    pub fn position(&self) -> Position {
+        Position {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            level: self.level,
+        }
+    }
 }
 
 impl Mountain {
@@ -236,67 +729,317 @@ impl Mountain {
         }
         let mut tiles = Vec::new();
 
+        // Each level is the ring of tiles exactly `level` steps from the centre
         for level in 0..levels {
             for x in -(level as i32)..=level as i32 {
                 for y in -(level as i32)..=level as i32 {
                     let z = -(x + y);
-                    if z.unsigned_abs() <= level {
+                    if x.unsigned_abs().max(y.unsigned_abs()).max(z.unsigned_abs()) == level {
                         tiles.push(Tile {
                             x: x.unsigned_abs(),
                             y: y.unsigned_abs(),
                             z: z.unsigned_abs(),
                             level,
                             content: TileContent::Empty,
+                            terrain: Terrain::Rock,
                         });
                     }
                 }
             }
         }
 
-        Self { tiles, levels }
+        // One camp on each camp level, on the first tile generated for it
+        let camps = (CAMP_INTERVAL..levels.saturating_sub(1))
+            .step_by(CAMP_INTERVAL as usize)
+            .filter_map(|level| tiles.iter().find(|tile| tile.level == level))
+            .map(Tile::position)
+            .collect();
+
+        let mut mountain = Self {
+            tiles: tiles.into(),
+            levels,
+            camps,
+            tile_index: Shared::default(),
+            neighbors: Shared::default(),
+            rings: Shared::default(),
+        };
+        mountain.rebuild_graph();
+        mountain
     }
 
-    pub fn get_tile(&self, x: u32, y: u32, z: u32) -> Option<&Tile> {
-        self.tiles
+    // Same as Mountain::new, but only the first call for a level count
+    // builds the graph. A bad level count panics before anything is cached,
+    // so the lock is safe to take back after one.
+    pub fn preset(levels: u32) -> Self {
+        let mut presets = PRESETS
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        presets
+            .entry(levels)
+            .or_insert_with(|| Mountain::new(levels))
+            .clone()
+    }
+
+    // Builds the presets for these level counts ahead of the first game that
+    // needs them, returning how many were new
+    pub fn preload(levels: impl IntoIterator<Item = u32>) -> usize {
+        let mut presets = PRESETS
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        levels
+            .into_iter()
+            .filter(|levels| {
+                if presets.contains_key(levels) {
+                    return false;
+                }
+                presets.insert(*levels, Mountain::new(*levels));
+                true
+            })
+            .count()
+    }
+
+    // A hand-made layout instead of the generated rings
+    pub fn from_tiles(levels: u32, tiles: Vec<Tile>, camps: Vec<Position>) -> Self {
+        let mut mountain = Self {
+            tiles: tiles.into(),
+            levels,
+            camps,
+            tile_index: Shared::default(),
+            neighbors: Shared::default(),
+            rings: Shared::default(),
+        };
+        mountain.rebuild_graph();
+        mountain
+    }
+
+    // Recomputes the coordinate index and neighbor lists. Called whenever
+    // tiles are added or removed; anything that edits `tiles` directly must
+    // call it too. Tile contents can change freely without a rebuild.
+    pub fn rebuild_graph(&mut self) {
+        let mut tile_index = HashMap::with_capacity(self.tiles.len());
+        for (i, tile) in self.tiles.iter().enumerate() {
+            tile_index.entry((tile.x, tile.y, tile.z)).or_insert(i);
+        }
+
+        // Neighbors are the distinct tiles one step away under the same
+        // distance rule movement uses
+        let neighbors = self
+            .tiles
             .iter()
-            .find(|tile| tile.x == x && tile.y == y && tile.z == z)
+            .map(|tile| {
+                let mut list = NeighborList::new();
+                for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        for dz in -1..=1 {
+                            if (dx, dy, dz) == (0, 0, 0) {
+                                continue;
+                            }
+                            let key = (
+                                tile.x.checked_add_signed(dx),
+                                tile.y.checked_add_signed(dy),
+                                tile.z.checked_add_signed(dz),
+                            );
+                            if let (Some(x), Some(y), Some(z)) = key {
+                                if let Some(index) = tile_index.get(&(x, y, z)) {
+                                    list.push(*index);
+                                }
+                            }
+                        }
+                    }
+                }
+                list
+            })
+            .collect();
+
+        // Bucket tiles by their largest coordinate. That value moves by at
+        // most one per step, so a range query only needs the buckets within
+        // `range` of the centre's own bucket.
+        let mut rings: Vec<Vec<usize>> = Vec::new();
+        for (i, tile) in self.tiles.iter().enumerate() {
+            let ring = tile.x.max(tile.y).max(tile.z) as usize;
+            if rings.len() <= ring {
+                rings.resize(ring + 1, Vec::new());
+            }
+            rings[ring].push(i);
+        }
+
+        self.tile_index = tile_index.into();
+        self.neighbors = Shared::new(neighbors);
+        self.rings = rings.into();
     }
 
-    pub fn get_tile_mut(&mut self, x: u32, y: u32, z: u32) -> Option<&mut Tile> {
+    pub fn add_tile(&mut self, tile: Tile) {
+        self.tiles.push(tile);
+        self.rebuild_graph();
+    }
+
+    // Tiles are addressed by coordinates, so every tile sharing them goes
+    pub fn remove_tile(&mut self, x: u32, y: u32, z: u32) -> Option<Tile> {
+        let index = self.tile_index(x, y, z)?;
+        let tile = self.tiles[index].clone();
         self.tiles
-            .iter_mut()
-            .find(|tile| tile.x == x && tile.y == y && tile.z == z)
+            .retain(|tile| (tile.x, tile.y, tile.z) != (x, y, z));
+        self.rebuild_graph();
+        Some(tile)
     }
 
-    pub fn get_neighbors(&self, x: u32, y: u32, z: u32) -> Vec<Position> {
-        let mut neighbors = Vec::new();
-        let directions = [
-            (1, 0, -1),
-            (1, -1, 0),
-            (0, -1, 1),
-            (-1, 0, 1),
-            (-1, 1, 0),
-            (0, 1, -1),
-        ];
-
-        for (dx, dy, dz) in directions {
-            let nx = x as i32 + dx;
-            let ny = y as i32 + dy;
-            let nz = z as i32 + dz;
-
-            if nx >= 0 && ny >= 0 && nz >= 0 {
-                let level = ((nx + ny + nz) / 3) as u32;
-                if level <= self.levels {
-                    neighbors.push(Position {
-                        x: nx as u32,
-                        y: ny as u32,
-                        z: nz as u32,
-                        level,
-                    });
-                }
+    // Summons a unit over one or more tiles. The first position is the
+    // anchor that holds the card; the footprint has to be connected and free.
+    pub fn place_unit(&mut self, card: Card, footprint: &[Position]) -> Result<(), GameError> {
+        let indices = self.check_footprint(footprint, None)?;
+        let id = card.id;
+        self.tiles[indices[0]].content = TileContent::Card(card);
+        for index in &indices[1..] {
+            self.tiles[*index].content = TileContent::Part(id);
+        }
+        Ok(())
+    }
+
+    // Moves a unit onto a new footprint of the same size. Tiles it already
+    // covers count as free.
+    pub fn move_unit(&mut self, unit_id: Uuid, footprint: &[Position]) -> Result<(), GameError> {
+        let current = self.unit_footprint(unit_id);
+        if current.is_empty() {
+            return Err(GameError::InvalidTarget);
+        }
+        if current.len() != footprint.len() {
+            return Err(GameError::InvalidFootprint);
+        }
+        self.check_footprint(footprint, Some(unit_id))?;
+        let card = self.remove_unit(unit_id).ok_or(GameError::InvalidTarget)?;
+        self.place_unit(card, footprint)
+    }
+
+    // Clears every tile a unit covers and hands back its card
+    pub fn remove_unit(&mut self, unit_id: Uuid) -> Option<Card> {
+        let mut card = None;
+        for index in self.unit_footprint(unit_id) {
+            match std::mem::replace(&mut self.tiles[index].content, TileContent::Empty) {
+                TileContent::Card(anchor) => card = Some(anchor),
+                _ => continue,
             }
         }
+        card
+    }
+
+    // Tile indices a unit covers, anchor first
+    pub fn unit_footprint(&self, unit_id: Uuid) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.tiles.len())
+            .filter(|index| self.tiles[*index].content.unit_id() == Some(unit_id))
+            .collect();
+        indices.sort_by_key(|index| !matches!(self.tiles[*index].content, TileContent::Card(_)));
+        indices
+    }
+
+    // Tiles touching any part of a unit, not counting the unit itself
+    pub fn unit_neighbors(&self, unit_id: Uuid) -> Vec<Position> {
+        let footprint = self.unit_footprint(unit_id);
+        let mut neighbors: Vec<usize> = footprint
+            .iter()
+            .flat_map(|index| self.neighbor_indices(*index).iter().copied())
+            .filter(|neighbor| !footprint.contains(neighbor))
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
         neighbors
+            .into_iter()
+            .map(|index| self.tiles[index].position())
+            .collect()
+    }
+
+    // Each unit with any tile in range, listed once however many tiles it covers
+    pub fn units_in_range(&self, center: Position, range: u32) -> Vec<Uuid> {
+        let mut units: Vec<Uuid> = self
+            .get_tiles_in_range(center, range)
+            .into_iter()
+            .filter_map(|tile| tile.content.unit_id())
+            .collect();
+        units.sort_unstable();
+        units.dedup();
+        units
+    }
+
+    fn check_footprint(
+        &self,
+        footprint: &[Position],
+        moving: Option<Uuid>,
+    ) -> Result<Vec<usize>, GameError> {
+        let indices = footprint
+            .iter()
+            .map(|position| self.tile_index(position.x, position.y, position.z))
+            .collect::<Option<Vec<usize>>>()
+            .ok_or(GameError::InvalidFootprint)?;
+        let mut distinct = indices.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        if indices.is_empty() || distinct.len() != indices.len() {
+            return Err(GameError::InvalidFootprint);
+        }
+
+        let free = |index: &usize| match &self.tiles[*index].content {
+            TileContent::Empty => true,
+            content => moving.is_some() && content.unit_id() == moving,
+        };
+        if !indices.iter().all(free) {
+            return Err(GameError::TileOccupied);
+        }
+
+        // Flood fill from the anchor to check the tiles form one piece
+        let mut reached = vec![indices[0]];
+        let mut frontier = vec![indices[0]];
+        while let Some(index) = frontier.pop() {
+            for neighbor in self.neighbor_indices(index) {
+                if indices.contains(neighbor) && !reached.contains(neighbor) {
+                    reached.push(*neighbor);
+                    frontier.push(*neighbor);
+                }
+            }
+        }
+        if reached.len() != indices.len() {
+            return Err(GameError::InvalidFootprint);
+        }
+        Ok(indices)
+    }
+
+    pub fn tile_index(&self, x: u32, y: u32, z: u32) -> Option<usize> {
+        self.tile_index.get(&(x, y, z)).copied()
+    }
+
+    pub fn neighbor_indices(&self, index: usize) -> &[usize] {
+        self.neighbors
+            .get(index)
+            .map_or(&[], |list| list.as_slice())
+    }
+
+    pub fn is_camp(&self, position: Position) -> bool {
+        self.camps
+            .iter()
+            .any(|camp| (camp.x, camp.y, camp.z) == (position.x, position.y, position.z))
+    }
+
+    pub fn summit_level(&self) -> u32 {
+        self.levels - 1
+    }
+
+    pub fn get_tile(&self, x: u32, y: u32, z: u32) -> Option<&Tile> {
+        self.tile_index(x, y, z).map(|index| &self.tiles[index])
+    }
+
+    pub fn get_tile_mut(&mut self, x: u32, y: u32, z: u32) -> Option<&mut Tile> {
+        let index = self.tile_index(x, y, z)?;
+        self.tiles.get_mut(index)
+    }
+
+    pub fn get_neighbors(&self, x: u32, y: u32, z: u32) -> Vec<Position> {
+        let Some(index) = self.tile_index(x, y, z) else {
+            return Vec::new();
+        };
+        self.neighbor_indices(index)
+            .iter()
+            .map(|neighbor| self.tiles[*neighbor].position())
+            .collect()
     }
 
     pub fn calculate_distance(&self, pos1: Position, pos2: Position) -> u32 {
@@ -306,6 +1049,24 @@ impl Mountain {
         dx.max(dy).max(dz) as u32
     }
 
+    // Sight runs along the straight line between the two tiles. A tile in
+    // between blocks it if a unit stands there or it rises above both ends;
+    // gaps in the board don't block anything.
+    pub fn has_line_of_sight(&self, from: Position, to: Position) -> bool {
+        let steps = self.calculate_distance(from, to);
+        let eye_level = from.level.max(to.level);
+        (1..steps).all(|step| {
+            let t = step as f32 / steps as f32;
+            let lerp = |a: u32, b: u32| (a as f32 + (b as f32 - a as f32) * t).round() as u32;
+            let Some(tile) =
+                self.get_tile(lerp(from.x, to.x), lerp(from.y, to.y), lerp(from.z, to.z))
+            else {
+                return true;
+            };
+            tile.level <= eye_level && tile.content.unit_id().is_none()
+        })
+    }
+
     pub fn is_valid_move(&self, current: Position, new: Position) -> bool {
         if self.get_tile(new.x, new.y, new.z).is_none()
             || self.get_tile(current.x, current.y, current.z).is_none()
@@ -317,18 +1078,114 @@ impl Mountain {
         distance == 1 && new.level <= self.levels
     }
 
+    // Climbing onto a higher level costs double; terrain adds on top
+    pub fn step_cost(&self, from: usize, to: usize) -> u32 {
+        let climb = if self.tiles[to].level > self.tiles[from].level {
+            2
+        } else {
+            1
+        };
+        climb + self.tiles[to].terrain.extra_cost()
+    }
+
+    // Cheapest route between two tiles as (positions after the start, total
+    // cost), never passing through tiles `blocked` rejects. The destination
+    // itself is not checked against `blocked`.
+    pub fn find_path(
+        &self,
+        from: Position,
+        to: Position,
+        blocked: impl Fn(&Tile) -> bool,
+    ) -> Option<(Vec<Position>, u32)> {
+        let start = self.tile_index(from.x, from.y, from.z)?;
+        let goal = self.tile_index(to.x, to.y, to.z)?;
+        if start == goal {
+            return Some((Vec::new(), 0));
+        }
+
+        let mut cost = vec![u32::MAX; self.tiles.len()];
+        let mut previous = vec![usize::MAX; self.tiles.len()];
+        let mut queue = BinaryHeap::new();
+        cost[start] = 0;
+        queue.push(Reverse((0, start)));
+
+        while let Some(Reverse((spent, index))) = queue.pop() {
+            if index == goal {
+                let mut path = vec![self.tiles[goal].position()];
+                let mut step = goal;
+                while previous[step] != start {
+                    step = previous[step];
+                    path.push(self.tiles[step].position());
+                }
+                path.reverse();
+                return Some((path, spent));
+            }
+            if spent > cost[index] {
+                continue;
+            }
+            for neighbor in self.neighbor_indices(index) {
+                if *neighbor != goal && blocked(&self.tiles[*neighbor]) {
+                    continue;
+                }
+                let next = spent.saturating_add(self.step_cost(index, *neighbor));
+                if next < cost[*neighbor] {
+                    cost[*neighbor] = next;
+                    previous[*neighbor] = index;
+                    queue.push(Reverse((next, *neighbor)));
+                }
+            }
+        }
+        None
+    }
+
+    // Every tile some route from `from` reaches for at most `budget`, with
+    // the cheapest cost, start excluded. Tiles `blocked` rejects can be
+    // reached but not passed through, as with find_path.
+    pub fn reachable(
+        &self,
+        from: Position,
+        budget: u32,
+        blocked: impl Fn(&Tile) -> bool,
+    ) -> Vec<(Position, u32)> {
+        let Some(start) = self.tile_index(from.x, from.y, from.z) else {
+            return Vec::new();
+        };
+        let mut cost = vec![u32::MAX; self.tiles.len()];
+        let mut queue = BinaryHeap::new();
+        cost[start] = 0;
+        queue.push(Reverse((0, start)));
+
+        while let Some(Reverse((spent, index))) = queue.pop() {
+            if spent > cost[index] || (index != start && blocked(&self.tiles[index])) {
+                continue;
+            }
+            for neighbor in self.neighbor_indices(index) {
+                let next = spent.saturating_add(self.step_cost(index, *neighbor));
+                if next <= budget && next < cost[*neighbor] {
+                    cost[*neighbor] = next;
+                    queue.push(Reverse((next, *neighbor)));
+                }
+            }
+        }
+        cost.iter()
+            .enumerate()
+            .filter(|(index, spent)| *index != start && **spent <= budget)
+            .map(|(index, spent)| (self.tiles[index].position(), *spent))
+            .collect()
+    }
+
     pub fn get_tiles_in_range(&self, center: Position, range: u32) -> Vec<&Tile> {
-        self.tiles
+        let ring = center.x.max(center.y).max(center.z);
+        let first = ring.saturating_sub(range) as usize;
+        let last = (ring.saturating_add(range) as usize).min(self.rings.len().saturating_sub(1));
+
+        self.rings
+            .get(first..=last)
+            .unwrap_or_default()
             .iter()
-            .filter(|tile| {
-                let pos = Position {
-                    x: tile.x,
-                    y: tile.y,
-                    z: tile.z,
-                    level: tile.level,
-                };
-                self.calculate_distance(center, pos) <= range
-            })
+            .flatten()
+            .map(|index| &self.tiles[*index])
+            .filter(|tile| self.calculate_distance(center, tile.position()) <= range)
             .collect()
     }
 
@@ -365,7 +1222,7 @@ mod card_tests {
         };
 
         let deck = Deck {
-            cards: vec![card.clone()],
+            cards: vec![card.clone()].into(),
             owner_id: Uuid::new_v4(),
         };
 
@@ -383,7 +1240,7 @@ mod card_tests {
     #[test]
     fn test_draw_card_empty_deck() {
         let deck = Deck {
-            cards: vec![],
+            cards: vec![].into(),
             owner_id: Uuid::new_v4(),
         };
 
@@ -398,6 +1255,42 @@ mod card_tests {
         assert_eq!(player.hand.len(), 0);
         assert_eq!(player.deck.cards.len(), 0);
     }
+
+    #[test]
+    fn test_deck_validate_copy_limits() {
+        let card = |name: &str, rarity: Rarity| Card {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            cost: 1,
+            power: 1,
+            rarity,
+            effects: vec![],
+            card_type: CardType::Climber,
+        };
+
+        let mut cards: Vec<Card> = (0..29)
+            .map(|i| card(&format!("Card {}", i / 3), Rarity::Common))
+            .collect();
+        cards.push(card("Yeti", Rarity::Legendary));
+        let mut deck = Deck {
+            cards: cards.into(),
+            owner_id: Uuid::new_v4(),
+        };
+        assert!(deck.validate(Format::Constructed).is_ok());
+
+        deck.cards[29] = card("Card 0", Rarity::Common);
+        assert!(matches!(
+            deck.validate(Format::Constructed),
+            Err(ValidationError::InvalidCardCount)
+        ));
+        assert!(deck.validate(Format::Draft).is_ok());
+
+        deck.cards.pop();
+        assert!(matches!(
+            deck.validate(Format::Draft),
+            Err(ValidationError::InvalidDeckSize)
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -406,7 +1299,7 @@ mod player_tests {
     #[test]
     fn test_player_new() {
         let deck = Deck {
-            cards: vec![],
+            cards: vec![].into(),
             owner_id: Uuid::new_v4(),
         };
 
@@ -430,7 +1323,7 @@ mod player_tests {
         };
 
         let deck = Deck {
-            cards: vec![card.clone()],
+            cards: vec![card.clone()].into(),
             owner_id: Uuid::new_v4(),
         };
 
@@ -440,6 +1333,64 @@ mod player_tests {
         assert_eq!(player.hand.len(), 0);
         assert_eq!(player.mana, 0);
     }
+
+    #[test]
+    fn test_derived_stats_invalidation() {
+        let card = Card {
+            id: Uuid::new_v4(),
+            name: "Test Card".to_string(),
+            cost: 1,
+            power: 2,
+            rarity: Rarity::Common,
+            effects: vec![],
+            card_type: CardType::Climber,
+        };
+        let deck = Deck {
+            cards: vec![card.clone(), card].into(),
+            owner_id: Uuid::new_v4(),
+        };
+        let mut player = Player::new("Test Player".to_string(), deck);
+        assert_eq!(player.get_power(), 0);
+
+        player.draw_card().unwrap();
+        assert_eq!(player.get_power(), 2);
+
+        player.add_buff(3, 5, Duration::Temporary(1));
+        assert_eq!(player.get_power(), 5);
+        assert_eq!(player.max_health(), 35);
+
+        player.update_turn();
+        player.update_turn();
+        assert_eq!(player.get_power(), 2);
+        assert_eq!(player.max_health(), 30);
+
+        player.remove_from_hand(0);
+        assert_eq!(player.get_power(), 0);
+    }
+
+    #[test]
+    fn test_modify_max_health() {
+        let deck = Deck {
+            cards: vec![].into(),
+            owner_id: Uuid::new_v4(),
+        };
+        let mut player = Player::new("Test Player".to_string(), deck);
+        player.add_health_boost(5, Duration::Permanent);
+        player.health = 35;
+
+        player.modify_max_health(-10, Duration::Temporary(1));
+        assert_eq!((player.max_health(), player.health), (25, 25));
+
+        player.modify_max_health(-4, Duration::Permanent);
+        assert_eq!(player.max_health(), 21);
+
+        player.update_turn();
+        player.update_turn();
+        assert_eq!((player.max_health(), player.health), (31, 21));
+
+        player.modify_max_health(-100, Duration::Permanent);
+        assert_eq!((player.max_health(), player.health), (6, 6));
+    }
 }
 
 #[cfg(test)]
@@ -473,6 +1424,63 @@ mod mountain_tests {
         assert!(!mountain.is_valid_move(start, invalid_move));
     }
 
+    #[test]
+    fn test_line_of_sight_blocked_by_units() {
+        let mut mountain = Mountain::new(4);
+        let from = mountain.get_tile(0, 0, 0).unwrap().position();
+        let to = mountain.get_tile(2, 0, 2).unwrap().position();
+        assert!(mountain.has_line_of_sight(from, to));
+
+        mountain.get_tile_mut(1, 0, 1).unwrap().content = TileContent::Card(Card {
+            id: Uuid::new_v4(),
+            name: "Ice Wall".to_string(),
+            cost: 1,
+            power: 1,
+            rarity: Rarity::Common,
+            effects: vec![],
+            card_type: CardType::Climber,
+        });
+        assert!(!mountain.has_line_of_sight(from, to));
+        assert!(!mountain.has_line_of_sight(to, from));
+    }
+
+    #[test]
+    fn test_large_unit_footprint() {
+        let mut mountain = Mountain::new(4);
+        let yeti = Card {
+            id: Uuid::new_v4(),
+            name: "Yeti".to_string(),
+            cost: 8,
+            power: 9,
+            rarity: Rarity::Legendary,
+            effects: vec![],
+            card_type: CardType::Climber,
+        };
+        let at = |x, y, z| mountain.get_tile(x, y, z).unwrap().position();
+        let footprint = [at(1, 0, 1), at(2, 0, 2)];
+        let apart = [at(1, 0, 1), at(3, 0, 3)];
+        let shifted = [at(2, 0, 2), at(3, 0, 3)];
+
+        assert!(matches!(
+            mountain.clone().place_unit(yeti.clone(), &apart),
+            Err(GameError::InvalidFootprint)
+        ));
+        mountain.place_unit(yeti.clone(), &footprint).unwrap();
+        assert_eq!(mountain.unit_footprint(yeti.id).len(), 2);
+        assert_eq!(mountain.units_in_range(footprint[0], 3), vec![yeti.id]);
+        assert!(!mountain.unit_neighbors(yeti.id).contains(&footprint[1]));
+
+        mountain.move_unit(yeti.id, &shifted).unwrap();
+        assert_eq!(
+            mountain.get_tile(1, 0, 1).unwrap().content,
+            TileContent::Empty
+        );
+        assert_eq!(
+            mountain.get_tile(3, 0, 3).unwrap().content,
+            TileContent::Part(yeti.id)
+        );
+    }
+
     #[test]
     fn test_range_calculation() {
         let mountain = Mountain::new(3);
@@ -531,4 +1539,68 @@ mod mountain_tests {
         assert_eq!(level_1.len(), 6, "Level 1 should have 6 tiles");
         assert_eq!(level_2.len(), 12, "Level 2 should have 12 tiles");
     }
+
+    #[test]
+    fn test_tiles_in_range_matches_full_scan() {
+        let mountain = Mountain::new(12);
+
+        for center in mountain.tiles.iter().step_by(7).map(Tile::position) {
+            for range in [0, 1, 3, 20] {
+                let expected: Vec<&Tile> = mountain
+                    .tiles
+                    .iter()
+                    .filter(|tile| mountain.calculate_distance(center, tile.position()) <= range)
+                    .collect();
+                assert_eq!(mountain.get_tiles_in_range(center, range), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbor_graph() {
+        let mut mountain = Mountain::new(3);
+        let center = Position::default();
+
+        let neighbors = mountain.get_neighbors(0, 0, 0);
+        assert_eq!(neighbors.len(), 3);
+        for neighbor in &neighbors {
+            assert_eq!(neighbor.level, 1);
+            assert_eq!(mountain.calculate_distance(center, *neighbor), 1);
+            assert!(mountain
+                .get_neighbors(neighbor.x, neighbor.y, neighbor.z)
+                .contains(&center));
+        }
+
+        let removed = mountain.remove_tile(1, 1, 0).unwrap();
+        assert_eq!(removed.level, 1);
+        assert!(!mountain
+            .get_neighbors(0, 0, 0)
+            .contains(&removed.position()));
+        assert!(mountain.get_neighbors(9, 9, 9).is_empty());
+    }
+
+    #[test]
+    fn test_levels_are_rings_and_neighbors_are_one_move_away() {
+        let mountain = Mountain::new(5);
+        for tile in mountain.tiles.iter() {
+            assert_eq!(tile.level, tile.x.max(tile.y).max(tile.z));
+        }
+
+        let reachable = |from: Position| {
+            let mut tiles: Vec<Position> = mountain
+                .tiles
+                .iter()
+                .map(Tile::position)
+                .filter(|to| mountain.is_valid_move(from, *to))
+                .collect();
+            tiles.sort_by_key(|to| (to.x, to.y, to.z));
+            tiles.dedup();
+            tiles
+        };
+        for tile in mountain.tiles.iter() {
+            let mut neighbors = mountain.get_neighbors(tile.x, tile.y, tile.z);
+            neighbors.sort_by_key(|to| (to.x, to.y, to.z));
+            assert_eq!(neighbors, reachable(tile.position()));
+        }
+    }
 }
@@ -0,0 +1,325 @@
+// src/moderation/mod.rs
+use crate::errors::ModerationError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SanctionKind {
+    ChatMute,
+    MatchmakingBan,
+    FullBan,
+}
+
+impl SanctionKind {
+    // A full ban covers every entry point, so it also blocks chat and queueing
+    fn covers(&self, other: SanctionKind) -> bool {
+        *self == other || *self == SanctionKind::FullBan
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sanction {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub kind: SanctionKind,
+    pub reason: String,
+    pub issued_by: Uuid,
+    pub issued_at: SystemTime,
+    pub expires_at: Option<SystemTime>, // None for permanent sanctions
+    pub revoked: bool,
+}
+
+impl Sanction {
+    pub fn is_active(&self, now: SystemTime) -> bool {
+        !self.revoked && self.expires_at.is_none_or(|expires| now < expires)
+    }
+}
+
+pub const MAX_CHAT_EXCERPT: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReportReason {
+    Harassment,
+    Cheating,
+    Stalling,
+    InappropriateName,
+    Other,
+}
+
+// Reports reference the game by id, which is also the key replays are
+// stored under, so reviewers can pull up exactly what happened.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerReport {
+    pub id: Uuid,
+    pub reporter: Uuid,
+    pub reported: Uuid,
+    pub game_id: Uuid,
+    pub reason: ReportReason,
+    pub chat_excerpt: Option<String>,
+    pub submitted_at: SystemTime,
+    pub resolved: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ModerationService {
+    sanctions: HashMap<Uuid, Vec<Sanction>>,
+    reports: Vec<PlayerReport>,
+}
+
+impl ModerationService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // A duration too long to put a date on is as good as permanent
+    pub fn issue(
+        &mut self,
+        admin_id: Uuid,
+        player_id: Uuid,
+        kind: SanctionKind,
+        duration: Option<Duration>,
+        reason: String,
+        now: SystemTime,
+    ) -> Uuid {
+        let sanction = Sanction {
+            id: Uuid::new_v4(),
+            player_id,
+            kind,
+            reason,
+            issued_by: admin_id,
+            issued_at: now,
+            expires_at: duration.and_then(|duration| now.checked_add(duration)),
+            revoked: false,
+        };
+        let sanction_id = sanction.id;
+        self.sanctions.entry(player_id).or_default().push(sanction);
+        sanction_id
+    }
+
+    pub fn revoke(&mut self, sanction_id: Uuid) -> Result<(), ModerationError> {
+        self.sanctions
+            .values_mut()
+            .flatten()
+            .find(|sanction| sanction.id == sanction_id)
+            .map(|sanction| sanction.revoked = true)
+            .ok_or(ModerationError::SanctionNotFound)
+    }
+
+    pub fn history(&self, player_id: Uuid) -> &[Sanction] {
+        self.sanctions
+            .get(&player_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    // Returns the longest-lasting active sanction covering `kind`, so clients
+    // are told the expiry that actually matters.
+    pub fn active_sanction(
+        &self,
+        player_id: Uuid,
+        kind: SanctionKind,
+        now: SystemTime,
+    ) -> Option<&Sanction> {
+        self.history(player_id)
+            .iter()
+            .filter(|sanction| sanction.kind.covers(kind) && sanction.is_active(now))
+            .max_by_key(|sanction| (sanction.expires_at.is_none(), sanction.expires_at))
+    }
+
+    pub fn check_login(&self, player_id: Uuid, now: SystemTime) -> Result<(), ModerationError> {
+        self.check(player_id, SanctionKind::FullBan, now)
+    }
+
+    pub fn check_matchmaking(
+        &self,
+        player_id: Uuid,
+        now: SystemTime,
+    ) -> Result<(), ModerationError> {
+        self.check(player_id, SanctionKind::MatchmakingBan, now)
+    }
+
+    pub fn check_chat(&self, player_id: Uuid, now: SystemTime) -> Result<(), ModerationError> {
+        self.check(player_id, SanctionKind::ChatMute, now)
+    }
+
+    pub fn submit_report(
+        &mut self,
+        reporter: Uuid,
+        reported: Uuid,
+        game_id: Uuid,
+        reason: ReportReason,
+        chat_excerpt: Option<String>,
+        now: SystemTime,
+    ) -> Result<Uuid, ModerationError> {
+        if reporter == reported {
+            return Err(ModerationError::CannotReportSelf);
+        }
+        // One report per pair per game; repeats add nothing for reviewers
+        if self.reports.iter().any(|report| {
+            report.reporter == reporter && report.reported == reported && report.game_id == game_id
+        }) {
+            return Err(ModerationError::DuplicateReport);
+        }
+
+        let report = PlayerReport {
+            id: Uuid::new_v4(),
+            reporter,
+            reported,
+            game_id,
+            reason,
+            chat_excerpt: chat_excerpt
+                .map(|excerpt| excerpt.chars().take(MAX_CHAT_EXCERPT).collect()),
+            submitted_at: now,
+            resolved: false,
+        };
+        let report_id = report.id;
+        self.reports.push(report);
+        Ok(report_id)
+    }
+
+    pub fn open_reports(&self) -> Vec<&PlayerReport> {
+        self.reports
+            .iter()
+            .filter(|report| !report.resolved)
+            .collect()
+    }
+
+    pub fn reports_against(&self, player_id: Uuid) -> Vec<&PlayerReport> {
+        self.reports
+            .iter()
+            .filter(|report| report.reported == player_id)
+            .collect()
+    }
+
+    pub fn resolve_report(&mut self, report_id: Uuid) -> Result<(), ModerationError> {
+        self.reports
+            .iter_mut()
+            .find(|report| report.id == report_id)
+            .map(|report| report.resolved = true)
+            .ok_or(ModerationError::ReportNotFound)
+    }
+
+    fn check(
+        &self,
+        player_id: Uuid,
+        kind: SanctionKind,
+        now: SystemTime,
+    ) -> Result<(), ModerationError> {
+        match self.active_sanction(player_id, kind, now) {
+            Some(sanction) => Err(ModerationError::Sanctioned(sanction.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod moderation_tests {
+    use super::*;
+
+    #[test]
+    fn test_sanction_expires() {
+        let mut moderation = ModerationService::new();
+        let player = Uuid::new_v4();
+        let now = SystemTime::now();
+
+        moderation.issue(
+            Uuid::new_v4(),
+            player,
+            SanctionKind::ChatMute,
+            Some(Duration::from_secs(60)),
+            "Spam".to_string(),
+            now,
+        );
+
+        assert!(moderation.check_chat(player, now).is_err());
+        assert!(moderation.check_matchmaking(player, now).is_ok());
+        assert!(moderation
+            .check_chat(player, now + Duration::from_secs(61))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_unrepresentable_duration_is_permanent() {
+        let mut moderation = ModerationService::new();
+        let player = Uuid::new_v4();
+        let now = SystemTime::now();
+
+        moderation.issue(
+            Uuid::new_v4(),
+            player,
+            SanctionKind::MatchmakingBan,
+            Some(Duration::MAX),
+            "Smurfing".to_string(),
+            now,
+        );
+
+        assert_eq!(moderation.history(player)[0].expires_at, None);
+        assert!(moderation.check_matchmaking(player, now).is_err());
+    }
+
+    #[test]
+    fn test_full_ban_blocks_every_entry_point_until_revoked() {
+        let mut moderation = ModerationService::new();
+        let player = Uuid::new_v4();
+        let now = SystemTime::now();
+
+        let ban = moderation.issue(
+            Uuid::new_v4(),
+            player,
+            SanctionKind::FullBan,
+            None,
+            "Cheating".to_string(),
+            now,
+        );
+
+        assert!(moderation.check_login(player, now).is_err());
+        assert!(moderation.check_matchmaking(player, now).is_err());
+        assert!(moderation.check_chat(player, now).is_err());
+
+        moderation.revoke(ban).unwrap();
+        assert!(moderation.check_login(player, now).is_ok());
+        assert_eq!(moderation.history(player).len(), 1);
+    }
+
+    #[test]
+    fn test_reports() {
+        let mut moderation = ModerationService::new();
+        let reporter = Uuid::new_v4();
+        let reported = Uuid::new_v4();
+        let game_id = Uuid::new_v4();
+        let now = SystemTime::now();
+
+        let report = moderation
+            .submit_report(
+                reporter,
+                reported,
+                game_id,
+                ReportReason::Harassment,
+                Some("x".repeat(MAX_CHAT_EXCERPT + 20)),
+                now,
+            )
+            .unwrap();
+
+        assert!(matches!(
+            moderation.submit_report(reporter, reported, game_id, ReportReason::Other, None, now),
+            Err(ModerationError::DuplicateReport)
+        ));
+        assert!(matches!(
+            moderation.submit_report(reporter, reporter, game_id, ReportReason::Other, None, now),
+            Err(ModerationError::CannotReportSelf)
+        ));
+
+        let against = moderation.reports_against(reported);
+        assert_eq!(against.len(), 1);
+        assert_eq!(
+            against[0].chat_excerpt.as_ref().unwrap().len(),
+            MAX_CHAT_EXCERPT
+        );
+
+        moderation.resolve_report(report).unwrap();
+        assert!(moderation.open_reports().is_empty());
+    }
+}
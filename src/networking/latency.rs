@@ -0,0 +1,119 @@
+// src/networking/latency.rs
+// Round-trip times per connection, from server pings the client echoes
+// back. The turn timer gives each player up to half their round trip on
+// top of the limit, capped by GameRules::max_latency_allowance, so a slow
+// link doesn't cost someone a turn they finished in time on their screen.
+use super::{unix_millis, ServerMessage};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+#[derive(Debug, Default)]
+struct Link {
+    // Smoothed the way TCP does it, so one slow reply doesn't swing it
+    rtt: Option<Duration>,
+    pending: HashMap<u64, SystemTime>,
+}
+
+#[derive(Debug, Default)]
+pub struct LatencyTracker {
+    links: HashMap<Uuid, Link>,
+    next_nonce: u64,
+}
+
+impl LatencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ping(&mut self, player_id: Uuid, now: SystemTime) -> ServerMessage {
+        self.next_nonce += 1;
+        let nonce = self.next_nonce;
+        self.links
+            .entry(player_id)
+            .or_default()
+            .pending
+            .insert(nonce, now);
+        ServerMessage::Ping {
+            nonce,
+            server_time_ms: unix_millis(now),
+        }
+    }
+
+    // Returns the sample, or None for a nonce that was never sent to this
+    // player or was already answered
+    pub fn pong(&mut self, player_id: Uuid, nonce: u64, now: SystemTime) -> Option<Duration> {
+        let link = self.links.get_mut(&player_id)?;
+        let sent = link.pending.remove(&nonce)?;
+        let sample = now.duration_since(sent).unwrap_or_default();
+        link.rtt = Some(match link.rtt {
+            Some(rtt) => (rtt * 7 + sample) / 8,
+            None => sample,
+        });
+        Some(sample)
+    }
+
+    pub fn rtt(&self, player_id: Uuid) -> Option<Duration> {
+        self.links.get(&player_id)?.rtt
+    }
+
+    // Extra turn time for a player: the one-way trip of their last reply
+    pub fn allowance(&self, player_id: Uuid) -> Duration {
+        self.rtt(player_id).unwrap_or_default() / 2
+    }
+
+    pub fn disconnect(&mut self, player_id: Uuid) {
+        self.links.remove(&player_id);
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod latency_tests {
+    use super::*;
+    use crate::game_state::GameRules;
+    use crate::runtime::ManualClock;
+    use crate::sessions::SessionManager;
+    use crate::testing::GameStateBuilder;
+
+    #[test]
+    fn test_round_trip_extends_the_turn_timer_within_the_cap() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let clock = ManualClock::new(start);
+        let game = GameStateBuilder::new()
+            .clock(clock.clone())
+            .build()
+            .with_rules(GameRules {
+                turn_time_limit: Some(Duration::from_secs(60)),
+                max_latency_allowance: Duration::from_millis(150),
+                ..GameRules::default()
+            });
+        let player_id = game.active_player;
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(game);
+        let session = sessions.get_mut(game_id).unwrap();
+
+        let mut latency = LatencyTracker::new();
+        let ServerMessage::Ping { nonce, .. } = latency.ping(player_id, start) else {
+            panic!("expected a ping");
+        };
+        let rtt = Duration::from_millis(400);
+        assert_eq!(latency.pong(player_id, nonce, start + rtt), Some(rtt));
+        assert_eq!(latency.pong(player_id, nonce, start + rtt), None);
+        assert_eq!(latency.allowance(player_id), Duration::from_millis(200));
+        session.sync_latency(&latency);
+
+        assert!(matches!(
+            session.turn_clock(),
+            ServerMessage::TurnClock { deadline_ms: Some(deadline), started_at_ms, .. }
+                if deadline == started_at_ms + 60_000
+        ));
+        clock.advance(Duration::from_millis(60_100));
+        session.game.enforce_timers().unwrap();
+        assert_eq!(session.game.active_player, player_id);
+        // Half the round trip is 200ms, but the rules cap it at 150ms
+        clock.advance(Duration::from_millis(50));
+        session.game.enforce_timers().unwrap();
+        assert_ne!(session.game.active_player, player_id);
+    }
+}
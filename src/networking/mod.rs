@@ -1,3 +1,605 @@
 // src/networking/mod.rs
+use crate::cards::bans::BanStatus;
+use crate::cosmetics::EquippedCosmetics;
+use crate::emotes::Emote;
+use crate::errors::ProtocolError;
+use crate::events::GameEvent;
+use crate::game_state::GameAction;
+use crate::guilds::GuildView;
+use crate::messages::{CatalogEntry, LocalizedMessage};
+use crate::models::Format;
+use crate::moderation::{ModerationService, ReportReason};
+use crate::profiles::ProfileCard;
+use crate::shuffle::{ShuffleCommitment, ShuffleReveal};
+use crate::summary::GameSummary;
+use crate::view::ScryView;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::time::SystemTime;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use uuid::Uuid;
+
+pub mod latency;
+#[cfg(feature = "quic")]
+pub mod quic;
+pub mod resume;
+
+pub const PROTOCOL_VERSION: u32 = 2;
+
+// Largest encoded message a peer will accept
+pub const MAX_FRAME_LEN: usize = 1 << 20;
+
+// Payloads smaller than this aren't worth compressing
+pub const COMPRESSION_THRESHOLD: usize = 512;
+
+// Server timestamps go out as milliseconds since the Unix epoch
+pub fn unix_millis(at: SystemTime) -> u64 {
+    at.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 // Placeholder for networking implementation
 pub struct NetworkManager;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WireFormat {
+    Json,
+    Bincode,
+}
+
+impl WireFormat {
+    // Preferred first; the binary encoding only exists when compiled in
+    pub fn supported() -> Vec<WireFormat> {
+        let mut formats = Vec::new();
+        if cfg!(feature = "binary-protocol") {
+            formats.push(WireFormat::Bincode);
+        }
+        formats.push(WireFormat::Json);
+        formats
+    }
+
+    // Picks the server's most preferred format the client also offered.
+    // JSON is the fallback every client understands.
+    pub fn negotiate(offered: &[WireFormat]) -> WireFormat {
+        WireFormat::supported()
+            .into_iter()
+            .find(|format| offered.contains(format))
+            .unwrap_or(WireFormat::Json)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    #[default]
+    None,
+    Deflate,
+}
+
+// Oldest clients the server still lets in. Anything older is told to
+// update before it can join a game with mechanics it doesn't know.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct VersionPolicy {
+    pub min_protocol_version: u32,
+    pub min_client_build: u32,
+    pub download_url: String,
+}
+
+impl Default for VersionPolicy {
+    fn default() -> Self {
+        Self {
+            min_protocol_version: PROTOCOL_VERSION,
+            min_client_build: 0,
+            download_url: "https://ascent.gg/download".to_string(),
+        }
+    }
+}
+
+impl VersionPolicy {
+    pub fn accepts(&self, protocol_version: u32, client_build: u32) -> bool {
+        protocol_version >= self.min_protocol_version
+            && protocol_version <= PROTOCOL_VERSION
+            && client_build >= self.min_client_build
+    }
+}
+
+// Answers a Hello: either the negotiated Welcome or UpdateRequired, after
+// which the server closes the connection
+pub fn handshake(
+    hello: &ClientMessage,
+    policy: &VersionPolicy,
+) -> Result<ServerMessage, ProtocolError> {
+    let ClientMessage::Hello {
+        protocol_version,
+        client_build,
+        formats,
+        compression,
+    } = hello
+    else {
+        return Err(ProtocolError::Decode("expected Hello".to_string()));
+    };
+    if !policy.accepts(*protocol_version, *client_build) {
+        return Ok(ServerMessage::UpdateRequired {
+            min_protocol_version: policy.min_protocol_version,
+            min_client_build: policy.min_client_build,
+            download_url: policy.download_url.clone(),
+        });
+    }
+    Ok(ServerMessage::Welcome {
+        protocol_version: PROTOCOL_VERSION,
+        format: WireFormat::negotiate(formats),
+        compression: Compression::negotiate(compression),
+    })
+}
+
+// Answers an Authenticate once its token has resolved to a player. A
+// banned account is turned away here, before it reaches anything else.
+pub fn authenticate(
+    player_id: Uuid,
+    moderation: &ModerationService,
+    now: SystemTime,
+) -> ServerMessage {
+    match moderation.check_login(player_id, now) {
+        Ok(()) => ServerMessage::Authenticated { player_id },
+        Err(error) => ServerMessage::Rejected {
+            message: error.message(),
+        },
+    }
+}
+
+impl Compression {
+    // Same rule as formats: first supported one the client offered
+    pub fn negotiate(offered: &[Compression]) -> Compression {
+        [Compression::Deflate]
+            .into_iter()
+            .find(|compression| offered.contains(compression))
+            .unwrap_or(Compression::None)
+    }
+}
+
+// Wraps an encoded message with a one-byte marker saying whether it was
+// compressed. Small payloads always go raw, so either side can read any
+// packed payload whatever was negotiated.
+pub fn pack(bytes: Vec<u8>, compression: Compression) -> Result<Vec<u8>, ProtocolError> {
+    if compression == Compression::None || bytes.len() < COMPRESSION_THRESHOLD {
+        let mut packed = Vec::with_capacity(bytes.len() + 1);
+        packed.push(0);
+        packed.extend_from_slice(&bytes);
+        return Ok(packed);
+    }
+    let mut encoder = DeflateEncoder::new(vec![1], flate2::Compression::default());
+    encoder
+        .write_all(&bytes)
+        .map_err(|e| ProtocolError::Encode(e.to_string()))?;
+    encoder
+        .finish()
+        .map_err(|e| ProtocolError::Encode(e.to_string()))
+}
+
+pub fn unpack(packed: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+    match packed.split_first() {
+        Some((0, bytes)) => Ok(bytes.to_vec()),
+        Some((1, bytes)) => {
+            // Cap the output so a tiny frame can't inflate without bound
+            let mut bytes_out = Vec::new();
+            DeflateDecoder::new(bytes)
+                .take(MAX_FRAME_LEN as u64 + 1)
+                .read_to_end(&mut bytes_out)
+                .map_err(|e| ProtocolError::Decode(e.to_string()))?;
+            if bytes_out.len() > MAX_FRAME_LEN {
+                return Err(ProtocolError::FrameTooLarge(bytes_out.len()));
+            }
+            Ok(bytes_out)
+        }
+        _ => Err(ProtocolError::Decode("unknown frame marker".to_string())),
+    }
+}
+
+pub fn encode<T: Serialize>(value: &T, format: WireFormat) -> Result<Vec<u8>, ProtocolError> {
+    match format {
+        WireFormat::Json => {
+            serde_json::to_vec(value).map_err(|e| ProtocolError::Encode(e.to_string()))
+        }
+        #[cfg(feature = "binary-protocol")]
+        WireFormat::Bincode => {
+            bincode::serialize(value).map_err(|e| ProtocolError::Encode(e.to_string()))
+        }
+        #[cfg(not(feature = "binary-protocol"))]
+        WireFormat::Bincode => Err(ProtocolError::UnsupportedFormat),
+    }
+}
+
+pub fn decode<T: DeserializeOwned>(bytes: &[u8], format: WireFormat) -> Result<T, ProtocolError> {
+    match format {
+        WireFormat::Json => {
+            serde_json::from_slice(bytes).map_err(|e| ProtocolError::Decode(e.to_string()))
+        }
+        #[cfg(feature = "binary-protocol")]
+        WireFormat::Bincode => {
+            bincode::deserialize(bytes).map_err(|e| ProtocolError::Decode(e.to_string()))
+        }
+        #[cfg(not(feature = "binary-protocol"))]
+        WireFormat::Bincode => Err(ProtocolError::UnsupportedFormat),
+    }
+}
+
+// Frames are the encoded message behind a big-endian u32 length, so any
+// byte stream transport can carry them
+pub async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    bytes: &[u8],
+) -> Result<(), ProtocolError> {
+    if bytes.len() > MAX_FRAME_LEN {
+        return Err(ProtocolError::FrameTooLarge(bytes.len()));
+    }
+    let io = |e: std::io::Error| ProtocolError::Io(e.to_string());
+    writer.write_u32(bytes.len() as u32).await.map_err(io)?;
+    writer.write_all(bytes).await.map_err(io)?;
+    writer.flush().await.map_err(io)
+}
+
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Vec<u8>, ProtocolError> {
+    let io = |e: std::io::Error| ProtocolError::Io(e.to_string());
+    let len = reader.read_u32().await.map_err(io)? as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(ProtocolError::FrameTooLarge(len));
+    }
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes).await.map_err(io)?;
+    Ok(bytes)
+}
+
+// Transports that can multiplex keep social traffic off the game stream
+// so an emote burst never delays a game event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    Game,
+    Social,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    Hello {
+        protocol_version: u32,
+        #[serde(default)]
+        client_build: u32,
+        formats: Vec<WireFormat>,
+        #[serde(default)]
+        compression: Vec<Compression>,
+    },
+    // Picks up an existing session on a new connection
+    Resume {
+        token: Uuid,
+        last_acked: u64,
+    },
+    Ack(u64),
+    // Echo of a server Ping
+    Pong {
+        nonce: u64,
+    },
+    Authenticate {
+        token: String,
+    },
+    JoinQueue,
+    LeaveQueue,
+    JoinLobby {
+        lobby_id: Uuid,
+        spectate: bool,
+        #[serde(default)]
+        password: Option<String>,
+    },
+    // `request_id` is picked by the client and reused on every retry of
+    // the same action, so a resend is never applied twice
+    Action {
+        request_id: Uuid,
+        action: GameAction,
+    },
+    // Several actions applied as one, or not at all; answered with a
+    // single ActionResult
+    ActionBatch {
+        request_id: Uuid,
+        actions: Vec<GameAction>,
+    },
+    // Asks for the message catalog, naming the version already held
+    GetMessageCatalog {
+        known_version: Option<String>,
+    },
+    // Asks for the player's own guild
+    GetGuild,
+    SendEmote(Emote),
+    SetEmotesMuted(bool),
+    ReportPlayer {
+        player_id: Uuid,
+        reason: ReportReason,
+        chat_excerpt: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerPresentation {
+    pub player_id: Uuid,
+    pub name: String,
+    pub cosmetics: EquippedCosmetics,
+    #[serde(default)]
+    pub profile: Option<ProfileCard>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerMessage {
+    Welcome {
+        protocol_version: u32,
+        format: WireFormat,
+        #[serde(default)]
+        compression: Compression,
+    },
+    // Sent instead of Welcome to a client below the minimum version
+    UpdateRequired {
+        min_protocol_version: u32,
+        min_client_build: u32,
+        download_url: String,
+    },
+    Authenticated {
+        player_id: Uuid,
+    },
+    Queued,
+    // Outcome of an Action
+    ActionResult {
+        request_id: Uuid,
+        error: Option<LocalizedMessage>,
+    },
+    LobbyJoined {
+        lobby_id: Uuid,
+        spectating: bool,
+    },
+    // A request was refused
+    Rejected {
+        message: LocalizedMessage,
+    },
+    GameStarted {
+        game_id: Uuid,
+        players: Vec<PlayerPresentation>,
+    },
+    // Hash of the deck shuffle, published before the first draw
+    ShuffleCommitted {
+        commitment: ShuffleCommitment,
+    },
+    // The shuffle seed, once the game is over
+    ShuffleRevealed {
+        reveal: ShuffleReveal,
+    },
+    SessionIssued {
+        token: Uuid,
+    },
+    // Followed by the missed messages, from `next_seq` on
+    Resumed {
+        next_seq: u64,
+    },
+    Event(GameEvent),
+    // Everything one action batch did, under the batch's request id
+    EventBatch {
+        batch_id: Uuid,
+        events: Vec<GameEvent>,
+    },
+    // Only to the player who has to answer; they reply with a ResolveScry
+    // action
+    ScryPrompt {
+        game_id: Uuid,
+        scry: ScryView,
+    },
+    // Sent with every TurnStarted. Clients count down against
+    // `deadline_ms`, correcting for their own clock with `server_time_ms`.
+    TurnClock {
+        player_id: Uuid,
+        turn_number: u32,
+        started_at_ms: u64,
+        deadline_ms: Option<u64>,
+        server_time_ms: u64,
+    },
+    // Clients answer with Pong straight away; used to measure round trips
+    Ping {
+        nonce: u64,
+        server_time_ms: u64,
+    },
+    GameOver {
+        summary: GameSummary,
+    },
+    // The game hit an internal error and was stopped at its last good state
+    GameHalted {
+        game_id: Uuid,
+    },
+    Emote {
+        player_id: Uuid,
+        emote: Emote,
+    },
+    Announcement {
+        id: Uuid,
+        message: LocalizedMessage,
+        text: String, // Shown when the client has no translation
+    },
+    // The message catalog; no entries when the client's copy is current
+    MessageCatalog {
+        version: String,
+        entries: Vec<CatalogEntry>,
+    },
+    // Answer to GetGuild; None when the player isn't in one
+    Guild {
+        guild: Option<GuildView>,
+    },
+    // One of the player's saved decks breaks a ban list change, now or
+    // from `effective_at_ms`
+    DeckBanNotice {
+        deck_name: String,
+        format: Format,
+        cards: Vec<(String, BanStatus)>,
+        effective_at_ms: u64,
+    },
+}
+
+impl ClientMessage {
+    pub fn channel(&self) -> Channel {
+        match self {
+            ClientMessage::GetGuild
+            | ClientMessage::SendEmote(_)
+            | ClientMessage::SetEmotesMuted(_)
+            | ClientMessage::ReportPlayer { .. } => Channel::Social,
+            _ => Channel::Game,
+        }
+    }
+}
+
+impl ServerMessage {
+    pub fn channel(&self) -> Channel {
+        match self {
+            ServerMessage::Emote { .. }
+            | ServerMessage::Announcement { .. }
+            | ServerMessage::Guild { .. }
+            | ServerMessage::DeckBanNotice { .. } => Channel::Social,
+            _ => Channel::Game,
+        }
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod protocol_tests {
+    use super::*;
+
+    fn sample() -> ServerMessage {
+        ServerMessage::Event(GameEvent::DamageDealt {
+            source: Uuid::new_v4(),
+            target: Uuid::new_v4(),
+            amount: 5,
+        })
+    }
+
+    #[test]
+    fn test_negotiation_falls_back_to_json() {
+        assert_eq!(WireFormat::negotiate(&[]), WireFormat::Json);
+        assert_eq!(WireFormat::negotiate(&[WireFormat::Json]), WireFormat::Json);
+
+        let message = sample();
+        let bytes = encode(&message, WireFormat::Json).unwrap();
+        assert_eq!(
+            decode::<ServerMessage>(&bytes, WireFormat::Json).unwrap(),
+            message
+        );
+    }
+
+    #[tokio::test]
+    async fn test_frames_round_trip_over_a_stream() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+        let message = sample();
+        let bytes = encode(&message, WireFormat::Json).unwrap();
+
+        let writer = tokio::spawn(async move {
+            write_frame(&mut client, &bytes).await.unwrap();
+            write_frame(&mut client, &vec![0; MAX_FRAME_LEN + 1]).await
+        });
+        let frame = read_frame(&mut server).await.unwrap();
+        assert_eq!(
+            decode::<ServerMessage>(&frame, WireFormat::Json).unwrap(),
+            message
+        );
+        assert!(matches!(
+            writer.await.unwrap(),
+            Err(ProtocolError::FrameTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_large_payloads_are_compressed() {
+        assert_eq!(
+            Compression::negotiate(&[Compression::Deflate]),
+            Compression::Deflate
+        );
+        assert_eq!(Compression::negotiate(&[]), Compression::None);
+
+        let snapshot = serde_json::to_vec(&vec![sample(); 64]).unwrap();
+        let packed = pack(snapshot.clone(), Compression::Deflate).unwrap();
+        assert!(packed.len() < snapshot.len() / 2);
+        assert_eq!(unpack(&packed).unwrap(), snapshot);
+
+        let small = pack(b"hi".to_vec(), Compression::Deflate).unwrap();
+        assert_eq!(small, b"\0hi");
+        assert_eq!(unpack(&small).unwrap(), b"hi");
+    }
+
+    #[test]
+    fn test_outdated_clients_are_told_to_update() {
+        let policy = VersionPolicy {
+            min_client_build: 120,
+            ..VersionPolicy::default()
+        };
+        let hello = |client_build| ClientMessage::Hello {
+            protocol_version: PROTOCOL_VERSION,
+            client_build,
+            formats: vec![WireFormat::Json],
+            compression: vec![],
+        };
+
+        assert!(matches!(
+            handshake(&hello(119), &policy).unwrap(),
+            ServerMessage::UpdateRequired {
+                min_client_build: 120,
+                ..
+            }
+        ));
+        assert!(matches!(
+            handshake(&hello(120), &policy).unwrap(),
+            ServerMessage::Welcome {
+                format: WireFormat::Json,
+                ..
+            }
+        ));
+        assert!(handshake(&ClientMessage::Ack(1), &policy).is_err());
+    }
+
+    #[test]
+    fn test_banned_accounts_cannot_log_in() {
+        use crate::moderation::SanctionKind;
+
+        let mut moderation = ModerationService::new();
+        let player_id = Uuid::new_v4();
+        let now = SystemTime::UNIX_EPOCH;
+        assert_eq!(
+            authenticate(player_id, &moderation, now),
+            ServerMessage::Authenticated { player_id }
+        );
+        moderation.issue(
+            Uuid::new_v4(),
+            player_id,
+            SanctionKind::FullBan,
+            None,
+            "cheating".to_string(),
+            now,
+        );
+        assert_eq!(
+            authenticate(player_id, &moderation, now),
+            ServerMessage::Rejected {
+                message: LocalizedMessage::error("banned"),
+            }
+        );
+    }
+
+    #[cfg(feature = "binary-protocol")]
+    #[test]
+    fn test_bincode_is_preferred_and_smaller() {
+        assert_eq!(
+            WireFormat::negotiate(&[WireFormat::Json, WireFormat::Bincode]),
+            WireFormat::Bincode
+        );
+
+        let message = sample();
+        let binary = encode(&message, WireFormat::Bincode).unwrap();
+        let json = encode(&message, WireFormat::Json).unwrap();
+        assert!(binary.len() < json.len());
+        assert_eq!(
+            decode::<ServerMessage>(&binary, WireFormat::Bincode).unwrap(),
+            message
+        );
+    }
+}
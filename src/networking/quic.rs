@@ -0,0 +1,141 @@
+// src/networking/quic.rs
+// QUIC transport for the same client/server messages the other transports
+// carry. Each connection has one stream per channel, so game events and
+// social traffic don't queue behind each other.
+use super::{
+    decode, encode, pack, read_frame, unpack, write_frame, Channel, Compression, WireFormat,
+};
+use crate::errors::ProtocolError;
+use quinn::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use quinn::{Connection, Endpoint, RecvStream, SendStream, ServerConfig};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::net::SocketAddr;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+// First byte on every stream, so the accepting side knows which channel it
+// is regardless of the order streams arrive in
+fn channel_tag(channel: Channel) -> u8 {
+    match channel {
+        Channel::Game => 0,
+        Channel::Social => 1,
+    }
+}
+
+pub fn server_endpoint(
+    addr: SocketAddr,
+    cert_chain: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+) -> Result<Endpoint, ProtocolError> {
+    let config = ServerConfig::with_single_cert(cert_chain, key)
+        .map_err(|e| ProtocolError::Io(e.to_string()))?;
+    Endpoint::server(config, addr).map_err(|e| ProtocolError::Io(e.to_string()))
+}
+
+#[derive(Debug)]
+struct Stream {
+    send: SendStream,
+    recv: RecvStream,
+}
+
+#[derive(Debug)]
+pub struct QuicSession {
+    pub connection: Connection,
+    pub format: WireFormat,
+    pub compression: Compression,
+    game: Stream,
+    social: Stream,
+}
+
+impl QuicSession {
+    // Client side: opens and tags both channel streams. Set `compression`
+    // once the handshake has settled it.
+    pub async fn open(connection: Connection, format: WireFormat) -> Result<Self, ProtocolError> {
+        let open = |channel| {
+            let connection = connection.clone();
+            async move {
+                let (mut send, recv) = connection
+                    .open_bi()
+                    .await
+                    .map_err(|e| ProtocolError::Io(e.to_string()))?;
+                send.write_u8(channel_tag(channel))
+                    .await
+                    .map_err(|e| ProtocolError::Io(e.to_string()))?;
+                Ok::<_, ProtocolError>(Stream { send, recv })
+            }
+        };
+        let game = open(Channel::Game).await?;
+        let social = open(Channel::Social).await?;
+        Ok(Self {
+            connection,
+            format,
+            compression: Compression::None,
+            game,
+            social,
+        })
+    }
+
+    // Server side: waits for the client's two tagged streams
+    pub async fn accept(connection: Connection, format: WireFormat) -> Result<Self, ProtocolError> {
+        let (mut game, mut social) = (None, None);
+        while game.is_none() || social.is_none() {
+            let (send, mut recv) = connection
+                .accept_bi()
+                .await
+                .map_err(|e| ProtocolError::Io(e.to_string()))?;
+            let tag = recv
+                .read_u8()
+                .await
+                .map_err(|e| ProtocolError::Io(e.to_string()))?;
+            let stream = Some(Stream { send, recv });
+            match tag {
+                0 => game = stream,
+                1 => social = stream,
+                other => return Err(ProtocolError::Decode(format!("unknown stream tag {other}"))),
+            }
+        }
+        Ok(Self {
+            connection,
+            format,
+            compression: Compression::None,
+            game: game.unwrap(),
+            social: social.unwrap(),
+        })
+    }
+
+    fn stream(&mut self, channel: Channel) -> &mut Stream {
+        match channel {
+            Channel::Game => &mut self.game,
+            Channel::Social => &mut self.social,
+        }
+    }
+
+    pub async fn send<T: Serialize>(
+        &mut self,
+        channel: Channel,
+        message: &T,
+    ) -> Result<(), ProtocolError> {
+        let bytes = pack(encode(message, self.format)?, self.compression)?;
+        write_frame(&mut self.stream(channel).send, &bytes).await
+    }
+
+    pub async fn recv<T: DeserializeOwned>(
+        &mut self,
+        channel: Channel,
+    ) -> Result<T, ProtocolError> {
+        let format = self.format;
+        let bytes = unpack(&read_frame(&mut self.stream(channel).recv).await?)?;
+        decode(&bytes, format)
+    }
+
+    pub async fn close(mut self) -> Result<(), ProtocolError> {
+        for channel in [Channel::Game, Channel::Social] {
+            self.stream(channel)
+                .send
+                .shutdown()
+                .await
+                .map_err(|e| ProtocolError::Io(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
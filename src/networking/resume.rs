@@ -0,0 +1,186 @@
+// src/networking/resume.rs
+// Lets a client pick its session back up on a new connection, e.g. after
+// switching from wifi to cellular. Game-channel messages are numbered from 1
+// and kept until the client acks them, so a resuming client only gets what
+// it missed. A drop that is resumed within the migration window never
+// reaches the game as a disconnect.
+use super::ServerMessage;
+use crate::errors::ResumeError;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+pub const DEFAULT_MIGRATION_WINDOW: Duration = Duration::from_secs(15);
+
+// Unacked messages kept per client before the oldest are dropped
+pub const MAX_UNACKED: usize = 1024;
+
+#[derive(Debug)]
+struct ClientSession {
+    player_id: Uuid,
+    game_id: Uuid,
+    next_seq: u64,
+    unacked: VecDeque<(u64, ServerMessage)>,
+    dropped_at: Option<SystemTime>,
+}
+
+#[derive(Debug)]
+pub struct ResumableSessions {
+    sessions: HashMap<Uuid, ClientSession>,
+    migration_window: Duration,
+}
+
+impl Default for ResumableSessions {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIGRATION_WINDOW)
+    }
+}
+
+impl ResumableSessions {
+    pub fn new(migration_window: Duration) -> Self {
+        Self {
+            sessions: HashMap::new(),
+            migration_window,
+        }
+    }
+
+    // Token the client presents in ClientMessage::Resume
+    pub fn issue(&mut self, player_id: Uuid, game_id: Uuid) -> Uuid {
+        let token = Uuid::new_v4();
+        self.sessions.insert(
+            token,
+            ClientSession {
+                player_id,
+                game_id,
+                next_seq: 1,
+                unacked: VecDeque::new(),
+                dropped_at: None,
+            },
+        );
+        token
+    }
+
+    // Numbers an outgoing game message and holds it until acked
+    pub fn record(&mut self, token: Uuid, message: ServerMessage) -> Result<u64, ResumeError> {
+        let session = self
+            .sessions
+            .get_mut(&token)
+            .ok_or(ResumeError::UnknownToken)?;
+        let seq = session.next_seq;
+        session.next_seq += 1;
+        session.unacked.push_back((seq, message));
+        if session.unacked.len() > MAX_UNACKED {
+            session.unacked.pop_front();
+        }
+        Ok(seq)
+    }
+
+    pub fn ack(&mut self, token: Uuid, seq: u64) {
+        if let Some(session) = self.sessions.get_mut(&token) {
+            session.unacked.retain(|(sent, _)| *sent > seq);
+        }
+    }
+
+    // The transport went away; the player still counts as connected until
+    // the migration window runs out
+    pub fn connection_lost(&mut self, token: Uuid, now: SystemTime) {
+        if let Some(session) = self.sessions.get_mut(&token) {
+            session.dropped_at.get_or_insert(now);
+        }
+    }
+
+    // Attaches the session to a new connection and returns everything sent
+    // after `last_acked`, in order
+    pub fn resume(
+        &mut self,
+        token: Uuid,
+        last_acked: u64,
+        now: SystemTime,
+    ) -> Result<Vec<(u64, ServerMessage)>, ResumeError> {
+        let window = self.migration_window;
+        let session = self
+            .sessions
+            .get_mut(&token)
+            .ok_or(ResumeError::UnknownToken)?;
+        if session
+            .dropped_at
+            .is_some_and(|dropped| now.duration_since(dropped).unwrap_or_default() >= window)
+        {
+            return Err(ResumeError::Expired);
+        }
+        if last_acked >= session.next_seq {
+            return Err(ResumeError::AheadOfServer);
+        }
+        session.unacked.retain(|(seq, _)| *seq > last_acked);
+        // Something between the ack and what's buffered was already dropped
+        if session
+            .unacked
+            .front()
+            .is_some_and(|(seq, _)| *seq != last_acked + 1)
+        {
+            return Err(ResumeError::TooFarBehind);
+        }
+        session.dropped_at = None;
+        Ok(session.unacked.iter().cloned().collect())
+    }
+
+    // Sessions that stayed dropped past the migration window, as
+    // (player, game) pairs. The server marks these players disconnected in
+    // their games, which starts the normal reconnect window.
+    pub fn expire(&mut self, now: SystemTime) -> Vec<(Uuid, Uuid)> {
+        let window = self.migration_window;
+        let mut expired = Vec::new();
+        self.sessions.retain(|_, session| {
+            let gone = session
+                .dropped_at
+                .is_some_and(|dropped| now.duration_since(dropped).unwrap_or_default() >= window);
+            if gone {
+                expired.push((session.player_id, session.game_id));
+            }
+            !gone
+        });
+        expired
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod resume_tests {
+    use super::*;
+    use crate::events::GameEvent;
+
+    #[test]
+    fn test_resume_replays_only_unacked_messages() {
+        let mut sessions = ResumableSessions::new(Duration::from_secs(10));
+        let token = sessions.issue(Uuid::new_v4(), Uuid::new_v4());
+        let event = |player_id| {
+            ServerMessage::Event(GameEvent::TurnStarted {
+                player_id,
+                turn_number: 1,
+            })
+        };
+        for _ in 0..3 {
+            sessions.record(token, event(Uuid::new_v4())).unwrap();
+        }
+        sessions.ack(token, 1);
+
+        let start = SystemTime::UNIX_EPOCH;
+        sessions.connection_lost(token, start);
+        let missed = sessions
+            .resume(token, 2, start + Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(missed.iter().map(|(seq, _)| *seq).collect::<Vec<_>>(), [3]);
+        assert!(sessions.expire(start + Duration::from_secs(60)).is_empty());
+
+        sessions.connection_lost(token, start);
+        assert_eq!(
+            sessions.resume(token, 2, start + Duration::from_secs(10)),
+            Err(ResumeError::Expired)
+        );
+        assert_eq!(sessions.expire(start + Duration::from_secs(10)).len(), 1);
+        assert_eq!(
+            sessions.resume(token, 2, start),
+            Err(ResumeError::UnknownToken)
+        );
+    }
+}
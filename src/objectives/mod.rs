@@ -0,0 +1,362 @@
+// src/objectives/mod.rs
+// Secret objectives mode: each player is dealt a hidden goal at the start
+// (reach level 5 by turn 8, deal 15 damage with traps) that pays a bonus or
+// wins the game outright. Goals are data, progress is read off the event
+// log, and everyone's objective is revealed once the game is over.
+use crate::errors::ObjectiveError;
+use crate::events::GameEvent;
+use crate::game_state::GameState;
+use crate::models::CardType;
+use crate::wallet::Currency;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub const DEFAULT_OBJECTIVES: &str = include_str!("../../data/objectives.json");
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum Goal {
+    ReachLevel {
+        level: u32,
+        by_turn: u32,
+    },
+    // Damage to other players from cards of `card_type`, or from anything
+    DealDamage {
+        amount: u32,
+        card_type: Option<CardType>,
+    },
+    PlayCards {
+        count: u32,
+        card_type: Option<CardType>,
+    },
+}
+
+impl Goal {
+    pub fn target(&self) -> u32 {
+        match self {
+            Goal::ReachLevel { level, .. } => *level,
+            Goal::DealDamage { amount, .. } => *amount,
+            Goal::PlayCards { count, .. } => *count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum ObjectiveReward {
+    // An alternate win: the first player to complete it takes the game
+    Victory,
+    Bonus { currency: Currency, amount: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Objective {
+    pub id: String,
+    pub name: String,
+    pub goal: Goal,
+    pub reward: ObjectiveReward,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ObjectivePool {
+    pub objectives: Vec<Objective>,
+}
+
+impl ObjectivePool {
+    pub fn from_json(data: &str) -> Result<Self, ObjectiveError> {
+        let pool: ObjectivePool =
+            serde_json::from_str(data).map_err(|e| ObjectiveError::InvalidData(e.to_string()))?;
+        if pool.objectives.is_empty() {
+            return Err(ObjectiveError::InvalidData("no objectives".to_string()));
+        }
+        let mut ids = HashSet::new();
+        for objective in &pool.objectives {
+            if !ids.insert(objective.id.as_str()) {
+                return Err(ObjectiveError::InvalidData(format!(
+                    "duplicate objective {}",
+                    objective.id
+                )));
+            }
+            if objective.goal.target() == 0 {
+                return Err(ObjectiveError::InvalidData(format!(
+                    "objective {} is complete from the start",
+                    objective.id
+                )));
+            }
+        }
+        Ok(pool)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectiveProgress {
+    pub player_id: Uuid,
+    pub objective: Objective,
+    pub progress: u32,
+    // Index of the event that completed it
+    pub completed_at: Option<usize>,
+}
+
+// One player's objective as shown to everyone at the end
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectiveReveal {
+    pub player_id: Uuid,
+    pub objective_id: String,
+    pub name: String,
+    pub progress: u32,
+    pub target: u32,
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SecretObjectives {
+    // In turn order
+    seats: Vec<ObjectiveProgress>,
+    // The card being resolved and who played it, so damage can be credited
+    // to its type
+    resolving: Option<(Uuid, CardType)>,
+    turn_number: u32,
+    seen: usize,
+}
+
+impl SecretObjectives {
+    // Players get different objectives while the pool lasts. The seed keeps
+    // the deal reproducible for replays.
+    pub fn deal(pool: &ObjectivePool, game_state: &GameState, seed: u64) -> Self {
+        let mut shuffled: Vec<&Objective> = pool.objectives.iter().collect();
+        shuffled.shuffle(&mut StdRng::seed_from_u64(seed));
+        let seats = game_state
+            .turn_order
+            .iter()
+            .zip(shuffled.iter().cycle())
+            .map(|(player_id, objective)| ObjectiveProgress {
+                player_id: *player_id,
+                objective: (*objective).clone(),
+                progress: 0,
+                completed_at: None,
+            })
+            .collect();
+        Self {
+            seats,
+            resolving: None,
+            turn_number: game_state.turn_number,
+            seen: game_state.events.len(),
+        }
+    }
+
+    // Catches up on events logged since the last call and returns the
+    // players who completed their objective along the way
+    pub fn observe(&mut self, game_state: &GameState) -> Vec<Uuid> {
+        let mut completed = Vec::new();
+        for (index, event) in game_state.events.iter().enumerate().skip(self.seen) {
+            match event {
+                GameEvent::TurnStarted { turn_number, .. } => {
+                    self.turn_number = *turn_number;
+                    self.resolving = None;
+                }
+                GameEvent::CardPlayed {
+                    player_id, card_id, ..
+                } => {
+                    // Played cards land in the graveyard
+                    let card_type = game_state.players.get(player_id).and_then(|player| {
+                        player
+                            .graveyard
+                            .iter()
+                            .find(|card| card.id == *card_id)
+                            .map(|card| card.card_type.clone())
+                    });
+                    self.resolving = card_type.clone().map(|kind| (*player_id, kind));
+                    self.advance(*player_id, index, &mut completed, |goal, _| match goal {
+                        Goal::PlayCards {
+                            card_type: wanted, ..
+                        } if wanted.is_none() || *wanted == card_type => Some(1),
+                        _ => None,
+                    });
+                }
+                GameEvent::PlayerMoved { player_id, to, .. } => {
+                    self.resolving = None;
+                    let turn_number = self.turn_number;
+                    self.advance(
+                        *player_id,
+                        index,
+                        &mut completed,
+                        |goal, progress| match goal {
+                            Goal::ReachLevel { by_turn, .. }
+                                if turn_number <= *by_turn && to.level > progress =>
+                            {
+                                Some(to.level - progress)
+                            }
+                            _ => None,
+                        },
+                    );
+                }
+                GameEvent::DamageDealt {
+                    source,
+                    target,
+                    amount,
+                } if source != target => {
+                    let from_card = self
+                        .resolving
+                        .as_ref()
+                        .filter(|(player_id, _)| player_id == source)
+                        .map(|(_, kind)| kind.clone());
+                    self.advance(*source, index, &mut completed, |goal, _| match goal {
+                        Goal::DealDamage { card_type, .. }
+                            if card_type.is_none() || *card_type == from_card =>
+                        {
+                            Some(*amount)
+                        }
+                        _ => None,
+                    });
+                }
+                _ => {}
+            }
+        }
+        self.seen = game_state.events.len();
+        completed
+    }
+
+    fn advance(
+        &mut self,
+        player_id: Uuid,
+        index: usize,
+        completed: &mut Vec<Uuid>,
+        gain: impl Fn(&Goal, u32) -> Option<u32>,
+    ) {
+        let Some(seat) = self
+            .seats
+            .iter_mut()
+            .find(|seat| seat.player_id == player_id && seat.completed_at.is_none())
+        else {
+            return;
+        };
+        if let Some(gain) = gain(&seat.objective.goal, seat.progress) {
+            seat.progress = seat.progress.saturating_add(gain);
+            if seat.progress >= seat.objective.goal.target() {
+                seat.completed_at = Some(index);
+                completed.push(player_id);
+            }
+        }
+    }
+
+    // A player may always look at their own objective
+    pub fn own(&self, player_id: Uuid) -> Option<&ObjectiveProgress> {
+        self.seats.iter().find(|seat| seat.player_id == player_id)
+    }
+
+    // Whoever completed a Victory objective first, if anyone has
+    pub fn winner(&self) -> Option<Uuid> {
+        self.seats
+            .iter()
+            .filter(|seat| seat.objective.reward == ObjectiveReward::Victory)
+            .filter_map(|seat| Some((seat.completed_at?, seat.player_id)))
+            .min()
+            .map(|(_, player_id)| player_id)
+    }
+
+    // Bonuses earned, to be paid once the game is over
+    pub fn bonus_rewards(&self) -> Vec<(Uuid, Currency, u64)> {
+        self.seats
+            .iter()
+            .filter(|seat| seat.completed_at.is_some())
+            .filter_map(|seat| match seat.objective.reward {
+                ObjectiveReward::Bonus { currency, amount } => {
+                    Some((seat.player_id, currency, amount))
+                }
+                ObjectiveReward::Victory => None,
+            })
+            .collect()
+    }
+
+    pub fn reveal(&self, game_state: &GameState) -> Result<Vec<ObjectiveReveal>, ObjectiveError> {
+        if game_state.winner().is_none() && self.winner().is_none() {
+            return Err(ObjectiveError::GameInProgress);
+        }
+        Ok(self
+            .seats
+            .iter()
+            .map(|seat| ObjectiveReveal {
+                player_id: seat.player_id,
+                objective_id: seat.objective.id.clone(),
+                name: seat.objective.name.clone(),
+                progress: seat.progress.min(seat.objective.goal.target()),
+                target: seat.objective.goal.target(),
+                completed: seat.completed_at.is_some(),
+            })
+            .collect())
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod objective_tests {
+    use super::*;
+    use crate::effects::{DamageEffect, Effect, EffectTarget, EffectValue};
+    use crate::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
+
+    #[test]
+    fn test_trap_damage_completes_bonus_objective_revealed_at_end() {
+        let pool = ObjectivePool::from_json(DEFAULT_OBJECTIVES).unwrap();
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Alice").build())
+            .player(PlayerBuilder::new("Bob").build())
+            .build();
+        let (alice, bob) = (game_state.turn_order[0], game_state.turn_order[1]);
+        let mut objectives = SecretObjectives::deal(&pool, &game_state, 3);
+        assert_ne!(
+            objectives.own(bob).unwrap().objective.id,
+            objectives.own(alice).unwrap().objective.id
+        );
+        // Stack the deal so Alice is the trapper
+        objectives.seats[0].objective = pool
+            .objectives
+            .iter()
+            .find(|objective| objective.id == "trapper")
+            .unwrap()
+            .clone();
+
+        for damage in [10, 5] {
+            let snare = CardBuilder::new("Snare")
+                .cost(0)
+                .card_type(CardType::Trap)
+                .effect(Effect::Damage(DamageEffect {
+                    value: EffectValue {
+                        base: damage,
+                        scaling: None,
+                    },
+                    target: EffectTarget::Specific(bob),
+                    penetrating: false,
+                    ranged: false,
+                    priority: 0,
+                }))
+                .build();
+            let snare_id = snare.id;
+            game_state
+                .players
+                .get_mut(&alice)
+                .unwrap()
+                .add_to_hand(snare);
+            game_state.play_card(alice, snare_id).unwrap();
+            let completed = objectives.observe(&game_state);
+            assert_eq!(completed, if damage == 5 { vec![alice] } else { vec![] });
+        }
+        assert_eq!(
+            objectives.bonus_rewards(),
+            vec![(alice, Currency::Gold, 50)]
+        );
+        assert!(matches!(
+            objectives.reveal(&game_state),
+            Err(ObjectiveError::GameInProgress)
+        ));
+
+        game_state.players.get_mut(&bob).unwrap().health = 0;
+        let reveal = objectives.reveal(&game_state).unwrap();
+        assert_eq!(
+            (reveal[0].objective_id.as_str(), reveal[0].completed),
+            ("trapper", true)
+        );
+        assert!(ObjectivePool::from_json(r#"{"objectives": []}"#).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+// src/practice/mod.rs
+// Solo practice against a bot playing a copy of the player's own deck, on
+// whichever mountain the player picked. A practice game is never hosted by
+// the SessionManager, so it stays out of matchmaking, ratings and the event
+// bus, and every move can be taken back for free.
+use crate::bots::{AscentBot, BotRunner};
+use crate::errors::GameError;
+use crate::game_state::{GameAction, GameState};
+use crate::models::{Card, Deck, Player};
+use crate::scenarios::MountainSpec;
+use crate::sessions::{GameSession, MatchInfo};
+use crate::simulation::STARTING_HAND;
+use crate::view::PlayerView;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use uuid::Uuid;
+
+pub struct PracticeGame {
+    pub session: GameSession,
+    pub player_id: Uuid,
+    pub mirror_id: Uuid,
+    bots: BotRunner,
+}
+
+impl PracticeGame {
+    // The player always goes first. The mirror gets the same cards under
+    // new ids, so no card is in two places at once.
+    pub fn start(
+        player_id: Uuid,
+        name: &str,
+        deck: &Deck,
+        mountain: &MountainSpec,
+        bot: Box<dyn AscentBot>,
+        seed: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mirror_id = Uuid::new_v4();
+        let mirrored: Vec<Card> = deck
+            .cards
+            .iter()
+            .map(|card| Card {
+                id: Uuid::new_v4(),
+                ..card.clone()
+            })
+            .collect();
+        let mut seat = |id: Uuid, name: String, mut cards: Vec<Card>| {
+            cards.shuffle(&mut rng);
+            let mut player = Player::new(
+                name,
+                Deck {
+                    cards: cards.into(),
+                    owner_id: id,
+                },
+            );
+            player.id = id;
+            for _ in 0..STARTING_HAND {
+                let _ = player.draw_card();
+            }
+            player
+        };
+        let mut player = seat(player_id, name.to_string(), deck.cards.to_vec());
+        player.mana = 1;
+        let mirror = seat(mirror_id, format!("Mirror of {}", name), mirrored);
+
+        let mut game = GameState::new(player, mirror).with_rng(Box::new(rng));
+        game.mountain = mountain.build();
+        let info = MatchInfo {
+            match_id: Uuid::new_v4(),
+            game_number: 1,
+            previous_game: None,
+        };
+        let mut bots = BotRunner::new();
+        bots.seat(mirror_id, bot);
+        Self {
+            session: GameSession::new(game, info),
+            player_id,
+            mirror_id,
+            bots,
+        }
+    }
+
+    // Applies the player's action, then lets the mirror play out any turns
+    // it is handed
+    pub fn act(&mut self, action: &GameAction) -> Result<(), GameError> {
+        self.session.apply_action(self.player_id, action)?;
+        while self.session.game.winner().is_none()
+            && self.session.game.active_player == self.mirror_id
+        {
+            let bots = &mut self.bots;
+            self.session.run(|game| bots.play_turn(game))?;
+        }
+        Ok(())
+    }
+
+    // Takes back the player's last action along with the mirror's answer to
+    // it. The mirror's turns leave no checkpoints, so the nearest one is
+    // from just before the player acted. Returns how many events were
+    // undone, or None with nothing left to undo.
+    pub fn undo(&mut self) -> Option<usize> {
+        self.session.rollback(1)
+    }
+
+    pub fn view(&self) -> PlayerView {
+        self.session.game.view_with_hints(self.player_id)
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod practice_tests {
+    use super::*;
+    use crate::testing::CardBuilder;
+
+    struct Passer;
+
+    impl AscentBot for Passer {
+        fn name(&self) -> &str {
+            "passer"
+        }
+
+        fn decide(&mut self, _view: &PlayerView) -> GameAction {
+            GameAction::EndTurn
+        }
+    }
+
+    #[test]
+    fn test_mirror_plays_back_and_undo_is_free() {
+        let player_id = Uuid::new_v4();
+        let cards: Vec<Card> = ["Rope", "Piton", "Ice Axe", "Lantern"]
+            .into_iter()
+            .map(|name| CardBuilder::new(name).cost(1).build())
+            .collect();
+        let deck = Deck {
+            cards: cards.clone().into(),
+            owner_id: player_id,
+        };
+        let mountain = MountainSpec {
+            levels: 4,
+            tiles: None,
+            camps: None,
+            features: Vec::new(),
+        };
+        let mut practice =
+            PracticeGame::start(player_id, "Alice", &deck, &mountain, Box::new(Passer), 7);
+        let game = &practice.session.game;
+        assert_eq!(game.active_player, player_id);
+        assert_eq!(game.mountain.levels, 4);
+        let mirror = &game.players[&practice.mirror_id];
+        let mut names: Vec<&str> = mirror
+            .hand
+            .iter()
+            .chain(mirror.deck.cards.iter())
+            .map(|card| card.name.as_str())
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, ["Ice Axe", "Lantern", "Piton", "Rope"]);
+        assert!(mirror
+            .hand
+            .iter()
+            .all(|card| cards.iter().all(|original| original.id != card.id)));
+        let opening = game.state_hash();
+        assert!(practice.undo().is_none());
+
+        let card_id = practice.view().hints.unwrap().payable[0];
+        practice.act(&GameAction::PlayCard { card_id }).unwrap();
+        practice.act(&GameAction::EndTurn).unwrap();
+        // The mirror passed and handed the turn straight back
+        assert_eq!(practice.session.game.active_player, player_id);
+        assert_eq!(practice.session.game.turn_number, 3);
+
+        assert!(practice.undo().is_some());
+        assert_eq!(practice.session.game.turn_number, 1);
+        assert!(practice.undo().is_some());
+        assert_eq!(practice.session.game.state_hash(), opening);
+        assert!(practice.undo().is_none());
+    }
+}
@@ -0,0 +1,207 @@
+// src/profiles/mod.rs
+// A player's public record: games and wins, the archetype they play most,
+// the best rank they've reached and the titles and borders that unlocks.
+// Kept up to date from finished matches and stored alongside the account.
+// The full profile is what the profile endpoint serves; the compact card
+// rides along in game start metadata.
+use crate::database::{MatchRecord, ProfileStorage};
+use crate::networking::ServerMessage;
+use crate::rating::Rating;
+use crate::stats::RankBand;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Milestone {
+    Wins(u32),
+    Games(u32),
+    Rank(RankBand),
+}
+
+const TITLES: &[(&str, Milestone)] = &[
+    ("title_first_ascent", Milestone::Wins(1)),
+    ("title_mountaineer", Milestone::Wins(50)),
+    ("title_veteran", Milestone::Games(100)),
+    ("title_summiteer", Milestone::Rank(RankBand::Summit)),
+];
+
+fn border_for(band: RankBand) -> &'static str {
+    match band {
+        RankBand::Foothills => "border_foothills",
+        RankBand::Ridge => "border_ridge",
+        RankBand::Peak => "border_peak",
+        RankBand::Summit => "border_summit",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub player_id: Uuid,
+    pub games_played: u32,
+    pub wins: u32,
+    // Games per archetype, for the favourite
+    pub archetypes: BTreeMap<String, u32>,
+    pub highest_rank: Option<RankBand>,
+    pub titles: BTreeSet<String>,
+    pub borders: BTreeSet<String>,
+    // Shown next to the name; must be one of `titles`
+    pub title: Option<String>,
+}
+
+// What other players see at game start
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileCard {
+    pub wins: u32,
+    pub highest_rank: Option<RankBand>,
+    pub title: Option<String>,
+    pub border: Option<String>,
+}
+
+impl Profile {
+    pub fn new(player_id: Uuid) -> Self {
+        Self {
+            player_id,
+            games_played: 0,
+            wins: 0,
+            archetypes: BTreeMap::new(),
+            highest_rank: None,
+            titles: BTreeSet::new(),
+            borders: BTreeSet::new(),
+            title: None,
+        }
+    }
+
+    // Ties go to the archetype that sorts first, so the answer is stable
+    pub fn favorite_archetype(&self) -> Option<&str> {
+        self.archetypes
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+            .map(|(name, _)| name.as_str())
+    }
+
+    pub fn card(&self) -> ProfileCard {
+        ProfileCard {
+            wins: self.wins,
+            highest_rank: self.highest_rank,
+            title: self.title.clone(),
+            border: self.highest_rank.map(|band| border_for(band).to_string()),
+        }
+    }
+
+    pub fn set_title(&mut self, title: Option<&str>) -> bool {
+        match title {
+            Some(title) if !self.titles.contains(title) => false,
+            _ => {
+                self.title = title.map(str::to_string);
+                true
+            }
+        }
+    }
+
+    fn record(&mut self, won: bool, archetype: Option<&str>, rating: Option<&Rating>) {
+        self.games_played += 1;
+        self.wins += won as u32;
+        if let Some(archetype) = archetype {
+            *self.archetypes.entry(archetype.to_string()).or_default() += 1;
+        }
+        if let Some(band) = rating.map(RankBand::of) {
+            if self.highest_rank.is_none_or(|highest| band > highest) {
+                self.highest_rank = Some(band);
+            }
+            self.borders.insert(border_for(band).to_string());
+        }
+        for (title, milestone) in TITLES {
+            let reached = match *milestone {
+                Milestone::Wins(wins) => self.wins >= wins,
+                Milestone::Games(games) => self.games_played >= games,
+                Milestone::Rank(band) => self.highest_rank.is_some_and(|highest| highest >= band),
+            };
+            if reached {
+                self.titles.insert(title.to_string());
+            }
+        }
+    }
+}
+
+// Folds a finished match into every seat's profile. `archetypes` and
+// `ratings` are by player, with ratings taken after the match.
+pub fn record_match(
+    storage: &mut impl ProfileStorage,
+    record: &MatchRecord,
+    archetypes: &HashMap<Uuid, String>,
+    ratings: &HashMap<Uuid, Rating>,
+) {
+    for player_id in &record.players {
+        let mut profile = storage
+            .load_profile(*player_id)
+            .unwrap_or_else(|| Profile::new(*player_id));
+        profile.record(
+            record.winner == Some(*player_id),
+            archetypes.get(player_id).map(String::as_str),
+            ratings.get(player_id),
+        );
+        storage.save_profile(profile);
+    }
+}
+
+// Adds each player's profile card to a GameStarted message
+pub fn attach_cards(message: &mut ServerMessage, storage: &impl ProfileStorage) {
+    if let ServerMessage::GameStarted { players, .. } = message {
+        for player in players {
+            player.profile = storage
+                .load_profile(player.player_id)
+                .map(|profile| profile.card());
+        }
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod profile_tests {
+    use super::*;
+    use crate::database::MemoryStorage;
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_matches_build_up_profile_and_card() {
+        let mut storage = MemoryStorage::default();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let archetypes = HashMap::from([(alice, "Avalanche".to_string())]);
+        let ratings = HashMap::from([
+            (alice, Rating::new(1750.0, 50.0, 0.06)),
+            (bob, Rating::new(1350.0, 50.0, 0.06)),
+        ]);
+        for winner in [alice, bob, alice] {
+            let record = MatchRecord {
+                game_id: Uuid::new_v4(),
+                players: vec![alice, bob],
+                winner: Some(winner),
+                played_at: SystemTime::UNIX_EPOCH,
+                summary: None,
+            };
+            record_match(&mut storage, &record, &archetypes, &ratings);
+        }
+
+        let mut profile = storage.load_profile(alice).unwrap();
+        assert_eq!((profile.games_played, profile.wins), (3, 2));
+        assert_eq!(profile.favorite_archetype(), Some("Avalanche"));
+        assert_eq!(profile.highest_rank, Some(RankBand::Peak));
+        assert!(profile.titles.contains("title_first_ascent"));
+        assert!(!profile.set_title(Some("title_summiteer")));
+        assert!(profile.set_title(Some("title_first_ascent")));
+        assert_eq!(
+            profile.card(),
+            ProfileCard {
+                wins: 2,
+                highest_rank: Some(RankBand::Peak),
+                title: Some("title_first_ascent".to_string()),
+                border: Some("border_peak".to_string()),
+            }
+        );
+        assert_eq!(
+            storage.load_profile(bob).unwrap().favorite_archetype(),
+            None
+        );
+    }
+}
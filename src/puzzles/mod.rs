@@ -0,0 +1,179 @@
+// src/puzzles/mod.rs
+use crate::errors::PuzzleError;
+use crate::game_state::GameState;
+use crate::models::{Card, Deck, Mountain, Player, Position};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum PuzzleGoal {
+    DefeatOpponent,
+    ReachLevel(u32),
+    OpponentHealthAtMost(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PuzzleSide {
+    pub id: Uuid,
+    pub name: String,
+    pub health: u32,
+    #[serde(default)]
+    pub mana: u32,
+    #[serde(default)]
+    pub position: Position,
+    #[serde(default)]
+    pub hand: Vec<Card>,
+    #[serde(default)]
+    pub deck: Vec<Card>,
+}
+
+impl PuzzleSide {
+    pub(crate) fn from_player(player: &Player) -> Self {
+        Self {
+            id: player.id,
+            name: player.name.clone(),
+            health: player.health,
+            mana: player.mana,
+            position: player.position,
+            hand: player.hand.clone(),
+            deck: player.deck.cards.to_vec(),
+        }
+    }
+
+    pub(crate) fn to_player(&self) -> Player {
+        let mut player = Player::new(
+            self.name.clone(),
+            Deck {
+                cards: self.deck.clone().into(),
+                owner_id: self.id,
+            },
+        );
+        player.id = self.id;
+        player.health = self.health;
+        player.max_health = self.health.max(player.max_health);
+        player.mana = self.mana;
+        player.position = self.position;
+        player.set_hand(self.hand.clone());
+        player
+    }
+}
+
+// A puzzle is a fixed position the player must win from within a single turn
+#[derive(Debug, Clone, Deserialize)]
+pub struct Puzzle {
+    pub name: String,
+    pub description: String,
+    pub mountain_levels: u32,
+    pub player: PuzzleSide,
+    pub opponent: PuzzleSide,
+    pub goal: PuzzleGoal,
+}
+
+impl Puzzle {
+    pub fn from_json(data: &str) -> Result<Self, PuzzleError> {
+        let puzzle: Puzzle =
+            serde_json::from_str(data).map_err(|e| PuzzleError::InvalidData(e.to_string()))?;
+        puzzle.validate()?;
+        Ok(puzzle)
+    }
+
+    pub fn validate(&self) -> Result<(), PuzzleError> {
+        if !(1..=50).contains(&self.mountain_levels) {
+            return Err(PuzzleError::InvalidData(
+                "mountain_levels must be between 1 and 50".to_string(),
+            ));
+        }
+        if self.player.id == self.opponent.id {
+            return Err(PuzzleError::InvalidData(
+                "player and opponent share an id".to_string(),
+            ));
+        }
+
+        let mountain = Mountain::new(self.mountain_levels);
+        for side in [&self.player, &self.opponent] {
+            if side.health == 0 {
+                return Err(PuzzleError::InvalidData(format!(
+                    "{} starts with no health",
+                    side.name
+                )));
+            }
+            let pos = side.position;
+            if mountain.get_tile(pos.x, pos.y, pos.z).is_none() {
+                return Err(PuzzleError::InvalidData(format!(
+                    "{} starts off the mountain",
+                    side.name
+                )));
+            }
+        }
+
+        if self.is_solved(&self.setup()) {
+            return Err(PuzzleError::InvalidData(
+                "goal is already met in the starting position".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn setup(&self) -> GameState {
+        let mut game_state = GameState::new(self.player.to_player(), self.opponent.to_player());
+        game_state.mountain = Mountain::new(self.mountain_levels);
+        game_state.active_player = self.player.id;
+        game_state
+    }
+
+    pub fn is_solved(&self, game_state: &GameState) -> bool {
+        let (Some(player), Some(opponent)) = (
+            game_state.players.get(&self.player.id),
+            game_state.players.get(&self.opponent.id),
+        ) else {
+            return false;
+        };
+
+        if player.health == 0 {
+            return false;
+        }
+        match self.goal {
+            PuzzleGoal::DefeatOpponent => opponent.health == 0,
+            PuzzleGoal::ReachLevel(level) => player.position.level >= level,
+            PuzzleGoal::OpponentHealthAtMost(health) => opponent.health <= health,
+        }
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod puzzle_tests {
+    use super::*;
+
+    const FIRST_ASCENT: &str = include_str!("../../data/puzzles/first_ascent.json");
+
+    #[test]
+    fn test_first_ascent_solution() {
+        let puzzle = Puzzle::from_json(FIRST_ASCENT).unwrap();
+        let mut game_state = puzzle.setup();
+        let player_id = puzzle.player.id;
+        let card_id = |game_state: &GameState, name: &str| {
+            game_state.players[&player_id]
+                .hand
+                .iter()
+                .find(|card| card.name == name)
+                .unwrap()
+                .id
+        };
+
+        // Healing first leaves too little mana for lethal
+        let mut wrong_line = puzzle.setup();
+        let heal = card_id(&wrong_line, "Second Wind");
+        wrong_line.play_card(player_id, heal).unwrap();
+        let rockfall = card_id(&wrong_line, "Rockfall");
+        assert!(wrong_line.play_card(player_id, rockfall).is_err());
+        assert!(!puzzle.is_solved(&wrong_line));
+
+        let rockfall = card_id(&game_state, "Rockfall");
+        game_state.play_card(player_id, rockfall).unwrap();
+        let shard = card_id(&game_state, "Ice Shard");
+        game_state.play_card(player_id, shard).unwrap();
+
+        assert!(puzzle.is_solved(&game_state));
+    }
+}
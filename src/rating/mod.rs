@@ -0,0 +1,203 @@
+// src/rating/mod.rs
+// Glicko-2 as described in Glickman, "Example of the Glicko-2 system".
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+pub const DEFAULT_RATING: f64 = 1500.0;
+pub const DEFAULT_DEVIATION: f64 = 350.0;
+pub const DEFAULT_VOLATILITY: f64 = 0.06;
+
+// Constrains how much volatility can change between rating periods
+const TAU: f64 = 0.5;
+const GLICKO2_SCALE: f64 = 173.7178;
+const CONVERGENCE_TOLERANCE: f64 = 0.000001;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatchOutcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl MatchOutcome {
+    fn score(&self) -> f64 {
+        match self {
+            MatchOutcome::Win => 1.0,
+            MatchOutcome::Draw => 0.5,
+            MatchOutcome::Loss => 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchResult {
+    pub opponent: Rating,
+    pub outcome: MatchOutcome,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self {
+            rating: DEFAULT_RATING,
+            deviation: DEFAULT_DEVIATION,
+            volatility: DEFAULT_VOLATILITY,
+        }
+    }
+}
+
+impl Rating {
+    pub fn new(rating: f64, deviation: f64, volatility: f64) -> Self {
+        Self {
+            rating,
+            deviation,
+            volatility,
+        }
+    }
+
+    // Conservative skill estimate used for leaderboards and seeding
+    pub fn lower_bound(&self) -> f64 {
+        self.rating - 2.0 * self.deviation
+    }
+
+    fn mu(&self) -> f64 {
+        (self.rating - DEFAULT_RATING) / GLICKO2_SCALE
+    }
+
+    fn phi(&self) -> f64 {
+        self.deviation / GLICKO2_SCALE
+    }
+
+    pub fn expected_score(&self, opponent: &Rating) -> f64 {
+        expected(self.mu(), opponent.mu(), opponent.phi())
+    }
+
+    // Applies one rating period. An empty period only grows the deviation,
+    // reflecting increased uncertainty about an inactive player.
+    pub fn update(&self, results: &[MatchResult]) -> Rating {
+        let mu = self.mu();
+        let phi = self.phi();
+
+        if results.is_empty() {
+            let phi_star = (phi.powi(2) + self.volatility.powi(2)).sqrt();
+            return Rating {
+                deviation: (phi_star * GLICKO2_SCALE).min(DEFAULT_DEVIATION),
+                ..*self
+            };
+        }
+
+        let mut v_inverse = 0.0;
+        let mut score_sum = 0.0;
+        for result in results {
+            let g_j = g(result.opponent.phi());
+            let e_j = expected(mu, result.opponent.mu(), result.opponent.phi());
+            v_inverse += g_j.powi(2) * e_j * (1.0 - e_j);
+            score_sum += g_j * (result.outcome.score() - e_j);
+        }
+        let v = 1.0 / v_inverse;
+        let delta = v * score_sum;
+
+        let volatility = new_volatility(phi, self.volatility, v, delta);
+        let phi_star = (phi.powi(2) + volatility.powi(2)).sqrt();
+        let new_phi = 1.0 / (1.0 / phi_star.powi(2) + 1.0 / v).sqrt();
+        let new_mu = mu + new_phi.powi(2) * score_sum;
+
+        Rating {
+            rating: new_mu * GLICKO2_SCALE + DEFAULT_RATING,
+            deviation: new_phi * GLICKO2_SCALE,
+            volatility,
+        }
+    }
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi.powi(2) / PI.powi(2)).sqrt()
+}
+
+fn expected(mu: f64, opponent_mu: f64, opponent_phi: f64) -> f64 {
+    1.0 / (1.0 + (-g(opponent_phi) * (mu - opponent_mu)).exp())
+}
+
+// Illinois-algorithm root finding for the new volatility (step 5 of the paper)
+fn new_volatility(phi: f64, sigma: f64, v: f64, delta: f64) -> f64 {
+    let a = sigma.powi(2).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        ex * (delta.powi(2) - phi.powi(2) - v - ex) / (2.0 * (phi.powi(2) + v + ex).powi(2))
+            - (x - a) / TAU.powi(2)
+    };
+
+    let mut big_a = a;
+    let mut big_b = if delta.powi(2) > phi.powi(2) + v {
+        (delta.powi(2) - phi.powi(2) - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * TAU) < 0.0 {
+            k += 1.0;
+        }
+        a - k * TAU
+    };
+
+    let mut f_a = f(big_a);
+    let mut f_b = f(big_b);
+    while (big_b - big_a).abs() > CONVERGENCE_TOLERANCE {
+        let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+        let f_c = f(big_c);
+        if f_c * f_b <= 0.0 {
+            big_a = big_b;
+            f_a = f_b;
+        } else {
+            f_a /= 2.0;
+        }
+        big_b = big_c;
+        f_b = f_c;
+    }
+
+    (big_a / 2.0).exp()
+}
+
+// TESTS
+#[cfg(test)]
+mod rating_tests {
+    use super::*;
+
+    #[test]
+    fn test_glickman_example() {
+        let player = Rating::new(1500.0, 200.0, 0.06);
+        let results = [
+            MatchResult {
+                opponent: Rating::new(1400.0, 30.0, 0.06),
+                outcome: MatchOutcome::Win,
+            },
+            MatchResult {
+                opponent: Rating::new(1550.0, 100.0, 0.06),
+                outcome: MatchOutcome::Loss,
+            },
+            MatchResult {
+                opponent: Rating::new(1700.0, 300.0, 0.06),
+                outcome: MatchOutcome::Loss,
+            },
+        ];
+
+        let updated = player.update(&results);
+
+        assert!((updated.rating - 1464.06).abs() < 0.01);
+        assert!((updated.deviation - 151.52).abs() < 0.01);
+        assert!((updated.volatility - 0.05999).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_inactivity_grows_deviation() {
+        let player = Rating::new(1600.0, 80.0, 0.06);
+        let updated = player.update(&[]);
+
+        assert_eq!(updated.rating, player.rating);
+        assert!(updated.deviation > player.deviation);
+    }
+}
@@ -0,0 +1,235 @@
+// src/recovery/mod.rs
+// Whole-server snapshots for disaster recovery. Every hosted game, the
+// matchmaking queue and each session's match details are captured at one
+// moment and written out as a single file, so a failover drill can bring a
+// fresh instance up from it without replaying incremental saves. The file
+// is written next to its destination and renamed into place, so a crash
+// halfway through leaves the last good snapshot alone.
+use crate::errors::RecoveryError;
+use crate::game_state::ENGINE_VERSION;
+use crate::matchmaking::{MatchmakingQueue, QueueEntry};
+use crate::sessions::{GameSession, SessionManager, SessionSnapshot};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Bumped whenever the layout changes in a way older servers can't read
+pub const SNAPSHOT_VERSION: u32 = 1;
+pub const COMPRESSION_LEVEL: i32 = 3;
+// Where `save_suspended` keeps the games paused for maintenance
+pub const SUSPENDED_FILE: &str = "maintenance.snapshot";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerSnapshot {
+    pub version: u32,
+    pub taken_at: SystemTime,
+    pub sessions: Vec<SessionSnapshot>,
+    pub queue: Vec<QueueEntry>,
+}
+
+impl ServerSnapshot {
+    // Both are borrowed for the whole capture, so nothing moves in between
+    pub fn capture(sessions: &SessionManager, queue: &MatchmakingQueue, now: SystemTime) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            taken_at: now,
+            sessions: sessions.snapshot(),
+            queue: queue.entries().to_vec(),
+        }
+    }
+
+    // One game on its own, e.g. one that has just halted
+    pub fn of_session(session: &GameSession, now: SystemTime) -> Self {
+        Self::of_sessions(std::slice::from_ref(session), now)
+    }
+
+    // Games held outside the manager, e.g. while maintenance has them
+    pub fn of_sessions(sessions: &[GameSession], now: SystemTime) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            taken_at: now,
+            sessions: sessions.iter().map(GameSession::snapshot).collect(),
+            queue: Vec::new(),
+        }
+    }
+
+    // zstd-compressed JSON
+    pub fn to_bytes(&self) -> Result<Vec<u8>, RecoveryError> {
+        let json =
+            serde_json::to_vec(self).map_err(|e| RecoveryError::InvalidData(e.to_string()))?;
+        zstd::encode_all(json.as_slice(), COMPRESSION_LEVEL)
+            .map_err(|e| RecoveryError::Io(e.to_string()))
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, RecoveryError> {
+        let json = zstd::decode_all(data).map_err(|e| RecoveryError::InvalidData(e.to_string()))?;
+        let snapshot: ServerSnapshot =
+            serde_json::from_slice(&json).map_err(|e| RecoveryError::InvalidData(e.to_string()))?;
+        if snapshot.version > SNAPSHOT_VERSION {
+            return Err(RecoveryError::UnsupportedVersion(snapshot.version));
+        }
+        Ok(snapshot)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), RecoveryError> {
+        let io = |e: std::io::Error| RecoveryError::Io(e.to_string());
+        let bytes = self.to_bytes()?;
+        let partial = path.with_extension("partial");
+        let mut file = std::fs::File::create(&partial).map_err(io)?;
+        file.write_all(&bytes).map_err(io)?;
+        file.sync_all().map_err(io)?;
+        std::fs::rename(&partial, path).map_err(io)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, RecoveryError> {
+        let bytes = std::fs::read(path).map_err(|e| RecoveryError::Io(e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    // Brings the snapshot up on this instance and returns how many games it
+    // now hosts from it. Every game is checked first, so a bad snapshot
+    // leaves the instance as it was.
+    pub fn restore(
+        self,
+        sessions: &mut SessionManager,
+        queue: &mut MatchmakingQueue,
+    ) -> Result<usize, RecoveryError> {
+        for snapshot in &self.sessions {
+            let game = &snapshot.game;
+            if sessions.get(game.game_id).is_some() {
+                return Err(RecoveryError::AlreadyHosted(game.game_id));
+            }
+            if game.rules_version.engine != ENGINE_VERSION {
+                return Err(RecoveryError::EngineMismatch {
+                    game_id: game.game_id,
+                    engine: game.rules_version.engine,
+                });
+            }
+            game.validate_invariants().map_err(|violation| {
+                RecoveryError::InvalidData(format!("game {}: {:?}", game.game_id, violation))
+            })?;
+        }
+        let restored = self.sessions.len();
+        for snapshot in self.sessions {
+            sessions.adopt(GameSession::restore(snapshot));
+        }
+        queue.restore(self.queue);
+        Ok(restored)
+    }
+}
+
+// Writes a halted game's last good state to `dir`, so it outlives a crash
+// and can be brought back up with an ordinary snapshot import once fixed
+pub fn save_halted(
+    session: &GameSession,
+    dir: &Path,
+    now: SystemTime,
+) -> Result<PathBuf, RecoveryError> {
+    let path = dir.join(format!("{}.halted.snapshot", session.game.game_id));
+    ServerSnapshot::of_session(session, now).save(&path)?;
+    Ok(path)
+}
+
+// Writes the games set aside for maintenance to `dir`, replacing whatever
+// was written for them before, so a restart during the window keeps them
+pub fn save_suspended(
+    sessions: &[GameSession],
+    dir: &Path,
+    now: SystemTime,
+) -> Result<PathBuf, RecoveryError> {
+    let path = dir.join(SUSPENDED_FILE);
+    ServerSnapshot::of_sessions(sessions, now).save(&path)?;
+    Ok(path)
+}
+
+// The games `save_suspended` last wrote to `dir`; none if it never did
+pub fn load_suspended(dir: &Path) -> Result<Vec<GameSession>, RecoveryError> {
+    let path = dir.join(SUSPENDED_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let snapshot = ServerSnapshot::load(&path)?;
+    Ok(snapshot
+        .sessions
+        .into_iter()
+        .map(GameSession::restore)
+        .collect())
+}
+
+// TESTS
+#[cfg(test)]
+mod recovery_tests {
+    use super::*;
+    use crate::game_state::GameAction;
+    use crate::moderation::ModerationService;
+    use crate::rating::Rating;
+    use crate::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_snapshot_round_trips_games_and_queue_through_a_file() {
+        let mut sessions = SessionManager::new();
+        let game = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Alice")
+                    .deck(vec![CardBuilder::new("Rope").build()])
+                    .build(),
+            )
+            .build();
+        let first = game.active_player;
+        let game_id = sessions.start(game);
+        sessions
+            .get_mut(game_id)
+            .unwrap()
+            .apply_action(first, &GameAction::EndTurn)
+            .unwrap();
+        let mut queue = MatchmakingQueue::new();
+        let waiting = Uuid::new_v4();
+        let now = SystemTime::UNIX_EPOCH;
+        queue
+            .enqueue(
+                waiting,
+                Rating::new(1500.0, 50.0, 0.06),
+                &ModerationService::new(),
+                now,
+            )
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!("ascent-{}.snapshot", Uuid::new_v4()));
+        ServerSnapshot::capture(&sessions, &queue, now)
+            .save(&path)
+            .unwrap();
+        let snapshot = ServerSnapshot::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let (mut fresh, mut fresh_queue) = (SessionManager::new(), MatchmakingQueue::new());
+        assert_eq!(
+            snapshot
+                .clone()
+                .restore(&mut fresh, &mut fresh_queue)
+                .unwrap(),
+            1
+        );
+        let (original, restored) = (sessions.get(game_id).unwrap(), fresh.get(game_id).unwrap());
+        assert_eq!(restored.game.state_hash(), original.game.state_hash());
+        assert_eq!(restored.info, original.info);
+        assert_eq!(fresh_queue.entries(), queue.entries());
+        // The restored game plays on
+        let second = restored.game.active_player;
+        fresh
+            .get_mut(game_id)
+            .unwrap()
+            .apply_action(second, &GameAction::EndTurn)
+            .unwrap();
+
+        assert!(matches!(
+            snapshot.restore(&mut fresh, &mut fresh_queue),
+            Err(RecoveryError::AlreadyHosted(id)) if id == game_id
+        ));
+        assert!(matches!(
+            ServerSnapshot::from_bytes(b"not a snapshot"),
+            Err(RecoveryError::InvalidData(_))
+        ));
+    }
+}
@@ -0,0 +1,533 @@
+// src/replay/mod.rs
+use crate::errors::ReplayError;
+use crate::events::GameEvent;
+use crate::game_state::{GameAction, GameState};
+use crate::models::Mountain;
+use crate::puzzles::PuzzleSide;
+use crate::runtime::seeded_rng;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+pub mod storage;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChapterKind {
+    TurnStart { player_id: Uuid, turn_number: u32 },
+    LethalDamage { source: Uuid, target: Uuid },
+    SummitCaptured { player_id: Uuid },
+}
+
+// Points at the event a scrubber should jump to for a key moment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChapterMarker {
+    pub event_index: usize,
+    pub turn_number: u32,
+    pub kind: ChapterKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplaySummary {
+    pub game_id: Uuid,
+    pub players: Vec<(Uuid, String)>,
+    pub decks: HashMap<Uuid, Vec<String>>,
+    pub winner: Option<Uuid>,
+    pub turns: u32,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub summary: ReplaySummary,
+    pub chapters: Vec<ChapterMarker>,
+    pub events: Vec<GameEvent>,
+    // What it takes to play the game again; only kept for seeded games
+    #[serde(default)]
+    pub script: Option<ReplayScript>,
+}
+
+// How one player changed between two points in a replay. Fields are the
+// ones a rules bug usually shows up in, as JSON so any of them can be
+// printed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub player_id: Uuid,
+    pub field: &'static str,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateDiff {
+    // Event counts the two states were actually taken at
+    pub from: usize,
+    pub to: usize,
+    pub turn_number: (u32, u32),
+    pub active_player: (Uuid, Uuid),
+    pub changes: Vec<FieldChange>,
+    pub events: Vec<GameEvent>,
+}
+
+fn inspected_fields(
+    game_state: &GameState,
+    player_id: Uuid,
+) -> Vec<(&'static str, serde_json::Value)> {
+    let Some(player) = game_state.players.get(&player_id) else {
+        return Vec::new();
+    };
+    let names = |cards: &[crate::models::Card]| -> Vec<String> {
+        cards.iter().map(|card| card.name.clone()).collect()
+    };
+    vec![
+        ("health", json!(player.health)),
+        ("max_health", json!(player.max_health)),
+        ("shield", json!(player.shield)),
+        ("mana", json!(player.mana)),
+        ("movement", json!(player.movement)),
+        ("position", json!(player.position)),
+        ("hand", json!(names(&player.hand))),
+        ("deck_size", json!(player.deck.cards.len())),
+        ("graveyard", json!(names(&player.graveyard))),
+        ("statuses", json!(player.statuses.as_slice())),
+        ("active_effects", json!(player.active_effects.as_slice())),
+    ]
+}
+
+impl Replay {
+    pub fn chapters_of_turn(&self, turn_number: u32) -> impl Iterator<Item = &ChapterMarker> {
+        self.chapters
+            .iter()
+            .filter(move |chapter| chapter.turn_number == turn_number)
+    }
+
+    pub fn events_from(&self, chapter: &ChapterMarker) -> &[GameEvent] {
+        &self.events[chapter.event_index.min(self.events.len())..]
+    }
+
+    // The game as it stood once its first `event_index` events had been
+    // logged. Actions log their events together, so this is the end of the
+    // action that logged event `event_index - 1`; the returned state's
+    // event count says where it actually stopped. Every event re-run along
+    // the way is checked against the recording.
+    pub fn state_at(&self, event_index: usize) -> Result<GameState, ReplayError> {
+        let script = self.script.as_ref().ok_or(ReplayError::NotReproducible)?;
+        if event_index > self.events.len() {
+            return Err(ReplayError::EventOutOfRange {
+                event_index,
+                len: self.events.len(),
+            });
+        }
+        let mut game_state = script.setup();
+        let mut actions = script.actions.iter().enumerate();
+        loop {
+            let logged = game_state.events.len().min(self.events.len());
+            if let Some(index) = (0..logged).find(|i| game_state.events[*i] != self.events[*i]) {
+                return Err(ReplayError::Diverged { event_index: index });
+            }
+            if game_state.events.len() >= event_index {
+                return Ok(game_state);
+            }
+            let Some((index, logged)) = actions.next() else {
+                return Err(ReplayError::Diverged {
+                    event_index: game_state.events.len(),
+                });
+            };
+            game_state
+                .apply_action(logged.player_id, &logged.action)
+                .map_err(|error| ReplayError::ActionRejected { index, error })?;
+        }
+    }
+
+    // What changed between two points, for tracking down where a reported
+    // bug crept in
+    pub fn diff(&self, from: usize, to: usize) -> Result<StateDiff, ReplayError> {
+        let before = self.state_at(from)?;
+        let after = self.state_at(to)?;
+        let changes = before
+            .turn_order
+            .iter()
+            .flat_map(|player_id| {
+                inspected_fields(&before, *player_id)
+                    .into_iter()
+                    .zip(inspected_fields(&after, *player_id))
+                    .filter(|((_, old), (_, new))| old != new)
+                    .map(|((field, old), (_, new))| FieldChange {
+                        player_id: *player_id,
+                        field,
+                        before: old,
+                        after: new,
+                    })
+            })
+            .collect();
+        let (from, to) = (before.events.len(), after.events.len());
+        Ok(StateDiff {
+            from,
+            to,
+            turn_number: (before.turn_number, after.turn_number),
+            active_player: (before.active_player, after.active_player),
+            changes,
+            events: after.events[from.min(to)..to].to_vec(),
+        })
+    }
+}
+
+// Decks are captured when recording starts, since by the end of a game the
+// cards are scattered across hands and the deck has been drawn down.
+#[derive(Debug, Clone)]
+pub struct ReplayRecorder {
+    game_id: Uuid,
+    players: Vec<(Uuid, String)>,
+    decks: HashMap<Uuid, Vec<String>>,
+    started_at: SystemTime,
+    script: Option<ReplayScript>,
+}
+
+impl ReplayRecorder {
+    pub fn start(game_state: &GameState, now: SystemTime) -> Self {
+        let players = game_state
+            .turn_order
+            .iter()
+            .filter_map(|id| game_state.players.get(id))
+            .map(|player| (player.id, player.name.clone()))
+            .collect();
+        let decks = game_state
+            .players
+            .values()
+            .map(|player| {
+                let cards = player
+                    .hand
+                    .iter()
+                    .chain(player.deck.cards.iter())
+                    .map(|card| card.name.clone())
+                    .collect();
+                (player.id, cards)
+            })
+            .collect();
+
+        Self {
+            game_id: game_state.game_id,
+            players,
+            decks,
+            started_at: now,
+            script: None,
+        }
+    }
+
+    // Also keeps the starting position and every action, so the replay can
+    // be stepped through with `state_at`. Only for two-player games built
+    // with `seeded_rng(seed)`, since the rest of the game has to come out
+    // the same when it is run again.
+    pub fn start_scripted(game_state: &GameState, seed: u64, now: SystemTime) -> Self {
+        let mut recorder = Self::start(game_state, now);
+        recorder.script = Some(ReplayScript {
+            mountain_levels: game_state.mountain.levels,
+            seed,
+            players: game_state
+                .seated_players()
+                .map(PuzzleSide::from_player)
+                .collect(),
+            actions: Vec::new(),
+        });
+        recorder
+    }
+
+    // Call with every action the game accepted, in order
+    pub fn record_action(&mut self, player_id: Uuid, action: &GameAction) {
+        if let Some(script) = &mut self.script {
+            script.actions.push(LoggedAction {
+                player_id,
+                action: action.clone(),
+            });
+        }
+    }
+
+    pub fn finish(self, game_state: &GameState, now: SystemTime) -> Replay {
+        let events = game_state.events.clone();
+        Replay {
+            summary: ReplaySummary {
+                game_id: self.game_id,
+                players: self.players,
+                decks: self.decks,
+                winner: game_state.winner(),
+                turns: game_state.turn_number,
+                duration: now.duration_since(self.started_at).unwrap_or_default(),
+            },
+            chapters: chapters(&events),
+            events,
+            script: self.script,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggedAction {
+    pub player_id: Uuid,
+    pub action: GameAction,
+}
+
+// A starting position and every action taken from it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayScript {
+    pub mountain_levels: u32,
+    #[serde(default)]
+    pub seed: u64,
+    pub players: Vec<PuzzleSide>,
+    pub actions: Vec<LoggedAction>,
+}
+
+impl ReplayScript {
+    pub fn setup(&self) -> GameState {
+        let mut game_state =
+            GameState::new(self.players[0].to_player(), self.players[1].to_player())
+                .with_rng(seeded_rng(self.seed));
+        game_state.mountain = Mountain::new(self.mountain_levels);
+        game_state
+    }
+}
+
+// A canonical game kept as a regression fixture: a fixed starting position,
+// every action taken, and the state hash the rules produced at the time.
+// Cards need explicit ids so the hash is reproducible.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GoldenLog {
+    pub name: String,
+    #[serde(flatten)]
+    pub script: ReplayScript,
+    pub final_state_hash: String,
+}
+
+impl GoldenLog {
+    pub fn from_json(data: &str) -> Result<Self, ReplayError> {
+        let log: GoldenLog =
+            serde_json::from_str(data).map_err(|e| ReplayError::InvalidData(e.to_string()))?;
+        if log.script.players.len() != 2 {
+            return Err(ReplayError::InvalidData(
+                "golden logs are two-player games".to_string(),
+            ));
+        }
+        Ok(log)
+    }
+
+    pub fn setup(&self) -> GameState {
+        self.script.setup()
+    }
+
+    pub fn replay(&self) -> Result<GameState, ReplayError> {
+        let mut game_state = self.setup();
+        for (index, logged) in self.script.actions.iter().enumerate() {
+            game_state
+                .apply_action(logged.player_id, &logged.action)
+                .map_err(|error| ReplayError::ActionRejected { index, error })?;
+        }
+        Ok(game_state)
+    }
+}
+
+fn chapters(events: &[GameEvent]) -> Vec<ChapterMarker> {
+    let mut chapters = Vec::new();
+    let mut turn_number = 0;
+
+    for (index, event) in events.iter().enumerate() {
+        let (event_index, kind) = match event {
+            GameEvent::TurnStarted {
+                player_id,
+                turn_number: turn,
+            } => {
+                turn_number = *turn;
+                let kind = ChapterKind::TurnStart {
+                    player_id: *player_id,
+                    turn_number: *turn,
+                };
+                (index, kind)
+            }
+            // Defeat is logged right after the blow that caused it, so the
+            // chapter points at the damage rather than the aftermath.
+            GameEvent::PlayerDefeated { player_id } => match events[..index].last() {
+                Some(GameEvent::DamageDealt { source, target, .. }) if target == player_id => {
+                    let kind = ChapterKind::LethalDamage {
+                        source: *source,
+                        target: *target,
+                    };
+                    (index - 1, kind)
+                }
+                _ => continue,
+            },
+            GameEvent::SummitReached { player_id } => {
+                let kind = ChapterKind::SummitCaptured {
+                    player_id: *player_id,
+                };
+                (index, kind)
+            }
+            _ => continue,
+        };
+
+        chapters.push(ChapterMarker {
+            event_index,
+            turn_number,
+            kind,
+        });
+    }
+    chapters
+}
+
+// TESTS
+#[cfg(test)]
+mod replay_tests {
+    use super::*;
+    use crate::effects::{DamageEffect, Effect, EffectTarget, EffectValue};
+    use crate::models::{Card, CardType, Deck, Player, Rarity};
+
+    #[test]
+    fn test_replay_chapters() {
+        let deck = || Deck {
+            cards: vec![].into(),
+            owner_id: Uuid::new_v4(),
+        };
+        let mut player1 = Player::new("Player 1".to_string(), deck());
+        let player2 = Player::new("Player 2".to_string(), deck());
+        let (p1, p2) = (player1.id, player2.id);
+        let finisher = Card {
+            id: Uuid::new_v4(),
+            name: "Avalanche".to_string(),
+            cost: 0,
+            power: 0,
+            rarity: Rarity::Rare,
+            effects: vec![Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base: 40,
+                    scaling: None,
+                },
+                target: EffectTarget::Specific(p2),
+                penetrating: false,
+                ranged: false,
+                priority: 0,
+            })],
+            card_type: CardType::Spell,
+        };
+        player1.add_to_hand(finisher.clone());
+
+        let mut game_state = GameState::new(player1, player2);
+        let start = SystemTime::now();
+        let recorder = ReplayRecorder::start(&game_state, start);
+
+        game_state.end_turn(p1).unwrap();
+        game_state.end_turn(p2).unwrap();
+        game_state.play_card(p1, finisher.id).unwrap();
+
+        let replay = recorder.finish(&game_state, start + Duration::from_secs(90));
+
+        assert_eq!(replay.summary.winner, Some(p1));
+        assert_eq!(replay.summary.decks[&p1], vec!["Avalanche".to_string()]);
+        assert_eq!(replay.summary.duration, Duration::from_secs(90));
+        assert_eq!(replay.chapters.len(), 4);
+
+        let lethal = replay.chapters.last().unwrap();
+        assert_eq!(lethal.turn_number, 3);
+        assert_eq!(
+            lethal.kind,
+            ChapterKind::LethalDamage {
+                source: p1,
+                target: p2
+            }
+        );
+        assert!(matches!(
+            replay.events_from(lethal)[0],
+            GameEvent::DamageDealt { amount: 30, .. }
+        ));
+    }
+
+    const GOLDEN_LOGS: &[&str] = &[include_str!("../../data/golden/ridge_skirmish.json")];
+
+    // A mismatch means a rules change altered the outcome of a stored game.
+    // If that change is intended, update final_state_hash in the log.
+    #[test]
+    fn test_golden_logs_replay_to_recorded_hash() {
+        for data in GOLDEN_LOGS {
+            let log = GoldenLog::from_json(data).unwrap();
+            let game_state = log.replay().unwrap();
+            assert_eq!(
+                format!("{:016x}", game_state.state_hash()),
+                log.final_state_hash,
+                "{} no longer replays to its recorded state",
+                log.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_state_at_rewinds_a_scripted_replay() {
+        let deck = || Deck {
+            cards: vec![].into(),
+            owner_id: Uuid::new_v4(),
+        };
+        let mut north = Player::new("North".to_string(), deck());
+        let south = Player::new("South".to_string(), deck());
+        let (n, s) = (north.id, south.id);
+        let blow = Card {
+            id: Uuid::new_v4(),
+            name: "Ice Shard".to_string(),
+            cost: 0,
+            power: 0,
+            rarity: Rarity::Common,
+            effects: vec![Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base: 6,
+                    scaling: None,
+                },
+                target: EffectTarget::Specific(s),
+                penetrating: false,
+                ranged: false,
+                priority: 0,
+            })],
+            card_type: CardType::Spell,
+        };
+        north.add_to_hand(blow.clone());
+
+        let mut game_state = GameState::new(north, south).with_rng(seeded_rng(7));
+        let start = SystemTime::UNIX_EPOCH;
+        let mut recorder = ReplayRecorder::start_scripted(&game_state, 7, start);
+        for (player_id, action) in [
+            (n, GameAction::EndTurn),
+            (s, GameAction::EndTurn),
+            (n, GameAction::PlayCard { card_id: blow.id }),
+        ] {
+            game_state.apply_action(player_id, &action).unwrap();
+            recorder.record_action(player_id, &action);
+        }
+        let replay = recorder.finish(&game_state, start);
+
+        let end = replay.state_at(replay.events.len()).unwrap();
+        assert_eq!(end.state_hash(), game_state.state_hash());
+        assert_eq!(replay.state_at(0).unwrap().turn_number, 1);
+
+        let diff = replay.diff(0, replay.events.len()).unwrap();
+        assert_eq!(diff.turn_number, (1, 3));
+        let health = diff
+            .changes
+            .iter()
+            .find(|change| change.player_id == s && change.field == "health")
+            .unwrap();
+        assert_eq!(
+            health.after.as_u64().unwrap() + 6,
+            health.before.as_u64().unwrap()
+        );
+        // The opening TurnStarted is logged by setup itself
+        assert_eq!(diff.from, 1);
+        assert_eq!(diff.events, replay.events[1..]);
+
+        let unscripted = Replay {
+            script: None,
+            ..replay.clone()
+        };
+        assert!(matches!(
+            unscripted.state_at(1),
+            Err(ReplayError::NotReproducible)
+        ));
+        assert!(matches!(
+            replay.state_at(replay.events.len() + 1),
+            Err(ReplayError::EventOutOfRange { .. })
+        ));
+    }
+}
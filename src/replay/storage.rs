@@ -0,0 +1,164 @@
+// src/replay/storage.rs
+// Replays at rest. The summary and chapters go in a small index that
+// search and listings read. The events go in a separate zstd-compressed
+// blob, one JSON event per line, so a long tournament replay can be
+// streamed out without inflating the whole thing in memory.
+use super::{ChapterMarker, Replay, ReplayScript, ReplaySummary};
+use crate::errors::ReplayError;
+use crate::events::GameEvent;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use uuid::Uuid;
+
+pub const COMPRESSION_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayIndexEntry {
+    pub summary: ReplaySummary,
+    pub chapters: Vec<ChapterMarker>,
+    pub event_count: usize,
+    pub compressed_len: usize,
+    // Kept with the index; it is small next to the event log
+    pub script: Option<ReplayScript>,
+}
+
+pub fn compress_events(events: &[GameEvent]) -> Result<Vec<u8>, ReplayError> {
+    let invalid = |e: std::io::Error| ReplayError::InvalidData(e.to_string());
+    let mut encoder = zstd::Encoder::new(Vec::new(), COMPRESSION_LEVEL).map_err(invalid)?;
+    for event in events {
+        serde_json::to_writer(&mut encoder, event)
+            .map_err(|e| ReplayError::InvalidData(e.to_string()))?;
+        encoder.write_all(b"\n").map_err(invalid)?;
+    }
+    encoder.finish().map_err(invalid)
+}
+
+// Decodes events one at a time as they are read
+pub struct EventStream<R: Read> {
+    lines: std::io::Lines<BufReader<zstd::Decoder<'static, BufReader<R>>>>,
+}
+
+impl<R: Read> EventStream<R> {
+    pub fn new(compressed: R) -> Result<Self, ReplayError> {
+        let decoder =
+            zstd::Decoder::new(compressed).map_err(|e| ReplayError::InvalidData(e.to_string()))?;
+        Ok(Self {
+            lines: BufReader::new(decoder).lines(),
+        })
+    }
+}
+
+impl<R: Read> Iterator for EventStream<R> {
+    type Item = Result<GameEvent, ReplayError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        Some(
+            line.map_err(|e| ReplayError::InvalidData(e.to_string()))
+                .and_then(|line| {
+                    serde_json::from_str(&line).map_err(|e| ReplayError::InvalidData(e.to_string()))
+                }),
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReplayStore {
+    index: HashMap<Uuid, ReplayIndexEntry>,
+    blobs: HashMap<Uuid, Vec<u8>>,
+}
+
+impl ReplayStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn put(&mut self, replay: &Replay) -> Result<(), ReplayError> {
+        let blob = compress_events(&replay.events)?;
+        let game_id = replay.summary.game_id;
+        self.index.insert(
+            game_id,
+            ReplayIndexEntry {
+                summary: replay.summary.clone(),
+                chapters: replay.chapters.clone(),
+                event_count: replay.events.len(),
+                compressed_len: blob.len(),
+                script: replay.script.clone(),
+            },
+        );
+        self.blobs.insert(game_id, blob);
+        Ok(())
+    }
+
+    // Listing and search only ever touch the index
+    pub fn entry(&self, game_id: Uuid) -> Option<&ReplayIndexEntry> {
+        self.index.get(&game_id)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &ReplayIndexEntry> {
+        self.index.values()
+    }
+
+    pub fn stream(&self, game_id: Uuid) -> Result<EventStream<&[u8]>, ReplayError> {
+        let blob = self
+            .blobs
+            .get(&game_id)
+            .ok_or_else(|| ReplayError::InvalidData(format!("no replay for {game_id}")))?;
+        EventStream::new(blob.as_slice())
+    }
+
+    // Loads every event, for callers that need the whole replay at once
+    pub fn hydrate(&self, game_id: Uuid) -> Result<Replay, ReplayError> {
+        let entry = self
+            .entry(game_id)
+            .ok_or_else(|| ReplayError::InvalidData(format!("no replay for {game_id}")))?;
+        Ok(Replay {
+            summary: entry.summary.clone(),
+            chapters: entry.chapters.clone(),
+            events: self.stream(game_id)?.collect::<Result<_, _>>()?,
+            script: entry.script.clone(),
+        })
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod storage_tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_stored_replay_streams_back_lazily() {
+        let player_id = Uuid::new_v4();
+        let events: Vec<GameEvent> = (1..=500)
+            .map(|turn_number| GameEvent::TurnStarted {
+                player_id,
+                turn_number,
+            })
+            .collect();
+        let replay = Replay {
+            summary: ReplaySummary {
+                game_id: Uuid::new_v4(),
+                players: vec![(player_id, "Climber".to_string())],
+                decks: HashMap::new(),
+                winner: None,
+                turns: 500,
+                duration: Duration::from_secs(3600),
+            },
+            chapters: crate::replay::chapters(&events),
+            events,
+            script: None,
+        };
+        let mut store = ReplayStore::new();
+        store.put(&replay).unwrap();
+        let game_id = replay.summary.game_id;
+
+        let entry = store.entry(game_id).unwrap();
+        assert_eq!(entry.event_count, 500);
+        assert!(entry.compressed_len < serde_json::to_vec(&replay.events).unwrap().len() / 4);
+
+        let third = store.stream(game_id).unwrap().nth(2).unwrap().unwrap();
+        assert_eq!(third, replay.events[2]);
+        assert_eq!(store.hydrate(game_id).unwrap(), replay);
+    }
+}
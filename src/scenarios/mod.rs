@@ -0,0 +1,197 @@
+// src/scenarios/mod.rs
+// Community maps and boss fights as data: a custom mountain, who starts
+// where with what, and the rules to play under. A loaded scenario sets up
+// an ordinary GameState, so it plays through the normal game pipeline.
+use crate::errors::ScenarioError;
+use crate::game_state::{GameRules, GameState};
+use crate::models::{Card, Mountain, Position, Terrain, Tile, TileContent};
+use crate::puzzles::PuzzleSide;
+use serde::Deserialize;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+// Terrain or a trap laid on one tile
+#[derive(Debug, Clone, Deserialize)]
+pub struct TileFeature {
+    pub position: Position,
+    #[serde(default)]
+    pub terrain: Option<Terrain>,
+    #[serde(default)]
+    pub trap: Option<Card>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MountainSpec {
+    pub levels: u32,
+    // Replaces the generated rings when given
+    #[serde(default)]
+    pub tiles: Option<Vec<Position>>,
+    // Replaces the usual camp placement when given
+    #[serde(default)]
+    pub camps: Option<Vec<Position>>,
+    #[serde(default)]
+    pub features: Vec<TileFeature>,
+}
+
+impl MountainSpec {
+    pub fn build(&self) -> Mountain {
+        let mut mountain = match &self.tiles {
+            Some(tiles) => Mountain::from_tiles(
+                self.levels,
+                tiles
+                    .iter()
+                    .map(|position| Tile {
+                        x: position.x,
+                        y: position.y,
+                        z: position.z,
+                        level: position.level,
+                        content: TileContent::Empty,
+                        terrain: Terrain::Rock,
+                    })
+                    .collect(),
+                Vec::new(),
+            ),
+            None => Mountain::preset(self.levels),
+        };
+        if let Some(camps) = &self.camps {
+            mountain.camps = camps.clone();
+        }
+        for feature in &self.features {
+            let pos = feature.position;
+            if let Some(tile) = mountain.get_tile_mut(pos.x, pos.y, pos.z) {
+                if let Some(terrain) = feature.terrain {
+                    tile.terrain = terrain;
+                }
+                if let Some(trap) = &feature.trap {
+                    tile.content = TileContent::Trap(trap.clone());
+                }
+            }
+        }
+        mountain
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    pub description: String,
+    pub mountain: MountainSpec,
+    pub seats: [PuzzleSide; 2],
+    // Seat that moves first; the first seat when left out
+    #[serde(default)]
+    pub first_player: Option<Uuid>,
+    #[serde(default)]
+    pub rules: GameRules,
+}
+
+fn invalid(message: impl Into<String>) -> ScenarioError {
+    ScenarioError::InvalidData(message.into())
+}
+
+impl Scenario {
+    pub fn from_json(data: &str) -> Result<Self, ScenarioError> {
+        let scenario: Scenario = serde_json::from_str(data).map_err(|e| invalid(e.to_string()))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn validate(&self) -> Result<(), ScenarioError> {
+        let spec = &self.mountain;
+        if !(1..=50).contains(&spec.levels) {
+            return Err(invalid("mountain levels must be between 1 and 50"));
+        }
+        if let Some(tiles) = &spec.tiles {
+            let mut seen = HashSet::new();
+            for tile in tiles {
+                if tile.level >= spec.levels {
+                    return Err(invalid(format!(
+                        "tile ({}, {}, {}) is above the top level",
+                        tile.x, tile.y, tile.z
+                    )));
+                }
+                if !seen.insert((tile.x, tile.y, tile.z)) {
+                    return Err(invalid(format!(
+                        "tile ({}, {}, {}) is listed twice",
+                        tile.x, tile.y, tile.z
+                    )));
+                }
+            }
+        }
+
+        let mountain = spec.build();
+        let on_mountain = |pos: &Position| mountain.get_tile(pos.x, pos.y, pos.z).is_some();
+        if let Some(camp) = spec.camps.iter().flatten().find(|pos| !on_mountain(pos)) {
+            return Err(invalid(format!("camp {:?} is off the mountain", camp)));
+        }
+        if let Some(feature) = spec.features.iter().find(|f| !on_mountain(&f.position)) {
+            return Err(invalid(format!(
+                "feature at {:?} is off the mountain",
+                feature.position
+            )));
+        }
+
+        let [first, second] = &self.seats;
+        if first.id == second.id {
+            return Err(invalid("seats share an id"));
+        }
+        if (first.position.x, first.position.y, first.position.z)
+            == (second.position.x, second.position.y, second.position.z)
+        {
+            return Err(invalid("seats start on the same tile"));
+        }
+        for side in &self.seats {
+            if side.health == 0 {
+                return Err(invalid(format!("{} starts with no health", side.name)));
+            }
+            if !on_mountain(&side.position) {
+                return Err(invalid(format!("{} starts off the mountain", side.name)));
+            }
+        }
+        if let Some(first_player) = self.first_player {
+            if self.seats.iter().all(|side| side.id != first_player) {
+                return Err(invalid("first_player is not one of the seats"));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn setup(&self) -> GameState {
+        let [first, second] = &self.seats;
+        let mut game_state =
+            GameState::new(first.to_player(), second.to_player()).with_rules(self.rules.clone());
+        game_state.mountain = self.mountain.build();
+        if let Some(first_player) = self.first_player {
+            game_state.active_player = first_player;
+        }
+        game_state
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod scenario_tests {
+    use super::*;
+
+    const SUMMIT_WARDEN: &str = include_str!("../../data/scenarios/summit_warden.json");
+
+    #[test]
+    fn test_boss_scenario_loads_and_sets_up() {
+        let scenario = Scenario::from_json(SUMMIT_WARDEN).unwrap();
+        let game_state = scenario.setup();
+        let warden = &scenario.seats[1];
+
+        assert_eq!(game_state.players[&warden.id].health, 60);
+        assert_eq!(game_state.active_player, scenario.seats[0].id);
+        assert_eq!(game_state.rules.respawn_after, Some(2));
+        assert_eq!(game_state.mountain.tiles.len(), 5);
+        let scree = game_state.mountain.get_tile(1, 1, 0).unwrap();
+        assert_eq!(scree.terrain, Terrain::Scree);
+        assert!(matches!(
+            game_state.mountain.get_tile(0, 1, 1).unwrap().content,
+            TileContent::Trap(_)
+        ));
+
+        let broken = SUMMIT_WARDEN.replace("\"level\": 2", "\"level\": 9");
+        assert!(Scenario::from_json(&broken).is_err());
+    }
+}
@@ -0,0 +1,449 @@
+// src/sessions/actor.rs
+// Each hosted game runs as its own task that owns the GameSession outright.
+// Everything else talks to it through a GameHandle, which only sends
+// commands down a bounded channel, so there is no lock to fight over
+// between games and no way to reach a GameState from outside its task.
+// A full channel is reported as Busy straight away instead of queueing
+// without limit, and every game counts how long its commands waited. A game
+// that halts is written to disk and everyone in it is told.
+use crate::errors::SessionError;
+use crate::game_state::GameAction;
+use crate::metrics::ErrorTelemetry;
+use crate::networking::ServerMessage;
+use crate::recovery::save_halted;
+use crate::sessions::{BatchOutcome, GameSession, SessionManager, SessionStatus};
+use crate::view::PlayerView;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, oneshot};
+use uuid::Uuid;
+
+// Commands a game will hold before turning more away
+pub const DEFAULT_CAPACITY: usize = 32;
+// Messages for the whole table a slow connection can fall behind by
+pub const NOTICE_CAPACITY: usize = 16;
+
+type Job = Box<dyn FnOnce(&mut GameSession) + Send>;
+
+enum Command {
+    Submit {
+        player_id: Uuid,
+        request_id: Uuid,
+        action: GameAction,
+        reply: oneshot::Sender<ServerMessage>,
+    },
+    SubmitBatch {
+        player_id: Uuid,
+        request_id: Uuid,
+        actions: Vec<GameAction>,
+        reply: oneshot::Sender<BatchOutcome>,
+    },
+    View {
+        viewer: Uuid,
+        reply: oneshot::Sender<PlayerView>,
+    },
+    With(Job),
+    Stop {
+        reply: oneshot::Sender<GameSession>,
+    },
+}
+
+struct Envelope {
+    command: Command,
+    queued_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    processed: AtomicU64,
+    rejected: AtomicU64,
+    wait_micros: AtomicU64,
+    max_wait_micros: AtomicU64,
+}
+
+// One game's queue at a point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActorStats {
+    pub depth: usize,
+    pub processed: u64,
+    // Turned away because the queue was full
+    pub rejected: u64,
+    pub mean_wait: Duration,
+    pub max_wait: Duration,
+}
+
+// The answer to a command, once the game gets to it
+#[derive(Debug)]
+pub struct Reply<T>(oneshot::Receiver<T>);
+
+impl<T> Reply<T> {
+    pub async fn wait(self) -> Result<T, SessionError> {
+        self.0.await.map_err(|_| SessionError::Closed)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GameHandle {
+    pub game_id: Uuid,
+    tx: mpsc::Sender<Envelope>,
+    counters: Arc<Counters>,
+    notices: broadcast::Sender<ServerMessage>,
+}
+
+impl GameHandle {
+    fn send<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> Command,
+    ) -> Result<Reply<T>, SessionError> {
+        let (reply, rx) = oneshot::channel();
+        let envelope = Envelope {
+            command: command(reply),
+            queued_at: Instant::now(),
+        };
+        match self.tx.try_send(envelope) {
+            Ok(()) => Ok(Reply(rx)),
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                self.counters.rejected.fetch_add(1, Ordering::Relaxed);
+                Err(SessionError::Busy)
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => Err(SessionError::Closed),
+        }
+    }
+
+    // Same as GameSession::submit, resends included
+    pub fn submit(
+        &self,
+        player_id: Uuid,
+        request_id: Uuid,
+        action: GameAction,
+    ) -> Result<Reply<ServerMessage>, SessionError> {
+        self.send(|reply| Command::Submit {
+            player_id,
+            request_id,
+            action,
+            reply,
+        })
+    }
+
+    pub fn submit_batch(
+        &self,
+        player_id: Uuid,
+        request_id: Uuid,
+        actions: Vec<GameAction>,
+    ) -> Result<Reply<BatchOutcome>, SessionError> {
+        self.send(|reply| Command::SubmitBatch {
+            player_id,
+            request_id,
+            actions,
+            reply,
+        })
+    }
+
+    pub fn view(&self, viewer: Uuid) -> Result<Reply<PlayerView>, SessionError> {
+        self.send(|reply| Command::View { viewer, reply })
+    }
+
+    // Anything else, run inside the game's task
+    pub fn with<T: Send + 'static>(
+        &self,
+        job: impl FnOnce(&mut GameSession) -> T + Send + 'static,
+    ) -> Result<Reply<T>, SessionError> {
+        self.send(|reply| {
+            Command::With(Box::new(move |session: &mut GameSession| {
+                let _ = reply.send(job(session));
+            }))
+        })
+    }
+
+    // Messages for everyone in the game, such as GameHalted. Each seat's
+    // connection holds one of these.
+    pub fn subscribe(&self) -> broadcast::Receiver<ServerMessage> {
+        self.notices.subscribe()
+    }
+
+    pub fn stats(&self) -> ActorStats {
+        let processed = self.counters.processed.load(Ordering::Relaxed);
+        let wait = self.counters.wait_micros.load(Ordering::Relaxed);
+        ActorStats {
+            depth: self.tx.max_capacity() - self.tx.capacity(),
+            processed,
+            rejected: self.counters.rejected.load(Ordering::Relaxed),
+            mean_wait: Duration::from_micros(wait.checked_div(processed).unwrap_or(0)),
+            max_wait: Duration::from_micros(self.counters.max_wait_micros.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+// What a game's task is given besides its session
+struct Task {
+    rx: mpsc::Receiver<Envelope>,
+    counters: Arc<Counters>,
+    notices: broadcast::Sender<ServerMessage>,
+    halt_dir: Option<PathBuf>,
+    telemetry: Arc<ErrorTelemetry>,
+}
+
+// The game has just halted: its last good state goes to disk before anyone
+// is told, so a crash straight after can't lose it
+fn on_halt(session: &GameSession, task: &Task) {
+    if let Some(dir) = &task.halt_dir {
+        let game_id = session.game.game_id;
+        match save_halted(session, dir, session.game.clock.now()) {
+            Ok(path) => tracing::info!(%game_id, path = %path.display(), "halted game saved"),
+            Err(error) => tracing::error!(%game_id, ?error, "halted game not saved"),
+        }
+    }
+    if let Some(notice) = session.halt_notice() {
+        // Nobody listening is fine; the seats see the halt on reconnect
+        let _ = task.notices.send(notice);
+    }
+}
+
+async fn run(mut session: GameSession, mut task: Task) {
+    let counters = task.counters.clone();
+    while let Some(envelope) = task.rx.recv().await {
+        let running = session.status == SessionStatus::Running;
+        let waited = envelope.queued_at.elapsed().as_micros() as u64;
+        counters.wait_micros.fetch_add(waited, Ordering::Relaxed);
+        counters
+            .max_wait_micros
+            .fetch_max(waited, Ordering::Relaxed);
+        counters.processed.fetch_add(1, Ordering::Relaxed);
+        match envelope.command {
+            Command::Submit {
+                player_id,
+                request_id,
+                action,
+                reply,
+            } => {
+                let _ = reply.send(session.submit(player_id, request_id, &action, &task.telemetry));
+            }
+            Command::SubmitBatch {
+                player_id,
+                request_id,
+                actions,
+                reply,
+            } => {
+                let _ = reply.send(session.submit_batch(
+                    player_id,
+                    request_id,
+                    &actions,
+                    &task.telemetry,
+                ));
+            }
+            Command::View { viewer, reply } => {
+                let _ = reply.send(session.game.view_for(viewer));
+            }
+            Command::With(job) => job(&mut session),
+            Command::Stop { reply } => {
+                let _ = reply.send(session);
+                return;
+            }
+        }
+        if running && session.status != SessionStatus::Running {
+            on_halt(&session, &task);
+        }
+        // One command per turn on the scheduler, so a flooded game can't
+        // starve the others sharing its worker thread
+        tokio::task::yield_now().await;
+    }
+}
+
+// The hosted games, each in its own task
+#[derive(Debug)]
+pub struct SessionActors {
+    handles: HashMap<Uuid, GameHandle>,
+    capacity: usize,
+    // Where halted games are written; None keeps them in memory only
+    halt_dir: Option<PathBuf>,
+    telemetry: Arc<ErrorTelemetry>,
+}
+
+impl Default for SessionActors {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl SessionActors {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            handles: HashMap::new(),
+            capacity: capacity.max(1),
+            halt_dir: None,
+            telemetry: Arc::default(),
+        }
+    }
+
+    // Counts rejected actions alongside the server's other rejections
+    pub fn with_telemetry(mut self, telemetry: Arc<ErrorTelemetry>) -> Self {
+        self.telemetry = telemetry;
+        self
+    }
+
+    pub fn with_halt_dir(mut self, dir: PathBuf) -> Self {
+        self.halt_dir = Some(dir);
+        self
+    }
+
+    // Must be called from inside a Tokio runtime
+    pub fn spawn(&mut self, session: GameSession) -> GameHandle {
+        let (tx, rx) = mpsc::channel(self.capacity);
+        let counters = Arc::new(Counters::default());
+        let (notices, _) = broadcast::channel(NOTICE_CAPACITY);
+        let handle = GameHandle {
+            game_id: session.game.game_id,
+            tx,
+            counters: counters.clone(),
+            notices: notices.clone(),
+        };
+        let task = Task {
+            rx,
+            counters,
+            notices,
+            halt_dir: self.halt_dir.clone(),
+            telemetry: self.telemetry.clone(),
+        };
+        tokio::spawn(run(session, task));
+        self.handles.insert(handle.game_id, handle.clone());
+        handle
+    }
+
+    // Moves every game a SessionManager was hosting into its own task
+    pub fn adopt_all(&mut self, mut manager: SessionManager) -> usize {
+        let ids: Vec<Uuid> = manager.ids().collect();
+        for game_id in &ids {
+            if let Some(session) = manager.remove(*game_id) {
+                self.spawn(session);
+            }
+        }
+        ids.len()
+    }
+
+    pub fn get(&self, game_id: Uuid) -> Option<&GameHandle> {
+        self.handles.get(&game_id)
+    }
+
+    pub fn stats(&self) -> HashMap<Uuid, ActorStats> {
+        self.handles
+            .iter()
+            .map(|(game_id, handle)| (*game_id, handle.stats()))
+            .collect()
+    }
+
+    // Ends the game's task once the commands ahead of this one are done
+    // and hands the session back, e.g. to move it to another instance
+    pub async fn stop(&mut self, game_id: Uuid) -> Result<GameSession, SessionError> {
+        let handle = self
+            .handles
+            .remove(&game_id)
+            .ok_or(SessionError::UnknownGame)?;
+        let (reply, rx) = oneshot::channel();
+        handle
+            .tx
+            .send(Envelope {
+                command: Command::Stop { reply },
+                queued_at: Instant::now(),
+            })
+            .await
+            .map_err(|_| SessionError::Closed)?;
+        rx.await.map_err(|_| SessionError::Closed)
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod actor_tests {
+    use super::*;
+    use crate::testing::GameStateBuilder;
+
+    #[tokio::test]
+    async fn test_game_task_owns_state_and_reports_backpressure() {
+        let mut manager = SessionManager::new();
+        let game_id = manager.start(GameStateBuilder::new().build());
+        let first = manager.get(game_id).unwrap().game.active_player;
+        let mut actors = SessionActors::new(1);
+        assert_eq!(actors.adopt_all(manager), 1);
+        let handle = actors.get(game_id).unwrap().clone();
+
+        // The task hasn't run yet, so the one slot is taken
+        let request_id = Uuid::new_v4();
+        let accepted = handle
+            .submit(first, request_id, GameAction::EndTurn)
+            .unwrap();
+        assert!(matches!(handle.view(first), Err(SessionError::Busy)));
+        assert_eq!(
+            accepted.wait().await.unwrap(),
+            ServerMessage::ActionResult {
+                request_id,
+                error: None,
+            }
+        );
+        let view = handle.view(first).unwrap().wait().await.unwrap();
+        assert!(!view.is_my_turn());
+        let turn = handle
+            .with(|session| session.game.turn_number)
+            .unwrap()
+            .wait()
+            .await
+            .unwrap();
+        assert_eq!(turn, 2);
+
+        let stats = handle.stats();
+        assert_eq!((stats.processed, stats.rejected, stats.depth), (3, 1, 0));
+        let session = actors.stop(game_id).await.unwrap();
+        assert_eq!(session.game.turn_number, 2);
+        assert!(matches!(handle.view(first), Err(SessionError::Closed)));
+        assert!(actors.stats().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_halted_game_is_saved_and_every_seat_told() {
+        use crate::errors::GameError;
+        use crate::recovery::ServerSnapshot;
+
+        let mut manager = SessionManager::new();
+        let game_id = manager.start(GameStateBuilder::new().build());
+        let (first, second) = {
+            let game = &manager.get(game_id).unwrap().game;
+            (game.turn_order[0], game.turn_order[1])
+        };
+        let dir = std::env::temp_dir().join(format!("ascent-halts-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut actors = SessionActors::default().with_halt_dir(dir.clone());
+        actors.adopt_all(manager);
+        let handle = actors.get(game_id).unwrap().clone();
+        let mut seats = [handle.subscribe(), handle.subscribe()];
+
+        let halted = handle
+            .with(move |session| {
+                session.run(|game| -> Result<(), GameError> {
+                    game.end_turn(first)?;
+                    panic!("resolution bug");
+                })
+            })
+            .unwrap()
+            .wait()
+            .await
+            .unwrap();
+        assert!(matches!(halted, Err(GameError::GameHalted)));
+        for seat in &mut seats {
+            assert_eq!(
+                seat.recv().await.unwrap(),
+                ServerMessage::GameHalted { game_id }
+            );
+        }
+
+        let path = dir.join(format!("{}.halted.snapshot", game_id));
+        let saved = ServerSnapshot::load(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let session = &saved.sessions[0];
+        assert!(matches!(session.status, SessionStatus::Halted { .. }));
+        // The last good state, from before the failed change
+        assert_eq!(session.game.active_player, first);
+        assert_ne!(session.game.active_player, second);
+    }
+}
@@ -0,0 +1,824 @@
+// src/sessions/mod.rs
+// Games the server is hosting, plus what it needs to keep a match going
+// after a game ends without sending the players back through matchmaking.
+// SessionManager is the plain single-owner version; the server runs each
+// session in its own task through `actor` instead of sharing it behind a
+// lock.
+use crate::cards::labs::LabsTable;
+use crate::errors::{GameError, SessionError};
+use crate::events::bus::EventBus;
+use crate::game_state::{GameAction, GameState};
+use crate::messages::LocalizedMessage;
+use crate::metrics::ErrorTelemetry;
+use crate::models::{Card, Deck, Format, Player};
+use crate::networking::latency::LatencyTracker;
+use crate::networking::{unix_millis, ServerMessage};
+use crate::summary::GameSummary;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use uuid::Uuid;
+
+pub mod actor;
+
+// How many actions back a game can be rolled back
+pub const MAX_CHECKPOINTS: usize = 64;
+// Request ids a game remembers for spotting resent actions
+pub const REMEMBERED_REQUESTS: usize = 256;
+
+// Links the games of one match together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchInfo {
+    pub match_id: Uuid,
+    pub game_number: u32,
+    pub previous_game: Option<Uuid>,
+}
+
+impl MatchInfo {
+    fn first() -> Self {
+        Self {
+            match_id: Uuid::new_v4(),
+            game_number: 1,
+            previous_game: None,
+        }
+    }
+}
+
+// A seat as it was when the game began, so the game can be dealt again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Seat {
+    player_id: Uuid,
+    name: String,
+    cards: Vec<Card>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionStatus {
+    Running,
+    // Resolution panicked or broke an invariant. The game was put back to
+    // its last good state and takes no more player actions until an admin
+    // forces a result or resumes it.
+    Halted { reason: String },
+}
+
+// A session as written out for failover. Rollback checkpoints and the
+// remembered request ids stay behind, so an admin can't roll back past the
+// move and a resend from before it is treated as new.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub game: GameState,
+    pub info: MatchInfo,
+    pub status: SessionStatus,
+    seats: Vec<Seat>,
+    rematch_requests: HashSet<Uuid>,
+    #[serde(default)]
+    rematch: Option<Uuid>,
+}
+
+// What came of a submitted batch: the reply for the player who sent it
+// and, if it went through, its events grouped for everyone in the game
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchOutcome {
+    pub result: ServerMessage,
+    pub events: Option<ServerMessage>,
+}
+
+#[derive(Debug)]
+pub struct GameSession {
+    pub game: GameState,
+    pub info: MatchInfo,
+    pub status: SessionStatus,
+    seats: Vec<Seat>,
+    rematch_requests: HashSet<Uuid>,
+    // The game dealt once everyone asked for a rematch
+    rematch: Option<Uuid>,
+    // The game as it was before each recent action. Cloning shares most of
+    // the state, so these stay cheap.
+    checkpoints: VecDeque<GameState>,
+    // Outcomes of recent requests by (player, request id), oldest first;
+    // None is success, otherwise the error sent back
+    requests: VecDeque<((Uuid, Uuid), Option<LocalizedMessage>)>,
+}
+
+impl GameSession {
+    // A session for a freshly dealt game, remembering its seats for rematches
+    pub fn new(game: GameState, info: MatchInfo) -> Self {
+        let seats = game
+            .seated_players()
+            .map(|player| Seat {
+                player_id: player.id,
+                name: player.name.clone(),
+                cards: player
+                    .hand
+                    .iter()
+                    .chain(player.deck.cards.iter())
+                    .cloned()
+                    .collect(),
+            })
+            .collect();
+        Self {
+            game,
+            info,
+            status: SessionStatus::Running,
+            seats,
+            rematch_requests: HashSet::new(),
+            rematch: None,
+            checkpoints: VecDeque::new(),
+            requests: VecDeque::new(),
+        }
+    }
+
+    // Applies a player's action, keeping a checkpoint to roll back to
+    pub fn apply_action(&mut self, player_id: Uuid, action: &GameAction) -> Result<(), GameError> {
+        self.apply_batch(player_id, std::slice::from_ref(action))
+    }
+
+    // Applies a batch of actions all or nothing, under a single checkpoint
+    pub fn apply_batch(
+        &mut self,
+        player_id: Uuid,
+        actions: &[GameAction],
+    ) -> Result<(), GameError> {
+        self.run_checkpointed(|game| game.apply_batch(player_id, actions))
+    }
+
+    // `run`, keeping the state from before a change that went through as a
+    // checkpoint to roll back to
+    pub fn run_checkpointed<T>(
+        &mut self,
+        change: impl FnOnce(&mut GameState) -> Result<T, GameError>,
+    ) -> Result<T, GameError> {
+        let before = self.game.clone();
+        let result = self.run(change)?;
+        self.push_checkpoint(before);
+        Ok(result)
+    }
+
+    // `run_checkpointed` for an admin's forced change, which also goes
+    // through on a halted session. It stays halted unless `resume`d.
+    pub fn force_checkpointed<T>(
+        &mut self,
+        change: impl FnOnce(&mut GameState) -> Result<T, GameError>,
+    ) -> Result<T, GameError> {
+        let before = self.game.clone();
+        let result = self.guarded(change)?;
+        self.push_checkpoint(before);
+        Ok(result)
+    }
+
+    fn push_checkpoint(&mut self, before: GameState) {
+        self.checkpoints.push_back(before);
+        if self.checkpoints.len() > MAX_CHECKPOINTS {
+            self.checkpoints.pop_front();
+        }
+    }
+
+    // Lets a halted session take actions again, returning whether it was
+    // halted
+    pub fn resume(&mut self) -> bool {
+        let halted = self.status != SessionStatus::Running;
+        self.status = SessionStatus::Running;
+        halted
+    }
+
+    // Applies an action the client sent under `request_id`. A resend of a
+    // request already handled gets the first outcome back and the action
+    // isn't applied again. Rejections are counted in `telemetry`.
+    pub fn submit(
+        &mut self,
+        player_id: Uuid,
+        request_id: Uuid,
+        action: &GameAction,
+        telemetry: &ErrorTelemetry,
+    ) -> ServerMessage {
+        self.submit_batch(
+            player_id,
+            request_id,
+            std::slice::from_ref(action),
+            telemetry,
+        )
+        .result
+    }
+
+    // Same as `submit` for a batch. The events a batch produced go out
+    // together under its request id, so clients never see half of one and
+    // can play its animations as a group.
+    pub fn submit_batch(
+        &mut self,
+        player_id: Uuid,
+        request_id: Uuid,
+        actions: &[GameAction],
+        telemetry: &ErrorTelemetry,
+    ) -> BatchOutcome {
+        let key = (player_id, request_id);
+        let logged = self.game.events.len();
+        let outcome = match self.requests.iter().find(|(seen, _)| *seen == key) {
+            Some((_, outcome)) => outcome.clone(),
+            None => {
+                // A resend isn't counted again
+                let outcome = self.apply_batch(player_id, actions).err().map(|e| {
+                    telemetry.game_error(&e, "action", &self.game);
+                    e.message()
+                });
+                self.requests.push_back((key, outcome.clone()));
+                if self.requests.len() > REMEMBERED_REQUESTS {
+                    self.requests.pop_front();
+                }
+                outcome
+            }
+        };
+        let events = self
+            .game
+            .events
+            .get(logged..)
+            .filter(|events| !events.is_empty())
+            .map(|events| ServerMessage::EventBatch {
+                batch_id: request_id,
+                events: events.to_vec(),
+            });
+        BatchOutcome {
+            result: ServerMessage::ActionResult {
+                request_id,
+                error: outcome,
+            },
+            events,
+        }
+    }
+
+    pub fn snapshot(&self) -> SessionSnapshot {
+        SessionSnapshot {
+            game: self.game.clone(),
+            info: self.info,
+            status: self.status.clone(),
+            seats: self.seats.clone(),
+            rematch_requests: self.rematch_requests.clone(),
+            rematch: self.rematch,
+        }
+    }
+
+    pub fn restore(snapshot: SessionSnapshot) -> Self {
+        Self {
+            game: snapshot.game,
+            info: snapshot.info,
+            status: snapshot.status,
+            seats: snapshot.seats,
+            rematch_requests: snapshot.rematch_requests,
+            rematch: snapshot.rematch,
+            checkpoints: VecDeque::new(),
+            requests: VecDeque::new(),
+        }
+    }
+
+    // Copies the latest round trips into the game for the turn timer
+    pub fn sync_latency(&mut self, latency: &LatencyTracker) {
+        for player_id in self.game.turn_order.clone() {
+            self.game
+                .latency_allowance
+                .insert(player_id, latency.allowance(player_id));
+        }
+    }
+
+    // Where the turn clock stands, by the server's clock. The deadline
+    // leaves out the latency allowance, which is the server's to give.
+    pub fn turn_clock(&self) -> ServerMessage {
+        let game = &self.game;
+        ServerMessage::TurnClock {
+            player_id: game.active_player,
+            turn_number: game.turn_number,
+            started_at_ms: unix_millis(game.turn_started_at),
+            deadline_ms: game
+                .rules
+                .turn_time_limit
+                .and_then(|limit| game.turn_started_at.checked_add(limit))
+                .map(unix_millis),
+            server_time_ms: unix_millis(game.clock.now()),
+        }
+    }
+
+    // Runs anything that changes the game so a bug in one game can't take
+    // the server down. A panic or broken invariant restores the state from
+    // before the call and halts the session; the game's actor then saves
+    // that state and sends `halt_notice` to every seat.
+    pub fn run<T>(
+        &mut self,
+        change: impl FnOnce(&mut GameState) -> Result<T, GameError>,
+    ) -> Result<T, GameError> {
+        if self.status != SessionStatus::Running {
+            return Err(GameError::GameHalted);
+        }
+        self.guarded(change)
+    }
+
+    // `run` without the check that the session is still running
+    fn guarded<T>(
+        &mut self,
+        change: impl FnOnce(&mut GameState) -> Result<T, GameError>,
+    ) -> Result<T, GameError> {
+        let before = self.game.clone();
+        let reason = match catch_unwind(AssertUnwindSafe(|| change(&mut self.game))) {
+            Ok(result) => match self.game.validate_invariants() {
+                Ok(()) => return result,
+                Err(violation) => format!("{:?}", violation),
+            },
+            Err(panic) => panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic".to_string()),
+        };
+        tracing::error!(game_id = %self.game.game_id, %reason, "game halted");
+        self.game = before;
+        self.status = SessionStatus::Halted { reason };
+        Err(GameError::GameHalted)
+    }
+
+    // Who has a scry to answer, and the message showing them the cards
+    pub fn scry_prompt(&self) -> Option<(Uuid, ServerMessage)> {
+        let chooser = self.game.pending_scry.first()?.chooser;
+        let scry = self.game.scry_for(chooser)?;
+        Some((
+            chooser,
+            ServerMessage::ScryPrompt {
+                game_id: self.game.game_id,
+                scry,
+            },
+        ))
+    }
+
+    pub fn halt_notice(&self) -> Option<ServerMessage> {
+        match &self.status {
+            SessionStatus::Running => None,
+            SessionStatus::Halted { .. } => Some(ServerMessage::GameHalted {
+                game_id: self.game.game_id,
+            }),
+        }
+    }
+
+    // Sent to both players once the game has a winner
+    pub fn game_over_notice(&self) -> Option<ServerMessage> {
+        self.game.winner()?;
+        Some(ServerMessage::GameOver {
+            summary: GameSummary::from_game(&self.game),
+        })
+    }
+
+    // Restores the latest checkpoint with at least `events` fewer events in
+    // its log than now, returning how many events were undone. Asking for
+    // none undoes nothing.
+    pub fn rollback(&mut self, events: usize) -> Option<usize> {
+        if events == 0 {
+            return None;
+        }
+        let target = self.game.events.len().checked_sub(events)?;
+        let index = self
+            .checkpoints
+            .iter()
+            .rposition(|checkpoint| checkpoint.events.len() <= target)?;
+        self.checkpoints.truncate(index + 1);
+        let restored = self.checkpoints.pop_back()?;
+        let undone = self.game.events.len() - restored.events.len();
+        self.game = restored;
+        Some(undone)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SessionManager {
+    sessions: HashMap<Uuid, GameSession>,
+}
+
+impl SessionManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Starts hosting a freshly created game as the first of a new match
+    pub fn start(&mut self, game: GameState) -> Uuid {
+        self.host(game, MatchInfo::first())
+    }
+
+    // `start` for a game dealt from players' decks. Labs cards depend on
+    // the match, so each seat's deck is checked here, once the match id
+    // exists, and nothing is hosted if one is refused. Rematches keep the
+    // match id and so the same answer.
+    pub fn start_match(
+        &mut self,
+        game: GameState,
+        format: Format,
+        labs: &LabsTable,
+    ) -> Result<Uuid, SessionError> {
+        let info = MatchInfo::first();
+        for player in game.seated_players() {
+            let deck = Deck {
+                cards: player
+                    .hand
+                    .iter()
+                    .chain(player.deck.cards.iter())
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .into(),
+                owner_id: player.id,
+            };
+            labs.check_deck(&deck, format, info.match_id)
+                .map_err(|error| SessionError::InvalidDeck(player.id, error))?;
+        }
+        Ok(self.host(game, info))
+    }
+
+    pub fn get(&self, game_id: Uuid) -> Option<&GameSession> {
+        self.sessions.get(&game_id)
+    }
+
+    pub fn get_mut(&mut self, game_id: Uuid) -> Option<&mut GameSession> {
+        self.sessions.get_mut(&game_id)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.sessions.keys().copied()
+    }
+
+    // Hands each hosted game's new events to the bus. Events of one game
+    // stay in order; games are published in no particular order.
+    pub fn publish(&self, bus: &mut EventBus) -> usize {
+        self.sessions
+            .values()
+            .map(|session| bus.publish(&session.game))
+            .sum()
+    }
+
+    pub fn remove(&mut self, game_id: Uuid) -> Option<GameSession> {
+        self.sessions.remove(&game_id)
+    }
+
+    // Every hosted game at this moment, by game id
+    pub fn snapshot(&self) -> Vec<SessionSnapshot> {
+        let mut snapshots: Vec<SessionSnapshot> =
+            self.sessions.values().map(GameSession::snapshot).collect();
+        snapshots.sort_by_key(|snapshot| snapshot.game.game_id);
+        snapshots
+    }
+
+    // Records a player's vote for a rematch. Once everyone in a finished
+    // game has asked, the next game of the match is dealt with the same
+    // decks and the seat order shifted by one, and its id is returned.
+    // Asking again after that returns the same game rather than a new one.
+    pub fn request_rematch(
+        &mut self,
+        game_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<Option<Uuid>, SessionError> {
+        let session = self
+            .sessions
+            .get_mut(&game_id)
+            .ok_or(SessionError::UnknownGame)?;
+        if !session.game.players.contains_key(&player_id) {
+            return Err(SessionError::NotAPlayer);
+        }
+        if session.game.winner().is_none() {
+            return Err(SessionError::GameInProgress);
+        }
+        if let Some(rematch) = session.rematch {
+            return Ok(Some(rematch));
+        }
+
+        session.rematch_requests.insert(player_id);
+        if session.rematch_requests.len() < session.seats.len() {
+            return Ok(None);
+        }
+
+        let mut seats = session.seats.clone();
+        seats.rotate_left(1);
+        let mut players = seats.iter().map(|seat| {
+            let mut player = Player::new(
+                seat.name.clone(),
+                Deck {
+                    cards: seat.cards.clone().into(),
+                    owner_id: seat.player_id,
+                },
+            );
+            player.id = seat.player_id;
+            player
+        });
+        let (Some(first), Some(second)) = (players.next(), players.next()) else {
+            return Err(SessionError::NotAPlayer);
+        };
+        let mut game = GameState::new(first, second)
+            .with_rules(session.game.rules.clone())
+            .with_card_data(
+                &session.game.rules_version.card_data,
+                session.game.cards.clone(),
+            )
+            .with_clock(session.game.clock.clone())
+            .with_rng(session.game.rng.clone());
+        for player in players {
+            game.turn_order.push(player.id);
+            game.players.insert(player.id, player);
+        }
+        game.teams = session.game.teams.clone();
+
+        let info = MatchInfo {
+            match_id: session.info.match_id,
+            game_number: session.info.game_number + 1,
+            previous_game: Some(game_id),
+        };
+        session.rematch_requests.clear();
+        let rematch = game.game_id;
+        session.rematch = Some(rematch);
+        self.host(game, info);
+        Ok(Some(rematch))
+    }
+
+    // Takes over a session another instance was hosting
+    pub fn adopt(&mut self, session: GameSession) -> Uuid {
+        let game_id = session.game.game_id;
+        self.sessions.insert(game_id, session);
+        game_id
+    }
+
+    fn host(&mut self, game: GameState, info: MatchInfo) -> Uuid {
+        let game_id = game.game_id;
+        self.sessions.insert(game_id, GameSession::new(game, info));
+        game_id
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod sessions_tests {
+    use super::*;
+    use crate::events::GameEvent;
+    use crate::metrics::{ErrorKey, GamePhase};
+    use crate::models::Position;
+    use crate::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
+    use std::time::Duration;
+
+    #[test]
+    fn test_rematch_swaps_first_player_and_links_games() {
+        let deck = vec![CardBuilder::new("Rope").build()];
+        let game = GameStateBuilder::new()
+            .player(PlayerBuilder::new("First").deck(deck.clone()).build())
+            .build();
+        let (first, second) = (game.turn_order[0], game.turn_order[1]);
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(game);
+
+        assert_eq!(
+            sessions.request_rematch(game_id, first),
+            Err(SessionError::GameInProgress)
+        );
+        let game = &mut sessions.get_mut(game_id).unwrap().game;
+        game.players.get_mut(&second).unwrap().health = 0;
+
+        assert_eq!(sessions.request_rematch(game_id, first), Ok(None));
+        let rematch = sessions.request_rematch(game_id, second).unwrap().unwrap();
+        assert_eq!(sessions.request_rematch(game_id, first), Ok(Some(rematch)));
+        assert_eq!(sessions.request_rematch(game_id, second), Ok(Some(rematch)));
+        assert_eq!(sessions.ids().count(), 2);
+        assert!(sessions.get(game_id).unwrap().rematch_requests.is_empty());
+
+        let session = sessions.get(rematch).unwrap();
+        assert_eq!(session.game.active_player, second);
+        assert_eq!(session.game.players[&first].deck.cards[0].id, deck[0].id);
+        assert_eq!(session.game.players[&first].health, 30);
+        assert_eq!(session.info.game_number, 2);
+        assert_eq!(session.info.previous_game, Some(game_id));
+        assert_eq!(
+            session.info.match_id,
+            sessions.get(game_id).unwrap().info.match_id
+        );
+    }
+
+    #[test]
+    fn test_team_rematch_keeps_the_teams() {
+        let [a1, a2, b1, b2] =
+            ["A1", "A2", "B1", "B2"].map(|name| PlayerBuilder::new(name).build());
+        let ids = [a1.id, a2.id, b1.id, b2.id];
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(GameState::new_teams([a1, a2], [b1, b2]));
+        let game = &mut sessions.get_mut(game_id).unwrap().game;
+        for id in &ids[2..] {
+            game.players.get_mut(id).unwrap().health = 0;
+        }
+
+        let mut rematch = None;
+        for id in ids {
+            rematch = sessions.request_rematch(game_id, id).unwrap();
+        }
+
+        let game = &sessions.get(rematch.unwrap()).unwrap().game;
+        assert_eq!(game.active_player, ids[2]);
+        assert_eq!(game.teams, sessions.get(game_id).unwrap().game.teams);
+        assert_eq!(game.team_of(ids[0]), game.team_of(ids[1]));
+        assert_ne!(game.team_of(ids[0]), game.team_of(ids[2]));
+        assert!(!game.are_enemies(ids[2], ids[3]));
+    }
+
+    #[test]
+    fn test_unbounded_turn_timer_has_no_deadline() {
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(GameStateBuilder::new().build());
+        let session = sessions.get_mut(game_id).unwrap();
+        session.game.rules.turn_time_limit = Some(Duration::MAX);
+        session.game.rules.max_latency_allowance = Duration::MAX;
+        session
+            .game
+            .latency_allowance
+            .insert(session.game.active_player, Duration::MAX);
+
+        assert!(matches!(
+            session.turn_clock(),
+            ServerMessage::TurnClock {
+                deadline_ms: None,
+                ..
+            }
+        ));
+        session.game.enforce_timers().unwrap();
+    }
+
+    #[test]
+    fn test_panicking_game_is_halted_and_restored() {
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(GameStateBuilder::new().build());
+        let session = sessions.get_mut(game_id).unwrap();
+        let first = session.game.active_player;
+        let events = session.game.events.len();
+
+        let result: Result<(), GameError> = session.run(|game| {
+            game.end_turn(first)?;
+            panic!("resolution bug");
+        });
+        assert!(matches!(result, Err(GameError::GameHalted)));
+        assert_eq!(session.game.events.len(), events);
+        assert_eq!(
+            session.status,
+            SessionStatus::Halted {
+                reason: "resolution bug".to_string()
+            }
+        );
+        assert!(matches!(
+            session.apply_action(first, &GameAction::EndTurn),
+            Err(GameError::GameHalted)
+        ));
+        assert_eq!(
+            session.halt_notice(),
+            Some(ServerMessage::GameHalted { game_id })
+        );
+    }
+
+    #[test]
+    fn test_resent_action_is_not_applied_twice() {
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(GameStateBuilder::new().build());
+        let session = sessions.get_mut(game_id).unwrap();
+        let first = session.game.active_player;
+        let request_id = Uuid::new_v4();
+        let telemetry = ErrorTelemetry::new();
+
+        let accepted = ServerMessage::ActionResult {
+            request_id,
+            error: None,
+        };
+        assert_eq!(
+            session.submit(first, request_id, &GameAction::EndTurn, &telemetry),
+            accepted
+        );
+        let turn = session.game.turn_number;
+        assert_eq!(
+            session.submit(first, request_id, &GameAction::EndTurn, &telemetry),
+            accepted
+        );
+        assert_eq!(session.game.turn_number, turn);
+
+        let second_request = Uuid::new_v4();
+        let refused = ServerMessage::ActionResult {
+            request_id: second_request,
+            error: Some(GameError::NotYourTurn.message()),
+        };
+        for _ in 0..2 {
+            assert_eq!(
+                session.submit(first, second_request, &GameAction::EndTurn, &telemetry),
+                refused
+            );
+        }
+        // Counted once, not once per resend
+        let key = ErrorKey {
+            code: GameError::NotYourTurn.code(),
+            endpoint: "action",
+            phase: Some(GamePhase::InProgress),
+        };
+        assert_eq!(telemetry.count(&key), 1);
+    }
+
+    #[test]
+    fn test_batch_applies_whole_or_not_at_all() {
+        let rope = CardBuilder::new("Rope").cost(1).build();
+        let game = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Alice")
+                    .hand(vec![rope.clone()])
+                    .mana(1)
+                    .build(),
+            )
+            .build();
+        let mut sessions = SessionManager::new();
+        let game_id = sessions.start(game);
+        let session = sessions.get_mut(game_id).unwrap();
+        let alice = session.game.active_player;
+        let opening = session.game.state_hash();
+        let play = GameAction::PlayCard { card_id: rope.id };
+        let off_the_map = Position {
+            x: 99,
+            y: 99,
+            z: 99,
+            level: 99,
+        };
+
+        let request_id = Uuid::new_v4();
+        let refused = session.submit_batch(
+            alice,
+            request_id,
+            &[
+                play.clone(),
+                GameAction::Move {
+                    position: off_the_map,
+                },
+            ],
+            &ErrorTelemetry::new(),
+        );
+        assert!(matches!(
+            refused.result,
+            ServerMessage::ActionResult { error: Some(_), .. }
+        ));
+        assert_eq!(refused.events, None);
+        assert_eq!(session.game.state_hash(), opening);
+
+        let position = session.game.reachable_tiles(alice)[0];
+        let request_id = Uuid::new_v4();
+        let played = session.submit_batch(
+            alice,
+            request_id,
+            &[play, GameAction::Move { position }],
+            &ErrorTelemetry::new(),
+        );
+        assert_eq!(
+            played.result,
+            ServerMessage::ActionResult {
+                request_id,
+                error: None
+            }
+        );
+        let Some(ServerMessage::EventBatch { batch_id, events }) = played.events else {
+            panic!("no event batch");
+        };
+        assert_eq!(batch_id, request_id);
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, GameEvent::CardPlayed { .. })));
+        assert_eq!(session.game.players[&alice].position, position);
+
+        // One checkpoint covers the whole batch
+        assert_eq!(session.rollback(1), Some(events.len()));
+        assert_eq!(session.game.state_hash(), opening);
+    }
+
+    #[test]
+    fn test_labs_cards_are_checked_when_the_match_starts() {
+        use crate::cards::labs::LabsRule;
+        use crate::errors::ValidationError;
+
+        let deal = || {
+            GameStateBuilder::new()
+                .player(
+                    PlayerBuilder::new("Tester")
+                        .deck(vec![CardBuilder::new("Sky Anchor").build()])
+                        .build(),
+                )
+                .build()
+        };
+        let rule = |formats: &[Format], match_percent| LabsRule {
+            formats: formats.iter().copied().collect(),
+            match_percent,
+        };
+        let mut labs = LabsTable::new();
+        let mut sessions = SessionManager::new();
+
+        labs.set("Sky Anchor", Some(rule(&[Format::Draft], 100)));
+        let game = deal();
+        let tester = game.turn_order[0];
+        assert_eq!(
+            sessions.start_match(game, Format::Constructed, &labs),
+            Err(SessionError::InvalidDeck(
+                tester,
+                ValidationError::LabsCard("Sky Anchor".to_string())
+            ))
+        );
+        assert_eq!(sessions.ids().count(), 0);
+        let game_id = sessions.start_match(deal(), Format::Draft, &labs).unwrap();
+        assert!(sessions.get(game_id).is_some());
+
+        labs.set("Sky Anchor", Some(rule(&[], 0)));
+        assert!(sessions.start_match(deal(), Format::Draft, &labs).is_err());
+        labs.set("Sky Anchor", None);
+        assert!(sessions.start_match(deal(), Format::Draft, &labs).is_ok());
+    }
+}
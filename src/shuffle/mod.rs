@@ -0,0 +1,206 @@
+// src/shuffle/mod.rs
+// Commit-reveal for deck shuffles. At game start the server shuffles every
+// deck from a secret seed and publishes a SHA-256 commitment to the seed
+// and the order it produced. The seed goes out once the game is over, so
+// anyone holding the replay can shuffle again and check that the order was
+// fixed before the first card was drawn.
+use crate::errors::ShuffleError;
+use crate::game_state::GameState;
+use crate::networking::ServerMessage;
+use crate::replay::Replay;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShuffleCommitment {
+    pub game_id: Uuid,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShuffleReveal {
+    pub game_id: Uuid,
+    pub seed: u64,
+    // Random bytes mixed into the hash so the seed can't be searched for
+    // from the commitment and the decklists
+    pub salt: String,
+}
+
+// Held by the server for the length of the game; only the commitment is
+// shown until the winner is decided
+#[derive(Debug, Clone)]
+pub struct SealedShuffle {
+    reveal: ShuffleReveal,
+    commitment: ShuffleCommitment,
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Decks are sorted before shuffling so the result only depends on which
+// cards are in them, not on the order they were submitted in
+fn shuffled(seed: u64, decks: &[(Uuid, Vec<String>)]) -> Vec<(Uuid, Vec<String>)> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    decks
+        .iter()
+        .map(|(player_id, cards)| {
+            let mut cards = cards.clone();
+            cards.sort();
+            cards.shuffle(&mut rng);
+            (*player_id, cards)
+        })
+        .collect()
+}
+
+fn commitment_hash(reveal: &ShuffleReveal, decks: &[(Uuid, Vec<String>)]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(reveal.game_id.as_bytes());
+    hasher.update(reveal.salt.as_bytes());
+    hasher.update(reveal.seed.to_le_bytes());
+    for (player_id, cards) in decks {
+        hasher.update(player_id.as_bytes());
+        for card in cards {
+            hasher.update((card.len() as u32).to_le_bytes());
+            hasher.update(card.as_bytes());
+        }
+    }
+    hex(&hasher.finalize())
+}
+
+impl SealedShuffle {
+    // Shuffles every deck in turn order. Call before the opening hands are
+    // drawn.
+    pub fn deal(game_state: &mut GameState, rng: &mut impl Rng) -> SealedShuffle {
+        let reveal = ShuffleReveal {
+            game_id: game_state.game_id,
+            seed: rng.random(),
+            salt: hex(&rng.random::<[u8; 16]>()),
+        };
+        let mut decks_rng = StdRng::seed_from_u64(reveal.seed);
+        let mut decks = Vec::new();
+        for player_id in game_state.turn_order.clone() {
+            let Some(player) = game_state.players.get_mut(&player_id) else {
+                continue;
+            };
+            let cards = &mut *player.deck.cards;
+            cards.sort_by(|a, b| a.name.cmp(&b.name));
+            cards.shuffle(&mut decks_rng);
+            decks.push((
+                player_id,
+                cards.iter().map(|card| card.name.clone()).collect(),
+            ));
+        }
+        let commitment = ShuffleCommitment {
+            game_id: reveal.game_id,
+            hash: commitment_hash(&reveal, &decks),
+        };
+        SealedShuffle { reveal, commitment }
+    }
+
+    pub fn commitment(&self) -> &ShuffleCommitment {
+        &self.commitment
+    }
+
+    // Sent to both players alongside GameStarted
+    pub fn commit_notice(&self) -> ServerMessage {
+        ServerMessage::ShuffleCommitted {
+            commitment: self.commitment.clone(),
+        }
+    }
+
+    // The seed, once the game has a winner and can no longer be affected
+    pub fn reveal_notice(&self, game_state: &GameState) -> Option<ServerMessage> {
+        game_state.winner()?;
+        Some(ServerMessage::ShuffleRevealed {
+            reveal: self.reveal.clone(),
+        })
+    }
+}
+
+// Checks a finished replay against what was committed to at the start: the
+// revealed seed has to reproduce each recorded deck order, and seed, salt
+// and order together have to hash to the published commitment.
+pub fn verify(
+    replay: &Replay,
+    commitment: &ShuffleCommitment,
+    reveal: &ShuffleReveal,
+) -> Result<(), ShuffleError> {
+    let game_id = replay.summary.game_id;
+    if commitment.game_id != game_id || reveal.game_id != game_id {
+        return Err(ShuffleError::WrongGame);
+    }
+    let recorded: Vec<(Uuid, Vec<String>)> = replay
+        .summary
+        .players
+        .iter()
+        .map(|(player_id, _)| {
+            let cards = replay.summary.decks.get(player_id).cloned();
+            (*player_id, cards.unwrap_or_default())
+        })
+        .collect();
+    for ((player_id, expected), (_, cards)) in
+        shuffled(reveal.seed, &recorded).iter().zip(&recorded)
+    {
+        if expected != cards {
+            return Err(ShuffleError::OrderMismatch {
+                player_id: *player_id,
+            });
+        }
+    }
+    if commitment_hash(reveal, &recorded) != commitment.hash {
+        return Err(ShuffleError::CommitmentMismatch);
+    }
+    Ok(())
+}
+
+// TESTS
+#[cfg(test)]
+mod shuffle_tests {
+    use super::*;
+    use crate::replay::ReplayRecorder;
+    use crate::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
+    use std::time::SystemTime;
+
+    fn deck() -> Vec<crate::models::Card> {
+        ["Avalanche", "Boulder", "Crampon", "Drift", "Echo", "Flare"]
+            .map(|name| CardBuilder::new(name).build())
+            .to_vec()
+    }
+
+    #[test]
+    fn test_revealed_seed_verifies_the_replay() {
+        let mut game_state = GameStateBuilder::new()
+            .player(PlayerBuilder::new("Alice").deck(deck()).build())
+            .player(PlayerBuilder::new("Bob").deck(deck()).build())
+            .build();
+        let sealed = SealedShuffle::deal(&mut game_state, &mut StdRng::seed_from_u64(3));
+        assert!(sealed.reveal_notice(&game_state).is_none());
+        let recorder = ReplayRecorder::start(&game_state, SystemTime::UNIX_EPOCH);
+        let replay = recorder.finish(&game_state, SystemTime::UNIX_EPOCH);
+        let reveal = sealed.reveal.clone();
+
+        assert_eq!(verify(&replay, sealed.commitment(), &reveal), Ok(()));
+
+        let wrong_seed = ShuffleReveal {
+            seed: reveal.seed + 1,
+            ..reveal.clone()
+        };
+        assert!(matches!(
+            verify(&replay, sealed.commitment(), &wrong_seed),
+            Err(ShuffleError::OrderMismatch { .. })
+        ));
+        let wrong_salt = ShuffleReveal {
+            salt: "00".to_string(),
+            ..reveal
+        };
+        assert_eq!(
+            verify(&replay, sealed.commitment(), &wrong_salt),
+            Err(ShuffleError::CommitmentMismatch)
+        );
+    }
+}
@@ -0,0 +1,324 @@
+// src/simulation/mod.rs
+use crate::bots::BotRunner;
+use crate::game_state::GameState;
+use crate::models::{Card, Deck, Mountain, Player};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use uuid::Uuid;
+
+pub const STARTING_HAND: usize = 3;
+
+// Seats carry fixed ids so card effects in the decks can target a specific
+// seat, the same way puzzle data does.
+#[derive(Debug, Clone)]
+pub struct SimulatedPlayer {
+    pub id: Uuid,
+    pub name: String,
+    pub deck: Vec<Card>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub name: String,
+    pub seats: [SimulatedPlayer; 2],
+    pub mountain_levels: u32,
+    pub max_turns: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameOutcome {
+    pub winner: Option<usize>, // Seat index; None when the turn limit is hit
+    pub turns: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResult {
+    pub name: String,
+    pub games: u32,
+    pub wins: [u32; 2],
+    pub draws: u32,
+    pub total_turns: u64,
+}
+
+impl BatchResult {
+    fn empty(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            games: 0,
+            wins: [0, 0],
+            draws: 0,
+            total_turns: 0,
+        }
+    }
+
+    fn record(mut self, outcome: GameOutcome) -> Self {
+        self.games += 1;
+        self.total_turns += outcome.turns as u64;
+        match outcome.winner {
+            Some(seat) => self.wins[seat] += 1,
+            None => self.draws += 1,
+        }
+        self
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        self.games += other.games;
+        self.wins[0] += other.wins[0];
+        self.wins[1] += other.wins[1];
+        self.draws += other.draws;
+        self.total_turns += other.total_turns;
+        self
+    }
+
+    pub fn win_rate(&self, seat: usize) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins[seat] as f64 / self.games as f64
+        }
+    }
+
+    pub fn average_turns(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.total_turns as f64 / self.games as f64
+        }
+    }
+}
+
+// Seats both players with shuffled decks and opening hands
+pub fn setup_game(config: &SimulationConfig, seed: u64) -> GameState {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let [first, second] = config.seats.clone().map(|seat| {
+        let mut cards = seat.deck;
+        cards.shuffle(&mut rng);
+        let mut player = Player::new(
+            seat.name,
+            Deck {
+                cards: cards.into(),
+                owner_id: seat.id,
+            },
+        );
+        player.id = seat.id;
+        for _ in 0..STARTING_HAND {
+            let _ = player.draw_card();
+        }
+        player
+    });
+
+    let mut game_state = GameState::new(first, second).with_rng(Box::new(rng));
+    game_state.mountain = Mountain::new(config.mountain_levels);
+    if let Some(player) = game_state.players.get_mut(&config.seats[0].id) {
+        player.mana = 1;
+    }
+    game_state
+}
+
+fn outcome(config: &SimulationConfig, game_state: &GameState) -> GameOutcome {
+    GameOutcome {
+        winner: game_state
+            .winner()
+            .and_then(|id| config.seats.iter().position(|seat| seat.id == id)),
+        turns: game_state.turn_number,
+    }
+}
+
+// Runs one headless game with a greedy policy: draw, play the most
+// expensive affordable card until nothing fits, then pass.
+pub fn simulate_game(config: &SimulationConfig, seed: u64) -> GameOutcome {
+    let mut game_state = setup_game(config, seed);
+    while game_state.winner().is_none() && game_state.turn_number <= config.max_turns {
+        let active = game_state.active_player;
+        play_greedy_turn(&mut game_state, active);
+        if game_state.winner().is_none() && game_state.end_turn(active).is_err() {
+            break;
+        }
+    }
+    outcome(config, &game_state)
+}
+
+// Same game, with bots in the runner taking their seats and the greedy
+// policy playing any seat without one
+pub fn simulate_bot_game(
+    config: &SimulationConfig,
+    bots: &mut BotRunner,
+    seed: u64,
+) -> GameOutcome {
+    let mut game_state = setup_game(config, seed);
+    while game_state.winner().is_none() && game_state.turn_number <= config.max_turns {
+        let active = game_state.active_player;
+        if bots.hosts(active) {
+            if let Some(player) = game_state.players.get_mut(&active) {
+                let _ = player.draw_card();
+            }
+            if bots.play_turn(&mut game_state).is_err() {
+                break;
+            }
+            continue;
+        }
+        play_greedy_turn(&mut game_state, active);
+        if game_state.winner().is_none() && game_state.end_turn(active).is_err() {
+            break;
+        }
+    }
+    outcome(config, &game_state)
+}
+
+fn play_greedy_turn(game_state: &mut GameState, player_id: Uuid) {
+    if let Some(player) = game_state.players.get_mut(&player_id) {
+        let _ = player.draw_card();
+    }
+
+    loop {
+        let Some(player) = game_state.players.get(&player_id) else {
+            return;
+        };
+        let playable = player
+            .hand
+            .iter()
+            .filter(|card| player.effective_cost(card) <= player.mana)
+            .max_by_key(|card| player.effective_cost(card))
+            .map(|card| card.id);
+
+        match playable {
+            // A card whose effects fail has still been paid for and left the
+            // hand, so the loop always makes progress.
+            Some(card_id) => {
+                let _ = game_state.play_card(player_id, card_id);
+                if game_state.winner().is_some() {
+                    return;
+                }
+            }
+            None => return,
+        }
+    }
+}
+
+// Each game gets its own seed derived from its position in the batch, so
+// results are reproducible no matter how rayon schedules the work.
+pub fn simulate_batch(
+    configs: &[SimulationConfig],
+    n_games: u32,
+    base_seed: u64,
+) -> Vec<BatchResult> {
+    configs
+        .par_iter()
+        .enumerate()
+        .map(|(config_index, config)| {
+            (0..n_games)
+                .into_par_iter()
+                .map(|game| {
+                    let seed = base_seed
+                        ^ ((config_index as u64) << 32)
+                        ^ (game as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+                    simulate_game(config, seed)
+                })
+                .fold(|| BatchResult::empty(&config.name), BatchResult::record)
+                .reduce(|| BatchResult::empty(&config.name), BatchResult::merge)
+        })
+        .collect()
+}
+
+// TESTS
+#[cfg(test)]
+mod simulation_tests {
+    use super::*;
+    use crate::bots::AscentBot;
+    use crate::effects::{DamageEffect, Effect, EffectTarget, EffectValue};
+    use crate::game_state::GameAction;
+    use crate::models::{CardType, Rarity};
+    use crate::view::PlayerView;
+
+    fn seat(name: &str, id: Uuid, target: Option<Uuid>) -> SimulatedPlayer {
+        let deck = (0..20)
+            .map(|i| Card {
+                id: Uuid::new_v4(),
+                name: format!("{} {}", name, i),
+                cost: 1 + i % 3,
+                power: 1,
+                rarity: Rarity::Common,
+                effects: target
+                    .map(|target| {
+                        vec![Effect::Damage(DamageEffect {
+                            value: EffectValue {
+                                base: 3,
+                                scaling: None,
+                            },
+                            target: EffectTarget::Specific(target),
+                            penetrating: false,
+                            ranged: false,
+                            priority: 0,
+                        })]
+                    })
+                    .unwrap_or_default(),
+                card_type: CardType::Spell,
+            })
+            .collect();
+        SimulatedPlayer {
+            id,
+            name: name.to_string(),
+            deck,
+        }
+    }
+
+    #[test]
+    fn test_batch_is_deterministic_and_aggregates() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let configs = vec![
+            SimulationConfig {
+                name: "burn vs blank".to_string(),
+                seats: [seat("Burn", a, Some(b)), seat("Blank", b, None)],
+                mountain_levels: 3,
+                max_turns: 40,
+            },
+            SimulationConfig {
+                name: "mirror".to_string(),
+                seats: [seat("Burn", a, Some(b)), seat("Burn", b, Some(a))],
+                mountain_levels: 3,
+                max_turns: 40,
+            },
+        ];
+
+        let results = simulate_batch(&configs, 64, 1);
+
+        assert_eq!(results[0].games, 64);
+        assert_eq!(results[0].win_rate(0), 1.0);
+        assert_eq!(
+            results[1].wins[0] + results[1].wins[1] + results[1].draws,
+            64
+        );
+        assert_eq!(results, simulate_batch(&configs, 64, 1));
+    }
+
+    struct Passer;
+
+    impl AscentBot for Passer {
+        fn name(&self) -> &str {
+            "passer"
+        }
+
+        fn decide(&mut self, _view: &PlayerView) -> GameAction {
+            GameAction::EndTurn
+        }
+    }
+
+    #[test]
+    fn test_bots_take_their_seats() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let config = SimulationConfig {
+            name: "passer vs burn".to_string(),
+            seats: [seat("Passer", a, Some(b)), seat("Burn", b, Some(a))],
+            mountain_levels: 3,
+            max_turns: 40,
+        };
+        let mut bots = BotRunner::new();
+        bots.seat(a, Box::new(Passer));
+
+        let outcome = simulate_bot_game(&config, &mut bots, 3);
+        assert_eq!(outcome.winner, Some(1));
+        assert!(bots.faults(a).is_empty());
+    }
+}
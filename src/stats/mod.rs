@@ -0,0 +1,188 @@
+// src/stats/mod.rs
+// Card and archetype win rates for balance dashboards, bucketed by format
+// and rank band. The stats job folds finished games in; the resulting rows
+// are what gets stored and served.
+use crate::rating::Rating;
+use crate::replay::ReplaySummary;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GameFormat {
+    Constructed,
+    Draft,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RankBand {
+    Foothills,
+    Ridge,
+    Peak,
+    Summit,
+}
+
+impl RankBand {
+    pub fn of(rating: &Rating) -> RankBand {
+        match rating.rating {
+            r if r < 1400.0 => RankBand::Foothills,
+            r if r < 1700.0 => RankBand::Ridge,
+            r if r < 2000.0 => RankBand::Peak,
+            _ => RankBand::Summit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Subject {
+    Card,
+    Archetype,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Tally {
+    plays: u32,
+    wins: u32,
+}
+
+// One stored row per subject and bucket
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatRow {
+    pub format: GameFormat,
+    pub band: RankBand,
+    pub subject: Subject,
+    pub name: String,
+    pub plays: u32,
+    pub wins: u32,
+    pub win_rate: f64,
+    pub play_rate: f64, // Share of decks in the bucket that included it
+}
+
+type Bucket = (GameFormat, RankBand);
+
+#[derive(Debug, Default)]
+pub struct StatsJob {
+    tallies: HashMap<(Bucket, Subject, String), Tally>,
+    decks: HashMap<Bucket, u32>,
+}
+
+impl StatsJob {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Counts each deck in a finished game once. `archetypes` labels decks
+    // by player; unlabelled decks still count towards card stats.
+    pub fn ingest(
+        &mut self,
+        summary: &ReplaySummary,
+        format: GameFormat,
+        band: RankBand,
+        archetypes: &HashMap<Uuid, String>,
+    ) {
+        let bucket = (format, band);
+        for (player_id, cards) in &summary.decks {
+            let won = summary.winner == Some(*player_id);
+            *self.decks.entry(bucket).or_default() += 1;
+
+            let names: HashSet<&String> = cards.iter().collect();
+            let subjects = names.into_iter().map(|name| (Subject::Card, name)).chain(
+                archetypes
+                    .get(player_id)
+                    .map(|archetype| (Subject::Archetype, archetype)),
+            );
+            for (subject, name) in subjects {
+                let tally = self
+                    .tallies
+                    .entry((bucket, subject, name.clone()))
+                    .or_default();
+                tally.plays += 1;
+                tally.wins += won as u32;
+            }
+        }
+    }
+
+    pub fn rows(&self) -> Vec<StatRow> {
+        let mut rows: Vec<StatRow> = self
+            .tallies
+            .iter()
+            .map(|(((format, band), subject, name), tally)| {
+                let decks = self.decks.get(&(*format, *band)).copied().unwrap_or(1);
+                StatRow {
+                    format: *format,
+                    band: *band,
+                    subject: *subject,
+                    name: name.clone(),
+                    plays: tally.plays,
+                    wins: tally.wins,
+                    win_rate: tally.wins as f64 / tally.plays as f64,
+                    play_rate: tally.plays as f64 / decks as f64,
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            (a.format, a.band, a.subject, &a.name).cmp(&(b.format, b.band, b.subject, &b.name))
+        });
+        rows
+    }
+
+    // What the dashboard endpoint serves for one bucket, most played first
+    pub fn query(&self, format: GameFormat, band: RankBand, subject: Subject) -> Vec<StatRow> {
+        let mut rows: Vec<StatRow> = self
+            .rows()
+            .into_iter()
+            .filter(|row| row.format == format && row.band == band && row.subject == subject)
+            .collect();
+        rows.sort_by(|a, b| b.plays.cmp(&a.plays).then_with(|| a.name.cmp(&b.name)));
+        rows
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod stats_tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_card_and_archetype_rates_per_bucket() {
+        let (climber, rival) = (Uuid::new_v4(), Uuid::new_v4());
+        let summary = ReplaySummary {
+            game_id: Uuid::new_v4(),
+            players: vec![],
+            decks: HashMap::from([
+                (
+                    climber,
+                    vec![
+                        "Rope".to_string(),
+                        "Rope".to_string(),
+                        "Boulder".to_string(),
+                    ],
+                ),
+                (rival, vec!["Boulder".to_string()]),
+            ]),
+            winner: Some(climber),
+            turns: 8,
+            duration: Duration::from_secs(600),
+        };
+        let archetypes = HashMap::from([(climber, "Ramp".to_string())]);
+        let band = RankBand::of(&Rating::default());
+        let mut job = StatsJob::new();
+        job.ingest(&summary, GameFormat::Constructed, band, &archetypes);
+
+        let cards = job.query(GameFormat::Constructed, band, Subject::Card);
+        assert_eq!(cards[0].name, "Boulder");
+        assert_eq!(
+            (cards[0].plays, cards[0].win_rate, cards[0].play_rate),
+            (2, 0.5, 1.0)
+        );
+        assert_eq!((cards[1].plays, cards[1].play_rate), (1, 0.5));
+
+        let archetypes = job.query(GameFormat::Constructed, band, Subject::Archetype);
+        assert_eq!(
+            (archetypes[0].name.as_str(), archetypes[0].win_rate),
+            ("Ramp", 1.0)
+        );
+        assert!(job.query(GameFormat::Draft, band, Subject::Card).is_empty());
+    }
+}
@@ -0,0 +1,534 @@
+// src/store/mod.rs
+use crate::cards::CardRegistry;
+use crate::collections::bulk::parse_deck_code;
+use crate::collections::provenance::{Acquisition, OwnedItem};
+use crate::collections::Collection;
+use crate::cosmetics::{Cosmetic, CosmeticInventory, CosmeticSlot};
+use crate::errors::StoreError;
+use crate::models::{Card, Deck, Format};
+use crate::wallet::{Currency, TransactionReason, Wallet};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::SeedableRng;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+pub const DEFAULT_CATALOG: &str = include_str!("../../data/store_catalog.json");
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum StoreItemKind {
+    Pack { cards: u32 },
+    // The cosmetic's id is the item's sku
+    Cosmetic { slot: CosmeticSlot },
+    // Saved under the item's name; the code is written as for
+    // `parse_deck_code`
+    Deck { code: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Price {
+    pub currency: Currency,
+    pub amount: u64,
+}
+
+impl Price {
+    // None when the amount is too large to take the discount from
+    fn discounted(&self, percent: u64) -> Option<u64> {
+        let discount = self.amount.checked_mul(percent)? / 100;
+        self.amount.checked_sub(discount)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StoreItem {
+    pub sku: String,
+    pub name: String,
+    pub kind: StoreItemKind,
+    pub prices: Vec<Price>,
+    pub rotating: bool, // Only purchasable while featured in the daily offers
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Catalog {
+    pub items: Vec<StoreItem>,
+    pub daily_offer_count: usize,
+    pub daily_discount_percent: u64,
+}
+
+impl Catalog {
+    pub fn from_json(data: &str) -> Result<Self, StoreError> {
+        let catalog: Catalog =
+            serde_json::from_str(data).map_err(|e| StoreError::InvalidCatalog(e.to_string()))?;
+
+        if catalog.daily_discount_percent > 100 {
+            return Err(StoreError::InvalidCatalog(
+                "daily_discount_percent must be at most 100".to_string(),
+            ));
+        }
+        for (i, item) in catalog.items.iter().enumerate() {
+            if item.prices.is_empty() {
+                return Err(StoreError::InvalidCatalog(format!(
+                    "item {} has no price",
+                    item.sku
+                )));
+            }
+            if item.rotating
+                && item
+                    .prices
+                    .iter()
+                    .any(|price| price.discounted(catalog.daily_discount_percent).is_none())
+            {
+                return Err(StoreError::InvalidCatalog(format!(
+                    "item {} is priced too high to discount",
+                    item.sku
+                )));
+            }
+            let contents_valid = match &item.kind {
+                StoreItemKind::Pack { cards } => *cards > 0,
+                StoreItemKind::Cosmetic { .. } => true,
+                StoreItemKind::Deck { code } => parse_deck_code(code).is_ok(),
+            };
+            if !contents_valid {
+                return Err(StoreError::InvalidCatalog(format!(
+                    "item {} has nothing to hand over",
+                    item.sku
+                )));
+            }
+            if catalog.items[..i].iter().any(|other| other.sku == item.sku) {
+                return Err(StoreError::InvalidCatalog(format!(
+                    "duplicate sku {}",
+                    item.sku
+                )));
+            }
+        }
+        Ok(catalog)
+    }
+
+    pub fn item(&self, sku: &str) -> Option<&StoreItem> {
+        self.items.iter().find(|item| item.sku == sku)
+    }
+
+    // The rotation is derived from the day number alone so every server
+    // instance agrees on today's offers without coordination.
+    pub fn daily_offers(&self, day: u64) -> Vec<&StoreItem> {
+        let rotating: Vec<&StoreItem> = self.items.iter().filter(|item| item.rotating).collect();
+        let mut rng = StdRng::seed_from_u64(day);
+        rotating
+            .choose_multiple(&mut rng, self.daily_offer_count)
+            .copied()
+            .collect()
+    }
+
+    pub fn price_on(&self, sku: &str, day: u64) -> Result<Vec<(Currency, u64)>, StoreError> {
+        let item = self.item(sku).ok_or(StoreError::ItemNotFound)?;
+
+        if !item.rotating {
+            return Ok(item
+                .prices
+                .iter()
+                .map(|price| (price.currency, price.amount))
+                .collect());
+        }
+
+        if !self.daily_offers(day).iter().any(|offer| offer.sku == sku) {
+            return Err(StoreError::NotOnOffer);
+        }
+        item.prices
+            .iter()
+            .map(|price| {
+                let amount = price
+                    .discounted(self.daily_discount_percent)
+                    .ok_or_else(|| {
+                        StoreError::InvalidCatalog(format!(
+                            "item {} is priced too high to discount",
+                            sku
+                        ))
+                    })?;
+                Ok((price.currency, amount))
+            })
+            .collect()
+    }
+}
+
+// What a purchase handed over. Cards come back in full since the
+// collection only keeps their ids.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Goods {
+    Cards(Vec<Card>),
+    Cosmetic(Cosmetic),
+    Deck { name: String, cards: Vec<Card> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FulfillmentRecord {
+    pub request_id: Uuid,
+    pub player_id: Uuid,
+    pub sku: String,
+    pub charged: Vec<(Currency, u64)>,
+    pub transactions: Vec<Uuid>,
+    pub goods: Goods,
+}
+
+// Where a purchase is paid from and delivered to; all three belong to the
+// buyer
+pub struct Account<'a> {
+    pub wallet: &'a mut Wallet,
+    pub collection: &'a mut Collection,
+    pub cosmetics: &'a mut CosmeticInventory,
+}
+
+#[derive(Debug)]
+pub struct Store {
+    pub catalog: Catalog,
+    // The card pool packs are opened from and decks are made of
+    pub cards: CardRegistry,
+    // By player and request id, so one player's id can never answer for
+    // another's purchase
+    fulfillments: HashMap<(Uuid, Uuid), FulfillmentRecord>,
+}
+
+impl Store {
+    pub fn new(catalog: Catalog, cards: CardRegistry) -> Self {
+        Self {
+            catalog,
+            cards,
+            fulfillments: HashMap::new(),
+        }
+    }
+
+    pub fn fulfillment(&self, player_id: Uuid, request_id: Uuid) -> Option<&FulfillmentRecord> {
+        self.fulfillments.get(&(player_id, request_id))
+    }
+
+    // Purchases are keyed by a client-generated request id: a retried request
+    // returns the original record instead of charging or delivering again.
+    // The same id reused for a different item is refused.
+    pub fn purchase(
+        &mut self,
+        account: Account<'_>,
+        sku: &str,
+        request_id: Uuid,
+        day: u64,
+        now: SystemTime,
+    ) -> Result<FulfillmentRecord, StoreError> {
+        let key = (account.wallet.owner_id, request_id);
+        if let Some(record) = self.fulfillments.get(&key) {
+            if record.sku != sku {
+                return Err(StoreError::RequestMismatch);
+            }
+            return Ok(record.clone());
+        }
+
+        let charged = self.catalog.price_on(sku, day)?;
+        // Made up before anything is charged, so an item that can't be
+        // handed over costs nothing
+        let goods = self.make_goods(sku, request_id, &account)?;
+        let transactions = account
+            .wallet
+            .debit_all(&charged, TransactionReason::Purchase(request_id))
+            .map_err(StoreError::Wallet)?;
+        deliver(&goods, sku, account.collection, account.cosmetics, now);
+
+        let record = FulfillmentRecord {
+            request_id,
+            player_id: account.wallet.owner_id,
+            sku: sku.to_string(),
+            charged,
+            transactions,
+            goods,
+        };
+        self.fulfillments.insert(key, record.clone());
+        Ok(record)
+    }
+
+    fn make_goods(
+        &self,
+        sku: &str,
+        request_id: Uuid,
+        account: &Account<'_>,
+    ) -> Result<Goods, StoreError> {
+        let item = self.catalog.item(sku).ok_or(StoreError::ItemNotFound)?;
+        let copy = |definition: &Card| Card {
+            id: Uuid::new_v4(),
+            ..definition.clone()
+        };
+        match &item.kind {
+            StoreItemKind::Pack { cards } => {
+                let mut pool: Vec<&Card> = self.cards.definitions().collect();
+                pool.sort_by_key(|card| card.id);
+                // Seeded by the request, so which cards a pack held can be
+                // worked out again when settling a dispute
+                let mut rng = StdRng::seed_from_u64(request_id.as_u128() as u64);
+                (0..*cards)
+                    .map(|_| pool.choose(&mut rng).map(|definition| copy(definition)))
+                    .collect::<Option<Vec<Card>>>()
+                    .map(Goods::Cards)
+                    .ok_or_else(|| {
+                        StoreError::InvalidCatalog("no cards to open packs from".to_string())
+                    })
+            }
+            StoreItemKind::Cosmetic { slot } => {
+                if account.cosmetics.owns(&item.sku) {
+                    return Err(StoreError::AlreadyOwned);
+                }
+                Ok(Goods::Cosmetic(Cosmetic {
+                    id: item.sku.clone(),
+                    name: item.name.clone(),
+                    slot: *slot,
+                }))
+            }
+            StoreItemKind::Deck { code } => {
+                if account.collection.decks.contains_key(&item.name) {
+                    return Err(StoreError::AlreadyOwned);
+                }
+                let entries = parse_deck_code(code)
+                    .map_err(|e| StoreError::InvalidCatalog(format!("{:?}", e)))?;
+                let mut cards = Vec::new();
+                for (name, count) in entries {
+                    let definition = self
+                        .cards
+                        .by_name(&name)
+                        .ok_or_else(|| StoreError::UnknownCard(name.clone()))?;
+                    cards.extend((0..count).map(|_| copy(definition)));
+                }
+                Ok(Goods::Deck {
+                    name: item.name.clone(),
+                    cards,
+                })
+            }
+        }
+    }
+}
+
+fn deliver(
+    goods: &Goods,
+    sku: &str,
+    collection: &mut Collection,
+    cosmetics: &mut CosmeticInventory,
+    now: SystemTime,
+) {
+    match goods {
+        Goods::Cards(cards) => {
+            for card in cards {
+                let source = Acquisition::Pack {
+                    product_id: sku.to_string(),
+                };
+                collection.grant(OwnedItem::Card(card.id), source, now);
+            }
+        }
+        Goods::Cosmetic(cosmetic) => {
+            // Only fails if already owned, which `make_goods` ruled out
+            let _ = cosmetics.grant(cosmetic.clone());
+        }
+        Goods::Deck { name, cards } => {
+            for card in cards {
+                let source = Acquisition::Purchase {
+                    product_id: sku.to_string(),
+                };
+                collection.grant(OwnedItem::Card(card.id), source, now);
+            }
+            let deck = Deck {
+                cards: cards.clone().into(),
+                owner_id: collection.owner_id,
+            };
+            collection.save_deck(name.clone(), deck, Format::Constructed);
+        }
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod store_tests {
+    use super::*;
+    use crate::testing::CardBuilder;
+
+    // Everything the default catalog's decks are made of
+    fn card_pool() -> CardRegistry {
+        CardRegistry::new(
+            [
+                "Rockfall",
+                "Swift Ascent",
+                "Piton",
+                "Rope Line",
+                "Ice Shard",
+                "Second Wind",
+                "Crampons",
+                "Summit Warden",
+            ]
+            .map(|name| CardBuilder::new(name).build()),
+        )
+    }
+
+    struct Buyer {
+        wallet: Wallet,
+        collection: Collection,
+        cosmetics: CosmeticInventory,
+    }
+
+    impl Buyer {
+        fn new(gold: u64, gems: u64) -> Self {
+            let owner_id = Uuid::new_v4();
+            let mut wallet = Wallet::new(owner_id);
+            for (currency, amount) in [(Currency::Gold, gold), (Currency::Gems, gems)] {
+                if amount > 0 {
+                    wallet
+                        .credit(currency, amount, TransactionReason::Reward)
+                        .unwrap();
+                }
+            }
+            Self {
+                wallet,
+                collection: Collection::new(owner_id),
+                cosmetics: CosmeticInventory::new(owner_id),
+            }
+        }
+
+        fn account(&mut self) -> Account<'_> {
+            Account {
+                wallet: &mut self.wallet,
+                collection: &mut self.collection,
+                cosmetics: &mut self.cosmetics,
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_catalog_rotation() {
+        let catalog = Catalog::from_json(DEFAULT_CATALOG).unwrap();
+
+        let offers = catalog.daily_offers(42);
+        assert_eq!(offers.len(), catalog.daily_offer_count);
+        assert!(offers.iter().all(|item| item.rotating));
+        assert_eq!(offers, catalog.daily_offers(42));
+
+        let off_rotation = catalog
+            .items
+            .iter()
+            .find(|item| item.rotating && !offers.contains(item))
+            .unwrap();
+        assert!(matches!(
+            catalog.price_on(&off_rotation.sku, 42),
+            Err(StoreError::NotOnOffer)
+        ));
+    }
+
+    #[test]
+    fn test_prices_too_large_to_discount_are_refused() {
+        let catalog = |amount: u64| {
+            format!(
+                r#"{{"items": [{{"sku": "pack", "name": "Pack",
+                "kind": {{"Cosmetic": {{"slot": "CardBack"}}}},
+                "prices": [{{"currency": "Gold", "amount": {amount}}}], "rotating": true}}],
+                "daily_offer_count": 1, "daily_discount_percent": 20}}"#
+            )
+        };
+        let mut priced = Catalog::from_json(&catalog(1_000)).unwrap();
+        assert_eq!(priced.price_on("pack", 0).unwrap()[0].1, 800);
+        assert!(matches!(
+            Catalog::from_json(&catalog(u64::MAX)),
+            Err(StoreError::InvalidCatalog(_))
+        ));
+
+        priced.items[0].prices[0].amount = u64::MAX;
+        assert!(matches!(
+            priced.price_on("pack", 0),
+            Err(StoreError::InvalidCatalog(_))
+        ));
+    }
+
+    #[test]
+    fn test_purchase_is_idempotent() {
+        let mut store = Store::new(Catalog::from_json(DEFAULT_CATALOG).unwrap(), card_pool());
+        let mut buyer = Buyer::new(150, 0);
+        let now = SystemTime::UNIX_EPOCH;
+
+        let request_id = Uuid::new_v4();
+        let first = store
+            .purchase(buyer.account(), "pack_standard", request_id, 0, now)
+            .unwrap();
+        let retry = store
+            .purchase(buyer.account(), "pack_standard", request_id, 0, now)
+            .unwrap();
+
+        assert_eq!(first, retry);
+        assert_eq!(buyer.wallet.balance(Currency::Gold), 50);
+        assert!(matches!(
+            store.purchase(buyer.account(), "pack_legendary", request_id, 0, now),
+            Err(StoreError::RequestMismatch)
+        ));
+
+        // Another player's colliding id is their own purchase, charged to them
+        let mut other = Buyer::new(0, 0);
+        assert!(matches!(
+            store.purchase(other.account(), "pack_standard", request_id, 0, now),
+            Err(StoreError::Wallet(_))
+        ));
+        assert!(store
+            .fulfillment(other.wallet.owner_id, request_id)
+            .is_none());
+        assert!(other.collection.cards.is_empty());
+        assert!(matches!(
+            store.purchase(buyer.account(), "pack_standard", Uuid::new_v4(), 0, now),
+            Err(StoreError::Wallet(_))
+        ));
+    }
+
+    #[test]
+    fn test_purchases_are_delivered_exactly_once() {
+        let catalog = Catalog::from_json(DEFAULT_CATALOG).unwrap();
+        let day = (0..)
+            .find(|day| {
+                let offers = catalog.daily_offers(*day);
+                ["back_glacier", "deck_frostbound"]
+                    .iter()
+                    .all(|sku| offers.iter().any(|offer| offer.sku == *sku))
+            })
+            .unwrap();
+        let mut store = Store::new(catalog, card_pool());
+        let mut buyer = Buyer::new(1_000, 1_000);
+        let now = SystemTime::UNIX_EPOCH;
+
+        for sku in ["pack_standard", "back_glacier", "deck_frostbound"] {
+            let request_id = Uuid::new_v4();
+            for _ in 0..2 {
+                store
+                    .purchase(buyer.account(), sku, request_id, day, now)
+                    .unwrap();
+            }
+        }
+
+        // Five from the pack and ten in the deck
+        assert_eq!(buyer.collection.cards.len(), 15);
+        assert_eq!(buyer.collection.provenance.len(), 15);
+        let pack = Acquisition::Pack {
+            product_id: "pack_standard".to_string(),
+        };
+        assert_eq!(
+            buyer
+                .collection
+                .provenance
+                .iter()
+                .filter(|record| record.source == pack)
+                .count(),
+            5
+        );
+        assert!(buyer.cosmetics.owns("back_glacier"));
+        let deck = &buyer.collection.decks["Frostbound Starter Deck"];
+        assert_eq!(deck.cards.len(), 10);
+        assert!(deck
+            .cards
+            .iter()
+            .all(|card| buyer.collection.cards.contains(&card.id)));
+        assert_eq!(buyer.wallet.balance(Currency::Gold), 1_000 - 100 - 200);
+
+        // Owning it already refuses a second purchase before charging
+        let gems = buyer.wallet.balance(Currency::Gems);
+        assert!(matches!(
+            store.purchase(buyer.account(), "back_glacier", Uuid::new_v4(), day, now),
+            Err(StoreError::AlreadyOwned)
+        ));
+        assert_eq!(buyer.wallet.balance(Currency::Gems), gems);
+    }
+}
@@ -0,0 +1,201 @@
+// src/suggestions/mod.rs
+// Deck builder suggestions. Given a partial deck and the cards the owner
+// has, picks cards one at a time to fill the deck, each time favouring
+// whatever closes the biggest gap in the mana curve, fits the deck's
+// archetype and wins more often. The result is what the deck builder
+// endpoint serves.
+use crate::collections::Collection;
+use crate::models::{Card, Deck, Format};
+use crate::stats::{StatRow, Subject};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+// Share of a deck wanted at each cost: 0-1, 2, 3, 4, 5, 6+
+pub const TARGET_CURVE: [f64; 6] = [0.15, 0.25, 0.25, 0.15, 0.1, 0.1];
+
+const CURVE_WEIGHT: f64 = 4.0;
+const ARCHETYPE_BONUS: f64 = 1.0;
+const WIN_RATE_WEIGHT: f64 = 5.0;
+
+fn curve_slot(cost: u32) -> usize {
+    (cost.max(1) as usize - 1).min(TARGET_CURVE.len() - 1)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Reason {
+    FillsCurve { cost: u32 },
+    FitsArchetype(String),
+    WinRate(f64),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Suggestion {
+    pub card_id: Uuid,
+    pub name: String,
+    pub cost: u32,
+    pub score: f64,
+    pub reasons: Vec<Reason>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SuggestionContext {
+    pub format: Format,
+    pub archetype: Option<String>,
+    // Archetype tags by card name
+    pub tags: HashMap<String, HashSet<String>>,
+    // Win rate by card name from the stats job
+    pub win_rates: HashMap<String, f64>,
+}
+
+impl SuggestionContext {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            archetype: None,
+            tags: HashMap::new(),
+            win_rates: HashMap::new(),
+        }
+    }
+
+    pub fn with_archetype(mut self, archetype: &str) -> Self {
+        self.archetype = Some(archetype.to_string());
+        self
+    }
+
+    pub fn with_tags(mut self, tags: HashMap<String, HashSet<String>>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    // Takes card rows for the player's format and rank band
+    pub fn with_stats(mut self, rows: &[StatRow]) -> Self {
+        self.win_rates = rows
+            .iter()
+            .filter(|row| row.subject == Subject::Card)
+            .map(|row| (row.name.clone(), row.win_rate))
+            .collect();
+        self
+    }
+
+    fn fits_archetype(&self, card: &Card) -> Option<&String> {
+        let archetype = self.archetype.as_ref()?;
+        self.tags
+            .get(&card.name)
+            .is_some_and(|tags| tags.contains(archetype))
+            .then_some(archetype)
+    }
+}
+
+// `owned` is the owner's collection resolved to cards; anything not in
+// `collection` or already in the deck is skipped
+pub fn suggest(
+    deck: &Deck,
+    collection: &Collection,
+    owned: &[Card],
+    context: &SuggestionContext,
+    limit: usize,
+) -> Vec<Suggestion> {
+    let mut curve = [0usize; TARGET_CURVE.len()];
+    let mut copies: HashMap<&str, usize> = HashMap::new();
+    for card in deck.cards.iter() {
+        curve[curve_slot(card.cost)] += 1;
+        *copies.entry(card.name.as_str()).or_default() += 1;
+    }
+    let in_deck: HashSet<Uuid> = deck.cards.iter().map(|card| card.id).collect();
+    let mut candidates: Vec<&Card> = owned
+        .iter()
+        .filter(|card| collection.cards.contains(&card.id) && !in_deck.contains(&card.id))
+        .collect();
+
+    let open_slots = context.format.deck_size().saturating_sub(deck.cards.len());
+    let mut suggestions = Vec::new();
+    while suggestions.len() < limit.min(open_slots) {
+        let size = (deck.cards.len() + suggestions.len() + 1) as f64;
+        let best = candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| {
+                context
+                    .format
+                    .max_copies(&card.rarity)
+                    .is_none_or(|max| copies.get(card.name.as_str()).copied().unwrap_or(0) < max)
+            })
+            .map(|(index, card)| {
+                let slot = curve_slot(card.cost);
+                let gap = TARGET_CURVE[slot] - curve[slot] as f64 / size;
+                let mut reasons = Vec::new();
+                let mut score = gap * CURVE_WEIGHT;
+                if gap > 0.0 {
+                    reasons.push(Reason::FillsCurve { cost: card.cost });
+                }
+                if let Some(archetype) = context.fits_archetype(card) {
+                    score += ARCHETYPE_BONUS;
+                    reasons.push(Reason::FitsArchetype(archetype.clone()));
+                }
+                if let Some(win_rate) = context.win_rates.get(&card.name) {
+                    score += (win_rate - 0.5) * WIN_RATE_WEIGHT;
+                    reasons.push(Reason::WinRate(*win_rate));
+                }
+                (index, score, reasons)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((index, score, reasons)) = best else {
+            break;
+        };
+        let card = candidates.swap_remove(index);
+        curve[curve_slot(card.cost)] += 1;
+        *copies.entry(card.name.as_str()).or_default() += 1;
+        suggestions.push(Suggestion {
+            card_id: card.id,
+            name: card.name.clone(),
+            cost: card.cost,
+            score,
+            reasons,
+        });
+    }
+    suggestions
+}
+
+// TESTS
+#[cfg(test)]
+mod suggestion_tests {
+    use super::*;
+    use crate::testing::CardBuilder;
+
+    #[test]
+    fn test_suggestions_fill_curve_and_follow_archetype() {
+        let owner = Uuid::new_v4();
+        let deck = Deck {
+            cards: (0..8)
+                .map(|i| CardBuilder::new(&format!("Two Drop {i}")).cost(2).build())
+                .collect::<Vec<_>>()
+                .into(),
+            owner_id: owner,
+        };
+        let owned = vec![
+            CardBuilder::new("Another Two").cost(2).build(),
+            CardBuilder::new("Ridge Runner").cost(1).build(),
+            CardBuilder::new("Avalanche Caller").cost(3).build(),
+            CardBuilder::new("Not Owned").cost(1).build(),
+        ];
+        let mut collection = Collection::new(owner);
+        collection
+            .cards
+            .extend(owned[..3].iter().map(|card| card.id));
+        let context = SuggestionContext::new(Format::Constructed)
+            .with_archetype("Aggro")
+            .with_tags(HashMap::from([(
+                "Avalanche Caller".to_string(),
+                HashSet::from(["Aggro".to_string()]),
+            )]));
+
+        let picks = suggest(&deck, &collection, &owned, &context, 3);
+        let names: Vec<&str> = picks.iter().map(|pick| pick.name.as_str()).collect();
+        assert_eq!(names, ["Avalanche Caller", "Ridge Runner", "Another Two"]);
+        assert!(picks[0]
+            .reasons
+            .contains(&Reason::FitsArchetype("Aggro".to_string())));
+        assert!(picks[2].score < picks[1].score);
+    }
+}
@@ -0,0 +1,236 @@
+// src/summary/mod.rs
+// End-of-game report built from the event log. Both players get it when
+// the match ends and it is stored with the match record. Damage and healing
+// that happen while a card resolves are credited to that card, which is how
+// the MVP card is picked.
+use crate::events::GameEvent;
+use crate::game_state::GameState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSummary {
+    pub player_id: Uuid,
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+    pub healing_done: u32,
+    pub cards_drawn: u32,
+    pub cards_played: u32,
+    pub highest_level: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hit {
+    pub source: Uuid,
+    pub target: Uuid,
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MvpCard {
+    pub player_id: Uuid,
+    pub card_id: Uuid,
+    pub name: String,
+    pub impact: u32, // Damage dealt plus healing done
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSummary {
+    pub game_id: Uuid,
+    pub winner: Option<Uuid>,
+    pub turns: u32,
+    pub players: Vec<PlayerSummary>, // In turn order
+    pub largest_hit: Option<Hit>,
+    pub mvp_card: Option<MvpCard>,
+}
+
+impl GameSummary {
+    pub fn from_game(game_state: &GameState) -> GameSummary {
+        let mut players: Vec<PlayerSummary> = game_state
+            .seated_players()
+            .map(|player| PlayerSummary {
+                player_id: player.id,
+                ..PlayerSummary::default()
+            })
+            .collect();
+        let index: HashMap<Uuid, usize> = players
+            .iter()
+            .enumerate()
+            .map(|(i, summary)| (summary.player_id, i))
+            .collect();
+        let mut largest_hit: Option<Hit> = None;
+        // Card being resolved and the impact credited to each card
+        let mut current: Option<(Uuid, Uuid, &str)> = None;
+        let mut impact: HashMap<Uuid, (Uuid, &str, u32)> = HashMap::new();
+
+        for event in &game_state.events {
+            match event {
+                GameEvent::TurnStarted { .. } => current = None,
+                GameEvent::CardPlayed {
+                    player_id,
+                    card_id,
+                    name,
+                } => {
+                    current = Some((*player_id, *card_id, name.as_str()));
+                    if let Some(&i) = index.get(player_id) {
+                        players[i].cards_played += 1;
+                    }
+                }
+                GameEvent::CardDrawn { player_id } => {
+                    if let Some(&i) = index.get(player_id) {
+                        players[i].cards_drawn += 1;
+                    }
+                }
+                GameEvent::DamageDealt {
+                    source,
+                    target,
+                    amount,
+                } => {
+                    if let Some(&i) = index.get(target) {
+                        players[i].damage_taken += amount;
+                    }
+                    // Fatigue and falls are self-inflicted, not dealt
+                    if source != target {
+                        if let Some(&i) = index.get(source) {
+                            players[i].damage_dealt += amount;
+                        }
+                        if let Some((player_id, card_id, name)) = current {
+                            impact.entry(card_id).or_insert((player_id, name, 0)).2 += amount;
+                        }
+                    }
+                    if largest_hit.is_none_or(|hit| *amount > hit.amount) && *amount > 0 {
+                        largest_hit = Some(Hit {
+                            source: *source,
+                            target: *target,
+                            amount: *amount,
+                        });
+                    }
+                }
+                GameEvent::Healed { source, amount, .. } => {
+                    if let Some(&i) = index.get(source) {
+                        players[i].healing_done += amount;
+                    }
+                    if let Some((player_id, card_id, name)) = current {
+                        impact.entry(card_id).or_insert((player_id, name, 0)).2 += amount;
+                    }
+                }
+                GameEvent::PlayerMoved { player_id, to, .. }
+                | GameEvent::PlayerRespawned {
+                    player_id,
+                    position: to,
+                } => {
+                    if let Some(&i) = index.get(player_id) {
+                        players[i].highest_level = players[i].highest_level.max(to.level);
+                    }
+                }
+                _ => {}
+            }
+        }
+        for player in game_state.seated_players() {
+            let summary = &mut players[index[&player.id]];
+            summary.highest_level = summary.highest_level.max(player.position.level);
+        }
+
+        let mvp_card = impact
+            .into_iter()
+            .filter(|(_, (_, _, impact))| *impact > 0)
+            .max_by_key(|(card_id, (_, _, impact))| (*impact, *card_id))
+            .map(|(card_id, (player_id, name, impact))| MvpCard {
+                player_id,
+                card_id,
+                name: name.to_string(),
+                impact,
+            });
+        GameSummary {
+            game_id: game_state.game_id,
+            winner: game_state.winner(),
+            turns: game_state.turn_number,
+            players,
+            largest_hit,
+            mvp_card,
+        }
+    }
+
+    // For erasure requests, alongside the rest of the match record
+    pub fn replace_player(&mut self, player_id: Uuid, pseudonym: Uuid) {
+        let swap = |id: &mut Uuid| {
+            if *id == player_id {
+                *id = pseudonym;
+            }
+        };
+        for player in &mut self.players {
+            swap(&mut player.player_id);
+        }
+        if let Some(winner) = &mut self.winner {
+            swap(winner);
+        }
+        if let Some(hit) = &mut self.largest_hit {
+            swap(&mut hit.source);
+            swap(&mut hit.target);
+        }
+        if let Some(mvp) = &mut self.mvp_card {
+            swap(&mut mvp.player_id);
+        }
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod summary_tests {
+    use super::*;
+    use crate::effects::{DamageEffect, DrawEffect, Effect, EffectTarget, EffectValue};
+    use crate::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
+
+    #[test]
+    fn test_summary_totals_and_mvp() {
+        let rival = Uuid::new_v4();
+        let bolt = |base| {
+            Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base,
+                    scaling: None,
+                },
+                target: EffectTarget::Specific(rival),
+                penetrating: false,
+                ranged: false,
+                priority: 0,
+            })
+        };
+        let small = CardBuilder::new("Pebble").effect(bolt(2)).build();
+        let big = CardBuilder::new("Rockfall").effect(bolt(7)).build();
+        let scout = CardBuilder::new("Scout Ahead")
+            .effect(Effect::Draw(DrawEffect {
+                cards: 1,
+                target: EffectTarget::Self_,
+                filter: None,
+                priority: 0,
+            }))
+            .build();
+        let mut game_state = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Climber")
+                    .mana(10)
+                    .hand(vec![small.clone(), big.clone(), scout.clone()])
+                    .deck(vec![CardBuilder::new("Rope").build()])
+                    .build(),
+            )
+            .player(PlayerBuilder::new("Rival").id(rival).build())
+            .build();
+        let climber = game_state.turn_order[0];
+        for card in [&small, &big, &scout] {
+            game_state.play_card(climber, card.id).unwrap();
+        }
+
+        let summary = GameSummary::from_game(&game_state);
+        let (mine, theirs) = (&summary.players[0], &summary.players[1]);
+        assert_eq!(
+            (mine.damage_dealt, mine.cards_played, mine.cards_drawn),
+            (9, 3, 1)
+        );
+        assert_eq!(theirs.damage_taken, 9);
+        assert_eq!(summary.largest_hit.unwrap().amount, 7);
+        let mvp = summary.mvp_card.unwrap();
+        assert_eq!((mvp.name.as_str(), mvp.impact), ("Rockfall", 7));
+    }
+}
@@ -0,0 +1,289 @@
+// src/testing/mod.rs
+// Fixture builders for tests. Every field starts from a sensible default so
+// tests only spell out what they care about.
+use crate::effects::Effect;
+use crate::game_state::GameState;
+use crate::models::{Card, CardType, Deck, Mountain, Player, Position, Rarity};
+use crate::runtime::{seeded_rng, GameClock};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct CardBuilder {
+    card: Card,
+}
+
+impl CardBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            card: Card {
+                id: Uuid::new_v4(),
+                name: name.to_string(),
+                cost: 1,
+                power: 1,
+                rarity: Rarity::Common,
+                effects: vec![],
+                card_type: CardType::Spell,
+            },
+        }
+    }
+
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.card.id = id;
+        self
+    }
+
+    pub fn cost(mut self, cost: u32) -> Self {
+        self.card.cost = cost;
+        self
+    }
+
+    pub fn power(mut self, power: u32) -> Self {
+        self.card.power = power;
+        self
+    }
+
+    pub fn rarity(mut self, rarity: Rarity) -> Self {
+        self.card.rarity = rarity;
+        self
+    }
+
+    pub fn card_type(mut self, card_type: CardType) -> Self {
+        self.card.card_type = card_type;
+        self
+    }
+
+    pub fn effect(mut self, effect: Effect) -> Self {
+        self.card.effects.push(effect);
+        self
+    }
+
+    pub fn build(self) -> Card {
+        self.card
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeckBuilder {
+    owner_id: Uuid,
+    cards: Vec<Card>,
+}
+
+impl Default for DeckBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeckBuilder {
+    pub fn new() -> Self {
+        Self {
+            owner_id: Uuid::new_v4(),
+            cards: vec![],
+        }
+    }
+
+    pub fn owner(mut self, owner_id: Uuid) -> Self {
+        self.owner_id = owner_id;
+        self
+    }
+
+    pub fn card(mut self, card: Card) -> Self {
+        self.cards.push(card);
+        self
+    }
+
+    // Adds `count` copies of the card, each with its own id
+    pub fn copies(mut self, card: Card, count: usize) -> Self {
+        for _ in 0..count {
+            self.cards.push(Card {
+                id: Uuid::new_v4(),
+                ..card.clone()
+            });
+        }
+        self
+    }
+
+    pub fn build(self) -> Deck {
+        Deck {
+            cards: self.cards.into(),
+            owner_id: self.owner_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PlayerBuilder {
+    id: Uuid,
+    name: String,
+    health: u32,
+    max_health: u32,
+    mana: u32,
+    position: Position,
+    hand: Vec<Card>,
+    deck: Vec<Card>,
+}
+
+impl PlayerBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            health: 30,
+            max_health: 30,
+            mana: 0,
+            position: Position::default(),
+            hand: vec![],
+            deck: vec![],
+        }
+    }
+
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn health(mut self, health: u32) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn max_health(mut self, max_health: u32) -> Self {
+        self.max_health = max_health;
+        self
+    }
+
+    pub fn mana(mut self, mana: u32) -> Self {
+        self.mana = mana;
+        self
+    }
+
+    pub fn position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn hand(mut self, hand: Vec<Card>) -> Self {
+        self.hand = hand;
+        self
+    }
+
+    pub fn deck(mut self, deck: Vec<Card>) -> Self {
+        self.deck = deck;
+        self
+    }
+
+    pub fn build(self) -> Player {
+        let deck = Deck {
+            cards: self.deck.into(),
+            owner_id: self.id,
+        };
+        let mut player = Player::new(self.name, deck);
+        player.id = self.id;
+        player.health = self.health;
+        player.max_health = self.max_health;
+        player.mana = self.mana;
+        player.position = self.position;
+        player.set_hand(self.hand);
+        player
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GameStateBuilder {
+    players: Vec<Player>,
+    levels: Option<u32>,
+    seed: u64,
+    clock: Option<Arc<dyn GameClock>>,
+}
+
+impl GameStateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Players take turns in the order they are added. Missing seats are
+    // filled with default players.
+    pub fn player(mut self, player: Player) -> Self {
+        self.players.push(player);
+        self
+    }
+
+    pub fn mountain_levels(mut self, levels: u32) -> Self {
+        self.levels = Some(levels);
+        self
+    }
+
+    // Games built here are seeded (with 0 unless told otherwise) so random
+    // targeting is reproducible
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn GameClock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    pub fn build(self) -> GameState {
+        let mut players = self.players.into_iter();
+        let first = players
+            .next()
+            .unwrap_or_else(|| PlayerBuilder::new("Player 1").build());
+        let second = players
+            .next()
+            .unwrap_or_else(|| PlayerBuilder::new("Player 2").build());
+
+        let mut game_state = GameState::new(first, second).with_rng(seeded_rng(self.seed));
+        if let Some(clock) = self.clock {
+            game_state = game_state.with_clock(clock);
+        }
+        for player in players {
+            game_state.turn_order.push(player.id);
+            game_state.players.insert(player.id, player);
+        }
+        if let Some(levels) = self.levels {
+            game_state.mountain = Mountain::new(levels);
+        }
+        game_state
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod testing_tests {
+    use super::*;
+
+    #[test]
+    fn test_game_state_builder_seats_players_in_order() {
+        let card = CardBuilder::new("Boulder").power(4).build();
+        let first = PlayerBuilder::new("First").hand(vec![card]).mana(3).build();
+        let first_id = first.id;
+        let third = PlayerBuilder::new("Third").build();
+        let third_id = third.id;
+
+        let game_state = GameStateBuilder::new()
+            .player(first)
+            .player(PlayerBuilder::new("Second").build())
+            .player(third)
+            .mountain_levels(3)
+            .build();
+
+        assert_eq!(game_state.active_player, first_id);
+        assert_eq!(game_state.turn_order.len(), 3);
+        assert_eq!(game_state.turn_order[2], third_id);
+        assert_eq!(game_state.players[&first_id].get_power(), 4);
+        assert_eq!(game_state.mountain.levels, 3);
+    }
+
+    #[test]
+    fn test_deck_builder_copies_get_unique_ids() {
+        let deck = DeckBuilder::new()
+            .copies(CardBuilder::new("Rope").build(), 3)
+            .build();
+
+        assert_eq!(deck.cards.len(), 3);
+        assert_ne!(deck.cards[0].id, deck.cards[1].id);
+    }
+}
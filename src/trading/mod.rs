@@ -0,0 +1,308 @@
+// src/trading/mod.rs
+// Card trades between players. Accepting an offer locks both sides' cards
+// in escrow. The swap only happens once both players confirm, and a trade
+// left unconfirmed past its deadline releases the cards. Nothing can be
+// spent twice or lost to a race between the two steps.
+use crate::collections::provenance::{Acquisition, OwnedItem};
+use crate::collections::Collection;
+use crate::errors::TradeError;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+pub const DEFAULT_ESCROW_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TradeState {
+    Proposed,
+    Escrowed {
+        expires_at: SystemTime,
+        confirmed: HashSet<Uuid>,
+    },
+    Completed,
+    Cancelled,
+    Expired,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeOffer {
+    pub id: Uuid,
+    pub from: Uuid,
+    pub to: Uuid,
+    pub offered: Vec<Uuid>,   // Cards `from` gives up
+    pub requested: Vec<Uuid>, // Cards `to` gives up
+    pub state: TradeState,
+}
+
+impl TradeOffer {
+    fn check_collections(&self, from: &Collection, to: &Collection) -> Result<(), TradeError> {
+        if from.owner_id != self.from || to.owner_id != self.to {
+            return Err(TradeError::WrongCollection);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct TradeDesk {
+    offers: HashMap<Uuid, TradeOffer>,
+    escrow_timeout: Duration,
+}
+
+impl Default for TradeDesk {
+    fn default() -> Self {
+        Self::new(DEFAULT_ESCROW_TIMEOUT)
+    }
+}
+
+impl TradeDesk {
+    pub fn new(escrow_timeout: Duration) -> Self {
+        Self {
+            offers: HashMap::new(),
+            escrow_timeout,
+        }
+    }
+
+    pub fn get(&self, trade_id: Uuid) -> Option<&TradeOffer> {
+        self.offers.get(&trade_id)
+    }
+
+    pub fn propose(
+        &mut self,
+        from: &Collection,
+        to: Uuid,
+        offered: Vec<Uuid>,
+        requested: Vec<Uuid>,
+    ) -> Result<Uuid, TradeError> {
+        from.check_tradable(&offered)?;
+        let offer = TradeOffer {
+            id: Uuid::new_v4(),
+            from: from.owner_id,
+            to,
+            offered,
+            requested,
+            state: TradeState::Proposed,
+        };
+        let trade_id = offer.id;
+        self.offers.insert(trade_id, offer);
+        Ok(trade_id)
+    }
+
+    // Agreement in principle: both sides' cards go into escrow together, or
+    // neither does
+    pub fn accept(
+        &mut self,
+        trade_id: Uuid,
+        from: &mut Collection,
+        to: &mut Collection,
+        now: SystemTime,
+    ) -> Result<(), TradeError> {
+        let offer = self.offers.get_mut(&trade_id).ok_or(TradeError::NotFound)?;
+        offer.check_collections(from, to)?;
+        if offer.state != TradeState::Proposed {
+            return Err(TradeError::InvalidState);
+        }
+        to.check_tradable(&offer.requested)?;
+        from.lock(&offer.offered)?;
+        to.lock(&offer.requested)?;
+        offer.state = TradeState::Escrowed {
+            expires_at: now + self.escrow_timeout,
+            confirmed: HashSet::new(),
+        };
+        Ok(())
+    }
+
+    // Final confirmation from one side. Returns true once both have
+    // confirmed and the cards have changed hands.
+    pub fn confirm(
+        &mut self,
+        trade_id: Uuid,
+        player_id: Uuid,
+        from: &mut Collection,
+        to: &mut Collection,
+        now: SystemTime,
+    ) -> Result<bool, TradeError> {
+        let offer = self.offers.get_mut(&trade_id).ok_or(TradeError::NotFound)?;
+        offer.check_collections(from, to)?;
+        if player_id != offer.from && player_id != offer.to {
+            return Err(TradeError::NotParticipant);
+        }
+        let TradeState::Escrowed {
+            expires_at,
+            confirmed,
+        } = &mut offer.state
+        else {
+            return Err(TradeError::InvalidState);
+        };
+        if now >= *expires_at {
+            settle(offer, from, to, TradeState::Expired);
+            return Err(TradeError::Expired);
+        }
+        confirmed.insert(player_id);
+        if confirmed.len() < 2 {
+            return Ok(false);
+        }
+
+        from.give_up(&offer.offered);
+        to.give_up(&offer.requested);
+        for card in &offer.offered {
+            let source = Acquisition::Trade {
+                trade_id,
+                from: offer.from,
+            };
+            to.grant(OwnedItem::Card(*card), source, now);
+        }
+        for card in &offer.requested {
+            let source = Acquisition::Trade {
+                trade_id,
+                from: offer.to,
+            };
+            from.grant(OwnedItem::Card(*card), source, now);
+        }
+        settle(offer, from, to, TradeState::Completed);
+        Ok(true)
+    }
+
+    // Either side can back out until the swap has happened
+    pub fn cancel(
+        &mut self,
+        trade_id: Uuid,
+        player_id: Uuid,
+        from: &mut Collection,
+        to: &mut Collection,
+    ) -> Result<(), TradeError> {
+        let offer = self.offers.get_mut(&trade_id).ok_or(TradeError::NotFound)?;
+        offer.check_collections(from, to)?;
+        if player_id != offer.from && player_id != offer.to {
+            return Err(TradeError::NotParticipant);
+        }
+        if !matches!(
+            offer.state,
+            TradeState::Proposed | TradeState::Escrowed { .. }
+        ) {
+            return Err(TradeError::InvalidState);
+        }
+        settle(offer, from, to, TradeState::Cancelled);
+        Ok(())
+    }
+
+    // Escrowed trades past their deadline; the caller releases each one
+    // with `expire`
+    pub fn overdue(&self, now: SystemTime) -> Vec<Uuid> {
+        self.offers
+            .values()
+            .filter(|offer| {
+                matches!(offer.state, TradeState::Escrowed { expires_at, .. } if now >= expires_at)
+            })
+            .map(|offer| offer.id)
+            .collect()
+    }
+
+    pub fn expire(
+        &mut self,
+        trade_id: Uuid,
+        from: &mut Collection,
+        to: &mut Collection,
+        now: SystemTime,
+    ) -> Result<(), TradeError> {
+        let offer = self.offers.get_mut(&trade_id).ok_or(TradeError::NotFound)?;
+        offer.check_collections(from, to)?;
+        match offer.state {
+            TradeState::Escrowed { expires_at, .. } if now >= expires_at => {
+                settle(offer, from, to, TradeState::Expired);
+                Ok(())
+            }
+            _ => Err(TradeError::InvalidState),
+        }
+    }
+}
+
+// Releases whatever the trade still holds in escrow and closes it
+fn settle(offer: &mut TradeOffer, from: &mut Collection, to: &mut Collection, state: TradeState) {
+    if matches!(offer.state, TradeState::Escrowed { .. }) {
+        from.release(&offer.offered);
+        to.release(&offer.requested);
+    }
+    offer.state = state;
+}
+
+// TESTS
+#[cfg(test)]
+mod trading_tests {
+    use super::*;
+    use crate::models::Deck;
+    use crate::testing::CardBuilder;
+
+    fn collection_with(cards: &[Uuid]) -> Collection {
+        let mut collection = Collection::new(Uuid::new_v4());
+        collection.cards.extend(cards);
+        collection
+    }
+
+    #[test]
+    fn test_escrow_swaps_only_after_both_confirm() {
+        let (sword_card, rope) = (
+            CardBuilder::new("Sword").build(),
+            CardBuilder::new("Rope").build(),
+        );
+        let (sword, shield) = (sword_card.id, Uuid::new_v4());
+        let mut alice = collection_with(&[sword, rope.id]);
+        let mut bob = collection_with(&[shield]);
+        alice.decks.insert(
+            "Climb".to_string(),
+            Deck {
+                cards: vec![sword_card, rope.clone()].into(),
+                owner_id: alice.owner_id,
+            },
+        );
+        let mut desk = TradeDesk::default();
+        let now = SystemTime::UNIX_EPOCH;
+
+        let trade = desk
+            .propose(&alice, bob.owner_id, vec![sword], vec![shield])
+            .unwrap();
+        desk.accept(trade, &mut alice, &mut bob, now).unwrap();
+
+        // Escrowed cards can't back a second trade
+        assert_eq!(
+            desk.propose(&alice, bob.owner_id, vec![sword], vec![]),
+            Err(TradeError::Locked(sword))
+        );
+
+        let (alice_id, bob_id) = (alice.owner_id, bob.owner_id);
+        assert_eq!(
+            desk.confirm(trade, alice_id, &mut alice, &mut bob, now),
+            Ok(false)
+        );
+        assert!(alice.cards.contains(&sword));
+        assert_eq!(
+            desk.confirm(trade, bob_id, &mut alice, &mut bob, now),
+            Ok(true)
+        );
+        assert!(alice.cards.contains(&shield) && bob.cards.contains(&sword));
+        assert!(!alice.cards.contains(&sword));
+        assert!(alice.locked.is_empty() && bob.locked.is_empty());
+        // The traded card leaves Alice's saved deck with it
+        assert_eq!(*alice.decks["Climb"].cards, [rope]);
+    }
+
+    #[test]
+    fn test_unconfirmed_escrow_expires_and_releases() {
+        let sword = Uuid::new_v4();
+        let mut alice = collection_with(&[sword]);
+        let mut bob = collection_with(&[]);
+        let mut desk = TradeDesk::new(Duration::from_secs(60));
+        let now = SystemTime::UNIX_EPOCH;
+
+        let trade = desk
+            .propose(&alice, bob.owner_id, vec![sword], vec![])
+            .unwrap();
+        desk.accept(trade, &mut alice, &mut bob, now).unwrap();
+
+        let later = now + Duration::from_secs(60);
+        assert_eq!(desk.overdue(later), [trade]);
+        desk.expire(trade, &mut alice, &mut bob, later).unwrap();
+        assert_eq!(desk.get(trade).unwrap().state, TradeState::Expired);
+        assert!(alice.cards.contains(&sword) && alice.locked.is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+// src/tutorial/mod.rs
+use crate::errors::TutorialError;
+use crate::game_state::{GameAction, GameState};
+use crate::models::{Mountain, Position};
+use crate::puzzles::PuzzleSide;
+use serde::Deserialize;
+use uuid::Uuid;
+
+// Script actions name cards rather than ids, since card ids are only
+// assigned once the tutorial game is built.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum TutorialAction {
+    PlayCard(String),
+    Move(Position),
+    EndTurn,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TutorialStep {
+    pub instruction: String,
+    pub expect: TutorialAction,
+    #[serde(default)]
+    pub opponent_response: Vec<TutorialAction>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TutorialScript {
+    pub name: String,
+    pub mountain_levels: u32,
+    pub player: PuzzleSide,
+    pub opponent: PuzzleSide,
+    pub steps: Vec<TutorialStep>,
+}
+
+impl TutorialScript {
+    pub fn from_json(data: &str) -> Result<Self, TutorialError> {
+        let script: TutorialScript =
+            serde_json::from_str(data).map_err(|e| TutorialError::InvalidScript(e.to_string()))?;
+        if script.steps.is_empty() {
+            return Err(TutorialError::InvalidScript(
+                "tutorial has no steps".to_string(),
+            ));
+        }
+        Ok(script)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TutorialEvent {
+    Instruction { step: usize, text: String },
+    OpponentAction(GameAction),
+    Completed,
+}
+
+#[derive(Debug)]
+pub struct Tutorial {
+    pub script: TutorialScript,
+    pub game_state: GameState,
+    pub step: usize,
+}
+
+impl Tutorial {
+    pub fn start(script: TutorialScript) -> (Self, Vec<TutorialEvent>) {
+        let mut game_state = GameState::new(script.player.to_player(), script.opponent.to_player());
+        game_state.mountain = Mountain::preset(script.mountain_levels);
+        game_state.active_player = script.player.id;
+
+        let tutorial = Self {
+            script,
+            game_state,
+            step: 0,
+        };
+        let events = vec![tutorial.instruction()];
+        (tutorial, events)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.step >= self.script.steps.len()
+    }
+
+    // Only the action the current step asks for is accepted. Anything else is
+    // rejected with the step's instruction so the client can repeat it. The
+    // action and the opponent's scripted answer go through together or not
+    // at all, so a broken script can't leave the step half played.
+    pub fn submit(&mut self, action: &GameAction) -> Result<Vec<TutorialEvent>, TutorialError> {
+        let step = self
+            .script
+            .steps
+            .get(self.step)
+            .ok_or(TutorialError::AlreadyComplete)?;
+        let player_id = self.script.player.id;
+
+        let expected = resolve(&self.game_state, player_id, &step.expect)?;
+        if *action != expected {
+            return Err(TutorialError::ActionNotAllowed {
+                hint: step.instruction.clone(),
+            });
+        }
+        let mut game_state = self.game_state.clone();
+        game_state
+            .apply_action(player_id, action)
+            .map_err(TutorialError::Game)?;
+
+        let mut events = Vec::new();
+        let opponent_id = self.script.opponent.id;
+        for scripted in &step.opponent_response {
+            let action = resolve(&game_state, opponent_id, scripted)?;
+            game_state
+                .apply_action(opponent_id, &action)
+                .map_err(TutorialError::Game)?;
+            events.push(TutorialEvent::OpponentAction(action));
+        }
+
+        self.game_state = game_state;
+        self.step += 1;
+        if self.is_complete() {
+            events.push(TutorialEvent::Completed);
+        } else {
+            events.push(self.instruction());
+        }
+        Ok(events)
+    }
+
+    fn instruction(&self) -> TutorialEvent {
+        TutorialEvent::Instruction {
+            step: self.step,
+            text: self.script.steps[self.step].instruction.clone(),
+        }
+    }
+}
+
+// A script action as the game action it stands for in `game_state`
+fn resolve(
+    game_state: &GameState,
+    player_id: Uuid,
+    action: &TutorialAction,
+) -> Result<GameAction, TutorialError> {
+    match action {
+        TutorialAction::PlayCard(name) => game_state.players[&player_id]
+            .hand
+            .iter()
+            .find(|card| card.name == *name)
+            .map(|card| GameAction::PlayCard { card_id: card.id })
+            .ok_or_else(|| TutorialError::InvalidScript(format!("{} is not in hand", name))),
+        TutorialAction::Move(position) => Ok(GameAction::Move {
+            position: *position,
+        }),
+        TutorialAction::EndTurn => Ok(GameAction::EndTurn),
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod tutorial_tests {
+    use super::*;
+
+    const BASICS: &str = include_str!("../../data/tutorials/basics.json");
+
+    #[test]
+    fn test_basics_tutorial_walkthrough() {
+        let script = TutorialScript::from_json(BASICS).unwrap();
+        let (mut tutorial, events) = Tutorial::start(script);
+        assert!(matches!(
+            events[0],
+            TutorialEvent::Instruction { step: 0, .. }
+        ));
+
+        // Ending the turn early is not what step one asks for
+        assert!(matches!(
+            tutorial.submit(&GameAction::EndTurn),
+            Err(TutorialError::ActionNotAllowed { .. })
+        ));
+
+        let player_id = tutorial.script.player.id;
+        let card_id = tutorial.game_state.players[&player_id].hand[0].id;
+        tutorial.submit(&GameAction::PlayCard { card_id }).unwrap();
+        tutorial
+            .submit(&GameAction::Move {
+                position: Position {
+                    x: 1,
+                    y: 0,
+                    z: 1,
+                    level: 1,
+                },
+            })
+            .unwrap();
+        let events = tutorial.submit(&GameAction::EndTurn).unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                TutorialEvent::OpponentAction(GameAction::EndTurn),
+                TutorialEvent::Completed
+            ]
+        );
+        assert!(tutorial.is_complete());
+        assert_eq!(tutorial.game_state.active_player, player_id);
+        assert_eq!(
+            tutorial.game_state.players[&tutorial.script.opponent.id].health,
+            16
+        );
+    }
+
+    #[test]
+    fn test_broken_script_leaves_step_unplayed() {
+        let mut script = TutorialScript::from_json(BASICS).unwrap();
+        script.steps[0].opponent_response = vec![TutorialAction::PlayCard("Nothing".to_string())];
+        let (mut tutorial, _) = Tutorial::start(script);
+        let before = tutorial.game_state.state_hash();
+
+        let player_id = tutorial.script.player.id;
+        let card_id = tutorial.game_state.players[&player_id].hand[0].id;
+        assert!(matches!(
+            tutorial.submit(&GameAction::PlayCard { card_id }),
+            Err(TutorialError::InvalidScript(_))
+        ));
+        assert_eq!(tutorial.step, 0);
+        assert_eq!(tutorial.game_state.state_hash(), before);
+    }
+}
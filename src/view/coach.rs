@@ -0,0 +1,217 @@
+// src/view/coach.rs
+// Coaches watch one player's game with that player's hand showing, but only
+// as it was some turns ago so nothing they see can be passed back while it
+// still matters. A player has to grant the seat. During the game the coach
+// can only watch; once it's over they get a one-way channel to the player
+// to go over it.
+use crate::errors::CoachError;
+use crate::game_state::GameState;
+use crate::models::Card;
+use crate::view::PlayerView;
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoachGrant {
+    pub player_id: Uuid,
+    // How many turns behind the live game the hand is shown
+    pub delay_turns: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct CoachSeats {
+    grants: HashMap<Uuid, CoachGrant>,
+    // Each coached player's hand at the end of every turn seen so far,
+    // oldest first
+    hands: HashMap<Uuid, VecDeque<(u32, Vec<Card>)>>,
+}
+
+impl CoachSeats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn grant(
+        &mut self,
+        game_state: &GameState,
+        player_id: Uuid,
+        coach_id: Uuid,
+        delay_turns: u32,
+    ) -> Result<(), CoachError> {
+        if !game_state.players.contains_key(&player_id) {
+            return Err(CoachError::NotAPlayer);
+        }
+        // A seated player coaching someone would see two hands
+        if game_state.players.contains_key(&coach_id) {
+            return Err(CoachError::CoachIsPlaying);
+        }
+        self.grants.insert(
+            coach_id,
+            CoachGrant {
+                player_id,
+                delay_turns,
+            },
+        );
+        self.record(game_state);
+        Ok(())
+    }
+
+    // Only the player who granted the seat can take it back
+    pub fn revoke(&mut self, player_id: Uuid, coach_id: Uuid) -> Result<(), CoachError> {
+        match self.grants.get(&coach_id) {
+            Some(grant) if grant.player_id == player_id => {
+                self.grants.remove(&coach_id);
+                Ok(())
+            }
+            _ => Err(CoachError::NotGranted),
+        }
+    }
+
+    pub fn grant_for(&self, coach_id: Uuid) -> Option<&CoachGrant> {
+        self.grants.get(&coach_id)
+    }
+
+    // Call after every change to the game, like publishing to the event bus
+    pub fn record(&mut self, game_state: &GameState) {
+        let longest = self
+            .grants
+            .values()
+            .map(|grant| grant.delay_turns)
+            .max()
+            .unwrap_or(0);
+        for grant in self.grants.values() {
+            let Some(player) = game_state.players.get(&grant.player_id) else {
+                continue;
+            };
+            let history = self.hands.entry(grant.player_id).or_default();
+            match history.back_mut() {
+                Some((turn, hand)) if *turn == game_state.turn_number => {
+                    hand.clone_from(&player.hand);
+                }
+                _ => history.push_back((game_state.turn_number, player.hand.clone())),
+            }
+            while history.len() > longest as usize + 1 {
+                history.pop_front();
+            }
+        }
+    }
+
+    // A spectator's view with the coached player's delayed hand filled in.
+    // The delay is dropped once the game has a winner.
+    pub fn view_for(
+        &self,
+        game_state: &GameState,
+        coach_id: Uuid,
+    ) -> Result<PlayerView, CoachError> {
+        let grant = self.grants.get(&coach_id).ok_or(CoachError::NotGranted)?;
+        let mut view = game_state.view_for(coach_id);
+        let hand = if view.winner.is_some() {
+            game_state
+                .players
+                .get(&grant.player_id)
+                .map(|player| player.hand.clone())
+        } else {
+            self.hands.get(&grant.player_id).and_then(|history| {
+                history
+                    .iter()
+                    .rev()
+                    .find(|(turn, _)| turn + grant.delay_turns <= game_state.turn_number)
+                    .map(|(_, hand)| hand.clone())
+            })
+        };
+        if let Some(seat) = view
+            .seats
+            .iter_mut()
+            .find(|seat| seat.player_id == grant.player_id)
+        {
+            seat.hand = hand;
+        }
+        Ok(view)
+    }
+
+    pub fn check_debrief(
+        &self,
+        game_state: &GameState,
+        coach_id: Uuid,
+        to: Uuid,
+    ) -> Result<(), CoachError> {
+        match self.grants.get(&coach_id) {
+            Some(grant) if grant.player_id == to => {}
+            _ => return Err(CoachError::NotGranted),
+        }
+        if game_state.winner().is_none() {
+            return Err(CoachError::GameInProgress);
+        }
+        Ok(())
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod coach_tests {
+    use super::*;
+    use crate::game_state::GameAction;
+    use crate::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
+
+    #[test]
+    fn test_coach_sees_only_their_players_hand_turns_late() {
+        let deck = || {
+            ["Rope", "Piton", "Flare", "Drift"]
+                .map(|name| CardBuilder::new(name).build())
+                .to_vec()
+        };
+        let mut game_state = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Alice")
+                    .hand(vec![CardBuilder::new("Boulder").build()])
+                    .deck(deck())
+                    .build(),
+            )
+            .player(PlayerBuilder::new("Bob").deck(deck()).build())
+            .build();
+        let (student, rival) = (game_state.turn_order[0], game_state.turn_order[1]);
+        let coach = Uuid::new_v4();
+        let mut seats = CoachSeats::new();
+        assert_eq!(
+            seats.grant(&game_state, student, rival, 1),
+            Err(CoachError::CoachIsPlaying)
+        );
+        seats.grant(&game_state, student, coach, 1).unwrap();
+        assert_eq!(seats.revoke(rival, coach), Err(CoachError::NotGranted));
+
+        let view = seats.view_for(&game_state, coach).unwrap();
+        assert_eq!(view.seat(student).unwrap().hand, None);
+        assert_eq!(view.seat(rival).unwrap().hand, None);
+
+        let opening = game_state.players[&student].hand.clone();
+        game_state
+            .apply_action(student, &GameAction::EndTurn)
+            .unwrap();
+        let drawn = CardBuilder::new("Flare").build();
+        game_state
+            .players
+            .get_mut(&student)
+            .unwrap()
+            .hand
+            .push(drawn);
+        seats.record(&game_state);
+        let view = seats.view_for(&game_state, coach).unwrap();
+        assert_eq!(view.seat(student).unwrap().hand.as_ref(), Some(&opening));
+        assert_ne!(game_state.players[&student].hand, opening);
+        assert!(view
+            .threats
+            .iter()
+            .all(|threat| threat.burst_damage.is_none()));
+
+        assert_eq!(
+            seats.check_debrief(&game_state, coach, student),
+            Err(CoachError::GameInProgress)
+        );
+        game_state.players.get_mut(&rival).unwrap().health = 0;
+        assert_eq!(seats.check_debrief(&game_state, coach, student), Ok(()));
+        assert_eq!(
+            seats.check_debrief(&game_state, coach, rival),
+            Err(CoachError::NotGranted)
+        );
+    }
+}
@@ -0,0 +1,94 @@
+// src/view/hints.rs
+// What the viewer can do this turn, worked out by the server so thin
+// clients can grey out illegal actions without carrying the rules engine.
+// Only filled in on request, see GameState::view_with_hints.
+use crate::effects::Effect;
+use crate::game_state::GameState;
+use crate::models::Position;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TurnHints {
+    // Cards in hand the viewer can pay for, Overload included
+    pub payable: Vec<Uuid>,
+    pub reachable: Vec<Position>,
+}
+
+impl GameState {
+    // None unless it's the viewer's turn and play isn't waiting on a choice
+    pub fn turn_hints(&self, viewer: Uuid) -> Option<TurnHints> {
+        let player = self.players.get(&viewer)?;
+        if !self.holds_turn(viewer) || self.winner().is_some() || !self.pending_scry.is_empty() {
+            return None;
+        }
+        let mut hints = TurnHints::default();
+        if self
+            .rules
+            .max_cards_per_turn
+            .is_none_or(|max| player.cards_played_this_turn < max)
+        {
+            hints.payable = player
+                .hand
+                .iter()
+                .filter(|card| {
+                    let borrowable: u32 = card.effects.iter().map(Effect::overload).sum();
+                    player.effective_cost(card) <= player.mana.saturating_add(borrowable)
+                })
+                .map(|card| card.id)
+                .collect();
+        }
+        if self
+            .rules
+            .max_moves_per_turn
+            .is_none_or(|max| player.moves_this_turn < max)
+        {
+            hints.reachable = self.reachable_tiles(viewer);
+        }
+        Some(hints)
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod hints_tests {
+    use crate::testing::{CardBuilder, GameStateBuilder, PlayerBuilder};
+
+    #[test]
+    fn test_hints_match_what_the_rules_accept() {
+        let cheap = CardBuilder::new("Rope").cost(1).build();
+        let dear = CardBuilder::new("Avalanche").cost(5).build();
+        let mut game_state = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Alice")
+                    .hand(vec![cheap.clone(), dear.clone()])
+                    .mana(2)
+                    .build(),
+            )
+            .player(PlayerBuilder::new("Bob").build())
+            .build();
+        let (alice, bob) = (game_state.turn_order[0], game_state.turn_order[1]);
+        assert!(game_state.view_for(alice).hints.is_none());
+        assert!(game_state.view_with_hints(bob).hints.is_none());
+
+        let hints = game_state.view_with_hints(alice).hints.unwrap();
+        assert_eq!(hints.payable, vec![cheap.id]);
+        assert!(!hints.reachable.is_empty());
+        for position in &hints.reachable {
+            assert!(game_state.clone().move_player(alice, *position).is_ok());
+        }
+        let origin = game_state.players[&alice].position;
+        let unreachable = game_state
+            .mountain
+            .tiles
+            .iter()
+            .map(|tile| tile.position())
+            .find(|position| {
+                *position != origin
+                    && !hints.reachable.contains(position)
+                    && game_state.player_at(*position, alice).is_none()
+            })
+            .unwrap();
+        assert!(game_state.move_player(alice, unreachable).is_err());
+    }
+}
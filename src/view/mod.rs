@@ -0,0 +1,249 @@
+// src/view/mod.rs
+// The game as one player is allowed to see it. Opponents' hands and every
+// deck's order stay hidden; only sizes are shown. Clients, bots and the
+// FFI layer all read this rather than the full GameState.
+use crate::game_state::{Expedition, GameState, PauseState};
+use crate::models::{Card, Position};
+use hints::TurnHints;
+use serde::{Deserialize, Serialize};
+use statuses::StatusBadge;
+use threat::ThreatScore;
+use uuid::Uuid;
+
+pub mod coach;
+pub mod hints;
+pub mod statuses;
+pub mod threat;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatView {
+    pub player_id: Uuid,
+    pub name: String,
+    pub health: u32,
+    pub max_health: u32,
+    pub shield: u32,
+    pub mana: u32,
+    // Borrowed this turn and locked next turn, and locked right now
+    pub overload: u32,
+    pub locked_mana: u32,
+    pub movement: u32,
+    pub position: Position,
+    pub hand_size: usize,
+    pub deck_size: usize,
+    // Only filled in for the viewer's own seat; `hand_costs` are what the
+    // cards in `hand` cost to play right now
+    pub hand: Option<Vec<Card>>,
+    pub hand_costs: Option<Vec<u32>>,
+    pub respawn_in: Option<u32>,
+    pub statuses: Vec<StatusBadge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub game_id: Uuid,
+    pub viewer: Uuid,
+    pub active_player: Uuid,
+    pub turn_number: u32,
+    pub paused: bool,
+    pub winner: Option<Uuid>,
+    pub seats: Vec<SeatView>, // In turn order
+    pub event_count: usize,
+    // Seat order, worked out from what the viewer can see
+    pub threats: Vec<ThreatScore>,
+    // Who play is waiting on to answer a scry, and the cards themselves
+    // only if that's the viewer
+    pub choosing: Option<Uuid>,
+    pub scry: Option<ScryView>,
+    // Face up for everyone
+    pub expedition: Option<Expedition>,
+    // Only from view_with_hints, and only on the viewer's own turn
+    pub hints: Option<TurnHints>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScryView {
+    pub deck_owner: Uuid,
+    pub cards: Vec<Card>,
+}
+
+impl PlayerView {
+    pub fn seat(&self, player_id: Uuid) -> Option<&SeatView> {
+        self.seats.iter().find(|seat| seat.player_id == player_id)
+    }
+
+    pub fn own_seat(&self) -> Option<&SeatView> {
+        self.seat(self.viewer)
+    }
+
+    pub fn is_my_turn(&self) -> bool {
+        self.active_player == self.viewer && self.winner.is_none() && !self.paused
+    }
+}
+
+impl GameState {
+    // The oldest pending scry, if it's `viewer` who has to answer it
+    pub fn scry_for(&self, viewer: Uuid) -> Option<ScryView> {
+        let choice = self.pending_scry.first()?;
+        if choice.chooser != viewer {
+            return None;
+        }
+        let deck = &self.players.get(&choice.deck_owner)?.deck.cards;
+        Some(ScryView {
+            deck_owner: choice.deck_owner,
+            cards: choice
+                .cards
+                .iter()
+                .filter_map(|id| deck.iter().find(|card| card.id == *id).cloned())
+                .collect(),
+        })
+    }
+
+    pub fn view_for(&self, viewer: Uuid) -> PlayerView {
+        let seats = self
+            .seated_players()
+            .map(|player| SeatView {
+                player_id: player.id,
+                name: player.name.clone(),
+                health: player.health,
+                max_health: player.max_health,
+                shield: player.shield,
+                mana: player.mana,
+                overload: player.overload,
+                locked_mana: player.locked_mana,
+                movement: player.movement,
+                position: player.position,
+                hand_size: player.hand.len(),
+                deck_size: player.deck.cards.len(),
+                hand: (player.id == viewer).then(|| player.hand.clone()),
+                hand_costs: (player.id == viewer).then(|| {
+                    player
+                        .hand
+                        .iter()
+                        .map(|card| player.effective_cost(card))
+                        .collect()
+                }),
+                respawn_in: self.respawns.get(&player.id).copied(),
+                statuses: statuses::badges(player),
+            })
+            .collect();
+        PlayerView {
+            game_id: self.game_id,
+            viewer,
+            active_player: self.active_player,
+            turn_number: self.turn_number,
+            paused: matches!(self.pause, PauseState::Paused { .. }),
+            winner: self.winner(),
+            seats,
+            event_count: self.events.len(),
+            threats: self.threat_assessment(Some(viewer)),
+            choosing: self.pending_scry.first().map(|choice| choice.chooser),
+            scry: self.scry_for(viewer),
+            expedition: self.expedition.clone(),
+            hints: None,
+        }
+    }
+
+    // For clients that asked for turn hints
+    pub fn view_with_hints(&self, viewer: Uuid) -> PlayerView {
+        PlayerView {
+            hints: self.turn_hints(viewer),
+            ..self.view_for(viewer)
+        }
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod view_tests {
+    use super::*;
+    use crate::effects::{Duration, Status, StatusKind};
+    use crate::models::Boost;
+    use crate::testing::GameStateBuilder;
+    use statuses::StatusCategory;
+
+    #[test]
+    fn test_view_hides_opponent_hand() {
+        let game_state = GameStateBuilder::new().build();
+        let viewer = game_state.turn_order[0];
+        let opponent = game_state.turn_order[1];
+        let view = game_state.view_for(viewer);
+
+        assert!(view.own_seat().unwrap().hand.is_some());
+        assert!(view.seat(opponent).unwrap().hand.is_none());
+        assert_eq!(
+            view.seat(opponent).unwrap().hand_size,
+            game_state.players[&opponent].hand.len()
+        );
+        assert_eq!(view.is_my_turn(), game_state.active_player == viewer);
+    }
+
+    #[test]
+    fn test_statuses_stack_into_badges() {
+        let mut game_state = GameStateBuilder::new().build();
+        let viewer = game_state.turn_order[0];
+        let player = game_state.players.get_mut(&viewer).unwrap();
+        for turns in [1, 3] {
+            player.statuses.push(Status {
+                kind: StatusKind::Poison { damage: 2 },
+                duration: Duration::Temporary(turns),
+                source: None,
+            });
+        }
+        player.power_boosts.push(Boost::new(3, Duration::Permanent));
+
+        let view = game_state.view_for(viewer);
+        let badges = &view.own_seat().unwrap().statuses;
+        assert_eq!(badges[0].id, "power_boost");
+        assert_eq!(
+            (badges[1].id.as_str(), badges[1].category),
+            ("poison", StatusCategory::DamageOverTime)
+        );
+        assert_eq!(
+            (badges[1].stacks, badges[1].magnitude, badges[1].remaining),
+            (2, 4, Duration::Temporary(3))
+        );
+        assert!(badges
+            .iter()
+            .all(|badge| statuses::lookup(&badge.id).is_some()));
+    }
+
+    #[test]
+    fn test_threat_counts_only_visible_burst() {
+        use crate::effects::{DamageEffect, Effect, EffectTarget, EffectValue};
+        use crate::testing::{CardBuilder, PlayerBuilder};
+
+        let bolt = CardBuilder::new("Bolt")
+            .cost(1)
+            .effect(Effect::Damage(DamageEffect {
+                value: EffectValue {
+                    base: 30,
+                    scaling: None,
+                },
+                target: EffectTarget::Enemies,
+                penetrating: false,
+                ranged: false,
+                priority: 0,
+            }))
+            .build();
+        let game_state = GameStateBuilder::new()
+            .player(
+                PlayerBuilder::new("Caster")
+                    .mana(1)
+                    .hand(vec![bolt])
+                    .build(),
+            )
+            .player(PlayerBuilder::new("Target").build())
+            .build();
+        let (caster, target) = (game_state.turn_order[0], game_state.turn_order[1]);
+
+        let spectator = game_state.threat_assessment(None);
+        assert_eq!(spectator[0].burst_damage, Some(30));
+        assert!(spectator[0].lethal);
+        assert!(spectator[0].score > spectator[1].score);
+
+        let threats = game_state.view_for(target).threats;
+        assert_eq!(threats[0].player_id, caster);
+        assert_eq!(threats[0].burst_damage, None);
+        assert!(!threats[0].lethal);
+    }
+}
@@ -0,0 +1,159 @@
+// src/view/statuses.rs
+// Everything that can sit on a player's status bar, under ids that never
+// change. Clients key icons and tooltips off `id` and fetch the registry
+// once, so the bar can be drawn from the view without replaying effects.
+use crate::effects::{Duration, EffectType, StatusKind};
+use crate::models::{Boost, Player};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusCategory {
+    Boost,
+    Debuff,
+    DamageOverTime,
+    Immunity,
+    Keyword,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StatusInfo {
+    pub id: &'static str,
+    pub category: StatusCategory,
+    pub name: &'static str,
+}
+
+const fn info(id: &'static str, category: StatusCategory, name: &'static str) -> StatusInfo {
+    StatusInfo { id, category, name }
+}
+
+pub const REGISTRY: &[StatusInfo] = &[
+    info("power_boost", StatusCategory::Boost, "Empowered"),
+    info("health_boost", StatusCategory::Boost, "Fortified"),
+    info("max_health_up", StatusCategory::Boost, "Hardy"),
+    info("max_health_down", StatusCategory::Debuff, "Frail"),
+    info("poison", StatusCategory::DamageOverTime, "Poisoned"),
+    info("weakened", StatusCategory::Debuff, "Weakened"),
+    info("slowed", StatusCategory::Debuff, "Slowed"),
+    info("hasted", StatusCategory::Boost, "Hasted"),
+    info("debuff_immunity", StatusCategory::Immunity, "Warded"),
+    info("guard", StatusCategory::Keyword, "Guard"),
+    info("stealth", StatusCategory::Keyword, "Stealth"),
+    info("effect_damage", StatusCategory::Keyword, "Damage"),
+    info("effect_heal", StatusCategory::Keyword, "Healing"),
+    info("effect_draw", StatusCategory::Keyword, "Draw"),
+    info("effect_boost", StatusCategory::Keyword, "Boost"),
+    info("effect_buff", StatusCategory::Keyword, "Buff"),
+];
+
+pub fn lookup(id: &str) -> Option<&'static StatusInfo> {
+    REGISTRY.iter().find(|info| info.id == id)
+}
+
+pub fn status_id(kind: &StatusKind) -> &'static str {
+    match kind {
+        StatusKind::Poison { .. } => "poison",
+        StatusKind::Weakened { .. } => "weakened",
+        StatusKind::DebuffImmunity => "debuff_immunity",
+        StatusKind::Guard => "guard",
+        StatusKind::Stealth => "stealth",
+        StatusKind::Slowed { .. } => "slowed",
+        StatusKind::Hasted { .. } => "hasted",
+    }
+}
+
+fn magnitude(kind: &StatusKind) -> u32 {
+    match kind {
+        StatusKind::Poison { damage } => *damage,
+        StatusKind::Weakened { power } => *power,
+        StatusKind::Slowed { movement } | StatusKind::Hasted { movement } => *movement,
+        StatusKind::DebuffImmunity | StatusKind::Guard | StatusKind::Stealth => 0,
+    }
+}
+
+fn effect_id(effect: &EffectType) -> &'static str {
+    match effect {
+        EffectType::Damage => "effect_damage",
+        EffectType::Heal => "effect_heal",
+        EffectType::Draw => "effect_draw",
+        EffectType::Boost => "effect_boost",
+        EffectType::Buff => "effect_buff",
+    }
+}
+
+// Whichever of two durations runs out later
+fn longer(a: Duration, b: Duration) -> Duration {
+    match (a, b) {
+        (Duration::Permanent, _) | (_, Duration::Permanent) => Duration::Permanent,
+        (Duration::Temporary(a), Duration::Temporary(b)) => Duration::Temporary(a.max(b)),
+        (Duration::UntilMountainLevel(a), Duration::UntilMountainLevel(b)) => {
+            Duration::UntilMountainLevel(a.max(b))
+        }
+        (level @ Duration::UntilMountainLevel(_), _)
+        | (_, level @ Duration::UntilMountainLevel(_)) => level,
+    }
+}
+
+fn boosts<'a>(
+    id: &'static str,
+    list: &'a [Boost],
+) -> impl Iterator<Item = (&'static str, u32, Duration)> + 'a {
+    list.iter()
+        .map(move |boost| (id, boost.amount, boost.duration))
+}
+
+// One icon on the bar: every instance with the same id stacked together
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusBadge {
+    pub id: String,
+    pub category: StatusCategory,
+    pub stacks: u32,
+    pub magnitude: u32, // Summed across stacks; 0 for on/off statuses
+    pub remaining: Duration,
+}
+
+// Badges in registry order, so the bar doesn't shuffle between updates
+pub fn badges(player: &Player) -> Vec<StatusBadge> {
+    let entries = boosts("power_boost", &player.power_boosts)
+        .chain(boosts("health_boost", &player.health_boosts))
+        .chain(player.max_health_modifiers.iter().map(|(delta, duration)| {
+            let id = if *delta >= 0 {
+                "max_health_up"
+            } else {
+                "max_health_down"
+            };
+            (id, delta.unsigned_abs(), *duration)
+        }))
+        .chain(player.statuses.iter().map(|status| {
+            (
+                status_id(&status.kind),
+                magnitude(&status.kind),
+                status.duration,
+            )
+        }))
+        .chain(
+            player
+                .active_effects
+                .iter()
+                .map(|(effect, duration)| (effect_id(effect), 0, *duration)),
+        );
+
+    let mut badges: Vec<StatusBadge> = Vec::new();
+    for (id, amount, duration) in entries {
+        match badges.iter_mut().find(|badge| badge.id == id) {
+            Some(badge) => {
+                badge.stacks += 1;
+                badge.magnitude += amount;
+                badge.remaining = longer(badge.remaining, duration);
+            }
+            None => badges.push(StatusBadge {
+                id: id.to_string(),
+                category: lookup(id).map_or(StatusCategory::Keyword, |info| info.category),
+                stacks: 1,
+                magnitude: amount,
+                remaining: duration,
+            }),
+        }
+    }
+    badges.sort_by_key(|badge| REGISTRY.iter().position(|info| info.id == badge.id));
+    badges
+}
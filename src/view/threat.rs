@@ -0,0 +1,130 @@
+// src/view/threat.rs
+// Rough read on how dangerous each player is right now, for bot heuristics
+// and commentary overlays. The score is a weighted sum of the parts; the
+// parts are exposed too so overlays can say why someone is a threat.
+use crate::effects::Effect;
+use crate::game_state::GameState;
+use crate::models::Player;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const LETHAL_WEIGHT: f32 = 15.0;
+const CAMP_WEIGHT: f32 = 3.0;
+const ALTITUDE_WEIGHT: f32 = 2.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreatScore {
+    pub player_id: Uuid,
+    // Damage the cards they can afford this turn would deal; None when
+    // their hand is hidden from whoever asked
+    pub burst_damage: Option<u32>,
+    // That damage is enough to finish off some enemy
+    pub lethal: bool,
+    // Camps nearer to this player than to any enemy
+    pub camps_controlled: u32,
+    // Levels above the average enemy; negative when below
+    pub altitude_advantage: i32,
+    pub summit_distance: u32,
+    pub score: f32,
+}
+
+// Cheapest cards first until the mana runs out
+fn burst_damage(player: &Player) -> u32 {
+    let mut costs: Vec<(u32, u32)> = player
+        .hand
+        .iter()
+        .map(|card| {
+            let damage = card
+                .effects
+                .iter()
+                .map(|effect| match effect {
+                    Effect::Damage(damage) => damage.value.base,
+                    _ => 0,
+                })
+                .sum();
+            (player.effective_cost(card), damage)
+        })
+        .collect();
+    costs.sort_unstable();
+
+    let mut mana = player.mana;
+    let mut total = 0u32;
+    for (cost, damage) in costs {
+        if cost > mana {
+            break;
+        }
+        mana -= cost;
+        total = total.saturating_add(damage);
+    }
+    total
+}
+
+impl GameState {
+    // One score per seat, in turn order. With a viewer, only their own hand
+    // counts towards burst damage, so a bot or client can't learn anything
+    // it couldn't see; spectator feeds pass None.
+    pub fn threat_assessment(&self, viewer: Option<Uuid>) -> Vec<ThreatScore> {
+        let summit = self.mountain.summit_level();
+        self.seated_players()
+            .map(|player| {
+                let enemies: Vec<&Player> = self
+                    .seated_players()
+                    .filter(|other| self.are_enemies(player.id, other.id) && other.health > 0)
+                    .collect();
+
+                let burst = viewer
+                    .is_none_or(|viewer| viewer == player.id)
+                    .then(|| burst_damage(player));
+                let lethal = burst.is_some_and(|burst| {
+                    burst > 0
+                        && enemies
+                            .iter()
+                            .any(|enemy| burst >= enemy.health.saturating_add(enemy.shield))
+                });
+
+                let distance =
+                    |from: &Player, to| self.mountain.calculate_distance(from.position, to);
+                let camps_controlled = self
+                    .mountain
+                    .camps
+                    .iter()
+                    .filter(|camp| {
+                        let own = distance(player, **camp);
+                        enemies.iter().all(|enemy| own < distance(enemy, **camp))
+                    })
+                    .count() as u32;
+
+                let altitude_advantage = if enemies.is_empty() {
+                    0
+                } else {
+                    let total: i64 = enemies
+                        .iter()
+                        .map(|enemy| enemy.position.level as i64)
+                        .sum();
+                    (player.position.level as i64 - total / enemies.len() as i64) as i32
+                };
+                let summit_distance = summit.saturating_sub(player.position.level);
+
+                let score = if player.health == 0 {
+                    0.0
+                } else {
+                    burst.unwrap_or(0) as f32
+                        + if lethal { LETHAL_WEIGHT } else { 0.0 }
+                        + camps_controlled as f32 * CAMP_WEIGHT
+                        + altitude_advantage as f32 * ALTITUDE_WEIGHT
+                        + (summit - summit_distance) as f32
+                };
+
+                ThreatScore {
+                    player_id: player.id,
+                    burst_damage: burst,
+                    lethal,
+                    camps_controlled,
+                    altitude_advantage,
+                    summit_distance,
+                    score,
+                }
+            })
+            .collect()
+    }
+}
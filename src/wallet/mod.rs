@@ -0,0 +1,213 @@
+// src/wallet/mod.rs
+use crate::errors::WalletError;
+use serde::Deserialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Currency {
+    Gold,
+    Gems,
+    Dust, // Spent on crafting
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionReason {
+    Reward,
+    Purchase(Uuid),
+    Crafting,
+    Disenchant,
+    Refund(Uuid),
+    Adjustment,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transaction {
+    pub id: Uuid,
+    pub currency: Currency,
+    pub amount: i64, // Positive for credits, negative for debits
+    pub balance_after: u64,
+    pub reason: TransactionReason,
+}
+
+#[derive(Debug, Clone)]
+pub struct Wallet {
+    pub owner_id: Uuid,
+    balances: HashMap<Currency, u64>,
+    history: Vec<Transaction>,
+}
+
+impl Wallet {
+    pub fn new(owner_id: Uuid) -> Self {
+        Self {
+            owner_id,
+            balances: HashMap::new(),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn balance(&self, currency: Currency) -> u64 {
+        self.balances.get(&currency).copied().unwrap_or(0)
+    }
+
+    pub fn history(&self) -> &[Transaction] {
+        &self.history
+    }
+
+    pub fn can_afford(&self, costs: &[(Currency, u64)]) -> bool {
+        self.check_debits(costs).is_ok()
+    }
+
+    pub fn credit(
+        &mut self,
+        currency: Currency,
+        amount: u64,
+        reason: TransactionReason,
+    ) -> Result<Uuid, WalletError> {
+        let signed = i64::try_from(amount).map_err(|_| WalletError::Overflow)?;
+        let balance = self
+            .balance(currency)
+            .checked_add(amount)
+            .ok_or(WalletError::Overflow)?;
+        Ok(self.record(currency, signed, balance, reason))
+    }
+
+    pub fn debit(
+        &mut self,
+        currency: Currency,
+        amount: u64,
+        reason: TransactionReason,
+    ) -> Result<Uuid, WalletError> {
+        let ids = self.debit_all(&[(currency, amount)], reason)?;
+        Ok(ids[0])
+    }
+
+    // Debits several currencies as one unit: either every balance covers its
+    // cost and all are charged, or nothing changes.
+    pub fn debit_all(
+        &mut self,
+        costs: &[(Currency, u64)],
+        reason: TransactionReason,
+    ) -> Result<Vec<Uuid>, WalletError> {
+        self.check_debits(costs)?;
+        let signed = costs
+            .iter()
+            .map(|(_, amount)| i64::try_from(*amount).map_err(|_| WalletError::Overflow))
+            .collect::<Result<Vec<i64>, _>>()?;
+
+        Ok(costs
+            .iter()
+            .zip(signed)
+            .map(|((currency, amount), signed)| {
+                let balance = self.balance(*currency) - amount;
+                self.record(*currency, -signed, balance, reason.clone())
+            })
+            .collect())
+    }
+
+    fn check_debits(&self, costs: &[(Currency, u64)]) -> Result<(), WalletError> {
+        let mut required: HashMap<Currency, u64> = HashMap::new();
+        for (currency, amount) in costs {
+            let total = required.entry(*currency).or_default();
+            *total = total.checked_add(*amount).ok_or(WalletError::Overflow)?;
+        }
+
+        for (currency, amount) in required {
+            let balance = self.balance(currency);
+            if balance < amount {
+                return Err(WalletError::InsufficientFunds {
+                    currency,
+                    balance,
+                    requested: amount,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn record(
+        &mut self,
+        currency: Currency,
+        amount: i64,
+        balance_after: u64,
+        reason: TransactionReason,
+    ) -> Uuid {
+        let transaction = Transaction {
+            id: Uuid::new_v4(),
+            currency,
+            amount,
+            balance_after,
+            reason,
+        };
+        let id = transaction.id;
+        self.balances.insert(currency, balance_after);
+        self.history.push(transaction);
+        id
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod wallet_tests {
+    use super::*;
+
+    #[test]
+    fn test_credit_and_debit() {
+        let mut wallet = Wallet::new(Uuid::new_v4());
+
+        wallet
+            .credit(Currency::Gold, 100, TransactionReason::Reward)
+            .unwrap();
+        wallet
+            .debit(Currency::Gold, 40, TransactionReason::Crafting)
+            .unwrap();
+
+        assert_eq!(wallet.balance(Currency::Gold), 60);
+        assert_eq!(wallet.balance(Currency::Gems), 0);
+        assert_eq!(wallet.history().len(), 2);
+        assert_eq!(wallet.history()[1].amount, -40);
+    }
+
+    #[test]
+    fn test_overdraft_leaves_wallet_untouched() {
+        let mut wallet = Wallet::new(Uuid::new_v4());
+        wallet
+            .credit(Currency::Gold, 100, TransactionReason::Reward)
+            .unwrap();
+        wallet
+            .credit(Currency::Gems, 5, TransactionReason::Reward)
+            .unwrap();
+
+        let result = wallet.debit_all(
+            &[(Currency::Gold, 50), (Currency::Gems, 10)],
+            TransactionReason::Adjustment,
+        );
+
+        assert!(matches!(
+            result,
+            Err(WalletError::InsufficientFunds {
+                currency: Currency::Gems,
+                ..
+            })
+        ));
+        assert_eq!(wallet.balance(Currency::Gold), 100);
+        assert_eq!(wallet.history().len(), 2);
+
+        // Amounts that don't fit the ledger, or whose total wraps around,
+        // are refused rather than recorded wrong
+        assert!(matches!(
+            wallet.credit(Currency::Gems, u64::MAX - 5, TransactionReason::Reward),
+            Err(WalletError::Overflow)
+        ));
+        assert!(matches!(
+            wallet.debit_all(
+                &[(Currency::Gold, u64::MAX), (Currency::Gold, 2)],
+                TransactionReason::Adjustment,
+            ),
+            Err(WalletError::Overflow)
+        ));
+        assert_eq!(wallet.balance(Currency::Gold), 100);
+        assert_eq!(wallet.balance(Currency::Gems), 5);
+        assert_eq!(wallet.history().len(), 2);
+    }
+}
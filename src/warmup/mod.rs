@@ -0,0 +1,333 @@
+// src/warmup/mod.rs
+// Work done on start before the instance reports ready, so the first games
+// after a deploy don't pay for it. The current card data is checked and its
+// effects compiled down to rules text, the usual mountains are generated
+// and the common statements are prepared on every database connection.
+// The load balancer keeps traffic away until the readiness state says so.
+use crate::cards::text::rules_text;
+use crate::cards::versions::CardDataLibrary;
+use crate::config::WarmupSettings;
+use crate::database::{
+    DatabaseCluster, DatabaseConnection, PreparedStatement, Role, PREPARED_STATEMENTS,
+};
+use crate::effects::Effect;
+use crate::errors::WarmupError;
+use crate::models::Mountain;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmupStep {
+    CardData,
+    Effects,
+    Mountains,
+    Statements,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessState {
+    Warming,
+    Ready,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StepTiming {
+    pub step: WarmupStep,
+    // Cards, effects, mountains or statements handled
+    pub items: usize,
+    pub took: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WarmupReport {
+    pub card_data: String,
+    pub steps: Vec<StepTiming>,
+}
+
+impl WarmupReport {
+    pub fn total(&self) -> Duration {
+        self.steps.iter().map(|timing| timing.took).sum()
+    }
+}
+
+// Every card's rules text for the version new games start on, rendered
+// once instead of on each request
+#[derive(Debug, Clone, Default)]
+pub struct CardTextCache {
+    pub version: String,
+    text: HashMap<Uuid, String>,
+}
+
+impl CardTextCache {
+    pub fn get(&self, def_id: &Uuid) -> Option<&str> {
+        self.text.get(def_id).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Warmed {
+    pub report: WarmupReport,
+    pub card_text: CardTextCache,
+}
+
+#[derive(Debug)]
+pub struct Readiness {
+    state: Mutex<ReadinessState>,
+}
+
+impl Default for Readiness {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(ReadinessState::Warming),
+        }
+    }
+}
+
+impl Readiness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> ReadinessState {
+        *self.state.lock().unwrap()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.state() == ReadinessState::Ready
+    }
+
+    // Runs every step and only then reports ready. `prepare` sends one
+    // statement to one connection. A failed warmup leaves the instance out
+    // of rotation; calling this again retries from the start.
+    pub fn warm_up(
+        &self,
+        settings: &WarmupSettings,
+        library: &CardDataLibrary,
+        database: &DatabaseCluster,
+        prepare: impl FnMut(&DatabaseConnection, &PreparedStatement) -> Result<(), String>,
+    ) -> Result<Warmed, WarmupError> {
+        *self.state.lock().unwrap() = ReadinessState::Warming;
+        let result = warm_up(settings, library, database, prepare);
+        let state = match &result {
+            Ok(warmed) => {
+                tracing::info!(
+                    card_data = %warmed.report.card_data,
+                    took = ?warmed.report.total(),
+                    "warmup finished"
+                );
+                ReadinessState::Ready
+            }
+            Err(error) => {
+                tracing::error!(?error, "warmup failed");
+                ReadinessState::Failed
+            }
+        };
+        *self.state.lock().unwrap() = state;
+        result
+    }
+}
+
+fn timed<T>(
+    steps: &mut Vec<StepTiming>,
+    step: WarmupStep,
+    work: impl FnOnce() -> Result<(usize, T), WarmupError>,
+) -> Result<T, WarmupError> {
+    let started = Instant::now();
+    let (items, value) = work()?;
+    let took = started.elapsed();
+    tracing::info!(?step, items, ?took, "warmup step done");
+    steps.push(StepTiming { step, items, took });
+    Ok(value)
+}
+
+fn warm_up(
+    settings: &WarmupSettings,
+    library: &CardDataLibrary,
+    database: &DatabaseCluster,
+    mut prepare: impl FnMut(&DatabaseConnection, &PreparedStatement) -> Result<(), String>,
+) -> Result<Warmed, WarmupError> {
+    let mut steps = Vec::new();
+    let data = timed(&mut steps, WarmupStep::CardData, || {
+        let data = library.current().ok_or(WarmupError::NoCardData)?;
+        Ok((data.registry.len(), data))
+    })?;
+
+    // Effects are data rather than scripts, so compiling them means checking
+    // every definition they point at exists and rendering their text
+    let card_text = timed(&mut steps, WarmupStep::Effects, || {
+        let mut effects = 0;
+        let mut text = HashMap::with_capacity(data.registry.len());
+        for card in data.registry.definitions() {
+            let mut pending: Vec<&Effect> = card.effects.iter().collect();
+            while let Some(effect) = pending.pop() {
+                effects += 1;
+                match effect {
+                    Effect::Transform(transform)
+                        if data.registry.get(&transform.into_def_id).is_none() =>
+                    {
+                        return Err(WarmupError::UnknownDefinition {
+                            card: card.name.clone(),
+                            def_id: transform.into_def_id,
+                        });
+                    }
+                    Effect::Triggered(triggered) => pending.push(&triggered.effect),
+                    _ => {}
+                }
+            }
+            text.insert(card.id, rules_text(card));
+        }
+        let cache = CardTextCache {
+            version: data.version.clone(),
+            text,
+        };
+        Ok((effects, cache))
+    })?;
+
+    if settings.enabled {
+        timed(&mut steps, WarmupStep::Mountains, || {
+            Mountain::preload(settings.mountain_levels.iter().copied());
+            Ok((settings.mountain_levels.len(), ()))
+        })?;
+    }
+
+    if settings.enabled && settings.prepare_statements {
+        timed(&mut steps, WarmupStep::Statements, || {
+            let mut prepared = 0;
+            for connection in database.connections() {
+                for statement in PREPARED_STATEMENTS
+                    .iter()
+                    .filter(|statement| connection.role == Role::Primary || !statement.writes)
+                {
+                    prepare(connection, statement).map_err(|reason| WarmupError::Statement {
+                        name: statement.name,
+                        url: connection.url.clone(),
+                        reason,
+                    })?;
+                    prepared += 1;
+                }
+            }
+            Ok((prepared, ()))
+        })?;
+    }
+
+    Ok(Warmed {
+        report: WarmupReport {
+            card_data: data.version.clone(),
+            steps,
+        },
+        card_text,
+    })
+}
+
+// TESTS
+#[cfg(test)]
+mod warmup_tests {
+    use super::*;
+    use crate::cards::errata::ErrataTable;
+    use crate::cards::CardRegistry;
+    use crate::effects::TransformEffect;
+    use crate::models::Shared;
+    use crate::testing::CardBuilder;
+
+    #[test]
+    fn test_ready_only_once_every_step_has_run() {
+        let readiness = Readiness::new();
+        let settings = WarmupSettings {
+            mountain_levels: vec![4, 7],
+            ..WarmupSettings::default()
+        };
+        let database =
+            DatabaseCluster::new("postgres://primary").with_replica("postgres://replica");
+        let mut library = CardDataLibrary::new();
+        let no_statements = |_: &DatabaseConnection, _: &PreparedStatement| Ok(());
+        assert_eq!(readiness.state(), ReadinessState::Warming);
+        assert!(matches!(
+            readiness.warm_up(&settings, &library, &database, no_statements),
+            Err(WarmupError::NoCardData)
+        ));
+        assert_eq!(readiness.state(), ReadinessState::Failed);
+
+        let frog = CardBuilder::new("Frog").build();
+        let hex = CardBuilder::new("Hex")
+            .effect(Effect::Transform(TransformEffect {
+                card_id: Uuid::new_v4(),
+                into_def_id: frog.id,
+                priority: 0,
+            }))
+            .build();
+        let errata = ErrataTable::new();
+        library
+            .publish("2026.1", &CardRegistry::new([hex.clone()]), &errata)
+            .unwrap();
+        assert!(matches!(
+            readiness.warm_up(&settings, &library, &database, no_statements),
+            Err(WarmupError::UnknownDefinition { card, def_id }) if card == "Hex" && def_id == frog.id
+        ));
+
+        library
+            .publish("2026.2", &CardRegistry::new([hex.clone(), frog]), &errata)
+            .unwrap();
+        let mut prepared = Vec::new();
+        let warmed = readiness
+            .warm_up(&settings, &library, &database, |connection, statement| {
+                prepared.push((connection.role, statement.name));
+                Ok(())
+            })
+            .unwrap();
+        assert!(readiness.is_ready());
+        let steps: Vec<WarmupStep> = warmed.report.steps.iter().map(|t| t.step).collect();
+        assert_eq!(
+            steps,
+            [
+                WarmupStep::CardData,
+                WarmupStep::Effects,
+                WarmupStep::Mountains,
+                WarmupStep::Statements
+            ]
+        );
+        assert_eq!(warmed.card_text.version, "2026.2");
+        assert_eq!(
+            warmed.card_text.get(&hex.id),
+            Some(rules_text(&hex).as_str())
+        );
+        // Replicas never see the writes
+        let writes = PREPARED_STATEMENTS.iter().filter(|s| s.writes).count();
+        assert_eq!(prepared.len(), 2 * PREPARED_STATEMENTS.len() - writes);
+        assert!(prepared
+            .iter()
+            .filter(|(role, _)| *role == Role::Replica)
+            .all(|(_, name)| PREPARED_STATEMENTS
+                .iter()
+                .any(|s| s.name == *name && !s.writes)));
+        // Games get the prebuilt graph rather than a fresh one
+        assert!(Shared::ptr_eq(
+            &Mountain::preset(4).tiles,
+            &Mountain::preset(4).tiles
+        ));
+
+        assert!(matches!(
+            readiness.warm_up(&settings, &library, &database, |_, _| Err(
+                "timeout".to_string()
+            )),
+            Err(WarmupError::Statement {
+                name: "load_profile",
+                ..
+            })
+        ));
+        assert!(!readiness.is_ready());
+    }
+}
@@ -0,0 +1,355 @@
+// src/webhooks/mod.rs
+// Outbound notifications for outside integrations (Discord bots,
+// tournament sites) so they don't have to poll. Each endpoint picks the
+// topics it wants; every delivery is signed with the endpoint's secret and
+// retried with exponential backoff until it goes through or runs out of
+// attempts. The HTTP client itself sits behind WebhookTransport.
+use crate::moderation::{PlayerReport, ReportReason};
+use crate::summary::GameSummary;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+pub const SIGNATURE_HEADER: &str = "X-Ascent-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Ascent-Timestamp";
+pub const TOPIC_HEADER: &str = "X-Ascent-Topic";
+
+pub const MAX_ATTEMPTS: u32 = 6;
+pub const BASE_BACKOFF: Duration = Duration::from_secs(10);
+pub const MAX_BACKOFF: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookTopic {
+    MatchFinished,
+    TournamentRoundComplete,
+    PlayerReported,
+}
+
+impl WebhookTopic {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookTopic::MatchFinished => "match_finished",
+            WebhookTopic::TournamentRoundComplete => "tournament_round_complete",
+            WebhookTopic::PlayerReported => "player_reported",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "topic", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    MatchFinished {
+        game_id: Uuid,
+        winner: Option<Uuid>,
+        players: Vec<Uuid>,
+        turns: u32,
+    },
+    TournamentRoundComplete {
+        tournament_id: Uuid,
+        round: u32,
+        // Games of the round as (game id, winner)
+        results: Vec<(Uuid, Option<Uuid>)>,
+    },
+    // Nothing from the report's text goes out, only who and why
+    PlayerReported {
+        report_id: Uuid,
+        reported: Uuid,
+        game_id: Uuid,
+        reason: ReportReason,
+    },
+}
+
+impl WebhookEvent {
+    pub fn match_finished(summary: &GameSummary) -> Self {
+        WebhookEvent::MatchFinished {
+            game_id: summary.game_id,
+            winner: summary.winner,
+            players: summary
+                .players
+                .iter()
+                .map(|player| player.player_id)
+                .collect(),
+            turns: summary.turns,
+        }
+    }
+
+    pub fn player_reported(report: &PlayerReport) -> Self {
+        WebhookEvent::PlayerReported {
+            report_id: report.id,
+            reported: report.reported,
+            game_id: report.game_id,
+            reason: report.reason,
+        }
+    }
+
+    pub fn topic(&self) -> WebhookTopic {
+        match self {
+            WebhookEvent::MatchFinished { .. } => WebhookTopic::MatchFinished,
+            WebhookEvent::TournamentRoundComplete { .. } => WebhookTopic::TournamentRoundComplete,
+            WebhookEvent::PlayerReported { .. } => WebhookTopic::PlayerReported,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WebhookEndpoint {
+    pub url: String,
+    pub secret: String,
+    pub topics: HashSet<WebhookTopic>,
+}
+
+// Hex HMAC-SHA256 of "<timestamp>.<body>". Receivers recompute it and
+// should refuse stale timestamps so a captured request can't be replayed.
+pub fn sign(secret: &str, timestamp: u64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub trait WebhookTransport {
+    // Err for anything other than a 2xx response, including timeouts
+    fn post(&mut self, url: &str, headers: &[(&str, String)], body: &str) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delivery {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub topic: WebhookTopic,
+    pub body: String,
+    pub attempts: u32,
+    pub next_attempt: SystemTime,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlushReport {
+    pub delivered: usize,
+    pub retrying: usize,
+    pub given_up: usize,
+}
+
+fn backoff(attempts: u32) -> Duration {
+    BASE_BACKOFF
+        .saturating_mul(1 << attempts.saturating_sub(1).min(16))
+        .min(MAX_BACKOFF)
+}
+
+#[derive(Debug, Default)]
+pub struct WebhookDispatcher {
+    endpoints: HashMap<Uuid, WebhookEndpoint>,
+    pending: VecDeque<Delivery>,
+    // Deliveries that used up their attempts, kept for operators to look at
+    dead_letters: Vec<Delivery>,
+}
+
+impl WebhookDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Endpoints listed in the server config
+    pub fn from_config(endpoints: &[WebhookEndpoint]) -> Self {
+        let mut dispatcher = Self::new();
+        for endpoint in endpoints {
+            dispatcher.register(endpoint.clone());
+        }
+        dispatcher
+    }
+
+    pub fn register(&mut self, endpoint: WebhookEndpoint) -> Uuid {
+        let id = Uuid::new_v4();
+        self.endpoints.insert(id, endpoint);
+        id
+    }
+
+    // Anything still queued for the endpoint is dropped with it
+    pub fn unregister(&mut self, endpoint_id: Uuid) -> bool {
+        self.pending
+            .retain(|delivery| delivery.endpoint_id != endpoint_id);
+        self.endpoints.remove(&endpoint_id).is_some()
+    }
+
+    // Queues the event for every endpoint subscribed to its topic and
+    // returns how many that was
+    pub fn fire(&mut self, event: &WebhookEvent, now: SystemTime) -> usize {
+        let topic = event.topic();
+        let body = serde_json::to_string(event).expect("webhook events always serialize");
+        let mut endpoints: Vec<Uuid> = self
+            .endpoints
+            .iter()
+            .filter(|(_, endpoint)| endpoint.topics.contains(&topic))
+            .map(|(id, _)| *id)
+            .collect();
+        endpoints.sort_unstable();
+        for endpoint_id in &endpoints {
+            self.pending.push_back(Delivery {
+                id: Uuid::new_v4(),
+                endpoint_id: *endpoint_id,
+                topic,
+                body: body.clone(),
+                attempts: 0,
+                next_attempt: now,
+                last_error: None,
+            });
+        }
+        endpoints.len()
+    }
+
+    // Polled by the scheduler; sends everything that is due. Each attempt is
+    // stamped and signed afresh, so a retry isn't refused as stale.
+    pub fn flush(&mut self, transport: &mut impl WebhookTransport, now: SystemTime) -> FlushReport {
+        let timestamp = now
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut report = FlushReport::default();
+        let mut waiting = VecDeque::new();
+        while let Some(mut delivery) = self.pending.pop_front() {
+            if delivery.next_attempt > now {
+                waiting.push_back(delivery);
+                continue;
+            }
+            let Some(endpoint) = self.endpoints.get(&delivery.endpoint_id) else {
+                continue;
+            };
+            let headers = [
+                (
+                    SIGNATURE_HEADER,
+                    format!(
+                        "sha256={}",
+                        sign(&endpoint.secret, timestamp, &delivery.body)
+                    ),
+                ),
+                (TIMESTAMP_HEADER, timestamp.to_string()),
+                (TOPIC_HEADER, delivery.topic.as_str().to_string()),
+            ];
+            delivery.attempts += 1;
+            match transport.post(&endpoint.url, &headers, &delivery.body) {
+                Ok(()) => report.delivered += 1,
+                Err(error) => {
+                    delivery.last_error = Some(error);
+                    if delivery.attempts >= MAX_ATTEMPTS {
+                        report.given_up += 1;
+                        self.dead_letters.push(delivery);
+                    } else {
+                        report.retrying += 1;
+                        delivery.next_attempt = now + backoff(delivery.attempts);
+                        waiting.push_back(delivery);
+                    }
+                }
+            }
+        }
+        self.pending = waiting;
+        report
+    }
+
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn dead_letters(&self) -> &[Delivery] {
+        &self.dead_letters
+    }
+}
+
+// TESTS
+#[cfg(test)]
+mod webhook_tests {
+    use super::*;
+
+    // Url, headers and body of a request that got through
+    type Received = (String, Vec<(String, String)>, String);
+
+    #[derive(Default)]
+    struct Flaky {
+        failures_left: u32,
+        received: Vec<Received>,
+    }
+
+    impl WebhookTransport for Flaky {
+        fn post(
+            &mut self,
+            url: &str,
+            headers: &[(&str, String)],
+            body: &str,
+        ) -> Result<(), String> {
+            if self.failures_left > 0 {
+                self.failures_left -= 1;
+                return Err("503".to_string());
+            }
+            let headers = headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect();
+            self.received
+                .push((url.to_string(), headers, body.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_signed_delivery_retries_with_backoff() {
+        let mut webhooks = WebhookDispatcher::new();
+        webhooks.register(WebhookEndpoint {
+            url: "https://bot.example/hooks".to_string(),
+            secret: "s3cret".to_string(),
+            topics: HashSet::from([WebhookTopic::MatchFinished]),
+        });
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let reported = WebhookEvent::PlayerReported {
+            report_id: Uuid::new_v4(),
+            reported: Uuid::new_v4(),
+            game_id: Uuid::new_v4(),
+            reason: ReportReason::Stalling,
+        };
+        assert_eq!(webhooks.fire(&reported, now), 0);
+        let finished = WebhookEvent::MatchFinished {
+            game_id: Uuid::new_v4(),
+            winner: None,
+            players: vec![],
+            turns: 12,
+        };
+        assert_eq!(webhooks.fire(&finished, now), 1);
+
+        let mut transport = Flaky {
+            failures_left: 2,
+            ..Flaky::default()
+        };
+        assert_eq!(webhooks.flush(&mut transport, now).retrying, 1);
+        // Not due again until the backoff has passed
+        assert_eq!(
+            webhooks.flush(&mut transport, now + BASE_BACKOFF / 2),
+            FlushReport::default()
+        );
+        let later = now + BASE_BACKOFF;
+        assert_eq!(webhooks.flush(&mut transport, later).retrying, 1);
+        let report = webhooks.flush(&mut transport, later + BASE_BACKOFF * 2);
+        assert_eq!(report.delivered, 1);
+        assert_eq!(webhooks.pending(), 0);
+
+        // The retry that got through carries the time it was sent, not the
+        // time the event fired
+        let (_, headers, body) = &transport.received[0];
+        let header = |name: &str| &headers.iter().find(|(key, _)| key == name).unwrap().1;
+        assert_eq!(header(TOPIC_HEADER), "match_finished");
+        assert_eq!(header(TIMESTAMP_HEADER), "1030");
+        assert_eq!(
+            *header(SIGNATURE_HEADER),
+            format!("sha256={}", sign("s3cret", 1_030, body))
+        );
+        assert_ne!(sign("s3cret", 1_000, body), sign("s3cret", 1_030, body));
+        assert_ne!(sign("other", 1_030, body), sign("s3cret", 1_030, body));
+    }
+}